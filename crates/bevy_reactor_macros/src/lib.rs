@@ -0,0 +1,862 @@
+//! Procedural macros for `bevy_reactor`.
+
+use std::collections::HashSet;
+
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    braced,
+    parse::{Parse, ParseStream},
+    token, Expr, ExprLit, ExprPath, Ident, Lit, LitStr, Pat, Path, Result, Token, UnOp,
+};
+
+/// The largest tuple which implements `ChildViewTuple`. Longer lists of children are split
+/// into nested tuples.
+const MAX_TUPLE: usize = 15;
+
+/// Style properties set by `StyleBuilderLayout`.
+const LAYOUT_PROPERTIES: &[&str] = &[
+    "display",
+    "position",
+    "overflow",
+    "overflow_x",
+    "overflow_y",
+    "direction",
+    "left",
+    "right",
+    "top",
+    "bottom",
+    "width",
+    "height",
+    "min_width",
+    "min_height",
+    "max_width",
+    "max_height",
+    "aspect_ratio",
+    "margin",
+    "margin_left",
+    "margin_right",
+    "margin_top",
+    "margin_bottom",
+    "padding",
+    "padding_left",
+    "padding_right",
+    "padding_top",
+    "padding_bottom",
+    "border",
+    "border_left",
+    "border_right",
+    "border_top",
+    "border_bottom",
+    "flex_direction",
+    "flex_wrap",
+    "flex",
+    "flex_grow",
+    "flex_shrink",
+    "flex_basis",
+    "row_gap",
+    "column_gap",
+    "gap",
+    "align_items",
+    "align_self",
+    "align_content",
+    "justify_items",
+    "justify_self",
+    "justify_content",
+    "grid_auto_flow",
+    "grid_template_rows",
+    "grid_template_columns",
+    "grid_auto_rows",
+    "grid_auto_columns",
+    "grid_row",
+    "grid_row_start",
+    "grid_row_span",
+    "grid_row_end",
+    "grid_column",
+    "grid_column_start",
+    "grid_column_span",
+    "grid_column_end",
+];
+
+/// Properties of a grid container, which have no effect unless the display is a grid.
+const GRID_PROPERTIES: &[&str] = &[
+    "grid_auto_flow",
+    "grid_template_rows",
+    "grid_template_columns",
+    "grid_auto_rows",
+    "grid_auto_columns",
+];
+
+/// Properties for which a negative value is invalid. Margins and insets such as `left` may
+/// legitimately be negative.
+const NON_NEGATIVE_PROPERTIES: &[&str] = &[
+    "width",
+    "height",
+    "min_width",
+    "min_height",
+    "max_width",
+    "max_height",
+    "aspect_ratio",
+    "padding",
+    "padding_left",
+    "padding_right",
+    "padding_top",
+    "padding_bottom",
+    "border",
+    "border_left",
+    "border_right",
+    "border_top",
+    "border_bottom",
+    "flex",
+    "flex_grow",
+    "flex_shrink",
+    "flex_basis",
+    "row_gap",
+    "column_gap",
+    "gap",
+    "outline_width",
+    "font_size",
+];
+
+/// Declarative syntax for building view trees. The macro expands to the usual `Element`,
+/// `Cond` and `For` builders, so it can be mixed freely with hand-written views.
+///
+/// ```ignore
+/// view! {
+///     <NodeBundle style={(style_panel, style_dark)} name="panel">
+///         "Count: "
+///         {move |cx| format!("{}", count.get(cx))}
+///         if move |cx| count.get(cx) > 10 {
+///             <NodeBundle style=style_warning>"That's a lot"</NodeBundle>
+///         } else {
+///             "Keep going"
+///         }
+///         for item in move |cx| items.get_clone(cx).into_iter() {
+///             {item_row(item)}
+///         }
+///         <NodeBundle style=style_spacer />
+///     </NodeBundle>
+/// }
+/// ```
+///
+/// * `<Bundle attr=value ...>children</Bundle>` creates an `Element::<Bundle>`. Self-closing
+///   tags such as `<Bundle />` have no children.
+/// * Attributes call the element's builder method of the same name; `style` calls
+///   `with_styles` and `name` calls `named`. Values are literals, paths, or any expression
+///   in braces or parentheses. `insert_if` takes a pair, as in
+///   `insert_if=(selected, || Outline::default())`.
+/// * String literals become text nodes.
+/// * `{expr}` is any child view. If the expression is a closure, it's a computed text node,
+///   as made by `text_computed`.
+/// * `if test { ... } else { ... }` becomes a `Cond`, where `test` is a reactive closure.
+/// * `for item in items { ... }` becomes a `For::each`, where `items` is a reactive closure
+///   returning an iterator. The pattern is bound to a reference to each item.
+///
+/// If there is more than one top-level node, the result is a fragment.
+#[proc_macro]
+pub fn view(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse::<Nodes>(input) {
+        Ok(nodes) => nodes.root().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Declarative syntax for style functions, in the manner of CSS. Each rule calls the
+/// `StyleBuilder` method of the same name, with the comma-separated arguments:
+///
+/// ```ignore
+/// let style_grid = styles! {
+///     display: ui::Display::Grid;
+///     grid_template_columns: vec![ui::RepeatedGridTrack::auto(2)];
+///     flex: 1., 0., ui::Val::Auto;
+///     padding: 4;
+///     background_color: "#222";
+/// };
+/// ```
+///
+/// The result is a non-capturing closure which can be used wherever a style function is
+/// accepted, such as `with_styles` or the `style` attribute of `view!`. The builder trait for
+/// each property is resolved when the macro is expanded, so no imports are needed; other
+/// properties are called as methods, so that extension traits which are in scope still work.
+///
+/// Obvious mistakes produce a compiler warning at the offending rule:
+///
+/// * a property which is set more than once, since only the last value takes effect;
+/// * a negative literal for a size, padding, border, gap or flex factor;
+/// * grid container properties, such as `grid_template_columns`, when `display` is set in
+///   the same block to something other than `Display::Grid`.
+///
+/// Procedural macros can't emit warnings on stable Rust, so each warning is produced by a
+/// deprecated item. This has some caveats:
+///
+/// * The warning reads "use of deprecated unit struct `StyleWarning`", followed by the
+///   actual problem in its note.
+/// * It is a deprecation warning, so it becomes a hard error under `-D warnings`, and can
+///   be silenced with `#[allow(deprecated)]`.
+///
+/// The checks are the only work done at compile time. The style itself is still built when
+/// the closure runs, by the same `StyleBuilder` calls as a hand-written style function, so
+/// `styles!` is no faster than writing the function by hand.
+#[proc_macro]
+pub fn styles(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse::<StyleRules>(input) {
+        Ok(rules) => rules.expand().into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive accessors for the fields of a struct held in a `Store`. For a struct `Player`,
+/// this generates a trait `PlayerStoreFields`, implemented for any `StoreField` whose value
+/// is a `Player`, with a method for each named field which returns a `StoreField` for it:
+///
+/// ```ignore
+/// #[derive(StoreFields)]
+/// struct Player {
+///     name: String,
+///     inventory: Vec<Item>,
+/// }
+///
+/// let items = store.root().inventory().get(cx);
+/// ```
+///
+/// The trait has the same visibility as the struct, and must be in scope to call its methods.
+/// Fields selected this way are tracked by version rather than by value, so checking whether
+/// a reader needs to react never clones or compares the field.
+#[proc_macro_derive(StoreFields)]
+pub fn derive_store_fields(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse::<syn::DeriveInput>(input).and_then(store_fields) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn store_fields(input: syn::DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "StoreFields can't be derived for generic types",
+        ));
+    }
+    let fields = match &input.data {
+        syn::Data::Struct(syn::DataStruct {
+            fields: syn::Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "StoreFields can only be derived for structs with named fields",
+            ))
+        }
+    };
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let trait_ident = Ident::new(&format!("{}StoreFields", ident), ident.span());
+    let trait_doc = format!(
+        "Accessors for the fields of a [`{}`] held in a store.",
+        ident
+    );
+    let mut decls = Vec::new();
+    let mut impls = Vec::new();
+    for (index, field) in fields.iter().enumerate() {
+        let name = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let doc = format!("The `{}` field.", name);
+        decls.push(quote! {
+            #[doc = #doc]
+            fn #name(&self) -> ::bevy_reactor::StoreField<S, #ty>;
+        });
+        impls.push(quote! {
+            fn #name(&self) -> ::bevy_reactor::StoreField<S, #ty> {
+                self.indexed_field(
+                    #index,
+                    |value: &#ident| &value.#name,
+                    |value: &mut #ident| &mut value.#name,
+                )
+            }
+        });
+    }
+    Ok(quote! {
+        #[doc = #trait_doc]
+        #vis trait #trait_ident<S> {
+            #(#decls)*
+        }
+
+        impl<S: Send + Sync + 'static> #trait_ident<S> for ::bevy_reactor::StoreField<S, #ident> {
+            #(#impls)*
+        }
+    })
+}
+
+/// A sequence of sibling nodes.
+struct Nodes(Vec<Node>);
+
+enum Node {
+    Element(ElementNode),
+    Text(LitStr),
+    Expr(Expr),
+    If(IfNode),
+    For(ForNode),
+}
+
+struct ElementNode {
+    bundle: Path,
+    attrs: Vec<Attr>,
+    children: Vec<Node>,
+}
+
+struct Attr {
+    name: Ident,
+    value: Expr,
+}
+
+struct IfNode {
+    test: Expr,
+    then_branch: Vec<Node>,
+    else_branch: Vec<Node>,
+}
+
+struct ForNode {
+    pat: Pat,
+    items: Expr,
+    body: Vec<Node>,
+}
+
+impl Parse for Nodes {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut nodes = Vec::new();
+        // Stop at the end of the input, or at the closing tag of the parent element.
+        while !input.is_empty() && !at_closing_tag(input) {
+            nodes.push(input.parse()?);
+        }
+        Ok(Self(nodes))
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lookahead = input.lookahead1();
+        if lookahead.peek(Token![<]) {
+            input.parse().map(Node::Element)
+        } else if lookahead.peek(LitStr) {
+            input.parse().map(Node::Text)
+        } else if lookahead.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            content.parse().map(Node::Expr)
+        } else if lookahead.peek(Token![if]) {
+            input.parse().map(Node::If)
+        } else if lookahead.peek(Token![for]) {
+            input.parse().map(Node::For)
+        } else {
+            Err(lookahead.error())
+        }
+    }
+}
+
+/// Returns true if the next tokens are the start of a closing tag.
+fn at_closing_tag(input: ParseStream) -> bool {
+    input.peek(Token![<]) && input.peek2(Token![/])
+}
+
+/// Parse a list of child nodes in braces.
+fn parse_block(input: ParseStream) -> Result<Vec<Node>> {
+    let content;
+    braced!(content in input);
+    let nodes: Nodes = content.parse()?;
+    if !content.is_empty() {
+        return Err(content.error("unexpected closing tag"));
+    }
+    Ok(nodes.0)
+}
+
+impl Parse for ElementNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![<]>()?;
+        let bundle = Path::parse_mod_style(input)?;
+        let mut attrs = Vec::new();
+        while !(input.peek(Token![>]) || input.peek(Token![/])) {
+            attrs.push(input.parse()?);
+        }
+
+        // Self-closing tag.
+        if input.peek(Token![/]) {
+            input.parse::<Token![/]>()?;
+            input.parse::<Token![>]>()?;
+            return Ok(Self {
+                bundle,
+                attrs,
+                children: Vec::new(),
+            });
+        }
+
+        input.parse::<Token![>]>()?;
+        let children = input.parse::<Nodes>()?.0;
+        if input.is_empty() {
+            return Err(syn::Error::new_spanned(
+                &bundle,
+                format!("missing closing tag </{}>", bundle.to_token_stream()),
+            ));
+        }
+        input.parse::<Token![<]>()?;
+        input.parse::<Token![/]>()?;
+        let closing = Path::parse_mod_style(input)?;
+        if closing.to_token_stream().to_string() != bundle.to_token_stream().to_string() {
+            return Err(syn::Error::new_spanned(
+                &closing,
+                format!("expected closing tag </{}>", bundle.to_token_stream()),
+            ));
+        }
+        input.parse::<Token![>]>()?;
+        Ok(Self {
+            bundle,
+            attrs,
+            children,
+        })
+    }
+}
+
+impl Parse for Attr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        // Unbraced values are restricted to single tokens and paths, since the `>` which
+        // closes the tag would otherwise be parsed as a comparison.
+        let value = if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            content.parse()?
+        } else if input.peek(token::Paren) {
+            let group: TokenTree = input.parse()?;
+            syn::parse2(group.into_token_stream())?
+        } else if input.peek(Lit) {
+            Expr::Lit(ExprLit {
+                attrs: Vec::new(),
+                lit: input.parse()?,
+            })
+        } else {
+            Expr::Path(input.parse::<ExprPath>()?)
+        };
+        Ok(Self { name, value })
+    }
+}
+
+impl Parse for IfNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![if]>()?;
+        let test = Expr::parse_without_eager_brace(input)?;
+        let then_branch = parse_block(input)?;
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                vec![Node::If(input.parse()?)]
+            } else {
+                parse_block(input)?
+            }
+        } else {
+            Vec::new()
+        };
+        Ok(Self {
+            test,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+impl Parse for ForNode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        input.parse::<Token![for]>()?;
+        let pat = Pat::parse_single(input)?;
+        input.parse::<Token![in]>()?;
+        let items = Expr::parse_without_eager_brace(input)?;
+        let body = parse_block(input)?;
+        Ok(Self { pat, items, body })
+    }
+}
+
+impl Nodes {
+    /// The view for the macro as a whole.
+    fn root(&self) -> TokenStream {
+        fragment(&self.0)
+    }
+}
+
+impl Node {
+    fn expand(&self) -> TokenStream {
+        match self {
+            Node::Element(element) => element.expand(),
+            Node::Text(text) => quote!(#text),
+            Node::Expr(expr @ Expr::Closure(_)) => quote!(::bevy_reactor::text_computed(#expr)),
+            Node::Expr(expr) => quote!(#expr),
+            Node::If(node) => {
+                let test = &node.test;
+                let then_branch = fragment(&node.then_branch);
+                let else_branch = fragment(&node.else_branch);
+                quote!(::bevy_reactor::Cond::new(
+                    #test,
+                    move || #then_branch,
+                    move || #else_branch
+                ))
+            }
+            Node::For(node) => {
+                let pat = &node.pat;
+                let items = &node.items;
+                let body = fragment(&node.body);
+                quote!(::bevy_reactor::For::each(#items, move |#pat| #body))
+            }
+        }
+    }
+}
+
+impl ElementNode {
+    fn expand(&self) -> TokenStream {
+        let bundle = &self.bundle;
+        let mut element = quote!(::bevy_reactor::Element::<#bundle>::new());
+        for Attr { name, value } in self.attrs.iter() {
+            element = match name.to_string().as_str() {
+                "style" => quote!(::bevy_reactor::WithStyles::with_styles(#element, #value)),
+                "name" => quote!(#element.named(#value)),
+                "insert_if" => match value {
+                    // The condition and the bundle factory are given as a pair.
+                    Expr::Tuple(args) => {
+                        let args = args.elems.iter();
+                        quote!(::bevy_reactor::EffectTarget::insert_if(#element, #(#args),*))
+                    }
+                    _ => {
+                        return syn::Error::new_spanned(value, "expected (condition, || bundle)")
+                            .to_compile_error()
+                    }
+                },
+                "insert" | "insert_computed" | "create_effect" => {
+                    quote!(::bevy_reactor::EffectTarget::#name(#element, #value))
+                }
+                _ => quote!(#element.#name(#value)),
+            };
+        }
+        if !self.children.is_empty() {
+            let children = tuple(&self.children);
+            element = quote!(::bevy_reactor::ParentView::with_children(#element, #children));
+        }
+        element
+    }
+}
+
+/// The rules of a `styles!` block.
+struct StyleRules(Vec<StyleRule>);
+
+struct StyleRule {
+    name: Ident,
+    args: Vec<Expr>,
+}
+
+impl Parse for StyleRules {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut rules = Vec::new();
+        while !input.is_empty() {
+            let name: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            let mut args = vec![input.parse()?];
+            while input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+                args.push(input.parse()?);
+            }
+            rules.push(StyleRule { name, args });
+            if !input.is_empty() {
+                input.parse::<Token![;]>()?;
+            }
+        }
+        Ok(Self(rules))
+    }
+}
+
+impl StyleRules {
+    fn expand(&self) -> TokenStream {
+        let warnings = self
+            .check()
+            .into_iter()
+            .map(|(span, message)| warning(span, &message));
+        let calls =
+            self.0.iter().map(
+                |StyleRule { name, args }| match builder_trait(&name.to_string()) {
+                    Some(builder) => {
+                        let builder = Ident::new(builder, name.span());
+                        quote!(::bevy_reactor::#builder::#name(ss, #(#args),*);)
+                    }
+                    None => quote!(ss.#name(#(#args),*);),
+                },
+            );
+        quote!(move |ss: &mut ::bevy_reactor::StyleBuilder| {
+            #(#warnings)*
+            #(#calls)*
+        })
+    }
+
+    /// Look for obviously invalid rules, returning a message for each.
+    fn check(&self) -> Vec<(Span, String)> {
+        let mut problems = Vec::new();
+        let mut seen = HashSet::new();
+        for rule in self.0.iter() {
+            let name = rule.name.to_string();
+            if !seen.insert(name.clone()) {
+                problems.push((
+                    rule.name.span(),
+                    format!(
+                        "`{}` is set more than once; only the last value is used",
+                        name
+                    ),
+                ));
+            }
+            if NON_NEGATIVE_PROPERTIES.contains(&name.as_str()) && rule.args.iter().any(is_negative)
+            {
+                problems.push((rule.name.span(), format!("`{}` cannot be negative", name)));
+            }
+        }
+
+        // Only a display which is set in the same block can be checked, since styles are
+        // usually composed from several functions.
+        let display = self
+            .0
+            .iter()
+            .rev()
+            .find(|rule| rule.name == "display")
+            .and_then(|rule| match rule.args.first() {
+                Some(Expr::Path(path)) => path.path.segments.last(),
+                _ => None,
+            });
+        if let Some(display) = display {
+            if display.ident != "Grid" {
+                for rule in self.0.iter() {
+                    let name = rule.name.to_string();
+                    if GRID_PROPERTIES.contains(&name.as_str()) {
+                        problems.push((
+                            rule.name.span(),
+                            format!(
+                                "`{}` has no effect unless `display` is `Display::Grid`",
+                                name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+        problems
+    }
+}
+
+/// The builder trait which defines the method for a style property, if it is a built-in one.
+fn builder_trait(name: &str) -> Option<&'static str> {
+    match name {
+        "background_color" => Some("StyleBuilderBackground"),
+        "border_color" => Some("StyleBuilderBorderColor"),
+        "color" | "font" | "font_size" => Some("StyleBuilderFont"),
+        "outline_color" | "outline_width" | "outline_offset" => Some("StyleBuilderOutline"),
+        "pointer_events" => Some("StyleBuilderPointerEvents"),
+        "z_index" => Some("StyleBuilderZIndex"),
+        _ if LAYOUT_PROPERTIES.contains(&name) => Some("StyleBuilderLayout"),
+        _ => None,
+    }
+}
+
+/// Returns true if the expression is a negative number, or a length such as `Val::Px(-4.)`
+/// or `UiRect::all(Val::Px(-4.))` with a negative number.
+fn is_negative(expr: &Expr) -> bool {
+    match expr {
+        Expr::Unary(unary) if matches!(unary.op, UnOp::Neg(_)) => match unary.expr.as_ref() {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(lit), ..
+            }) => !lit.base10_digits().trim_start_matches('0').is_empty(),
+            Expr::Lit(ExprLit {
+                lit: Lit::Float(lit),
+                ..
+            }) => lit.base10_parse::<f64>().is_ok_and(|value| value != 0.),
+            _ => false,
+        },
+        Expr::Paren(paren) => is_negative(&paren.expr),
+        Expr::Call(call) => {
+            let Expr::Path(func) = call.func.as_ref() else {
+                return false;
+            };
+            let segments: Vec<String> = func
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect();
+            let is_length = match segments.as_slice() {
+                [.., ty, _] if ty == "UiRect" => true,
+                [.., variant] => {
+                    ["Px", "Percent", "Vw", "Vh", "VMin", "VMax"].contains(&variant.as_str())
+                }
+                [] => false,
+            };
+            is_length && call.args.iter().any(is_negative)
+        }
+        _ => false,
+    }
+}
+
+/// A compiler warning at `span`. Procedural macros can't emit warnings directly on stable
+/// Rust, so this uses a deprecated item, whose note is shown in the warning.
+fn warning(span: Span, message: &str) -> TokenStream {
+    quote_spanned! {span=>
+        {
+            #[deprecated(note = #message)]
+            struct StyleWarning;
+            let _ = StyleWarning;
+        }
+    }
+}
+
+/// A single view for a list of nodes: nothing, the node itself, or a fragment.
+fn fragment(nodes: &[Node]) -> TokenStream {
+    match nodes {
+        [] => quote!(()),
+        [node] => node.expand(),
+        _ => {
+            let children = tuple(nodes);
+            quote!(::bevy_reactor::ChildViewTuple::fragment(#children))
+        }
+    }
+}
+
+/// A tuple of child views, nested if necessary to stay within the size of the tuples which
+/// implement `ChildViewTuple`.
+fn tuple(nodes: &[Node]) -> TokenStream {
+    let mut items: Vec<TokenStream> = nodes.iter().map(Node::expand).collect();
+    while items.len() > MAX_TUPLE {
+        items = items
+            .chunks(MAX_TUPLE)
+            .map(|chunk| quote!((#(#chunk,)*)))
+            .collect();
+    }
+    quote!((#(#items,)*))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(input: TokenStream) -> Result<String> {
+        syn::parse2::<Nodes>(input).map(|nodes| nodes.root().to_string())
+    }
+
+    #[test]
+    fn test_element() {
+        let expanded = expand(quote! {
+            <NodeBundle style=style_panel name="panel">
+                "Count: "
+                {move |cx| count.get(cx).to_string()}
+                <NodeBundle />
+            </NodeBundle>
+        })
+        .unwrap();
+        let expected = quote! {
+            ::bevy_reactor::ParentView::with_children(
+                ::bevy_reactor::WithStyles::with_styles(
+                    ::bevy_reactor::Element::<NodeBundle>::new(),
+                    style_panel
+                ).named("panel"),
+                (
+                    "Count: ",
+                    ::bevy_reactor::text_computed(move |cx| count.get(cx).to_string()),
+                    ::bevy_reactor::Element::<NodeBundle>::new(),
+                )
+            )
+        };
+        assert_eq!(expanded, expected.to_string());
+    }
+
+    #[test]
+    fn test_control_flow() {
+        let expanded = expand(quote! {
+            if move |cx| open.get(cx) {
+                for item in move |cx| items.get_clone(cx).into_iter() {
+                    {item_row(item)}
+                }
+            }
+        })
+        .unwrap();
+        let expected = quote! {
+            ::bevy_reactor::Cond::new(
+                move |cx| open.get(cx),
+                move || ::bevy_reactor::For::each(
+                    move |cx| items.get_clone(cx).into_iter(),
+                    move |item| item_row(item)
+                ),
+                move || ()
+            )
+        };
+        assert_eq!(expanded, expected.to_string());
+    }
+
+    fn expand_styles(input: TokenStream) -> Result<String> {
+        syn::parse2::<StyleRules>(input).map(|rules| rules.expand().to_string())
+    }
+
+    fn style_problems(input: TokenStream) -> Vec<String> {
+        let rules = syn::parse2::<StyleRules>(input).unwrap();
+        rules
+            .check()
+            .into_iter()
+            .map(|(_, message)| message)
+            .collect()
+    }
+
+    #[test]
+    fn test_styles() {
+        let expanded = expand_styles(quote! {
+            display: ui::Display::Flex;
+            flex: 1., 0., ui::Val::Auto;
+            background_color: "#222";
+            custom_property: 4
+        })
+        .unwrap();
+        let expected = quote! {
+            move |ss: &mut ::bevy_reactor::StyleBuilder| {
+                ::bevy_reactor::StyleBuilderLayout::display(ss, ui::Display::Flex);
+                ::bevy_reactor::StyleBuilderLayout::flex(ss, 1., 0., ui::Val::Auto);
+                ::bevy_reactor::StyleBuilderBackground::background_color(ss, "#222");
+                ss.custom_property(4);
+            }
+        };
+        assert_eq!(expanded, expected.to_string());
+        assert!(expand_styles(quote!(width 10.)).is_err());
+    }
+
+    #[test]
+    fn test_style_warnings() {
+        assert!(style_problems(quote! {
+            display: ui::Display::Grid;
+            grid_template_columns: vec![];
+            margin_left: -4.;
+            width: ui::Val::Percent(100.);
+        })
+        .is_empty());
+
+        assert_eq!(
+            style_problems(quote! {
+                display: ui::Display::Flex;
+                grid_template_columns: vec![];
+                width: -10;
+                padding: ui::UiRect::all(ui::Val::Px(-2.));
+                width: 10;
+            }),
+            vec![
+                "`width` cannot be negative",
+                "`padding` cannot be negative",
+                "`width` is set more than once; only the last value is used",
+                "`grid_template_columns` has no effect unless `display` is `Display::Grid`",
+            ]
+        );
+        assert!(expand_styles(quote!(width: -10))
+            .unwrap()
+            .contains("deprecated"));
+    }
+
+    #[test]
+    fn test_mismatched_tag() {
+        assert!(expand(quote!(<NodeBundle>"text"</ButtonBundle>)).is_err());
+        assert!(expand(quote!(<NodeBundle>"text")).is_err());
+    }
+}
@@ -134,8 +134,8 @@ where
     }
 
     /// Set the color for this overlay as a signal.
-    pub fn with_color_signal(mut self, color: impl Into<Signal<LinearRgba>>) -> Self {
-        self.color = color.into();
+    pub fn with_color_signal(mut self, color: impl IntoSignal<LinearRgba>) -> Self {
+        self.color = color.into_signal();
         self
     }
 
@@ -146,8 +146,8 @@ where
     }
 
     /// Set the transform for this overlay as a signal.
-    pub fn with_transform_signal(mut self, transform: impl Into<Signal<Transform>>) -> Self {
-        self.transform = transform.into();
+    pub fn with_transform_signal(mut self, transform: impl IntoSignal<Transform>) -> Self {
+        self.transform = transform.into_signal();
         self
     }
 }
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use bevy_reactor::*;
+
+/// Resource which holds text copied by editing controls such as
+/// [`TextInput`](crate::controls::TextInput).
+///
+/// On native platforms, the clipboard is private to the app. In the browser, copied text is
+/// also written to the system clipboard, and pasting reads from the system clipboard, so
+/// that text can be copied to and from other pages. Browsers only allow this in response to
+/// user input, and may ask the user for permission; if reading the system clipboard fails,
+/// the text last copied within the app is pasted instead.
+#[derive(Resource, Default)]
+pub struct Clipboard {
+    text: String,
+}
+
+impl Clipboard {
+    /// The text most recently copied within the app.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Copy text to the clipboard.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+        #[cfg(target_arch = "wasm32")]
+        if let Some(window) = web_sys::window() {
+            // The write completes in the background; there is nothing to do if it fails.
+            let _ = window.navigator().clipboard().write_text(&self.text);
+        }
+    }
+
+    /// Read text from the clipboard, and pass it to `on_paste`. On native platforms the
+    /// callback is run immediately; in the browser, it is run in a later frame, once the
+    /// system clipboard has been read.
+    pub fn paste(world: &mut World, on_paste: Callback<String>) {
+        let text = world
+            .get_resource::<Clipboard>()
+            .map(|clipboard| clipboard.text.clone())
+            .unwrap_or_default();
+        #[cfg(not(target_arch = "wasm32"))]
+        world.run_callback(on_paste, text);
+        #[cfg(target_arch = "wasm32")]
+        world.spawn_task(read_system_clipboard(text), move |world, text| {
+            world.run_callback(on_paste, text);
+        });
+    }
+}
+
+/// Read the browser's clipboard, returning `fallback` if it can't be read.
+#[cfg(target_arch = "wasm32")]
+async fn read_system_clipboard(fallback: String) -> String {
+    let Some(window) = web_sys::window() else {
+        return fallback;
+    };
+    let promise = window.navigator().clipboard().read_text();
+    match wasm_bindgen_futures::JsFuture::from(promise).await {
+        Ok(text) => text.as_string().unwrap_or(fallback),
+        Err(_) => fallback,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reactor::testing::TestHarness;
+
+    use super::*;
+
+    #[test]
+    fn test_clipboard() {
+        let mut harness = TestHarness::new();
+        let world = harness.world_mut();
+        world.init_resource::<Clipboard>();
+        world.resource_mut::<Clipboard>().set_text("copied");
+        let (pasted, on_paste) = {
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            let mut cx = Cx::new((), world, &mut scope);
+            let pasted = cx.create_mutable(String::new());
+            let on_paste = cx.create_callback(move |cx: &mut Cx<String>| {
+                let text = cx.props.clone();
+                pasted.set_clone(cx, text);
+            });
+            (pasted, on_paste)
+        };
+        Clipboard::paste(world, on_paste);
+        harness.update();
+        assert_eq!(pasted.get_clone(harness.world_mut()), "copied");
+    }
+}
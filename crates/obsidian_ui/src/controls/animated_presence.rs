@@ -0,0 +1,85 @@
+use std::sync::Arc;
+
+use bevy_reactor::*;
+
+use crate::hooks::{BistableTransitionState, CreateBistableTransition};
+
+/// Function which builds the content of an [`AnimatedPresence`].
+type PresenceFn = Arc<dyn Fn(Signal<BistableTransitionState>) -> ViewHandle + Send + Sync>;
+
+/// A wrapper which plays an enter transition when its content is shown, and delays razing
+/// the content until an exit transition has finished. The content is built by a function
+/// which is passed the transition state, so that it can animate itself, for example with an
+/// [`AnimatedTransition`](crate::animation::AnimatedTransition):
+///
+/// ```ignore
+/// AnimatedPresence::new(open.signal(), |state| {
+///     Element::<NodeBundle>::new().create_effect(move |cx, ent| {
+///         let target = if state.get(cx).is_open() { 1. } else { 0. };
+///         let mut entt = cx.world_mut().entity_mut(ent);
+///         AnimatedTransition::<AnimatedScale>::start(&mut entt, Vec3::splat(target), 0.3);
+///     })
+/// })
+/// ```
+///
+/// To animate a row leaving a `For` list, keep the item in the list while it exits: drive
+/// `show` from a flag on the item, and remove the item in the `on_exited` callback.
+#[derive(Clone)]
+pub struct AnimatedPresence {
+    show: Signal<bool>,
+    duration: f32,
+    content: PresenceFn,
+    on_exited: Option<Callback>,
+}
+
+impl AnimatedPresence {
+    /// Create a new `AnimatedPresence`, which shows the content built by `content` while
+    /// `show` is true.
+    pub fn new<V: Into<ViewHandle>>(
+        show: Signal<bool>,
+        content: impl Fn(Signal<BistableTransitionState>) -> V + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            show,
+            duration: 0.3,
+            content: Arc::new(move |state| content(state).into()),
+            on_exited: None,
+        }
+    }
+
+    /// Set the duration of the enter and exit transitions, in seconds.
+    pub fn with_duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Set a callback which is called when the exit transition has finished, and the content
+    /// has been razed.
+    pub fn on_exited(mut self, on_exited: Option<Callback>) -> Self {
+        self.on_exited = on_exited;
+        self
+    }
+}
+
+impl ViewFactory for AnimatedPresence {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let on_exited = self.on_exited;
+        let state = cx.create_bistable_transition(self.show, self.duration);
+
+        cx.create_effect(move |ve| {
+            let state = state.get(ve);
+            if state == BistableTransitionState::Exited {
+                if let Some(on_exited) = on_exited {
+                    ve.run_callback(on_exited, ());
+                }
+            }
+        });
+
+        let content = self.content.clone();
+        Cond::new(
+            move |cx| state.get(cx) != BistableTransitionState::Exited,
+            move || content(state),
+            || (),
+        )
+    }
+}
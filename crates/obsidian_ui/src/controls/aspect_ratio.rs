@@ -0,0 +1,100 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::hooks::CreateSizeSignal;
+
+/// How an [`AspectRatio`] container fits its content into the available space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AspectRatioMode {
+    /// The content is as large as possible while fitting entirely within the container,
+    /// leaving empty bars on two sides (letterboxing).
+    #[default]
+    Contain,
+
+    /// The content is as small as possible while covering the entire container. The parts
+    /// that overflow the container are clipped.
+    Cover,
+}
+
+fn style_aspect_ratio(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Relative)
+        .overflow(ui::OverflowAxis::Clip)
+        .min_width(0)
+        .min_height(0);
+}
+
+fn style_aspect_ratio_inner(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+/// A container which sizes its content to a fixed aspect ratio within the space available,
+/// centering it. Used for viewport previews, thumbnails and similar widgets. The container
+/// itself takes its size from its parent, so it should be given a size or a flex grow
+/// factor.
+pub struct AspectRatio {
+    /// The desired ratio of width to height.
+    pub ratio: Signal<f32>,
+
+    /// Whether the content should be fitted inside the container, or cover it.
+    pub mode: AspectRatioMode,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// The content, which will be sized to the desired aspect ratio.
+    pub children: ViewHandle,
+}
+
+impl Default for AspectRatio {
+    fn default() -> Self {
+        Self {
+            ratio: Signal::Constant(1.),
+            mode: AspectRatioMode::default(),
+            style: StyleHandle::default(),
+            children: ViewHandle::default(),
+        }
+    }
+}
+
+impl ViewFactory for AspectRatio {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let id = cx.create_entity();
+        let size = cx.create_size_signal(id);
+        let ratio = self.ratio;
+        let mode = self.mode;
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("aspect_ratio")
+            .with_styles((style_aspect_ratio, self.style.clone()))
+            .with_children(
+                Element::<NodeBundle>::new()
+                    .with_styles(style_aspect_ratio_inner)
+                    .create_effect(move |cx, ent| {
+                        let available = size.get(cx);
+                        let ratio = ratio.get(cx).max(f32::EPSILON);
+                        let content = fit_aspect_ratio(available, ratio, mode);
+                        let offset = (available - content) * 0.5;
+                        let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                        style.left = ui::Val::Px(offset.x);
+                        style.top = ui::Val::Px(offset.y);
+                        style.width = ui::Val::Px(content.x);
+                        style.height = ui::Val::Px(content.y);
+                    })
+                    .with_child(&self.children),
+            )
+    }
+}
+
+/// Compute the size of a rectangle with the given aspect ratio, fitted to `available`.
+fn fit_aspect_ratio(available: Vec2, ratio: f32, mode: AspectRatioMode) -> Vec2 {
+    let by_width = Vec2::new(available.x, available.x / ratio);
+    let by_height = Vec2::new(available.y * ratio, available.y);
+    let width_fits = by_width.y <= available.y;
+    match (mode, width_fits) {
+        (AspectRatioMode::Contain, true) | (AspectRatioMode::Cover, false) => by_width,
+        (AspectRatioMode::Contain, false) | (AspectRatioMode::Cover, true) => by_height,
+    }
+}
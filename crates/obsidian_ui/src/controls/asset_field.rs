@@ -0,0 +1,190 @@
+use std::any::TypeId;
+
+use bevy::{
+    asset::{AssetPath, UntypedHandle},
+    prelude::*,
+    ui,
+};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{colors, size::Size};
+
+use super::Button;
+
+/// Event sent when the browse button of an [`AssetField`] is clicked. The application's asset
+/// browser should respond by showing only assets of type `asset_type`, and by invoking
+/// `on_select` with the handle chosen by the user. Applications without an asset browser,
+/// including those running in the browser, can add the
+/// [`FileDialogPlugin`](crate::file_dialog::FileDialogPlugin) to open the platform's file
+/// dialog instead.
+#[derive(Event, Clone)]
+pub struct BrowseAssets {
+    /// The type of asset the field accepts.
+    pub asset_type: TypeId,
+
+    /// Name of the asset type, for display.
+    pub type_name: &'static str,
+
+    /// Callback to invoke with the asset selected in the browser.
+    pub on_select: Callback<UntypedHandle>,
+
+    /// Function which loads an asset of the field's type from a path.
+    pub load: fn(&AssetServer, AssetPath<'static>) -> UntypedHandle,
+}
+
+/// Component that marks a UI node as representing an asset, for example a tile in an asset
+/// browser. The node can be dragged onto an [`AssetField`] of a matching type to assign it.
+#[derive(Component, Clone)]
+pub struct AssetReference(pub UntypedHandle);
+
+fn style_asset_field(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .gap(2)
+        .background_color(colors::U1)
+        .color(colors::FOREGROUND);
+}
+
+fn style_asset_thumbnail(ss: &mut StyleBuilder) {
+    ss.width(16).height(16).margin_left(4);
+}
+
+fn style_asset_path(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .flex_grow(1.)
+        .min_width(0)
+        .overflow(ui::OverflowAxis::Clip)
+        .padding((6, 0));
+}
+
+fn style_asset_field_button(ss: &mut StyleBuilder) {
+    ss.padding((4, 0));
+}
+
+/// A field which references an asset of type `A`. Displays the asset path, and a thumbnail
+/// if the asset is an image. The asset can be changed by using the browse button, or by
+/// dragging an [`AssetReference`] onto the field.
+pub struct AssetField<A: Asset> {
+    /// The referenced asset.
+    pub value: Signal<Handle<A>>,
+
+    /// Field vertical size.
+    pub size: Size,
+
+    /// Whether the field is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the field.
+    pub style: StyleHandle,
+
+    /// Callback called when a new asset is chosen.
+    pub on_change: Option<Callback<Handle<A>>>,
+}
+
+impl<A: Asset> Default for AssetField<A> {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(Handle::default()),
+            size: Size::default(),
+            disabled: Signal::Constant(false),
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl<A: Asset> ViewFactory for AssetField<A> {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let value = self.value.clone();
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+        let size = self.size;
+
+        let on_select = cx.create_callback(move |cx: &mut Cx<UntypedHandle>| {
+            if let (Ok(handle), Some(on_change)) = (cx.props.clone().try_typed::<A>(), on_change) {
+                cx.run_callback(on_change, handle);
+            }
+        });
+
+        let on_browse = cx.create_callback(move |cx| {
+            cx.world_mut().send_event(BrowseAssets {
+                asset_type: TypeId::of::<A>(),
+                type_name: std::any::type_name::<A>(),
+                on_select,
+                load: |server, path| server.load::<A>(path).untyped(),
+            });
+        });
+
+        let thumbnail = value.clone();
+        let path = value.clone();
+
+        Element::<NodeBundle>::new()
+            .named("asset_field")
+            .with_styles((
+                style_asset_field,
+                move |ss: &mut StyleBuilder| {
+                    ss.min_height(size.height());
+                },
+                self.style.clone(),
+            ))
+            .insert(On::<Pointer<Drop>>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<Pointer<Drop>>>()
+                    .unwrap();
+                event.stop_propagation();
+                let dropped = event.dropped;
+                if disabled.get(world) {
+                    return;
+                }
+                if let Some(reference) = world.get::<AssetReference>(dropped) {
+                    if let (Ok(handle), Some(on_change)) =
+                        (reference.0.clone().try_typed::<A>(), on_change)
+                    {
+                        world.run_callback(on_change, handle);
+                    }
+                }
+            }))
+            .with_children((
+                Cond::new(
+                    move |cx| {
+                        TypeId::of::<A>() == TypeId::of::<Image>()
+                            && thumbnail.map(cx, |h| *h != Handle::default())
+                    },
+                    {
+                        let value = value.clone();
+                        move || {
+                            let value = value.clone();
+                            Element::<ImageBundle>::new()
+                                .with_styles(style_asset_thumbnail)
+                                .insert_computed(move |cx| {
+                                    UiImage::new(value.get_clone(cx).untyped().typed::<Image>())
+                                })
+                        }
+                    },
+                    || (),
+                ),
+                Element::<NodeBundle>::new()
+                    .with_styles(style_asset_path)
+                    .with_children(text_computed(move |cx| {
+                        let handle = path.get_clone(cx);
+                        match handle.path() {
+                            Some(path) => path.to_string(),
+                            None if handle == Handle::default() => "None".to_string(),
+                            None => format!("{:?}", handle.id()),
+                        }
+                    })),
+                Button {
+                    children: "Browse".into(),
+                    size: Size::Xxs,
+                    disabled,
+                    style: StyleHandle::new(style_asset_field_button),
+                    on_click: Some(on_browse),
+                    ..default()
+                },
+            ))
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::{prelude::*, ui};
+use bevy_color::Srgba;
+use bevy_reactor::*;
+
+use crate::colors;
+
+fn style_bar_chart(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::FlexEnd)
+        .column_gap(1)
+        .min_height(32);
+}
+
+fn style_bar(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).flex_basis(0).min_width(1);
+}
+
+/// A bar chart, with one bar per value, drawn from left to right. Intended for small inline
+/// charts such as frame time or memory histories, so there are no axes or labels. Each bar
+/// is a UI node, so the chart can be styled like any other element.
+pub struct BarChart {
+    /// The values to chart. Negative values are drawn as empty bars.
+    pub values: Signal<Vec<f32>>,
+
+    /// The value which fills the height of the chart. If `None`, the chart is scaled to the
+    /// largest value.
+    pub max: Option<f32>,
+
+    /// Color of the bars.
+    pub color: Signal<Srgba>,
+
+    /// Additional styles to be applied to the chart.
+    pub style: StyleHandle,
+}
+
+impl Default for BarChart {
+    fn default() -> Self {
+        Self {
+            values: Signal::Constant(Vec::new()),
+            max: None,
+            color: Signal::Constant(colors::ACCENT),
+            style: StyleHandle::default(),
+        }
+    }
+}
+
+impl ViewFactory for BarChart {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let values = self.values.clone();
+        let max = self.max;
+        let color = self.color;
+
+        Element::<NodeBundle>::new()
+            .named("bar_chart")
+            .with_styles((style_bar_chart, self.style.clone()))
+            .with_children(For::index(
+                move |cx| bar_heights(&values.get_clone(cx), max).into_iter(),
+                move |height, _| {
+                    let height = *height;
+                    Element::<NodeBundle>::new()
+                        .with_styles((style_bar, move |ss: &mut StyleBuilder| {
+                            ss.height(ui::Val::Percent(height * 100.));
+                        }))
+                        .style_dyn(color, |color, ss| {
+                            ss.background_color(color);
+                        })
+                },
+            ))
+    }
+}
+
+/// The heights of the bars, as fractions of the height of the chart.
+fn bar_heights(values: &[f32], max: Option<f32>) -> Vec<f32> {
+    let max = max.unwrap_or_else(|| values.iter().copied().fold(0., f32::max));
+    if max <= 0. {
+        return vec![0.; values.len()];
+    }
+    values
+        .iter()
+        .map(|value| (value / max).clamp(0., 1.))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bar_heights() {
+        assert_eq!(bar_heights(&[1., 2., 4.], None), vec![0.25, 0.5, 1.]);
+        assert_eq!(bar_heights(&[1., -2., 8.], Some(4.)), vec![0.25, 0., 1.]);
+        assert_eq!(bar_heights(&[0., 0.], None), vec![0., 0.]);
+        assert!(bar_heights(&[], None).is_empty());
+    }
+}
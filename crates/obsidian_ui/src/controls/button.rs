@@ -18,7 +18,7 @@ use bevy_reactor::*;
 use crate::{colors, materials::RoundedRectMaterial, size::Size};
 
 /// The variant determines the button's color scheme
-#[derive(Clone, Copy, PartialEq, Default, Debug)]
+#[derive(Clone, Copy, PartialEq, Default, Debug, Reflect)]
 pub enum ButtonVariant {
     /// The default apperance.
     #[default]
@@ -56,7 +56,7 @@ fn style_button_bg(ss: &mut StyleBuilder) {
 }
 
 /// Button widget
-#[derive(Default)]
+#[derive(Default, Reflect)]
 pub struct Button {
     /// Color variant - default, primary or danger.
     pub variant: Signal<ButtonVariant>,
@@ -68,12 +68,15 @@ pub struct Button {
     pub disabled: Signal<bool>,
 
     /// The content to display inside the button.
+    #[reflect(ignore)]
     pub children: ViewHandle,
 
     /// Additional styles to be applied to the button.
+    #[reflect(ignore)]
     pub style: StyleHandle,
 
     /// Callback called when clicked
+    #[reflect(ignore)]
     pub on_click: Option<Callback>,
 
     /// The tab index of the button (default 0).
@@ -86,6 +89,26 @@ pub struct Button {
     pub autofocus: bool,
 }
 
+impl Button {
+    /// Set the color variant, either as a constant or a signal.
+    pub fn with_variant(mut self, variant: impl IntoSignal<ButtonVariant>) -> Self {
+        self.variant = variant.into_signal();
+        self
+    }
+
+    /// Set whether the button is disabled, either as a constant or a signal.
+    pub fn with_disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set the content to display inside the button.
+    pub fn with_children(mut self, children: impl Into<ViewHandle>) -> Self {
+        self.children = children.into();
+        self
+    }
+}
+
 impl ViewFactory for Button {
     fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
         let id = cx.create_entity();
@@ -178,7 +201,7 @@ impl ViewFactory for Button {
                     }
                 }),
             ))
-            .insert_if(self.autofocus, AutoFocus)
+            .insert_if(self.autofocus, || AutoFocus)
             .with_children((
                 Element::<MaterialNodeBundle<RoundedRectMaterial>>::new()
                     .insert(material.clone())
@@ -204,21 +227,10 @@ impl ViewFactory for Button {
                         let material = ui_materials.get_mut(material.clone()).unwrap();
                         material.color = LinearRgba::from(color).into();
                     })
-                    .create_effect(move |cx, entt| {
-                        let is_focused = focused.get(cx);
-                        let mut entt = cx.world_mut().entity_mut(entt);
-                        match is_focused {
-                            true => {
-                                entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
-                                    offset: ui::Val::Px(2.0),
-                                    width: ui::Val::Px(2.0),
-                                });
-                            }
-                            false => {
-                                entt.remove::<Outline>();
-                            }
-                        };
+                    .insert_if(focused, || Outline {
+                        color: colors::FOCUS.into(),
+                        offset: ui::Val::Px(2.0),
+                        width: ui::Val::Px(2.0),
                     }),
                 self.children.clone(),
             ))
@@ -0,0 +1,535 @@
+use std::f32::consts::{FRAC_PI_2, FRAC_PI_8, PI, TAU};
+
+use bevy::{prelude::*, render::camera::ScalingMode, ui};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use super::{Button, ButtonVariant, Slider};
+use crate::size::Size;
+
+/// Differences smaller than this are treated as having reached the target pose.
+const SETTLE_EPSILON: f32 = 0.0001;
+
+/// The position of a camera orbiting around a focus point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RigPose {
+    /// The point which the camera looks at and orbits around.
+    pub focus: Vec3,
+    /// Rotation around the vertical axis, in radians.
+    pub yaw: f32,
+    /// Rotation above or below the horizon, in radians. Negative values look down.
+    pub pitch: f32,
+    /// Distance from the camera to the focus point.
+    pub distance: f32,
+}
+
+impl Default for RigPose {
+    fn default() -> Self {
+        Self {
+            focus: Vec3::ZERO,
+            yaw: 0.5,
+            pitch: -0.4,
+            distance: 3.,
+        }
+    }
+}
+
+impl RigPose {
+    /// The orientation of the camera.
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(EulerRot::YXZ, self.yaw, self.pitch, 0.)
+    }
+
+    /// The camera transform for this pose.
+    pub fn transform(&self) -> Transform {
+        let rotation = self.rotation();
+        Transform::from_translation(self.focus + rotation * Vec3::Z * self.distance)
+            .with_rotation(rotation)
+    }
+}
+
+impl Animatable for RigPose {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        Self {
+            focus: self.focus.lerp(target.focus, t),
+            yaw: self.yaw + (target.yaw - self.yaw) * t,
+            pitch: self.pitch + (target.pitch - self.pitch) * t,
+            distance: self.distance + (target.distance - self.distance) * t,
+        }
+    }
+
+    fn is_near(&self, target: &Self) -> bool {
+        self.focus.distance(target.focus) < SETTLE_EPSILON * target.distance
+            && (self.yaw - target.yaw).abs() < SETTLE_EPSILON
+            && (self.pitch - target.pitch).abs() < SETTLE_EPSILON
+            && (self.distance - target.distance).abs() < SETTLE_EPSILON * target.distance
+    }
+}
+
+/// The axis-aligned views offered by the [`ViewCube`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ViewDirection {
+    /// Looking along -Z.
+    Front,
+    /// Looking along +Z.
+    Back,
+    /// Looking along +X.
+    Left,
+    /// Looking along -X.
+    Right,
+    /// Looking down.
+    Top,
+    /// Looking up.
+    Bottom,
+}
+
+impl ViewDirection {
+    /// The yaw and pitch of the camera for this view.
+    fn angles(self) -> (f32, f32) {
+        match self {
+            ViewDirection::Front => (0., 0.),
+            ViewDirection::Back => (PI, 0.),
+            ViewDirection::Left => (-FRAC_PI_2, 0.),
+            ViewDirection::Right => (FRAC_PI_2, 0.),
+            ViewDirection::Top => (0., -FRAC_PI_2),
+            ViewDirection::Bottom => (0., FRAC_PI_2),
+        }
+    }
+
+    /// Returns true if `pose` is looking in this direction.
+    fn matches(self, pose: &RigPose) -> bool {
+        let (yaw, pitch) = self.angles();
+        let yaw_diff = (pose.yaw - yaw).rem_euclid(TAU);
+        let yaw_matches = yaw_diff < SETTLE_EPSILON || TAU - yaw_diff < SETTLE_EPSILON;
+        (pose.pitch - pitch).abs() < SETTLE_EPSILON && (yaw_matches || pitch.abs() == FRAC_PI_2)
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ViewDirection::Front => "Front",
+            ViewDirection::Back => "Back",
+            ViewDirection::Left => "Left",
+            ViewDirection::Right => "Right",
+            ViewDirection::Top => "Top",
+            ViewDirection::Bottom => "Bottom",
+        }
+    }
+}
+
+/// The type of projection used by a camera rig.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RigProjection {
+    /// Perspective projection.
+    #[default]
+    Perspective,
+    /// Orthographic projection, scaled to match the perspective view at the focus distance.
+    Orthographic,
+}
+
+/// A reactive camera controller which orbits, pans, zooms and flies around a focus point.
+/// Input handlers and controls write to the rig's target pose; the current pose is an
+/// [animated signal](Signal::animated) which follows the target smoothly, so that jumps such
+/// as switching to a preset view are animated. Because both poses are signals, anything can
+/// react to the camera: the [`Viewport3d`](super::Viewport3d) transform, a [`ViewCube`]
+/// highlighting the current view, or an inspector showing the coordinates.
+///
+/// Create a rig with [`CreateCameraRig::create_camera_rig`].
+///
+/// ```ignore
+/// let rig = cx.create_camera_rig(RigPose::default(), 12.);
+/// Viewport3d {
+///     transform: rig.transform(cx),
+///     projection: rig.camera_projection(cx),
+///     ..default()
+/// }
+/// ```
+#[derive(Clone, Copy)]
+pub struct CameraRig {
+    target: Mutable<RigPose>,
+    pose: Signal<RigPose>,
+    projection: Mutable<RigProjection>,
+    distance_range: (f32, f32),
+}
+
+impl CameraRig {
+    /// Set the range of distances the camera can be zoomed within.
+    pub fn with_distance_range(mut self, min: f32, max: f32) -> Self {
+        self.distance_range = (min, max);
+        self
+    }
+
+    /// The range of distances the camera can be zoomed within.
+    pub fn distance_range(&self) -> (f32, f32) {
+        self.distance_range
+    }
+
+    /// The current, smoothed, pose of the camera.
+    pub fn pose(&self) -> Signal<RigPose> {
+        self.pose
+    }
+
+    /// The pose which the camera is moving towards.
+    pub fn target(&self) -> Signal<RigPose> {
+        self.target.signal()
+    }
+
+    /// The projection mode of the camera.
+    pub fn projection(&self) -> Signal<RigProjection> {
+        self.projection.signal()
+    }
+
+    /// A signal containing the camera transform for the current pose.
+    pub fn transform<'p, S: RunContextSetup<'p>>(&self, cx: &mut S) -> Signal<Transform> {
+        let pose = self.pose;
+        cx.create_derived(move |cx| pose.get(cx).transform())
+    }
+
+    /// A signal containing the camera projection. In orthographic mode the scale follows the
+    /// distance to the focus point, so that zooming works the same way in both modes.
+    pub fn camera_projection<'p, S: RunContextSetup<'p>>(&self, cx: &mut S) -> Signal<Projection> {
+        let pose = self.pose;
+        let projection = self.projection;
+        cx.create_derived(move |cx| match projection.get(cx) {
+            RigProjection::Perspective => Projection::Perspective(default()),
+            RigProjection::Orthographic => {
+                // Matches the visible height of the default perspective at the focus.
+                let height = 2. * pose.get(cx).distance * FRAC_PI_8.tan();
+                Projection::Orthographic(OrthographicProjection {
+                    scaling_mode: ScalingMode::FixedVertical(height),
+                    near: -1000.,
+                    ..default()
+                })
+            }
+        })
+    }
+
+    /// Orbit the camera around the focus point by the given yaw and pitch, in radians.
+    pub fn orbit<R: ReadMutable + WriteMutable>(&self, cx: &mut R, delta: Vec2) {
+        self.update_target(cx, |pose| {
+            pose.yaw += delta.x;
+            pose.pitch += delta.y;
+        });
+    }
+
+    /// Returns event handlers which orbit the camera while the element they are inserted on
+    /// is dragged. The pointer is locked for the duration of the drag, so that the camera can
+    /// be turned indefinitely without the cursor reaching the edge of the screen. `speed` is
+    /// the rotation, in radians, per logical pixel of mouse motion.
+    pub fn orbit_handlers(&self, cx: &mut Cx, speed: f32) -> impl Bundle {
+        let rig = *self;
+        // The element being dragged, which owns the pointer lock.
+        let dragging = cx.create_mutable::<Option<Entity>>(None);
+        cx.create_effect(move |cx| {
+            let Some(owner) = dragging.get(cx) else {
+                return;
+            };
+            let delta = cx.use_pointer_delta();
+            let world = cx.world_mut();
+            if delta != Vec2::ZERO && world.resource::<PointerLock>().owner() == Some(owner) {
+                rig.orbit(world, -delta * speed);
+            }
+        });
+        (
+            On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
+                    .unwrap();
+                event.stop_propagation();
+                let owner = event.listener();
+                world.resource_mut::<PointerLock>().lock(owner);
+                dragging.set(world, Some(owner));
+            }),
+            On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                let owner = world
+                    .resource::<ListenerInput<Pointer<DragEnd>>>()
+                    .listener();
+                let mut lock = world.resource_mut::<PointerLock>();
+                if lock.owner() == Some(owner) {
+                    lock.release();
+                }
+                dragging.set(world, None);
+            }),
+        )
+    }
+
+    /// Move the focus point within the view plane. `delta` is in units of the distance to
+    /// the focus point, so that the same gesture pans further when zoomed out.
+    pub fn pan<R: ReadMutable + WriteMutable>(&self, cx: &mut R, delta: Vec2) {
+        self.update_target(cx, |pose| {
+            pose.focus += pose.rotation() * Vec3::new(delta.x, delta.y, 0.) * pose.distance;
+        });
+    }
+
+    /// Multiply the distance to the focus point by `factor`.
+    pub fn zoom<R: ReadMutable + WriteMutable>(&self, cx: &mut R, factor: f32) {
+        self.update_target(cx, |pose| pose.distance *= factor);
+    }
+
+    /// Set the distance to the focus point.
+    pub fn set_distance<R: ReadMutable + WriteMutable>(&self, cx: &mut R, distance: f32) {
+        self.update_target(cx, |pose| pose.distance = distance);
+    }
+
+    /// Move the camera, along with its focus point, relative to the camera's orientation:
+    /// +X is right, +Y is up and -Z is forward.
+    pub fn fly<R: ReadMutable + WriteMutable>(&self, cx: &mut R, motion: Vec3) {
+        self.update_target(cx, |pose| pose.focus += pose.rotation() * motion);
+    }
+
+    /// Turn the camera to look along one of the axes. The camera takes the shortest way
+    /// around to reach the new view.
+    pub fn set_view<R: ReadMutable + WriteMutable>(&self, cx: &mut R, view: ViewDirection) {
+        let (yaw, pitch) = view.angles();
+        self.update_target(cx, |pose| {
+            pose.yaw = yaw + ((pose.yaw - yaw) / TAU).round() * TAU;
+            pose.pitch = pitch;
+        });
+    }
+
+    /// Set the target pose.
+    pub fn set_target<R: ReadMutable + WriteMutable>(&self, cx: &mut R, target: RigPose) {
+        self.update_target(cx, |pose| *pose = target);
+    }
+
+    /// Set the projection mode.
+    pub fn set_projection<W: WriteMutable>(&self, cx: &mut W, projection: RigProjection) {
+        self.projection.set(cx, projection);
+    }
+
+    fn update_target<R: ReadMutable + WriteMutable>(
+        &self,
+        cx: &mut R,
+        update: impl FnOnce(&mut RigPose),
+    ) {
+        let mut pose = self.target.get(cx);
+        update(&mut pose);
+        pose.pitch = pose.pitch.clamp(-FRAC_PI_2, FRAC_PI_2);
+        pose.distance = pose
+            .distance
+            .clamp(self.distance_range.0, self.distance_range.1);
+        self.target.set(cx, pose);
+    }
+}
+
+/// Method to create a [`CameraRig`].
+pub trait CreateCameraRig {
+    /// Create a camera rig starting at `pose`. `smoothing` is the rate at which the camera
+    /// approaches its target, per second; larger values are snappier, and zero disables
+    /// smoothing entirely.
+    fn create_camera_rig(&mut self, pose: RigPose, smoothing: f32) -> CameraRig;
+}
+
+impl<'p, 'w, Props> CreateCameraRig for Cx<'p, 'w, Props> {
+    fn create_camera_rig(&mut self, pose: RigPose, smoothing: f32) -> CameraRig {
+        let target = self.create_mutable(pose);
+        let projection = self.create_mutable(RigProjection::default());
+        CameraRig {
+            target,
+            pose: target.signal().animated(self, smoothing),
+            projection,
+            distance_range: (0.01, f32::MAX),
+        }
+    }
+}
+
+fn style_view_cube(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Grid)
+        .grid_template_columns(vec![ui::RepeatedGridTrack::flex(3, 1.)])
+        .gap(2);
+}
+
+/// A compact set of buttons which turn a [`CameraRig`] to look along one of the axes. The
+/// button for the current view is highlighted.
+#[derive(Clone)]
+pub struct ViewCube {
+    rig: CameraRig,
+    style: StyleHandle,
+}
+
+impl ViewCube {
+    /// Create a view cube which controls `rig`.
+    pub fn new(rig: CameraRig) -> Self {
+        Self {
+            rig,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set additional styles for the view cube.
+    pub fn with_style(mut self, style: StyleHandle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl ViewFactory for ViewCube {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let rig = self.rig;
+        let mut cell = |view: Option<ViewDirection>| -> ViewHandle {
+            let Some(view) = view else {
+                return ViewHandle::default();
+            };
+            Button {
+                children: view.label().into(),
+                size: Size::Xxs,
+                variant: cx.create_derived(move |cx| {
+                    if view.matches(&rig.target().get(cx)) {
+                        ButtonVariant::Selected
+                    } else {
+                        ButtonVariant::Default
+                    }
+                }),
+                on_click: Some(cx.create_callback(move |cx| rig.set_view(cx, view))),
+                ..default()
+            }
+            .into()
+        };
+        // Laid out as an unfolded cube.
+        let children = (
+            cell(None),
+            cell(Some(ViewDirection::Top)),
+            cell(None),
+            cell(Some(ViewDirection::Left)),
+            cell(Some(ViewDirection::Front)),
+            cell(Some(ViewDirection::Right)),
+            cell(None),
+            cell(Some(ViewDirection::Bottom)),
+            cell(Some(ViewDirection::Back)),
+        );
+        Element::<NodeBundle>::new()
+            .named("view_cube")
+            .with_styles((style_view_cube, self.style.clone()))
+            .with_children(children)
+    }
+}
+
+/// A slider which controls the distance of a [`CameraRig`] from its focus point, within the
+/// rig's distance range.
+#[derive(Clone)]
+pub struct ZoomSlider {
+    rig: CameraRig,
+    style: StyleHandle,
+}
+
+impl ZoomSlider {
+    /// Create a zoom slider which controls `rig`.
+    pub fn new(rig: CameraRig) -> Self {
+        Self {
+            rig,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set additional styles for the slider.
+    pub fn with_style(mut self, style: StyleHandle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl ViewFactory for ZoomSlider {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let rig = self.rig;
+        let (min, max) = rig.distance_range();
+        ViewFactoryState::new(Slider {
+            value: cx.create_derived(move |cx| rig.target().get(cx).distance),
+            min: min.into(),
+            max: max.into(),
+            precision: 1,
+            formatted_value: Some(cx.create_derived(move |cx| {
+                format!("Distance: {:.1}", rig.target().get(cx).distance)
+            })),
+            style: self.style.clone(),
+            on_change: Some(cx.create_callback(move |cx| {
+                let distance = cx.props;
+                rig.set_distance(cx, distance);
+            })),
+            ..default()
+        })
+    }
+}
+
+/// A button which switches a [`CameraRig`] between perspective and orthographic projection.
+#[derive(Clone)]
+pub struct ProjectionToggle {
+    rig: CameraRig,
+    style: StyleHandle,
+}
+
+impl ProjectionToggle {
+    /// Create a projection toggle which controls `rig`.
+    pub fn new(rig: CameraRig) -> Self {
+        Self {
+            rig,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set additional styles for the button.
+    pub fn with_style(mut self, style: StyleHandle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl ViewFactory for ProjectionToggle {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let rig = self.rig;
+        ViewFactoryState::new(Button {
+            children: TextComputed::new(move |cx| match rig.projection().get(cx) {
+                RigProjection::Perspective => "Persp".to_string(),
+                RigProjection::Orthographic => "Ortho".to_string(),
+            })
+            .into(),
+            size: Size::Xxs,
+            style: self.style.clone(),
+            on_click: Some(cx.create_callback(move |cx| {
+                let next = match rig.projection.get(cx) {
+                    RigProjection::Perspective => RigProjection::Orthographic,
+                    RigProjection::Orthographic => RigProjection::Perspective,
+                };
+                rig.set_projection(cx, next);
+            })),
+            ..default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_direction() {
+        let pose = RigPose {
+            yaw: TAU + FRAC_PI_2,
+            pitch: 0.,
+            ..default()
+        };
+        assert!(ViewDirection::Right.matches(&pose));
+        assert!(!ViewDirection::Front.matches(&pose));
+
+        // The top view matches regardless of yaw.
+        let pose = RigPose {
+            yaw: 1.,
+            pitch: -FRAC_PI_2,
+            ..default()
+        };
+        assert!(ViewDirection::Top.matches(&pose));
+        let eye = pose.transform().translation;
+        assert!((eye - Vec3::new(0., 3., 0.)).length() < 0.001);
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let start = RigPose::default();
+        let goal = RigPose {
+            distance: 5.,
+            ..start
+        };
+        let halfway = start.interpolate(&goal, 0.5);
+        assert_eq!(halfway.distance, 4.);
+        assert!(!halfway.is_near(&goal));
+        assert!(halfway.interpolate(&goal, 0.99999).is_near(&goal));
+    }
+}
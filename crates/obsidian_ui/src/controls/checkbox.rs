@@ -52,7 +52,7 @@ fn style_checkbox_label(ss: &mut StyleBuilder) {
 }
 
 /// A checkbox widget.
-#[derive(Default)]
+#[derive(Default, Reflect)]
 pub struct Checkbox {
     /// Whether the checkbox is checked.
     pub checked: Signal<bool>,
@@ -61,18 +61,41 @@ pub struct Checkbox {
     pub disabled: Signal<bool>,
 
     /// The content to display inside the button.
+    #[reflect(ignore)]
     pub label: ViewHandle,
 
     /// Additional styles to be applied to the button.
+    #[reflect(ignore)]
     pub style: StyleHandle,
 
     /// Callback called when clicked
+    #[reflect(ignore)]
     pub on_change: Option<Callback<bool>>,
 
     /// The tab index of the button (default 0).
     pub tab_index: i32,
 }
 
+impl Checkbox {
+    /// Set whether the checkbox is checked, either as a constant or a signal.
+    pub fn with_checked(mut self, checked: impl IntoSignal<bool>) -> Self {
+        self.checked = checked.into_signal();
+        self
+    }
+
+    /// Set whether the checkbox is disabled, either as a constant or a signal.
+    pub fn with_disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+
+    /// Set the content to display next to the checkbox.
+    pub fn with_label(mut self, label: impl Into<ViewHandle>) -> Self {
+        self.label = label.into();
+        self
+    }
+}
+
 impl ViewFactory for Checkbox {
     /// Construct a button widget.
     fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
@@ -184,21 +207,10 @@ impl ViewFactory for Checkbox {
                         // let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
                         // bg.0 = color.into();
                     })
-                    .create_effect(move |cx, entt| {
-                        let is_focused = focused.get(cx);
-                        let mut entt = cx.world_mut().entity_mut(entt);
-                        match is_focused {
-                            true => {
-                                entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
-                                    offset: ui::Val::Px(2.0),
-                                    width: ui::Val::Px(2.0),
-                                });
-                            }
-                            false => {
-                                entt.remove::<Outline>();
-                            }
-                        };
+                    .insert_if(focused, || Outline {
+                        color: colors::FOCUS.into(),
+                        offset: ui::Val::Px(2.0),
+                        width: ui::Val::Px(2.0),
                     })
                     .with_children(cond(
                         move |cx| checked.get(cx),
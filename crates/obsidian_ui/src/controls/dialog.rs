@@ -6,11 +6,11 @@ use bevy_mod_picking::{
 };
 use bevy_reactor::*;
 
+use super::AnimatedPresence;
 use crate::{
     animation::{AnimatedBackgroundColor, AnimatedScale, AnimatedTransition},
     colors,
     focus::{KeyPressEvent, TabGroup},
-    hooks::{BistableTransitionState, CreateBistableTransition},
     typography::text_default,
 };
 
@@ -64,6 +64,10 @@ pub struct Dialog {
     /// The content of the dialog.
     pub children: ViewHandle,
 
+    /// Named content regions, which are displayed after `children` using the standard
+    /// dialog layout: [`Dialog::HEADER`], [`Dialog::BODY`] and [`Dialog::FOOTER`].
+    pub slots: Slots,
+
     /// Callback called when the dialog's close button is clicked.
     pub on_close: Option<Callback>,
 
@@ -71,26 +75,34 @@ pub struct Dialog {
     pub on_exited: Option<Callback>,
 }
 
+impl Dialog {
+    /// Slot for content displayed in a [`DialogHeader`].
+    pub const HEADER: &'static str = "header";
+
+    /// Slot for content displayed in a [`DialogBody`].
+    pub const BODY: &'static str = "body";
+
+    /// Slot for content displayed in a [`DialogFooter`], typically the action buttons.
+    pub const FOOTER: &'static str = "footer";
+}
+
 impl ViewFactory for Dialog {
     fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
         let on_close = self.on_close;
-        let on_exited = self.on_exited;
-        let state = cx.create_bistable_transition(self.open, TRANSITION_DURATION);
-        let children = self.children.clone();
+        let children = (
+            self.children.clone(),
+            self.slots
+                .map(Dialog::HEADER, |children| DialogHeader { children }),
+            self.slots
+                .map(Dialog::BODY, |children| DialogBody { children }),
+            self.slots
+                .map(Dialog::FOOTER, |children| DialogFooter { children }),
+        )
+            .fragment();
         let width = self.width;
 
-        cx.create_effect(move |ve| {
-            let state = state.get(ve);
-            if state == BistableTransitionState::Exited {
-                if let Some(on_exited) = on_exited {
-                    ve.run_callback(on_exited, ());
-                }
-            }
-        });
-
-        Cond::new(
-            move |cx| state.get(cx) != BistableTransitionState::Exited,
-            move || {
+        ViewFactoryState::new(
+            AnimatedPresence::new(self.open, move |state| {
                 Portal::new(
                     Element::<NodeBundle>::new()
                         .with_styles(style_dialog_overlay)
@@ -103,14 +115,22 @@ impl ViewFactory for Dialog {
                             }),
                             On::<KeyPressEvent>::run({
                                 move |world: &mut World| {
-                                    let mut event = world
-                                        .get_resource_mut::<ListenerInput<KeyPressEvent>>()
-                                        .unwrap();
-                                    if !event.repeat && event.key_code == KeyCode::Escape {
-                                        event.stop_propagation();
-                                        if let Some(on_close) = on_close {
-                                            world.run_callback(on_close, ());
-                                        }
+                                    let event = world.resource::<ListenerInput<KeyPressEvent>>();
+                                    if event.repeat || event.key_code != KeyCode::Escape {
+                                        return;
+                                    }
+                                    // Only the topmost overlay, from any view root, is
+                                    // dismissed.
+                                    let overlay = event.listener();
+                                    if !world.resource::<OverlayStack>().is_top_node(world, overlay)
+                                    {
+                                        return;
+                                    }
+                                    world
+                                        .resource_mut::<ListenerInput<KeyPressEvent>>()
+                                        .stop_propagation();
+                                    if let Some(on_close) = on_close {
+                                        world.run_callback(on_close, ());
                                     }
                                 }
                             }),
@@ -118,13 +138,10 @@ impl ViewFactory for Dialog {
                         .create_effect(move |cx, ent| {
                             let state = state.get(cx);
                             let mut entt = cx.world_mut().entity_mut(ent);
-                            let target = match state {
-                                BistableTransitionState::Entering
-                                | BistableTransitionState::Entered
-                                | BistableTransitionState::ExitStart => colors::U2.with_alpha(0.7),
-                                BistableTransitionState::EnterStart
-                                | BistableTransitionState::Exiting
-                                | BistableTransitionState::Exited => colors::U2.with_alpha(0.0),
+                            let target = if state.is_open() {
+                                colors::U2.with_alpha(0.7)
+                            } else {
+                                colors::U2.with_alpha(0.0)
                             };
                             AnimatedTransition::<AnimatedBackgroundColor>::start(
                                 &mut entt,
@@ -148,13 +165,10 @@ impl ViewFactory for Dialog {
                                 .create_effect(move |cx, ent| {
                                     let state = state.get(cx);
                                     let mut entt = cx.world_mut().entity_mut(ent);
-                                    let target = match state {
-                                        BistableTransitionState::EnterStart
-                                        | BistableTransitionState::Exiting
-                                        | BistableTransitionState::Exited => Vec3::splat(0.0),
-                                        BistableTransitionState::Entering
-                                        | BistableTransitionState::Entered
-                                        | BistableTransitionState::ExitStart => Vec3::splat(1.0),
+                                    let target = if state.is_open() {
+                                        Vec3::splat(1.0)
+                                    } else {
+                                        Vec3::splat(0.0)
                                     };
                                     AnimatedTransition::<AnimatedScale>::start(
                                         &mut entt,
@@ -165,8 +179,9 @@ impl ViewFactory for Dialog {
                                 .with_child(&children),
                         ),
                 )
-            },
-            || (),
+            })
+            .with_duration(TRANSITION_DURATION)
+            .on_exited(self.on_exited),
         )
     }
 }
@@ -0,0 +1,200 @@
+use bevy::{ecs::event::ManualEventReader, prelude::*, ui};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use crate::{colors, size::Size};
+
+use super::{Button, ButtonVariant};
+
+/// Resource which indicates that an [`EntityField`] is in "eyedropper" mode. While this is
+/// set, the next click on an entity - either in the viewport, or on an outliner row marked with
+/// [`EntityReference`] - will be sent to the field's callback rather than being handled
+/// normally.
+#[derive(Resource, Default)]
+pub struct EntityPickMode {
+    /// The entity field which is waiting for the user to pick an entity.
+    pub field: Option<Entity>,
+
+    /// The `on_change` callback of that field.
+    pub on_change: Option<Callback<Option<Entity>>>,
+}
+
+impl EntityPickMode {
+    /// True if an entity field is currently waiting for the user to pick an entity.
+    pub fn is_active(&self) -> bool {
+        self.field.is_some()
+    }
+}
+
+/// Component that marks a UI node as representing some other entity, for example a row in an
+/// outliner. Clicking on such a node while in eyedropper mode picks the referenced entity, and
+/// the node can be dragged onto an [`EntityField`] to assign it.
+#[derive(Component, Clone, Copy)]
+pub struct EntityReference(pub Entity);
+
+fn style_entity_field(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .gap(2)
+        .background_color(colors::U1)
+        .color(colors::FOREGROUND);
+}
+
+fn style_entity_name(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .flex_grow(1.)
+        .min_width(0)
+        .overflow(ui::OverflowAxis::Clip)
+        .padding((6, 0));
+}
+
+fn style_entity_field_button(ss: &mut StyleBuilder) {
+    ss.padding((4, 0));
+}
+
+/// A field which references an entity, for use in inspectors. Displays the referenced entity's
+/// `Name`. The reference can be changed by using the eyedropper button and then clicking on an
+/// entity, or by dragging an [`EntityReference`] onto the field.
+#[derive(Default)]
+pub struct EntityField {
+    /// The referenced entity.
+    pub value: Signal<Option<Entity>>,
+
+    /// Field vertical size.
+    pub size: Size,
+
+    /// Whether the field is disabled.
+    pub disabled: Signal<bool>,
+
+    /// Additional styles to be applied to the field.
+    pub style: StyleHandle,
+
+    /// Callback called when a new entity is chosen, or the field is cleared.
+    pub on_change: Option<Callback<Option<Entity>>>,
+}
+
+impl ViewFactory for EntityField {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let id = cx.create_entity();
+        let value = self.value;
+        let disabled = self.disabled;
+        let on_change = self.on_change;
+        let size = self.size;
+
+        let picking =
+            cx.create_derived(move |cx| cx.use_resource::<EntityPickMode>().field == Some(id));
+
+        let on_pick = cx.create_callback(move |cx| {
+            let mut mode = cx.world_mut().resource_mut::<EntityPickMode>();
+            if mode.field == Some(id) {
+                *mode = EntityPickMode::default();
+            } else {
+                *mode = EntityPickMode {
+                    field: Some(id),
+                    on_change,
+                };
+            }
+        });
+
+        let pick_variant = cx.create_derived(move |cx| match picking.get(cx) {
+            true => ButtonVariant::Selected,
+            false => ButtonVariant::Default,
+        });
+
+        let on_clear = cx.create_callback(move |cx| {
+            if let Some(on_change) = on_change {
+                cx.run_callback(on_change, None);
+            }
+        });
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("entity_field")
+            .with_styles((
+                style_entity_field,
+                move |ss: &mut StyleBuilder| {
+                    ss.min_height(size.height());
+                },
+                self.style.clone(),
+            ))
+            .insert(On::<Pointer<Drop>>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<Pointer<Drop>>>()
+                    .unwrap();
+                event.stop_propagation();
+                let dropped = event.dropped;
+                if disabled.get(world) {
+                    return;
+                }
+                if let Some(reference) = world.get::<EntityReference>(dropped).copied() {
+                    if let Some(on_change) = on_change {
+                        world.run_callback(on_change, Some(reference.0));
+                    }
+                }
+            }))
+            .with_children((
+                Element::<NodeBundle>::new()
+                    .with_styles(style_entity_name)
+                    .with_children(text_computed(move |cx| match value.get(cx) {
+                        Some(entity) if cx.world().get_entity(entity).is_some() => {
+                            match cx.use_component::<Name>(entity) {
+                                Some(name) => name.as_str().to_string(),
+                                None => format!("{:?}", entity),
+                            }
+                        }
+                        Some(_) => "(missing)".to_string(),
+                        None => "None".to_string(),
+                    })),
+                Button {
+                    children: "Pick".into(),
+                    size: Size::Xxs,
+                    variant: pick_variant,
+                    disabled,
+                    style: StyleHandle::new(style_entity_field_button),
+                    on_click: Some(on_pick),
+                    ..default()
+                },
+                Button {
+                    children: "\u{00d7}".into(),
+                    size: Size::Xxs,
+                    disabled,
+                    style: StyleHandle::new(style_entity_field_button),
+                    on_click: Some(on_clear),
+                    ..default()
+                },
+            ))
+    }
+}
+
+/// System which completes a pick operation when the user clicks on an entity while an
+/// [`EntityField`] is in eyedropper mode.
+pub(crate) fn pick_entity_on_click(
+    world: &mut World,
+    mut reader: Local<ManualEventReader<Pointer<Click>>>,
+) {
+    let clicked: Vec<Entity> = reader
+        .read(world.resource::<Events<Pointer<Click>>>())
+        .map(|ev| ev.target)
+        .collect();
+    let mode = world.resource::<EntityPickMode>();
+    if !mode.is_active() {
+        return;
+    }
+    let on_change = mode.on_change;
+    for target in clicked {
+        // Outliner rows refer to another entity; other UI nodes are ignored. Anything else
+        // was picked in the viewport.
+        let picked = match world.get::<EntityReference>(target) {
+            Some(reference) => reference.0,
+            None if world.get::<Node>(target).is_some() => continue,
+            None => target,
+        };
+        *world.resource_mut::<EntityPickMode>() = EntityPickMode::default();
+        if let Some(on_change) = on_change {
+            world.run_callback(on_change, Some(picked));
+        }
+        break;
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::filter::{FilterExpr, Filterable};
+
+fn style_filtered_list(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch);
+}
+
+/// A list which shows only the items matching a search filter, such as the rows of an
+/// outliner or asset browser. Rows can display their label with a [`HighlightedText`] that
+/// uses the same filter, so that the matching text is highlighted.
+///
+/// [`HighlightedText`]: crate::controls::HighlightedText
+pub struct FilteredList<Item: Filterable + Send + Sync + Clone + PartialEq + 'static> {
+    /// The list of items to filter.
+    pub items: Signal<Vec<Item>>,
+
+    /// The search filter. Only items which match it are shown.
+    pub filter: Signal<FilterExpr>,
+
+    /// View shown when no items match the filter.
+    pub fallback: ViewHandle,
+
+    /// Additional styles to be applied to the list.
+    pub style: StyleHandle,
+
+    /// Function which produces the view for each matching item.
+    pub each: Arc<dyn Fn(&Item) -> ViewHandle + Send + Sync>,
+}
+
+impl<Item: Filterable + Send + Sync + Clone + PartialEq + 'static> ViewFactory
+    for FilteredList<Item>
+{
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let items = self.items.clone();
+        let filter = self.filter.clone();
+        let each = self.each.clone();
+
+        Element::<NodeBundle>::new()
+            .named("filtered_list")
+            .with_styles((style_filtered_list, self.style.clone()))
+            .with_children(
+                For::index(
+                    move |cx| {
+                        let filter = filter.get_clone(cx);
+                        items
+                            .map(cx, |items| matching_items(items, &filter))
+                            .into_iter()
+                    },
+                    move |item, _| each(item),
+                )
+                .with_fallback(self.fallback.clone()),
+            )
+    }
+}
+
+/// The items which match the filter, in their original order.
+fn matching_items<Item: Filterable + Clone>(items: &[Item], filter: &FilterExpr) -> Vec<Item> {
+    items
+        .iter()
+        .filter(|item| filter.matches(*item))
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reactor::testing::TestHarness;
+
+    use super::*;
+
+    fn fruit_list(cx: &mut Cx) -> impl View {
+        let filter = cx.create_mutable(FilterExpr::default());
+        cx.register_signal("filter", filter);
+        let items = ["Apple", "Banana", "Pineapple"].map(String::from).to_vec();
+        ViewFactoryState::new(FilteredList {
+            items: Signal::Constant(items),
+            filter: filter.signal(),
+            fallback: text("None").into(),
+            style: StyleHandle::default(),
+            each: Arc::new(|item: &String| text(&format!("[{}]", item)).into()),
+        })
+    }
+
+    #[test]
+    fn test_filtered_list() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(fruit_list.bind(()));
+        assert_eq!(harness.text(root), "[Apple][Banana][Pineapple]");
+
+        let filter = harness.mutable::<FilterExpr>("filter");
+        harness.set(filter, FilterExpr::parse("APPLE"));
+        assert_eq!(harness.text(root), "[Apple][Pineapple]");
+        harness.set(filter, FilterExpr::parse("apple -pine"));
+        assert_eq!(harness.text(root), "[Apple]");
+        harness.set(filter, FilterExpr::parse("cherry"));
+        assert_eq!(harness.text(root), "None");
+    }
+}
@@ -1,9 +1,13 @@
 use bevy::{prelude::*, ui};
 use bevy_color::Srgba;
-use bevy_mod_picking::prelude::*;
+use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
 
-use crate::{colors, materials::GradientRectMaterial};
+use crate::{
+    colors,
+    drag::{is_precision_drag, DragConstraints},
+    materials::GradientRectMaterial,
+};
 
 const THUMB_WIDTH: f32 = 12.;
 
@@ -72,6 +76,9 @@ impl Default for ColorGradient {
 #[derive(Clone, PartialEq, Default, Copy)]
 struct DragState {
     dragging: bool,
+    /// True when fine-adjusting: the pointer is locked and the value follows the mouse
+    /// motion at a reduced rate.
+    fine: bool,
     offset: f32,
 }
 
@@ -123,6 +130,9 @@ fn style_thumb(ss: &mut StyleBuilder) {
 }
 
 /// Horizontal slider widget that displays a gradient bar and a draggable button.
+///
+/// Holding Shift when the drag starts fine-adjusts the value: the pointer is locked, and the
+/// value follows the mouse at a tenth of the usual rate.
 pub struct GradientSlider {
     /// Gradient to display.
     pub gradient: Signal<ColorGradient>,
@@ -164,6 +174,33 @@ impl Default for GradientSlider {
     }
 }
 
+impl GradientSlider {
+    /// Set the gradient to display, either as a constant or a signal.
+    pub fn with_gradient(mut self, gradient: impl IntoSignal<ColorGradient>) -> Self {
+        self.gradient = gradient.into_signal();
+        self
+    }
+
+    /// Set the current value, either as a constant or a signal.
+    pub fn with_value(mut self, value: impl IntoSignal<f32>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the range of the slider, either as constants or signals.
+    pub fn with_range(mut self, min: impl IntoSignal<f32>, max: impl IntoSignal<f32>) -> Self {
+        self.min = min.into_signal();
+        self.max = max.into_signal();
+        self
+    }
+
+    /// Set whether the slider is disabled, either as a constant or a signal.
+    pub fn with_disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+}
+
 impl ViewFactory for GradientSlider {
     fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
         let slider_id = cx.create_entity();
@@ -231,6 +268,39 @@ impl ViewFactory for GradientSlider {
             }
         });
 
+        // While fine-adjusting, the value follows the motion of the locked pointer, so it is
+        // not limited by the edges of the slider or the screen. The unrounded value is kept
+        // in the drag offset, so that motions smaller than the precision accumulate.
+        cx.create_effect(move |cx| {
+            let delta = cx.use_pointer_delta();
+            let world = cx.world_mut();
+            let ds = drag_state.get(world);
+            if !ds.fine
+                || delta.x == 0.
+                || world.resource::<PointerLock>().owner() != Some(slider_id)
+            {
+                return;
+            }
+            let ent = world.entity(slider_id);
+            let (Some(node), Some(transform)) = (ent.get::<Node>(), ent.get::<GlobalTransform>())
+            else {
+                return;
+            };
+            let slider_width = node.logical_rect(transform).width();
+            let min = min.get(world);
+            let max = max.get(world);
+            if max <= min || slider_width <= 0. {
+                return;
+            }
+            let scale = DragConstraints::default().precision_scale;
+            let offset = (ds.offset + delta.x * scale * (max - min) / slider_width).clamp(min, max);
+            drag_state.set(world, DragState { offset, ..ds });
+            let rounding = f32::powi(10., precision as i32);
+            if let Some(on_change) = on_change {
+                world.run_callback(on_change, (offset * rounding).round() / rounding);
+            }
+        });
+
         Element::<NodeBundle>::for_entity(slider_id)
             .with_styles((style_slider, self.style.clone()))
             .insert((
@@ -240,31 +310,30 @@ impl ViewFactory for GradientSlider {
                         .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
                         .unwrap();
                     event.stop_propagation();
+                    let fine = is_precision_drag(world);
                     drag_state.set(
                         world,
                         DragState {
                             dragging: true,
+                            fine,
                             offset: value.get(world),
                         },
                     );
-                }),
-                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
-                    let ds = drag_state.get(world);
-                    if ds.dragging {
-                        drag_state.set(
-                            world,
-                            DragState {
-                                dragging: false,
-                                offset: value.get(world),
-                            },
-                        );
+                    // Distances are measured from where the pointer is captured, so the
+                    // value and the origin are both taken now.
+                    world.resource_mut::<PointerCapture>().capture(slider_id);
+                    if fine {
+                        world.resource_mut::<PointerLock>().lock(slider_id);
                     }
                 }),
-                On::<Pointer<Drag>>::run(move |world: &mut World| {
+                On::<CapturedPointerUp>::run(move |world: &mut World| {
+                    end_drag(world, slider_id, drag_state, value);
+                }),
+                On::<CapturedPointerMove>::run(move |world: &mut World| {
                     let ds = drag_state.get(world);
-                    if ds.dragging {
+                    if ds.dragging && !ds.fine {
                         let event = world
-                            .get_resource::<ListenerInput<Pointer<Drag>>>()
+                            .get_resource::<ListenerInput<CapturedPointerMove>>()
                             .unwrap();
                         let ent = world.entity(slider_id);
                         let node = ent.get::<Node>();
@@ -288,6 +357,13 @@ impl ViewFactory for GradientSlider {
                         }
                     }
                 }),
+                On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
+                    let mut capture = world.resource_mut::<PointerCapture>();
+                    if capture.target() == Some(slider_id) {
+                        capture.release();
+                    }
+                    end_drag(world, slider_id, drag_state, value);
+                }),
             ))
             .with_children((
                 // Element::<NodeBundle>::new().with_styles(style_alpha),
@@ -334,3 +410,24 @@ impl ViewFactory for GradientSlider {
             ))
     }
 }
+
+/// End a drag of the slider, releasing the pointer lock if fine adjustment was in progress.
+fn end_drag(
+    world: &mut World,
+    slider_id: Entity,
+    drag_state: Mutable<DragState>,
+    value: Signal<f32>,
+) {
+    let mut lock = world.resource_mut::<PointerLock>();
+    if lock.owner() == Some(slider_id) {
+        lock.release();
+    }
+    drag_state.set(
+        world,
+        DragState {
+            dragging: false,
+            fine: false,
+            offset: value.get(world),
+        },
+    );
+}
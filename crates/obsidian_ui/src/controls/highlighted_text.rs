@@ -0,0 +1,54 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::{colors, filter::FilterExpr};
+
+fn style_highlighted_text(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center);
+}
+
+fn style_highlight(ss: &mut StyleBuilder) {
+    ss.background_color(colors::TEXT_SELECT);
+}
+
+/// Displays a text label, highlighting the portions of it which match a search filter. Used
+/// for the rows of searchable lists, grids and trees.
+#[derive(Default)]
+pub struct HighlightedText {
+    /// The text to display.
+    pub text: Signal<String>,
+
+    /// The search filter. Plain text terms in the filter will be highlighted.
+    pub filter: Signal<FilterExpr>,
+
+    /// Additional styles to be applied to the text.
+    pub style: StyleHandle,
+}
+
+impl ViewFactory for HighlightedText {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let label = self.text.clone();
+        let filter = self.filter.clone();
+
+        Element::<NodeBundle>::new()
+            .named("highlighted_text")
+            .with_styles((style_highlighted_text, self.style.clone()))
+            .with_children(For::index(
+                move |cx| {
+                    let label = label.get_clone(cx);
+                    filter.map(cx, |f| f.highlight_segments(&label)).into_iter()
+                },
+                |(segment, highlighted), _| {
+                    Element::<NodeBundle>::new()
+                        .with_styles(if *highlighted {
+                            StyleHandle::new(style_highlight)
+                        } else {
+                            StyleHandle::default()
+                        })
+                        .with_children(text(segment))
+                },
+            ))
+    }
+}
@@ -1,19 +1,105 @@
+#[cfg(feature = "animation")]
+mod animated_presence;
+mod aspect_ratio;
+#[cfg(feature = "inputs")]
+mod asset_field;
+#[cfg(feature = "charts")]
+mod bar_chart;
+#[cfg(feature = "picking")]
 mod button;
+#[cfg(feature = "previews")]
+mod camera_rig;
+#[cfg(feature = "inputs")]
 mod checkbox;
+#[cfg(feature = "overlays")]
 mod dialog;
+#[cfg(feature = "inputs")]
+mod entity_field;
+#[cfg(feature = "data-views")]
+mod filtered_list;
+#[cfg(feature = "color-tools")]
 mod gradient_slider;
+#[cfg(feature = "data-views")]
+mod highlighted_text;
+#[cfg(feature = "previews")]
+mod preview_viewport;
+#[cfg(feature = "picking")]
 mod scrollview;
+#[cfg(feature = "inputs")]
 mod slider;
+#[cfg(feature = "inputs")]
+mod spin_box;
+#[cfg(feature = "picking")]
 mod splitter;
+mod stack;
+#[cfg(feature = "color-tools")]
 mod swatch;
+#[cfg(feature = "inputs")]
 mod text_input;
+#[cfg(feature = "overlays")]
+mod tooltip;
+#[cfg(feature = "overlays")]
+mod tour;
+#[cfg(feature = "previews")]
+mod transform_gizmo;
+#[cfg(feature = "previews")]
+mod viewport_3d;
+#[cfg(feature = "data-views")]
+mod virtual_list;
+mod wrap_panel;
 
+#[cfg(feature = "animation")]
+pub use animated_presence::*;
+pub use aspect_ratio::*;
+#[cfg(feature = "inputs")]
+pub use asset_field::*;
+#[cfg(feature = "charts")]
+pub use bar_chart::*;
+#[cfg(feature = "picking")]
 pub use button::*;
+#[cfg(feature = "previews")]
+pub use camera_rig::*;
+#[cfg(feature = "inputs")]
 pub use checkbox::*;
+#[cfg(feature = "overlays")]
 pub use dialog::*;
+#[cfg(feature = "inputs")]
+pub use entity_field::*;
+#[cfg(feature = "data-views")]
+pub use filtered_list::*;
+#[cfg(feature = "color-tools")]
 pub use gradient_slider::*;
+#[cfg(feature = "data-views")]
+pub use highlighted_text::*;
+#[cfg(feature = "previews")]
+pub(crate) use preview_viewport::propagate_preview_layers;
+#[cfg(feature = "previews")]
+pub use preview_viewport::PreviewViewport;
+#[cfg(feature = "picking")]
 pub use scrollview::{ScrollView, ScrollViewProps, Scrollbar, ScrollbarProps};
+#[cfg(feature = "inputs")]
 pub use slider::*;
+#[cfg(feature = "inputs")]
+pub use spin_box::*;
+#[cfg(feature = "picking")]
 pub use splitter::*;
+pub use stack::*;
+#[cfg(feature = "color-tools")]
 pub use swatch::*;
+#[cfg(feature = "inputs")]
 pub use text_input::*;
+#[cfg(feature = "overlays")]
+pub use tooltip::*;
+#[cfg(feature = "overlays")]
+pub use tour::*;
+#[cfg(feature = "previews")]
+pub use transform_gizmo::*;
+#[cfg(feature = "previews")]
+pub(crate) use viewport_3d::update_viewport_image_size;
+#[cfg(feature = "previews")]
+pub use viewport_3d::Viewport3d;
+#[cfg(feature = "data-views")]
+pub(crate) use virtual_list::update_virtual_rows;
+#[cfg(feature = "data-views")]
+pub use virtual_list::VirtualList;
+pub use wrap_panel::*;
@@ -0,0 +1,182 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_mod_picking::prelude::*;
+use bevy_reactor::*;
+
+use super::{
+    camera_rig::{CreateCameraRig, RigPose},
+    viewport_3d::{viewport_element, Viewport3d},
+};
+use crate::{colors, scrolling::ScrollWheel};
+
+/// Radians of orbit per logical pixel of mouse motion.
+const ORBIT_SPEED: f32 = 0.01;
+
+/// Zoom factor per pixel of scroll.
+const ZOOM_SPEED: f32 = 0.005;
+
+/// Function which populates a preview scene.
+type SceneFn = Arc<dyn Fn(&mut Cx, Entity) + Send + Sync>;
+
+/// Marks the root entity of a preview scene. The render layers are copied to all of the
+/// root's descendants, so that the scene is only seen by its preview camera.
+#[derive(Component)]
+pub(crate) struct PreviewScene(RenderLayers);
+
+/// Displays a small 3D scene, such as a mesh or material preview, rendered to a texture and
+/// embedded in the UI. Dragging on the viewport orbits the camera around the focus point, with
+/// the pointer locked so that the drag is not limited by the edges of the screen, and the
+/// scroll wheel zooms.
+///
+/// The scene is built by a function which is passed a root entity; entities spawned as
+/// children of the root are shown in the preview. The function is run as an effect: when
+/// the signals it reads change, the scene is despawned and built again. Each preview should
+/// use its own render layer, so that it doesn't see other previews or the main scene.
+///
+/// ```ignore
+/// PreviewViewport::new(move |cx, root| {
+///     let material = material_handle.get_clone(cx);
+///     cx.world_mut().entity_mut(root).with_children(|parent| {
+///         parent.spawn(PbrBundle { mesh: sphere.clone(), material, ..default() });
+///         parent.spawn(DirectionalLightBundle::default());
+///     });
+/// })
+/// .with_layer(1)
+/// ```
+#[derive(Clone)]
+pub struct PreviewViewport {
+    scene: SceneFn,
+    layer: u8,
+    focus: Vec3,
+    distance: f32,
+    zoom_range: (f32, f32),
+    clear_color: Color,
+    style: StyleHandle,
+}
+
+impl PreviewViewport {
+    /// Create a preview of the scene built by `scene`.
+    pub fn new(scene: impl Fn(&mut Cx, Entity) + Send + Sync + 'static) -> Self {
+        Self {
+            scene: Arc::new(scene),
+            layer: 1,
+            focus: Vec3::ZERO,
+            distance: 3.,
+            zoom_range: (0.5, 20.),
+            clear_color: colors::U1.into(),
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set the render layer used by the preview scene and camera. The default is layer 1.
+    pub fn with_layer(mut self, layer: u8) -> Self {
+        self.layer = layer;
+        self
+    }
+
+    /// Set the point which the camera orbits around.
+    pub fn with_focus(mut self, focus: Vec3) -> Self {
+        self.focus = focus;
+        self
+    }
+
+    /// Set the initial distance of the camera from the focus point, and the range it can be
+    /// zoomed within.
+    pub fn with_distance(mut self, distance: f32, min: f32, max: f32) -> Self {
+        self.distance = distance;
+        self.zoom_range = (min, max);
+        self
+    }
+
+    /// Set the background color of the preview.
+    pub fn with_clear_color(mut self, color: impl Into<Color>) -> Self {
+        self.clear_color = color.into();
+        self
+    }
+
+    /// Set additional styles for the viewport element, typically its size.
+    pub fn with_style(mut self, style: StyleHandle) -> Self {
+        self.style = style;
+        self
+    }
+}
+
+impl ViewFactory for PreviewViewport {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let layers = RenderLayers::layer(self.layer);
+        let (min_distance, max_distance) = self.zoom_range;
+        let rig = cx
+            .create_camera_rig(
+                RigPose {
+                    focus: self.focus,
+                    distance: self.distance,
+                    ..default()
+                },
+                0.,
+            )
+            .with_distance_range(min_distance, max_distance);
+        let transform = rig.transform(cx);
+        let projection = rig.camera_projection(cx);
+        let orbit_handlers = rig.orbit_handlers(cx, ORBIT_SPEED);
+
+        // The scene root is despawned along with its descendants.
+        let scene_root = cx.create_entity();
+        cx.world_mut()
+            .entity_mut(scene_root)
+            .insert((SpatialBundle::default(), PreviewScene(layers)));
+        cx.on_cleanup(move |world| {
+            if let Some(entt) = world.get_entity_mut(scene_root) {
+                entt.despawn_recursive();
+            }
+        });
+        let scene = self.scene.clone();
+        cx.create_effect(move |cx| {
+            scene(cx, scene_root);
+            cx.on_cleanup(move |world| {
+                if let Some(mut entt) = world.get_entity_mut(scene_root) {
+                    entt.despawn_descendants();
+                }
+            });
+        });
+
+        viewport_element(
+            cx,
+            &Viewport3d {
+                transform,
+                projection,
+                render_layers: layers.into(),
+                clear_color: Some(self.clear_color),
+                style: self.style.clone(),
+            },
+        )
+        .named("preview_viewport")
+        .insert((
+            orbit_handlers,
+            On::<ScrollWheel>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<ScrollWheel>>()
+                    .unwrap();
+                event.stop_propagation();
+                let delta = event.delta;
+                rig.zoom(world, (-delta.y * ZOOM_SPEED).exp());
+            }),
+        ))
+    }
+}
+
+/// Copy the render layers of each preview scene to the entities within it.
+pub(crate) fn propagate_preview_layers(
+    mut commands: Commands,
+    scenes: Query<(Entity, &PreviewScene)>,
+    children: Query<&Children>,
+    layers: Query<&RenderLayers>,
+) {
+    for (root, scene) in scenes.iter() {
+        for entity in children.iter_descendants(root) {
+            if layers.get(entity).ok() != Some(&scene.0) {
+                commands.entity(entity).insert(scene.0);
+            }
+        }
+    }
+}
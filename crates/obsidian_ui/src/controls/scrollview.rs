@@ -1,8 +1,13 @@
 use bevy::{prelude::*, ui};
-use bevy_mod_picking::prelude::*;
+use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
 
-use crate::scrolling::{ScrollArea, ScrollBar, ScrollBarThumb, ScrollContent, ScrollWheel};
+use crate::scrolling::{
+    CullOffscreen, ScrollArea, ScrollBar, ScrollBarThumb, ScrollContent, ScrollWheel,
+};
+
+/// Distance outside the visible region within which content is not culled.
+const CULL_MARGIN: f32 = 200.;
 
 // Style definitions for scrollview widget.
 
@@ -76,6 +81,10 @@ pub struct ScrollViewProps {
     pub scroll_enable_x: bool,
     /// Whether to enable vertical scrolling.
     pub scroll_enable_y: bool,
+    /// Whether to hide, and pause the reactions of, top-level content items which are
+    /// scrolled well out of view. This gives long lists some of the benefit of virtualization,
+    /// at the cost of a frame's delay before items scrolled back into view are updated.
+    pub cull_offscreen: bool,
 }
 
 #[derive(Clone, PartialEq, Default, Copy)]
@@ -131,6 +140,9 @@ impl ViewFactory for ScrollView {
                         ),
                     ))
                     .with_styles(style_scroll_region)
+                    .insert_if(self.0.cull_offscreen, || CullOffscreen {
+                        margin: CULL_MARGIN,
+                    })
                     .with_children(
                         Element::<NodeBundle>::new()
                             .insert(ScrollContent)
@@ -256,7 +268,11 @@ impl ViewFactory for Scrollbar {
                                 drag_state.set(
                                     world,
                                     DragState {
-                                        mode: DragMode::DragY,
+                                        mode: if vertical {
+                                            DragMode::DragY
+                                        } else {
+                                            DragMode::DragX
+                                        },
                                         offset: if vertical {
                                             scroll_area.scroll_top
                                         } else {
@@ -264,14 +280,15 @@ impl ViewFactory for Scrollbar {
                                         },
                                     },
                                 );
+                                // Distances are measured from where the pointer is captured,
+                                // so the scroll offset and the origin are both taken now.
+                                world.resource_mut::<PointerCapture>().capture(id_thumb);
                             }
                         }),
-                        On::<Pointer<Drag>>::run(move |world: &mut World| {
-                            let mut event = world
-                                .get_resource_mut::<ListenerInput<Pointer<Drag>>>()
-                                .unwrap();
-                            event.stop_propagation();
-                            let distance = event.distance;
+                        On::<CapturedPointerMove>::run(move |world: &mut World| {
+                            let distance = world
+                                .resource::<ListenerInput<CapturedPointerMove>>()
+                                .distance;
                             let ds = drag_state.get(world);
                             if let Some(mut scroll_area) =
                                 world.get_mut::<ScrollArea>(id_scroll_area)
@@ -279,11 +296,24 @@ impl ViewFactory for Scrollbar {
                                 handle_thumb_drag(&mut scroll_area, &ds, distance);
                             }
                         }),
-                        On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                        On::<CapturedPointerUp>::run(move |world: &mut World| {
+                            drag_state.set(
+                                world,
+                                DragState {
+                                    mode: DragMode::None,
+                                    offset: 0.,
+                                },
+                            );
+                        }),
+                        On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
                             let mut event = world
-                                .get_resource_mut::<ListenerInput<Pointer<DragEnd>>>()
+                                .get_resource_mut::<ListenerInput<Pointer<PointerCancel>>>()
                                 .unwrap();
                             event.stop_propagation();
+                            let mut capture = world.resource_mut::<PointerCapture>();
+                            if capture.target() == Some(id_thumb) {
+                                capture.release();
+                            }
                             drag_state.set(
                                 world,
                                 DragState {
@@ -292,12 +322,6 @@ impl ViewFactory for Scrollbar {
                                 },
                             );
                         }),
-                        // On::<Pointer<PointerCancel>>::run(
-                        //     move |mut ev: ListenerMut<Pointer<DragEnd>>, mut atoms: AtomStore| {
-                        //         ev.stop_propagation();
-                        //         handle_thumb_drag_end(&mut atoms, drag_state);
-                        //     },
-                        // ),
                     )),
             )
     }
@@ -307,7 +331,7 @@ fn handle_thumb_drag(scroll_area: &mut ScrollArea, ds: &DragState, distance: Vec
     if ds.mode == DragMode::DragY {
         let left = scroll_area.scroll_left;
         let top = if scroll_area.visible_size.y > 0. {
-            ds.offset + distance.y * scroll_area.content_size.x / scroll_area.visible_size.y
+            ds.offset + distance.y * scroll_area.content_size.y / scroll_area.visible_size.y
         } else {
             0.
         };
@@ -315,7 +339,7 @@ fn handle_thumb_drag(scroll_area: &mut ScrollArea, ds: &DragState, distance: Vec
     } else if ds.mode == DragMode::DragX {
         let top = scroll_area.scroll_top;
         let left = if scroll_area.visible_size.x > 0. {
-            ds.offset + distance.x * scroll_area.content_size.y / scroll_area.visible_size.x
+            ds.offset + distance.x * scroll_area.content_size.x / scroll_area.visible_size.x
         } else {
             0.
         };
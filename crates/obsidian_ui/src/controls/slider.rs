@@ -1,9 +1,14 @@
 use bevy::{prelude::*, ui};
 use bevy_color::{LinearRgba, Luminance};
-use bevy_mod_picking::prelude::*;
+use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
 
-use crate::{colors, materials::SliderRectMaterial, RoundedCorners};
+use crate::{
+    colors,
+    drag::{is_precision_drag, DragConstraints},
+    materials::SliderRectMaterial,
+    RoundedCorners,
+};
 
 #[derive(Clone, PartialEq, Default, Copy)]
 enum DragType {
@@ -62,6 +67,23 @@ fn style_button_icon_right(ss: &mut StyleBuilder) {
     ss.background_image("obsidian_ui://icons/chevron_right.png");
 }
 
+/// Colors of the arrow buttons. Each rule overrides the ones before it.
+fn style_button_icon_state(ss: &mut StyleBuilder) {
+    ss.background_color(colors::TRANSPARENT)
+        .when_class("hovering", |ss| {
+            ss.background_color(colors::U4)
+                .when_class("hovering-button", |ss| {
+                    ss.background_color(colors::U4.lighter(0.1));
+                });
+        })
+        .when_class("dragging", |ss| {
+            ss.background_color(colors::TRANSPARENT);
+        })
+        .when_class("held", |ss| {
+            ss.background_color(colors::FOREGROUND);
+        });
+}
+
 fn style_label(ss: &mut StyleBuilder) {
     ss.flex_grow(1.)
         .display(ui::Display::Flex)
@@ -75,6 +97,7 @@ fn style_label(ss: &mut StyleBuilder) {
 }
 
 /// Horizontal slider widget
+#[derive(Reflect)]
 pub struct Slider {
     /// Current slider value.
     pub value: Signal<f32>,
@@ -96,12 +119,15 @@ pub struct Slider {
 
     /// Signal which returns the value formatted as a string. It `None`, then a default
     /// formatter will be used.
+    #[reflect(ignore)]
     pub formatted_value: Option<Signal<String>>,
 
     /// Style handle for slider root element.
+    #[reflect(ignore)]
     pub style: StyleHandle,
 
     /// Callback called when value changes
+    #[reflect(ignore)]
     pub on_change: Option<Callback<f32>>,
 }
 
@@ -121,6 +147,27 @@ impl Default for Slider {
     }
 }
 
+impl Slider {
+    /// Set the current value, either as a constant or a signal.
+    pub fn with_value(mut self, value: impl IntoSignal<f32>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set the range of the slider, either as constants or signals.
+    pub fn with_range(mut self, min: impl IntoSignal<f32>, max: impl IntoSignal<f32>) -> Self {
+        self.min = min.into_signal();
+        self.max = max.into_signal();
+        self
+    }
+
+    /// Set whether the slider is disabled, either as a constant or a signal.
+    pub fn with_disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+}
+
 impl ViewFactory for Slider {
     fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
         let slider_id = cx.create_entity();
@@ -163,8 +210,10 @@ impl ViewFactory for Slider {
                             offset: value.get(world),
                         },
                     );
+                    // Keep receiving moves even if the cursor leaves the slider.
+                    world.resource_mut::<PointerCapture>().capture(slider_id);
                 }),
-                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                On::<CapturedPointerUp>::run(move |world: &mut World| {
                     let ds = drag_state.get(world);
                     if ds.dragging == DragType::Dragging {
                         drag_state.set(
@@ -176,11 +225,11 @@ impl ViewFactory for Slider {
                         );
                     }
                 }),
-                On::<Pointer<Drag>>::run(move |world: &mut World| {
+                On::<CapturedPointerMove>::run(move |world: &mut World| {
                     let ds = drag_state.get(world);
                     if ds.dragging == DragType::Dragging {
                         let event = world
-                            .get_resource::<ListenerInput<Pointer<Drag>>>()
+                            .get_resource::<ListenerInput<CapturedPointerMove>>()
                             .unwrap();
                         let ent = world.entity(slider_id);
                         let node = ent.get::<Node>();
@@ -192,18 +241,36 @@ impl ViewFactory for Slider {
                             let max = max.get(world);
                             let range = max - min;
                             let new_value = if range > 0. {
-                                ds.offset + (event.distance.x * range) / slider_width
+                                DragConstraints::default()
+                                    .with_grid(Vec2::splat(f32::powi(10., -(precision as i32))))
+                                    .with_bounds(Vec2::splat(min), Vec2::splat(max))
+                                    .apply_1d(
+                                        ds.offset,
+                                        (event.distance.x * range) / slider_width,
+                                        is_precision_drag(world),
+                                    )
                             } else {
                                 min + range * 0.5
                             };
-                            let rounding = f32::powi(10., precision as i32);
-                            let new_value = (new_value * rounding).round() / rounding;
                             if let Some(on_change) = on_change {
-                                world.run_callback(on_change, new_value.clamp(min, max));
+                                world.run_callback(on_change, new_value);
                             }
                         }
                     }
                 }),
+                On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
+                    let mut capture = world.resource_mut::<PointerCapture>();
+                    if capture.target() == Some(slider_id) {
+                        capture.release();
+                    }
+                    drag_state.set(
+                        world,
+                        DragState {
+                            dragging: DragType::None,
+                            offset: value.get(world),
+                        },
+                    );
+                }),
             ))
             .create_effect(move |cx, _ent| {
                 let min = min.get(cx);
@@ -358,21 +425,17 @@ fn slider_button(cx: &mut Cx<SliderButtonProps>) -> Element<NodeBundle> {
                     } else {
                         style_button_icon_left
                     },
+                    style_button_icon_state,
                 ))
-                .create_effect(move |cx, ent| {
-                    let ds = drag_state.get(cx);
-                    let is_hovering = hovering.get(cx) && step != 0.0;
-                    let is_hovering_inc = button_hovering.get(cx);
-                    let color = match (ds.dragging, is_hovering, is_hovering_inc) {
-                        (DragType::HoldIncrement, _, _) if step > 0.0 => colors::FOREGROUND,
-                        (DragType::HoldDecrement, _, _) if step < 0.0 => colors::FOREGROUND,
-                        (DragType::Dragging, _, _) => colors::TRANSPARENT,
-                        (_, true, true) => colors::U4.lighter(0.1),
-                        (_, true, false) => colors::U4,
-                        _ => colors::TRANSPARENT,
-                    };
-                    let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
-                    bg.0 = color.into();
+                .class("hovering", move |cx: &Rcx| hovering.get(cx) && step != 0.0)
+                .class("hovering-button", button_hovering)
+                .class("dragging", move |cx: &Rcx| {
+                    drag_state.get(cx).dragging == DragType::Dragging
+                })
+                .class("held", move |cx: &Rcx| {
+                    let dragging = drag_state.get(cx).dragging;
+                    (dragging == DragType::HoldIncrement && step > 0.0)
+                        || (dragging == DragType::HoldDecrement && step < 0.0)
                 }),
         )
 }
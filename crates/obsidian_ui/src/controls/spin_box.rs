@@ -0,0 +1,188 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use super::Button;
+use crate::{colors, scrub::ScrubValue, size::Size, RoundedCorners};
+
+fn style_spin_box(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Stretch)
+        .min_width(64)
+        .height(20)
+        .background_color(colors::U1);
+}
+
+fn style_value(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::Center)
+        .justify_content(ui::JustifyContent::Center)
+        .font("obsidian_ui://fonts/Open_Sans/static/OpenSans-Medium.ttf")
+        .font_size(16);
+}
+
+fn style_step_button(ss: &mut StyleBuilder) {
+    ss.padding(0).width(20);
+}
+
+fn style_numeric_row(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Grid)
+        .grid_template_columns(vec![
+            ui::RepeatedGridTrack::flex(1, 1.),
+            ui::RepeatedGridTrack::flex(1, 2.),
+        ])
+        .align_items(ui::AlignItems::Center)
+        .gap(8);
+}
+
+fn style_row_label(ss: &mut StyleBuilder) {
+    ss.color(colors::U4)
+        .font("obsidian_ui://fonts/Open_Sans/static/OpenSans-Medium.ttf")
+        .font_size(14);
+}
+
+/// A numeric input which shows its value between decrement and increment buttons. Dragging
+/// horizontally on the value scrubs it, as described in [`ScrubValue`].
+#[derive(Clone)]
+pub struct SpinBox {
+    /// Current value.
+    pub value: Signal<f32>,
+
+    /// Minimum value.
+    pub min: Signal<f32>,
+
+    /// Maximum value.
+    pub max: Signal<f32>,
+
+    /// Number of decimal places to round to (0 = integer).
+    pub precision: usize,
+
+    /// Amount to increment when using the buttons.
+    pub step: f32,
+
+    /// Change in value per logical pixel scrubbed.
+    pub scrub_speed: f32,
+
+    /// Style handle for the root element.
+    pub style: StyleHandle,
+
+    /// Callback called when the value changes.
+    pub on_change: Option<Callback<f32>>,
+}
+
+impl Default for SpinBox {
+    fn default() -> Self {
+        Self {
+            value: Signal::Constant(0.),
+            min: Signal::Constant(f32::MIN),
+            max: Signal::Constant(f32::MAX),
+            precision: 0,
+            step: 1.,
+            scrub_speed: 1.,
+            style: StyleHandle::default(),
+            on_change: None,
+        }
+    }
+}
+
+impl SpinBox {
+    /// Create a callback which clamps and rounds a new value before passing it to
+    /// `on_change`.
+    fn create_setter(&self, cx: &mut Cx) -> Callback<f32> {
+        let min = self.min;
+        let max = self.max;
+        let rounding = f32::powi(10., self.precision as i32);
+        let on_change = self.on_change;
+        cx.create_callback(move |cx: &mut Cx<f32>| {
+            let value = ((cx.props * rounding).round() / rounding).clamp(min.get(cx), max.get(cx));
+            if let Some(on_change) = on_change {
+                cx.run_callback(on_change, value);
+            }
+        })
+    }
+
+    /// The scrubbing behavior for this spin box, which can also be attached to other
+    /// elements such as a label.
+    fn scrub(&self, setter: Callback<f32>) -> ScrubValue {
+        ScrubValue::new(self.value, setter).with_speed(self.scrub_speed)
+    }
+}
+
+impl ViewFactory for SpinBox {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let value_id = cx.create_entity();
+        let value = self.value;
+        let step = self.step;
+        let precision = self.precision;
+        let setter = self.create_setter(cx);
+        let scrub = self.scrub(setter).handlers(cx, value_id);
+        let decrement = cx.create_callback(move |cx: &mut Cx| {
+            let next = value.get(cx) - step;
+            cx.run_callback(setter, next);
+        });
+        let increment = cx.create_callback(move |cx: &mut Cx| {
+            let next = value.get(cx) + step;
+            cx.run_callback(setter, next);
+        });
+
+        Element::<NodeBundle>::new()
+            .named("spin_box")
+            .with_styles((style_spin_box, self.style.clone()))
+            .with_children((
+                Button {
+                    children: "-".into(),
+                    size: Size::Xs,
+                    style: StyleHandle::new(style_step_button),
+                    on_click: Some(decrement),
+                    corners: RoundedCorners::Left,
+                    ..default()
+                },
+                Element::<NodeBundle>::for_entity(value_id)
+                    .with_styles(style_value)
+                    .insert(scrub)
+                    .with_children(text_computed(move |cx| {
+                        format!("{:.*}", precision, value.get(cx))
+                    })),
+                Button {
+                    children: "+".into(),
+                    size: Size::Xs,
+                    style: StyleHandle::new(style_step_button),
+                    on_click: Some(increment),
+                    corners: RoundedCorners::Right,
+                    ..default()
+                },
+            ))
+    }
+}
+
+/// A row of an inspector which edits a number: a label followed by a [`SpinBox`]. Dragging
+/// horizontally on the label scrubs the value, in the same way as dragging on the spin box.
+#[derive(Clone, Default)]
+pub struct NumericRow {
+    /// The label shown at the start of the row.
+    pub label: String,
+
+    /// The spin box which edits the value.
+    pub spin_box: SpinBox,
+}
+
+impl ViewFactory for NumericRow {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let label_id = cx.create_entity();
+        let setter = self.spin_box.create_setter(cx);
+        let scrub = self.spin_box.scrub(setter).handlers(cx, label_id);
+
+        Element::<NodeBundle>::new()
+            .named("numeric_row")
+            .with_styles(style_numeric_row)
+            .with_children((
+                Element::<NodeBundle>::for_entity(label_id)
+                    .with_styles(style_row_label)
+                    .insert(scrub)
+                    .with_children(self.label.clone()),
+                self.spin_box.clone(),
+            ))
+    }
+}
@@ -3,7 +3,10 @@ use bevy_color::Luminance;
 use bevy_mod_picking::{events::PointerCancel, prelude::*};
 use bevy_reactor::*;
 
-use crate::colors;
+use crate::{
+    colors,
+    drag::{is_precision_drag, DragConstraints},
+};
 
 /// The direction of the splitter. Represents the direction of the bar, not the items being split.
 #[derive(Clone, PartialEq, Default)]
@@ -36,7 +39,14 @@ fn style_vsplitter_inner(ss: &mut StyleBuilder) {
     ss.display(ui::Display::Flex)
         .width(3)
         // .pointer_events(PointerEvents::None)
-        .height(ui::Val::Percent(20.));
+        .height(ui::Val::Percent(20.))
+        .background_color(colors::U3)
+        .when_class("hovering", |ss| {
+            ss.background_color(colors::U3.lighter(0.02));
+        })
+        .when_class("dragging", |ss| {
+            ss.background_color(colors::U3.lighter(0.05));
+        });
 }
 
 /// Splitter bar which can be dragged
@@ -71,8 +81,10 @@ impl ViewFactory for Splitter {
                             offset: current_offset.get(world),
                         },
                     );
+                    // Keep receiving moves even if the cursor outruns the splitter bar.
+                    world.resource_mut::<PointerCapture>().capture(id);
                 }),
-                On::<Pointer<DragEnd>>::run(move |world: &mut World| {
+                On::<CapturedPointerUp>::run(move |world: &mut World| {
                     drag_state.set(
                         world,
                         DragState {
@@ -81,21 +93,27 @@ impl ViewFactory for Splitter {
                         },
                     );
                 }),
-                On::<Pointer<Drag>>::run({
+                On::<CapturedPointerMove>::run({
                     let on_change = self.on_change;
                     move |world: &mut World| {
                         let event = world
-                            .get_resource::<ListenerInput<Pointer<Drag>>>()
+                            .get_resource::<ListenerInput<CapturedPointerMove>>()
                             .unwrap();
                         let ev = event.distance;
                         let ds = drag_state.get(world);
                         if ds.dragging {
-                            world.run_callback(on_change, ev.x + ds.offset);
+                            let value = DragConstraints::default().apply_1d(
+                                ds.offset,
+                                ev.x,
+                                is_precision_drag(world),
+                            );
+                            world.run_callback(on_change, value);
                         }
                     }
                 }),
                 On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
                     println!("Splitter Cancel");
+                    world.resource_mut::<PointerCapture>().release();
                     drag_state.set(
                         world,
                         DragState {
@@ -108,18 +126,8 @@ impl ViewFactory for Splitter {
             .with_children(
                 Element::<NodeBundle>::new()
                     .with_styles(style_vsplitter_inner)
-                    .create_effect(move |cx, ent| {
-                        // Color change on hover / drag
-                        let ds = drag_state.get(cx);
-                        let is_hovering = hovering.get(cx);
-                        let color = match (ds.dragging, is_hovering) {
-                            (true, _) => colors::U3.lighter(0.05),
-                            (false, true) => colors::U3.lighter(0.02),
-                            (false, false) => colors::U3,
-                        };
-                        let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
-                        bg.0 = color.into();
-                    }),
+                    .class("hovering", hovering)
+                    .class("dragging", move |cx: &Rcx| drag_state.get(cx).dragging),
             )
     }
 }
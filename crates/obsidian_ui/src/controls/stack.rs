@@ -0,0 +1,188 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::colors;
+
+/// A container which arranges its children in a horizontal row.
+#[derive(Clone, Default)]
+pub struct HStack {
+    /// Spacing between children, in pixels.
+    pub gap: f32,
+
+    /// Cross-axis (vertical) alignment of children.
+    pub align: ui::AlignItems,
+
+    /// Main-axis (horizontal) distribution of children.
+    pub justify: ui::JustifyContent,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// The content of the stack.
+    pub children: ViewHandle,
+}
+
+impl ViewFactory for HStack {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let gap = self.gap;
+        let align = self.align;
+        let justify = self.justify;
+        Element::<NodeBundle>::new()
+            .named("hstack")
+            .with_styles((
+                move |ss: &mut StyleBuilder| {
+                    ss.display(ui::Display::Flex)
+                        .flex_direction(ui::FlexDirection::Row)
+                        .align_items(align)
+                        .justify_content(justify)
+                        .column_gap(gap);
+                },
+                self.style.clone(),
+            ))
+            .with_child(&self.children)
+    }
+}
+
+/// A container which arranges its children in a vertical column.
+#[derive(Clone, Default)]
+pub struct VStack {
+    /// Spacing between children, in pixels.
+    pub gap: f32,
+
+    /// Cross-axis (horizontal) alignment of children.
+    pub align: ui::AlignItems,
+
+    /// Main-axis (vertical) distribution of children.
+    pub justify: ui::JustifyContent,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// The content of the stack.
+    pub children: ViewHandle,
+}
+
+impl ViewFactory for VStack {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let gap = self.gap;
+        let align = self.align;
+        let justify = self.justify;
+        Element::<NodeBundle>::new()
+            .named("vstack")
+            .with_styles((
+                move |ss: &mut StyleBuilder| {
+                    ss.display(ui::Display::Flex)
+                        .flex_direction(ui::FlexDirection::Column)
+                        .align_items(align)
+                        .justify_content(justify)
+                        .row_gap(gap);
+                },
+                self.style.clone(),
+            ))
+            .with_child(&self.children)
+    }
+}
+
+/// A container which arranges its children in a grid with a fixed number of equal-width
+/// columns. Rows are added as needed.
+#[derive(Clone)]
+pub struct Grid {
+    /// Number of columns.
+    pub columns: u16,
+
+    /// Horizontal spacing between columns, in pixels.
+    pub column_gap: f32,
+
+    /// Vertical spacing between rows, in pixels.
+    pub row_gap: f32,
+
+    /// Alignment of children within their grid cells.
+    pub align: ui::AlignItems,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// The content of the grid.
+    pub children: ViewHandle,
+}
+
+impl Default for Grid {
+    fn default() -> Self {
+        Self {
+            columns: 2,
+            column_gap: 0.,
+            row_gap: 0.,
+            align: ui::AlignItems::default(),
+            style: StyleHandle::default(),
+            children: ViewHandle::default(),
+        }
+    }
+}
+
+impl ViewFactory for Grid {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let columns = self.columns;
+        let column_gap = self.column_gap;
+        let row_gap = self.row_gap;
+        let align = self.align;
+        Element::<NodeBundle>::new()
+            .named("grid")
+            .with_styles((
+                move |ss: &mut StyleBuilder| {
+                    ss.display(ui::Display::Grid)
+                        .grid_template_columns(vec![ui::RepeatedGridTrack::flex(columns, 1.)])
+                        .align_items(align)
+                        .column_gap(column_gap)
+                        .row_gap(row_gap);
+                },
+                self.style.clone(),
+            ))
+            .with_child(&self.children)
+    }
+}
+
+fn style_spacer(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.);
+}
+
+/// An invisible element which expands to fill the available space in a stack, pushing the
+/// elements on either side of it apart.
+#[derive(Clone, Default)]
+pub struct Spacer;
+
+impl ViewFactory for Spacer {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        Element::<NodeBundle>::new()
+            .named("spacer")
+            .with_styles(style_spacer)
+    }
+}
+
+/// A thin line used to separate groups of elements. A horizontal divider is used within a
+/// [`VStack`], a vertical one within an [`HStack`].
+#[derive(Clone, Default)]
+pub struct Divider {
+    /// Whether the divider is a vertical line rather than a horizontal one.
+    pub vertical: bool,
+
+    /// Additional styles to be applied to the divider.
+    pub style: StyleHandle,
+}
+
+impl ViewFactory for Divider {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let vertical = self.vertical;
+        Element::<NodeBundle>::new().named("divider").with_styles((
+            move |ss: &mut StyleBuilder| {
+                ss.background_color(colors::U3)
+                    .align_self(ui::AlignSelf::Stretch);
+                if vertical {
+                    ss.width(1).margin((4, 0));
+                } else {
+                    ss.height(1).margin((0, 4));
+                }
+            },
+            self.style.clone(),
+        ))
+    }
+}
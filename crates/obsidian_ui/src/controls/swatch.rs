@@ -11,7 +11,10 @@ fn style_swatch(ss: &mut StyleBuilder) {
         .display(ui::Display::Flex)
         .padding((12, 0))
         .border(0)
-        .color(colors::FOREGROUND);
+        .color(colors::FOREGROUND)
+        .when_class("selected", |ss| {
+            ss.border(2).border_color(colors::FOREGROUND);
+        });
 }
 
 /// Color swatch widget.
@@ -33,6 +36,20 @@ pub struct Swatch {
     pub on_click: Option<Callback>,
 }
 
+impl Swatch {
+    /// Set the color to display, either as a constant or a signal.
+    pub fn with_color(mut self, color: impl IntoSignal<Srgba>) -> Self {
+        self.color = color.into_signal();
+        self
+    }
+
+    /// Set whether the swatch is selected, either as a constant or a signal.
+    pub fn with_selected(mut self, selected: impl IntoSignal<bool>) -> Self {
+        self.selected = selected.into_signal();
+        self
+    }
+}
+
 impl ViewFactory for Swatch {
     fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
         let color = self.color;
@@ -59,10 +76,9 @@ impl ViewFactory for Swatch {
                     })
                 },
             ))
-            .create_effect(move |cx, ent| {
-                let color = color.get(cx);
-                let mut bg = cx.world_mut().get_mut::<BackgroundColor>(ent).unwrap();
-                bg.0 = color.into();
+            .style_dyn(color, |color, ss| {
+                ss.background_color(color);
             })
+            .class("selected", self.selected)
     }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    clipboard::Clipboard,
     focus::{AutoFocus, KeyCharEvent, KeyPressEvent, TabIndex},
     hooks::CreateFocusSignal,
     RoundedCorners,
@@ -49,6 +50,20 @@ pub struct TextInputProps {
     pub adornments_suffix: ViewHandle,
 }
 
+impl TextInputProps {
+    /// Set the text, either as a constant or a signal.
+    pub fn with_value(mut self, value: impl IntoSignal<String>) -> Self {
+        self.value = value.into_signal();
+        self
+    }
+
+    /// Set whether the text input is disabled, either as a constant or a signal.
+    pub fn with_disabled(mut self, disabled: impl IntoSignal<bool>) -> Self {
+        self.disabled = disabled.into_signal();
+        self
+    }
+}
+
 fn style_text_input(ss: &mut StyleBuilder) {
     ss.border(1)
         .display(ui::Display::Flex)
@@ -170,6 +185,22 @@ impl ViewFactory for TextInput {
 
         let value = self.0.value.clone();
 
+        // Replaces the selection with pasted text.
+        let on_paste = {
+            let on_change = self.0.on_change;
+            let value = value.clone();
+            cx.create_callback(move |cx: &mut Cx<String>| {
+                let pasted = cx.props.clone();
+                let sel = selection.get(cx);
+                let mut new_text = value.get_clone(cx);
+                new_text.replace_range(sel.range(), &pasted);
+                if let Some(on_change) = on_change {
+                    cx.run_callback(on_change, new_text);
+                }
+                selection.set(cx, Selection::single(sel.start() + pasted.len()));
+            })
+        };
+
         let mut ui_materials = cx
             .world_mut()
             .get_resource_mut::<Assets<RoundedRectMaterial>>()
@@ -370,6 +401,41 @@ impl ViewFactory for TextInput {
                                     }
                                     handled = true;
                                 }
+
+                                KeyCode::KeyA if event.command => {
+                                    selection.set(world, Selection::new(text_len, 0));
+                                    handled = true;
+                                }
+
+                                KeyCode::KeyC if event.command => {
+                                    if !sel.is_empty() {
+                                        let text = value.get_clone(world);
+                                        world
+                                            .resource_mut::<Clipboard>()
+                                            .set_text(&text[sel.range()]);
+                                    }
+                                    handled = true;
+                                }
+
+                                KeyCode::KeyX if event.command => {
+                                    if !sel.is_empty() {
+                                        let mut new_text = value.get_clone(world);
+                                        world
+                                            .resource_mut::<Clipboard>()
+                                            .set_text(&new_text[sel.range()]);
+                                        new_text.replace_range(sel.range(), "");
+                                        if let Some(on_change) = on_change {
+                                            world.run_callback(on_change, new_text);
+                                        }
+                                        selection.set(world, Selection::single(sel.start()));
+                                    }
+                                    handled = true;
+                                }
+
+                                KeyCode::KeyV if event.command => {
+                                    Clipboard::paste(world, on_paste);
+                                    handled = true;
+                                }
                                 _ => {}
                             }
 
@@ -383,7 +449,7 @@ impl ViewFactory for TextInput {
                     }
                 }),
             ))
-            .insert_if(self.0.autofocus, AutoFocus)
+            .insert_if(self.0.autofocus, || AutoFocus)
             .with_children((
                 // Background
                 Element::<MaterialNodeBundle<RoundedRectMaterial>>::new()
@@ -404,22 +470,11 @@ impl ViewFactory for TextInput {
                         let material = ui_materials.get_mut(material.clone()).unwrap();
                         material.color = LinearRgba::from(color).into();
                     })
-                    .create_effect(move |cx, entt| {
-                        let is_focused = focused.get(cx);
-                        let mut entt = cx.world_mut().entity_mut(entt);
-                        // TODO: Don't do this as an outline, do it as an inset border.
-                        match is_focused {
-                            true => {
-                                entt.insert(Outline {
-                                    color: colors::FOCUS.into(),
-                                    offset: ui::Val::Px(2.0),
-                                    width: ui::Val::Px(2.0),
-                                });
-                            }
-                            false => {
-                                entt.remove::<Outline>();
-                            }
-                        };
+                    // TODO: Don't do this as an outline, do it as an inset border.
+                    .insert_if(focused, || Outline {
+                        color: colors::FOCUS.into(),
+                        offset: ui::Val::Px(2.0),
+                        width: ui::Val::Px(2.0),
                     }),
                 // Prefix adornments
                 self.0.adornments_prefix.clone(),
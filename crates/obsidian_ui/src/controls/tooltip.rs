@@ -0,0 +1,93 @@
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::{colors, typography::text_default};
+
+/// Distance between the anchor element and the tooltip.
+const TOOLTIP_GAP: f32 = 4.;
+
+fn style_tooltip(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .padding((6, 2))
+        .background_color(colors::U1)
+        .border(1)
+        .border_color(colors::U3)
+        .color(colors::FOREGROUND)
+        .font_size(14)
+        .z_index(300)
+        .pointer_events(false);
+}
+
+/// Shows a short description next to an element while the pointer hovers over it. The
+/// tooltip is a [`Floating`] view, so it is flipped to the other side of the element if it
+/// doesn't fit in the window.
+///
+/// The tooltip can be placed anywhere in the view tree, since it is displayed in an overlay;
+/// typically it is a sibling of the element it describes:
+///
+/// ```ignore
+/// let snap = cx.create_entity();
+/// (
+///     Element::<NodeBundle>::for_entity(snap).with_styles(style_snap_icon),
+///     Tooltip::new(snap, "Snap to grid"),
+/// )
+/// ```
+pub struct Tooltip {
+    /// The element which the tooltip describes. Hovering over the element or any of its
+    /// descendants shows the tooltip.
+    pub anchor: Entity,
+
+    /// The content of the tooltip, usually text.
+    pub content: ViewHandle,
+
+    /// Which side of the element the tooltip is placed on.
+    pub placement: Placement,
+
+    /// Additional styles to be applied to the tooltip.
+    pub style: StyleHandle,
+}
+
+impl Tooltip {
+    /// Construct a tooltip which shows `content` below `anchor`.
+    pub fn new(anchor: Entity, content: impl Into<ViewHandle>) -> Self {
+        Self {
+            anchor,
+            content: content.into(),
+            placement: Placement::Bottom,
+            style: StyleHandle::default(),
+        }
+    }
+
+    /// Set which side of the element the tooltip is placed on.
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+}
+
+impl ViewFactory for Tooltip {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let anchor = self.anchor;
+        let hovering = cx.create_hover_signal(anchor);
+        let content = self.content.clone();
+        let placement = self.placement;
+        let style = self.style.clone();
+
+        Cond::new(
+            move |cx| hovering.get(cx),
+            move || {
+                Floating::new(
+                    anchor,
+                    Element::<NodeBundle>::new()
+                        .named("tooltip")
+                        .with_styles((text_default, style_tooltip, style.clone()))
+                        .with_child(&content),
+                )
+                .with_placement(placement)
+                .with_align(FloatAlign::Center)
+                .with_offset(TOOLTIP_GAP)
+            },
+            || (),
+        )
+    }
+}
@@ -0,0 +1,331 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui, utils::HashSet};
+use bevy_color::Alpha;
+use bevy_reactor::*;
+
+use crate::{
+    colors,
+    controls::{Button, ButtonVariant},
+    focus::TabGroup,
+    size::Size,
+    typography::text_default,
+};
+
+/// A single step of a [`Tour`].
+#[derive(Clone, Default)]
+pub struct TourStep {
+    /// The [`Name`] of the element to highlight. If no element with this name exists, the
+    /// explanation is shown without a spotlight.
+    pub target: String,
+
+    /// The heading of the explanation popover.
+    pub title: String,
+
+    /// The explanation text.
+    pub text: String,
+}
+
+/// Resource which records the ids of tours which have been completed or skipped, so that
+/// they aren't shown again. Apps which want the record to survive a restart can save the
+/// contents of this resource and restore it on startup.
+#[derive(Resource, Default, Clone, Debug)]
+pub struct CompletedTours(pub HashSet<String>);
+
+/// Which side of the spotlight a shade covers.
+#[derive(Clone, Copy)]
+enum Shade {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+/// Distance between the spotlight and the explanation popover.
+const POPOVER_GAP: f32 = 8.;
+
+/// Padding around the spotlighted element.
+const SPOTLIGHT_PADDING: f32 = 4.;
+
+fn style_tour_overlay(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .right(0)
+        .bottom(0)
+        .z_index(200);
+}
+
+fn style_tour_shade(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .background_color(colors::U1.with_alpha(0.7));
+}
+
+fn style_tour_spotlight(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .border(2)
+        .border_color(colors::PRIMARY);
+}
+
+fn style_tour_popover(ss: &mut StyleBuilder) {
+    ss.position(PositionType::Absolute)
+        .display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .row_gap(6)
+        .width(300)
+        .padding((12, 8))
+        .background_color(colors::U2)
+        .border(1)
+        .border_color(colors::U1);
+}
+
+fn style_tour_title(ss: &mut StyleBuilder) {
+    ss.font_size(18);
+}
+
+fn style_tour_footer(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .justify_content(ui::JustifyContent::FlexEnd)
+        .align_items(ui::AlignItems::Center)
+        .column_gap(4);
+}
+
+fn style_tour_counter(ss: &mut StyleBuilder) {
+    ss.flex_grow(1.).color(colors::U4);
+}
+
+/// A guided tour which walks the user through a sequence of elements. Each step dims the
+/// screen except for a spotlight over the target element, and shows an explanation popover
+/// next to it with next, back and skip controls. Once the tour has been finished or skipped,
+/// its id is recorded in the [`CompletedTours`] resource and the tour is not shown again.
+#[derive(Default)]
+pub struct Tour {
+    /// Unique id for this tour, used to record completion.
+    pub id: String,
+
+    /// The steps of the tour, in order.
+    pub steps: Vec<TourStep>,
+
+    /// Callback called when the tour is finished or skipped.
+    pub on_finish: Option<Callback>,
+}
+
+impl ViewFactory for Tour {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let steps = Arc::new(self.steps.clone());
+        let count = steps.len();
+        let step = cx.create_mutable::<usize>(0);
+        let spotlight = cx.create_mutable::<Option<Rect>>(None);
+        let on_finish = self.on_finish;
+
+        let id = self.id.clone();
+        let active = cx.create_derived(move |cx| {
+            !cx.use_resource::<CompletedTours>().0.contains(&id) && step.get(cx) < count
+        });
+
+        let id = self.id.clone();
+        let finish = cx.create_callback(move |cx| {
+            cx.world_mut()
+                .resource_mut::<CompletedTours>()
+                .0
+                .insert(id.clone());
+            if let Some(on_finish) = on_finish {
+                cx.run_callback(on_finish, ());
+            }
+        });
+        let next = cx.create_callback(move |cx| {
+            let index = step.get(cx);
+            if index + 1 < count {
+                step.set(cx, index + 1);
+            } else {
+                cx.run_callback(finish, ());
+            }
+        });
+        let back = cx.create_callback(move |cx| {
+            let index = step.get(cx);
+            if index > 0 {
+                step.set(cx, index - 1);
+            }
+        });
+        let first = cx.create_derived(move |cx| step.get(cx) == 0);
+
+        Cond::new(
+            move |cx| active.get(cx),
+            move || {
+                let steps_title = steps.clone();
+                let steps_text = steps.clone();
+                let steps_target = steps.clone();
+                Portal::new(
+                    Element::<NodeBundle>::new()
+                        .with_styles(style_tour_overlay)
+                        .insert(TabGroup {
+                            order: 0,
+                            modal: true,
+                        })
+                        .create_deferred_effect(move |cx, _| {
+                            // Locate the target element after layout, and track its geometry
+                            // so that the spotlight follows it.
+                            let target = steps_target
+                                .get(step.get(cx))
+                                .and_then(|s| find_named(cx.world_mut(), &s.target));
+                            let rect = target.and_then(|target| {
+                                let node = cx.use_component::<Node>(target)?;
+                                let transform = cx.use_component::<GlobalTransform>(target)?;
+                                Some(node.logical_rect(transform).inset(SPOTLIGHT_PADDING))
+                            });
+                            spotlight.set(cx, rect);
+                        })
+                        .with_children((
+                            shade(spotlight, Shade::Top),
+                            shade(spotlight, Shade::Bottom),
+                            shade(spotlight, Shade::Left),
+                            shade(spotlight, Shade::Right),
+                            Element::<NodeBundle>::new()
+                                .with_styles(style_tour_spotlight)
+                                .create_effect(move |cx, ent| {
+                                    let rect = spotlight.get(cx);
+                                    let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                                    match rect {
+                                        Some(rect) => {
+                                            style.display = ui::Display::Flex;
+                                            style.left = ui::Val::Px(rect.min.x);
+                                            style.top = ui::Val::Px(rect.min.y);
+                                            style.width = ui::Val::Px(rect.width());
+                                            style.height = ui::Val::Px(rect.height());
+                                        }
+                                        None => style.display = ui::Display::None,
+                                    }
+                                }),
+                            Element::<NodeBundle>::new()
+                                .with_styles((text_default, style_tour_popover))
+                                .create_effect(move |cx, ent| {
+                                    let rect = spotlight.get(cx);
+                                    let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+                                    match rect {
+                                        // Anchor the popover below the spotlight.
+                                        Some(rect) => {
+                                            style.left = ui::Val::Px(rect.min.x);
+                                            style.top = ui::Val::Px(rect.max.y + POPOVER_GAP);
+                                        }
+                                        None => {
+                                            style.left = ui::Val::Percent(40.);
+                                            style.top = ui::Val::Percent(40.);
+                                        }
+                                    }
+                                })
+                                .with_children((
+                                    Element::<NodeBundle>::new()
+                                        .with_styles(style_tour_title)
+                                        .with_children(text_computed(move |cx| {
+                                            steps_title[step.get(cx).min(count - 1)].title.clone()
+                                        })),
+                                    text_computed(move |cx| {
+                                        steps_text[step.get(cx).min(count - 1)].text.clone()
+                                    }),
+                                    Element::<NodeBundle>::new()
+                                        .with_styles(style_tour_footer)
+                                        .with_children((
+                                            Element::<NodeBundle>::new()
+                                                .with_styles(style_tour_counter)
+                                                .with_children(text_computed(move |cx| {
+                                                    format!("{} / {}", step.get(cx) + 1, count)
+                                                })),
+                                            Button {
+                                                children: "Skip".into(),
+                                                size: Size::Sm,
+                                                on_click: Some(finish),
+                                                ..default()
+                                            },
+                                            Button {
+                                                children: "Back".into(),
+                                                size: Size::Sm,
+                                                disabled: first,
+                                                on_click: Some(back),
+                                                ..default()
+                                            },
+                                            Button {
+                                                children: text_computed(move |cx| {
+                                                    if step.get(cx) + 1 < count {
+                                                        "Next".to_string()
+                                                    } else {
+                                                        "Done".to_string()
+                                                    }
+                                                })
+                                                .into(),
+                                                size: Size::Sm,
+                                                autofocus: true,
+                                                on_click: Some(next),
+                                                ..default()
+                                            }
+                                            .with_variant(ButtonVariant::Primary),
+                                        )),
+                                )),
+                        )),
+                )
+            },
+            || (),
+        )
+    }
+}
+
+/// One of the four dimmed rectangles which surround the spotlight.
+fn shade(spotlight: Mutable<Option<Rect>>, side: Shade) -> Element<NodeBundle> {
+    Element::<NodeBundle>::new()
+        .with_styles(style_tour_shade)
+        .create_effect(move |cx, ent| {
+            let rect = spotlight.get(cx);
+            let mut style = cx.world_mut().get_mut::<Style>(ent).unwrap();
+            let Some(rect) = rect else {
+                // No spotlight, so the top shade covers the whole screen.
+                style.display = match side {
+                    Shade::Top => ui::Display::Flex,
+                    _ => ui::Display::None,
+                };
+                style.left = ui::Val::Px(0.);
+                style.right = ui::Val::Px(0.);
+                style.top = ui::Val::Px(0.);
+                style.bottom = ui::Val::Px(0.);
+                style.width = ui::Val::Auto;
+                style.height = ui::Val::Auto;
+                return;
+            };
+            style.display = ui::Display::Flex;
+            match side {
+                Shade::Top => {
+                    style.left = ui::Val::Px(0.);
+                    style.right = ui::Val::Px(0.);
+                    style.top = ui::Val::Px(0.);
+                    style.bottom = ui::Val::Auto;
+                    style.height = ui::Val::Px(rect.min.y);
+                }
+                Shade::Bottom => {
+                    style.left = ui::Val::Px(0.);
+                    style.right = ui::Val::Px(0.);
+                    style.top = ui::Val::Px(rect.max.y);
+                    style.bottom = ui::Val::Px(0.);
+                }
+                Shade::Left => {
+                    style.left = ui::Val::Px(0.);
+                    style.top = ui::Val::Px(rect.min.y);
+                    style.width = ui::Val::Px(rect.min.x);
+                    style.height = ui::Val::Px(rect.height());
+                }
+                Shade::Right => {
+                    style.left = ui::Val::Px(rect.max.x);
+                    style.right = ui::Val::Px(0.);
+                    style.top = ui::Val::Px(rect.min.y);
+                    style.height = ui::Val::Px(rect.height());
+                }
+            }
+        })
+}
+
+/// Find the first entity with the given [`Name`].
+fn find_named(world: &mut World, name: &str) -> Option<Entity> {
+    world
+        .query::<(Entity, &Name)>()
+        .iter(world)
+        .find(|(_, n)| n.as_str() == name)
+        .map(|(e, _)| e)
+}
@@ -0,0 +1,417 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{
+    math::primitives::{Cuboid, Torus},
+    prelude::*,
+    render::camera::RenderTarget,
+};
+use bevy_mod_picking::{events::PointerCancel, prelude::*};
+use bevy_reactor::*;
+
+use super::viewport_3d::viewport_pixel_scale;
+use crate::drag::{is_precision_drag, DragConstraints};
+
+/// Radians of rotation per pixel dragged.
+const ROTATE_SPEED: f32 = 0.01;
+
+/// Change in scale per pixel dragged.
+const SCALE_SPEED: f32 = 0.01;
+
+/// Which kind of manipulation a [`TransformGizmo`] performs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GizmoMode {
+    /// Move the target along an axis.
+    #[default]
+    Translate,
+    /// Rotate the target around an axis.
+    Rotate,
+    /// Scale the target along an axis.
+    Scale,
+}
+
+/// One of the three axes of a [`TransformGizmo`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum GizmoAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl GizmoAxis {
+    const ALL: [GizmoAxis; 3] = [GizmoAxis::X, GizmoAxis::Y, GizmoAxis::Z];
+
+    fn direction(self) -> Vec3 {
+        match self {
+            GizmoAxis::X => Vec3::X,
+            GizmoAxis::Y => Vec3::Y,
+            GizmoAxis::Z => Vec3::Z,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            GizmoAxis::X => Color::rgb(0.9, 0.2, 0.2),
+            GizmoAxis::Y => Color::rgb(0.2, 0.8, 0.2),
+            GizmoAxis::Z => Color::rgb(0.2, 0.4, 0.9),
+        }
+    }
+
+    /// Rotation which turns a torus, which lies in the XZ plane, to circle this axis.
+    fn ring_rotation(self) -> Quat {
+        match self {
+            GizmoAxis::X => Quat::from_rotation_z(FRAC_PI_2),
+            GizmoAxis::Y => Quat::IDENTITY,
+            GizmoAxis::Z => Quat::from_rotation_x(FRAC_PI_2),
+        }
+    }
+}
+
+/// A 3D manipulator which moves, rotates or scales the `target` entity by dragging on
+/// handles along each axis. The handles are spawned reactively, and are rebuilt when the
+/// mode changes. Drag results are written back to the target's `Transform` through
+/// [`bind_component`](bevy_reactor::RunContextSetup::bind_component), so the gizmo stays in
+/// step with any other editor of the same transform, such as an inspector panel.
+///
+/// Axes are in the target's parent space, and the handles are oriented to match. The camera
+/// may render either to a window or to a [`Viewport3d`](super::Viewport3d). Snapping and
+/// precision dragging (holding Shift) use the x components of the [`DragConstraints`]: the
+/// grid step is in world units for translation, radians for rotation, and scale units for
+/// scaling. Handles are picked with `bevy_mod_picking`, so the app must include a 3D picking
+/// backend.
+///
+/// ```ignore
+/// TransformGizmo::new(selected, camera)
+///     .with_mode(mode.signal())
+///     .with_constraints(DragConstraints::default().with_grid(Vec2::splat(0.25)))
+/// ```
+#[derive(Clone)]
+pub struct TransformGizmo {
+    target: Entity,
+    camera: Entity,
+    mode: Signal<GizmoMode>,
+    constraints: DragConstraints,
+    size: f32,
+}
+
+impl TransformGizmo {
+    /// Create a gizmo which manipulates `target`, as seen through `camera`. The camera is
+    /// used to map pointer motion onto each axis.
+    pub fn new(target: Entity, camera: Entity) -> Self {
+        Self {
+            target,
+            camera,
+            mode: GizmoMode::Translate.into(),
+            constraints: DragConstraints::default(),
+            size: 1.,
+        }
+    }
+
+    /// Set the kind of manipulation, either as a constant or a signal.
+    pub fn with_mode(mut self, mode: impl IntoSignal<GizmoMode>) -> Self {
+        self.mode = mode.into_signal();
+        self
+    }
+
+    /// Set the snapping and precision constraints applied while dragging.
+    pub fn with_constraints(mut self, constraints: DragConstraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Set the length of the handles, in world units.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl ViewFactory for TransformGizmo {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let target = self.target;
+        let camera = self.camera;
+        let mode = self.mode;
+        let constraints = self.constraints;
+        let size = self.size;
+
+        // Two-way binding between the target's transform and the gizmo.
+        let initial = cx
+            .world()
+            .get::<Transform>(target)
+            .copied()
+            .unwrap_or_default();
+        let transform = cx.create_mutable(initial);
+        cx.bind_component(transform, target, |t: &Transform| t, |t| t);
+
+        // The transform at the start of the current drag.
+        let drag_start = cx.create_mutable(Transform::default());
+
+        let root = cx.create_entity();
+        cx.world_mut()
+            .entity_mut(root)
+            .insert((Name::new("TransformGizmo"), SpatialBundle::default()));
+        cx.on_cleanup(move |world| {
+            if let Some(entt) = world.get_entity_mut(root) {
+                entt.despawn_recursive();
+            }
+        });
+
+        // The gizmo follows the position of the target, and is aligned with its parent.
+        cx.create_effect(move |cx| {
+            let translation = transform.get(cx).translation;
+            let parent = cx
+                .use_component::<Parent>(target)
+                .map(|parent| parent.get());
+            let parent_transform = parent
+                .and_then(|parent| cx.use_component::<GlobalTransform>(parent))
+                .copied()
+                .unwrap_or_default();
+            let (_, rotation, _) = parent_transform.to_scale_rotation_translation();
+            cx.world_mut().entity_mut(root).insert(
+                Transform::from_translation(parent_transform.transform_point(translation))
+                    .with_rotation(rotation),
+            );
+        });
+
+        // Build the handles for the current mode.
+        cx.create_effect(move |cx| {
+            let mode = mode.get(cx);
+            for axis in GizmoAxis::ALL {
+                let handle = spawn_handle(cx.world_mut(), mode, axis, size);
+                cx.world_mut().entity_mut(handle).insert((
+                    On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                        let mut event = world
+                            .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
+                            .unwrap();
+                        event.stop_propagation();
+                        // Distances are measured from where the pointer is captured, so the
+                        // starting transform and the origin are both taken now.
+                        let start = transform.get(world);
+                        drag_start.set(world, start);
+                        world.resource_mut::<PointerCapture>().capture(handle);
+                    }),
+                    On::<CapturedPointerMove>::run(move |world: &mut World| {
+                        let distance = world
+                            .resource::<ListenerInput<CapturedPointerMove>>()
+                            .distance;
+                        let start = drag_start.get(world);
+                        let Some(screen_axis) = screen_axis(world, camera, target, &start, axis)
+                        else {
+                            return;
+                        };
+                        let precise = is_precision_drag(world);
+                        let next = drag_transform(
+                            &start,
+                            mode,
+                            axis,
+                            screen_axis,
+                            distance,
+                            &constraints,
+                            precise,
+                        );
+                        transform.set(world, next);
+                    }),
+                    On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
+                        let mut capture = world.resource_mut::<PointerCapture>();
+                        if capture.target() == Some(handle) {
+                            capture.release();
+                        }
+                    }),
+                ));
+                cx.world_mut().entity_mut(root).add_child(handle);
+            }
+            cx.on_cleanup(move |world| {
+                if let Some(mut entt) = world.get_entity_mut(root) {
+                    entt.despawn_descendants();
+                }
+            });
+        });
+
+        EmptyView
+    }
+}
+
+/// Spawn the mesh for one handle of the gizmo.
+fn spawn_handle(world: &mut World, mode: GizmoMode, axis: GizmoAxis, size: f32) -> Entity {
+    let direction = axis.direction();
+    let thickness = size * 0.04;
+    let (mesh, transform) = match mode {
+        GizmoMode::Translate => (
+            Mesh::from(Cuboid::from_size(
+                direction * size + (Vec3::ONE - direction) * thickness,
+            )),
+            Transform::from_translation(direction * size * 0.5),
+        ),
+        GizmoMode::Rotate => (
+            Mesh::from(Torus::new(size - thickness, size + thickness)),
+            Transform::from_rotation(axis.ring_rotation()),
+        ),
+        GizmoMode::Scale => (
+            Mesh::from(Cuboid::from_size(Vec3::splat(thickness * 4.))),
+            Transform::from_translation(direction * size),
+        ),
+    };
+    let mesh = world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let material = world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(StandardMaterial {
+            base_color: axis.color(),
+            unlit: true,
+            ..default()
+        });
+    world
+        .spawn(PbrBundle {
+            mesh,
+            material,
+            transform,
+            ..default()
+        })
+        .id()
+}
+
+/// The direction and length, in the logical window pixels used for pointer positions, of one
+/// unit along `axis` in the parent space of `target`, starting at the position of `transform`.
+fn screen_axis(
+    world: &mut World,
+    camera: Entity,
+    target: Entity,
+    transform: &Transform,
+    axis: GizmoAxis,
+) -> Option<Vec2> {
+    let parent_transform = world
+        .get::<Parent>(target)
+        .and_then(|parent| world.get::<GlobalTransform>(parent.get()))
+        .copied()
+        .unwrap_or_default();
+    let mut cameras = world.query::<(&Camera, &GlobalTransform)>();
+    let (camera, camera_transform) = cameras.get(world, camera).ok()?;
+    let camera = camera.clone();
+    let camera_transform = *camera_transform;
+    // A camera which renders to a viewport element projects onto the pixels of its image,
+    // which need not match the size of the element in the window.
+    let pixel_scale = match &camera.target {
+        RenderTarget::Image(image) => viewport_pixel_scale(world, image)?,
+        _ => Vec2::ONE,
+    };
+    project_axis(
+        |point| camera.world_to_viewport(&camera_transform, point),
+        &parent_transform,
+        transform,
+        axis,
+        pixel_scale,
+    )
+}
+
+/// Project one unit along `axis`, in the space described by `parent_transform`, onto the
+/// screen. `project` maps a world position to viewport pixels, and `pixel_scale` is the size
+/// of a viewport pixel in window pixels.
+fn project_axis(
+    project: impl Fn(Vec3) -> Option<Vec2>,
+    parent_transform: &GlobalTransform,
+    transform: &Transform,
+    axis: GizmoAxis,
+    pixel_scale: Vec2,
+) -> Option<Vec2> {
+    let origin = parent_transform.transform_point(transform.translation);
+    let end = parent_transform.transform_point(transform.translation + axis.direction());
+    let screen = (project(end)? - project(origin)?) * pixel_scale;
+    (screen.length_squared() > f32::EPSILON).then_some(screen)
+}
+
+/// Compute the transform resulting from dragging a handle by `distance` pixels.
+fn drag_transform(
+    start: &Transform,
+    mode: GizmoMode,
+    axis: GizmoAxis,
+    screen_axis: Vec2,
+    distance: Vec2,
+    constraints: &DragConstraints,
+    precise: bool,
+) -> Transform {
+    let direction = axis.direction();
+    let mut result = *start;
+    match mode {
+        GizmoMode::Translate => {
+            // Distance along the axis, in world units.
+            let along = distance.dot(screen_axis) / screen_axis.length_squared();
+            let origin = start.translation.dot(direction);
+            let value = constraints.apply_1d(origin, along, precise);
+            result.translation += direction * (value - origin);
+        }
+        GizmoMode::Rotate => {
+            // Dragging across the axis turns the target.
+            let across = distance.dot(screen_axis.normalize().perp()) * ROTATE_SPEED;
+            let angle = constraints.apply_1d(0., across, precise);
+            result.rotation = Quat::from_axis_angle(direction, angle) * start.rotation;
+        }
+        GizmoMode::Scale => {
+            let along = distance.dot(screen_axis.normalize()) * SCALE_SPEED;
+            let origin = start.scale.dot(direction);
+            let value = constraints.apply_1d(origin, along, precise);
+            result.scale += direction * (value - origin);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A projection looking down -z, with 100 pixels per world unit and y pointing down.
+    fn project(point: Vec3) -> Option<Vec2> {
+        Some(Vec2::new(point.x, -point.y) * 100.)
+    }
+
+    #[test]
+    fn test_project_axis() {
+        let start = Transform::from_xyz(1., 0., 0.);
+        let identity = GlobalTransform::IDENTITY;
+        let x = project_axis(project, &identity, &start, GizmoAxis::X, Vec2::ONE);
+        assert_eq!(x, Some(Vec2::new(100., 0.)));
+
+        // The z axis points straight at the camera, so can't be dragged along.
+        let z = project_axis(project, &identity, &start, GizmoAxis::Z, Vec2::ONE);
+        assert_eq!(z, None);
+
+        // Axes are in the parent's space: a parent rotated a quarter turn about z turns the
+        // x axis to point up the screen, and its translation moves the origin.
+        let parent = GlobalTransform::from(
+            Transform::from_xyz(5., 5., 0.).with_rotation(Quat::from_rotation_z(FRAC_PI_2)),
+        );
+        let x = project_axis(project, &parent, &start, GizmoAxis::X, Vec2::ONE).unwrap();
+        assert!(x.abs_diff_eq(Vec2::new(0., -100.), 1e-3));
+
+        // A viewport image drawn at half its size halves the distance on screen.
+        let x = project_axis(project, &identity, &start, GizmoAxis::X, Vec2::splat(0.5));
+        assert_eq!(x, Some(Vec2::new(50., 0.)));
+    }
+
+    #[test]
+    fn test_drag_transform() {
+        let start = Transform::from_xyz(1., 0., 0.);
+        let constraints = DragConstraints::default().with_grid(Vec2::splat(0.5));
+
+        // 100 pixels per unit along x; dragging 130 pixels snaps to 1.5 units.
+        let moved = drag_transform(
+            &start,
+            GizmoMode::Translate,
+            GizmoAxis::X,
+            Vec2::new(100., 0.),
+            Vec2::new(130., 40.),
+            &constraints,
+            false,
+        );
+        assert_eq!(moved.translation, Vec3::new(2.5, 0., 0.));
+
+        let scaled = drag_transform(
+            &start,
+            GizmoMode::Scale,
+            GizmoAxis::Y,
+            Vec2::new(0., -100.),
+            Vec2::new(0., -100.),
+            &DragConstraints::default(),
+            false,
+        );
+        assert_eq!(scaled.scale, Vec3::new(1., 2., 1.));
+    }
+}
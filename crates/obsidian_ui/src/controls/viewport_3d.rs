@@ -0,0 +1,222 @@
+use bevy::{
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_resource::{
+            Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsages,
+        },
+        view::RenderLayers,
+    },
+    window::PrimaryWindow,
+};
+use bevy_reactor::*;
+
+/// Holds the image which a viewport is rendered to, on the element which displays it.
+#[derive(Component)]
+pub(crate) struct ViewportImage(Handle<Image>);
+
+/// The render order to give the next viewport camera. Each viewport camera gets its own
+/// order, so that the order in which viewports are rendered is well-defined. Orders are
+/// negative, so that viewports are rendered before the window cameras which display them.
+#[derive(Resource)]
+struct NextViewportOrder(isize);
+
+impl Default for NextViewportOrder {
+    fn default() -> Self {
+        Self(-1)
+    }
+}
+
+/// Allocate a unique render order for a viewport camera.
+fn next_viewport_order(world: &mut World) -> isize {
+    let mut next = world.get_resource_or_insert_with(NextViewportOrder::default);
+    let order = next.0;
+    next.0 -= 1;
+    order
+}
+
+/// Displays the view from a 3D camera as a UI element. The camera renders to a texture which
+/// is resized to match the element, so the viewport can be laid out like any other element.
+/// The camera transform and render layers are signals, so they can be driven reactively,
+/// for example from an orbit control or a camera selector.
+#[derive(Clone, Default)]
+pub struct Viewport3d {
+    /// The transform of the camera.
+    pub transform: Signal<Transform>,
+
+    /// The projection of the camera.
+    pub projection: Signal<Projection>,
+
+    /// The render layers which the camera sees.
+    pub render_layers: Signal<RenderLayers>,
+
+    /// The background color. If `None`, the app's clear color is used.
+    pub clear_color: Option<Color>,
+
+    /// Additional styles for the viewport element, typically its size.
+    pub style: StyleHandle,
+}
+
+impl ViewFactory for Viewport3d {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        viewport_element(cx, self)
+    }
+}
+
+/// Create the element and camera for a [`Viewport3d`]. Controls which add interaction to a
+/// viewport use this directly, so that they can add event handlers to the element.
+pub(crate) fn viewport_element(cx: &mut Cx, props: &Viewport3d) -> Element<ImageBundle> {
+    // Start with a small buffer; it is resized by `update_viewport_image_size`.
+    let size = Extent3d {
+        width: 16,
+        height: 16,
+        ..Extent3d::default()
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: None,
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Bgra8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_DST
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..Image::default()
+    };
+    image.resize(size);
+    let image = cx.world_mut().resource_mut::<Assets<Image>>().add(image);
+
+    let order = next_viewport_order(cx.world_mut());
+    let camera = cx.create_owned_entity();
+    cx.world_mut().entity_mut(camera).insert(Camera3dBundle {
+        camera: Camera {
+            order,
+            target: RenderTarget::Image(image.clone()),
+            clear_color: props
+                .clear_color
+                .map_or(ClearColorConfig::Default, ClearColorConfig::Custom),
+            ..default()
+        },
+        ..default()
+    });
+
+    let transform = props.transform;
+    let projection = props.projection.clone();
+    let render_layers = props.render_layers;
+    cx.create_effect(move |cx| {
+        let transform = transform.get(cx);
+        cx.world_mut().entity_mut(camera).insert(transform);
+    });
+    cx.create_effect(move |cx| {
+        let projection = projection.get_clone(cx);
+        cx.world_mut().entity_mut(camera).insert(projection);
+    });
+    cx.create_effect(move |cx| {
+        let render_layers = render_layers.get(cx);
+        cx.world_mut().entity_mut(camera).insert(render_layers);
+    });
+
+    Element::<ImageBundle>::new()
+        .named("viewport_3d")
+        .with_styles(props.style.clone())
+        .insert((UiImage::new(image.clone()), ViewportImage(image)))
+}
+
+/// The size of one pixel of the viewport image rendered to `image`, measured in the logical
+/// window pixels used for pointer positions. Returns `None` if no viewport element displays
+/// the image, or if it hasn't been laid out yet.
+pub(crate) fn viewport_pixel_scale(world: &mut World, image: &Handle<Image>) -> Option<Vec2> {
+    let mut viewports = world.query::<(&Node, &ViewportImage)>();
+    let node_size = viewports
+        .iter(world)
+        .find(|(_, viewport)| viewport.0 == *image)
+        .map(|(node, _)| node.size())?;
+    let image_size = world.resource::<Assets<Image>>().get(image)?.size_f32();
+    let logical_size = node_size * world.resource::<UiScale>().0;
+    (node_size.min_element() > 0.).then(|| logical_size / image_size)
+}
+
+/// Resize the image of each viewport to match the size of its element.
+pub(crate) fn update_viewport_image_size(
+    viewports: Query<(&Node, &ViewportImage)>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let scale_factor = windows
+        .get_single()
+        .map(|window| window.scale_factor())
+        .unwrap_or(1.);
+    for (node, viewport) in viewports.iter() {
+        let size = node.physical_size(scale_factor, ui_scale.0).max(Vec2::ONE);
+        let Some(image) = images.get(&viewport.0) else {
+            continue;
+        };
+        if image.width() != size.x as u32 || image.height() != size.y as u32 {
+            images.get_mut(&viewport.0).unwrap().resize(Extent3d {
+                width: size.x as u32,
+                height: size.y as u32,
+                ..Extent3d::default()
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_reactor::testing::TestHarness;
+
+    use super::*;
+
+    fn two_viewports(cx: &mut Cx) -> impl View {
+        let transform = cx.create_mutable(Transform::from_xyz(0., 0., 5.));
+        cx.register_signal("transform", transform);
+        Element::<NodeBundle>::new().with_children((
+            ViewFactoryState::new(Viewport3d {
+                transform: transform.signal(),
+                ..default()
+            }),
+            ViewFactoryState::new(Viewport3d::default()),
+        ))
+    }
+
+    #[test]
+    fn test_viewport_cameras() {
+        let mut harness = TestHarness::new();
+        harness.mount(two_viewports.bind(()));
+        let mut cameras = harness.world_mut().query::<(&Camera, &Transform)>();
+        let mut orders: Vec<isize> = cameras
+            .iter(harness.world())
+            .map(|(camera, _)| camera.order)
+            .collect();
+        orders.sort();
+        assert_eq!(orders, vec![-2, -1]);
+
+        // Each camera renders to the image displayed by its own element.
+        let mut elements = harness.world_mut().query::<&ViewportImage>();
+        let images: Vec<Handle<Image>> = elements
+            .iter(harness.world())
+            .map(|image| image.0.clone())
+            .collect();
+        assert_eq!(images.len(), 2);
+        assert_ne!(images[0], images[1]);
+        for (camera, _) in cameras.iter(harness.world()) {
+            let RenderTarget::Image(ref target) = camera.target else {
+                panic!("Viewport camera should render to an image");
+            };
+            assert!(images.contains(target));
+        }
+
+        // The camera follows the transform signal.
+        let transform = harness.mutable::<Transform>("transform");
+        let moved = Transform::from_xyz(1., 2., 3.);
+        harness.set(transform, moved);
+        assert!(cameras
+            .iter(harness.world())
+            .any(|(_, transform)| *transform == moved));
+    }
+}
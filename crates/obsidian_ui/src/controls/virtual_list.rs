@@ -0,0 +1,354 @@
+use std::{ops::Range, sync::Arc};
+
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+/// Default number of rows built ahead of time beyond each edge of the visible region.
+const DEFAULT_PREFETCH: usize = 10;
+
+fn style_virtual_list(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Relative)
+        .min_width(ui::Val::Percent(100.));
+}
+
+/// A list of fixed-height rows in which only the rows near the visible region are built, so
+/// that lists with many thousands of rows stay cheap to display. The list is meant to be
+/// placed inside a [`ScrollView`], or any other container which clips its content; rows are
+/// built and razed as they are scrolled into and out of view.
+///
+/// To keep fast scrolling from hitching while new rows are built, a margin of `prefetch` rows
+/// beyond each edge of the visible region is built ahead of time, one row per frame. A row in
+/// the margin is only built if the [`FrameBudget`] still has time remaining when the list
+/// updates, and the time taken to build it is charged against the budget; otherwise it is
+/// put off to a later frame.
+///
+/// [`ScrollView`]: crate::controls::ScrollView
+pub struct VirtualList {
+    /// The number of rows in the list.
+    pub count: Signal<usize>,
+
+    /// The height of each row.
+    pub row_height: f32,
+
+    /// Number of rows beyond each edge of the visible region which are built ahead of time.
+    pub prefetch: usize,
+
+    /// Additional styles to be applied to the list.
+    pub style: StyleHandle,
+
+    /// Function which produces the view for the row with the given index.
+    pub each: Arc<dyn Fn(usize) -> ViewHandle + Send + Sync>,
+}
+
+impl VirtualList {
+    /// Construct a list of `count` rows of height `row_height`, where `each` produces the
+    /// view for the row with the given index.
+    pub fn new<V: Into<ViewHandle>>(
+        count: impl IntoSignal<usize>,
+        row_height: f32,
+        each: impl Fn(usize) -> V + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            count: count.into_signal(),
+            row_height,
+            prefetch: DEFAULT_PREFETCH,
+            style: StyleHandle::default(),
+            each: Arc::new(move |index| each(index).into()),
+        }
+    }
+
+    /// Set the number of rows beyond each edge of the visible region which are built ahead
+    /// of time.
+    pub fn with_prefetch(mut self, prefetch: usize) -> Self {
+        self.prefetch = prefetch;
+        self
+    }
+}
+
+/// Component which holds the state of a [`VirtualList`], for [`update_virtual_rows`].
+#[derive(Component)]
+pub(crate) struct VirtualRows {
+    /// The number of rows in the list.
+    count: usize,
+    /// Number of rows beyond each edge of the visible region which are built ahead of time.
+    prefetch: usize,
+    /// The most recent request.
+    request: RowRequest,
+    /// Mutable which the request is copied to, for the list to react to.
+    rows: Mutable<RowRequest>,
+}
+
+/// Component which marks the element of a row of a [`VirtualList`] with the row's index.
+#[derive(Component)]
+pub(crate) struct VirtualRow(usize);
+
+/// The rows which a [`VirtualList`] should build.
+#[derive(Clone, PartialEq, Default)]
+struct RowRequest {
+    /// Rows which must be built.
+    keep: Range<usize>,
+    /// Rows to build instead of `keep` if the frame budget has time remaining.
+    prefetch: Range<usize>,
+    /// Counts the frames in which a prefetch was requested. Since a request equal to the
+    /// previous one doesn't trigger a reaction, this lets a prefetch which was put off for lack
+    /// of time be retried in the next frame.
+    attempt: u32,
+}
+
+impl ViewFactory for VirtualList {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let count = self.count;
+        let row_height = self.row_height;
+        let prefetch = self.prefetch;
+        let each = self.each.clone();
+        let rows = cx.create_mutable(RowRequest::default());
+
+        Element::<NodeBundle>::new()
+            .named("virtual_list")
+            .with_styles((style_virtual_list, self.style.clone()))
+            .create_effect(move |cx, entity| {
+                let count = count.get(cx);
+                let mut entt = cx.world_mut().entity_mut(entity);
+                if let Some(mut style) = entt.get_mut::<ui::Style>() {
+                    style.height = ui::Val::Px(count as f32 * row_height);
+                }
+                match entt.get_mut::<VirtualRows>() {
+                    Some(mut state) => state.count = count,
+                    None => {
+                        entt.insert(VirtualRows {
+                            count,
+                            prefetch,
+                            request: RowRequest::default(),
+                            rows,
+                        });
+                    }
+                }
+            })
+            .with_children(For::each(
+                move |cx| {
+                    let count = count.get(cx);
+                    let request = rows.get_clone(cx);
+                    // Rows are built within this reaction, so the time taken to build them is
+                    // charged against the frame budget along with the rest of the reaction.
+                    let budget = cx.world().get_resource::<FrameBudget>();
+                    rows_to_build(&request, count, budget)
+                },
+                move |index| {
+                    let top = *index as f32 * row_height;
+                    Element::<NodeBundle>::new()
+                        .insert(VirtualRow(*index))
+                        .with_styles(move |ss: &mut StyleBuilder| {
+                            ss.position(ui::PositionType::Absolute)
+                                .left(0)
+                                .right(0)
+                                .top(top)
+                                .height(row_height);
+                        })
+                        .with_child(&each(*index))
+                },
+            ))
+    }
+}
+
+/// Update the rows to be built for each virtual list, based on the region of the list which
+/// is visible within its clipping container, and on the rows which have been built so far.
+/// Whether the next row of the prefetch margin is built is decided by the list when it
+/// reacts to the request, depending on the time left in that frame's budget.
+pub(crate) fn update_virtual_rows(
+    mut commands: Commands,
+    mut query: Query<(
+        &Node,
+        &GlobalTransform,
+        Option<&ui::CalculatedClip>,
+        Option<&Children>,
+        &mut VirtualRows,
+    )>,
+    query_rows: Query<&VirtualRow>,
+) {
+    for (node, gt, clip, children, mut state) in query.iter_mut() {
+        let list = node.logical_rect(gt);
+        if list.is_empty() {
+            // Not laid out yet, or has no rows.
+            continue;
+        }
+        let clip = clip.map_or(list, |clip| clip.clip);
+        let visible = visible_rows(list, clip, state.count);
+        let built = children.map_or(0..0, |children| {
+            built_rows(query_rows.iter_many(children).map(|row| row.0))
+        });
+        let keep = next_rows(&built, &visible, state.count, state.prefetch, false);
+        let prefetch = next_rows(&built, &visible, state.count, state.prefetch, true);
+        let mut attempt = state.request.attempt;
+        if prefetch != built {
+            attempt = attempt.wrapping_add(1);
+        }
+        let request = RowRequest {
+            keep,
+            prefetch,
+            attempt,
+        };
+        if request != state.request {
+            state.request = request.clone();
+            let rows = state.rows;
+            commands.add(move |world: &mut World| rows.set_clone(world, request));
+        }
+    }
+}
+
+/// The range spanned by the indices of the rows which are built.
+fn built_rows(indices: impl Iterator<Item = usize>) -> Range<usize> {
+    indices.fold(0..0, |built, index| {
+        if built.is_empty() {
+            index..index + 1
+        } else {
+            built.start.min(index)..built.end.max(index + 1)
+        }
+    })
+}
+
+/// The rows to build in response to a request: the prefetch range only if the frame budget
+/// has time remaining, limited to the rows of the list, which may have shrunk since the
+/// request was made.
+fn rows_to_build(request: &RowRequest, count: usize, budget: Option<&FrameBudget>) -> Range<usize> {
+    let rows = match budget {
+        Some(budget) if budget.remaining().is_zero() => &request.keep,
+        _ => &request.prefetch,
+    };
+    rows.start.min(count)..rows.end.min(count)
+}
+
+/// The range of rows of a list occupying `list` which intersect the clipping rectangle.
+fn visible_rows(list: Rect, clip: Rect, count: usize) -> Range<usize> {
+    let visible = list.intersect(clip);
+    if visible.is_empty() || count == 0 {
+        return 0..0;
+    }
+    let row_height = list.height() / count as f32;
+    let first = ((visible.min.y - list.min.y) / row_height).floor() as usize;
+    let last = ((visible.max.y - list.min.y) / row_height).ceil() as usize;
+    first.min(count)..last.min(count)
+}
+
+/// The range of rows to build, given the rows which are currently built and those which are
+/// visible. Visible rows are always built, and built rows beyond the prefetch margin are
+/// dropped. When `idle`, one more row within the margin is built, preferring rows below the
+/// visible region, which are the ones usually scrolled to next.
+fn next_rows(
+    built: &Range<usize>,
+    visible: &Range<usize>,
+    count: usize,
+    prefetch: usize,
+    idle: bool,
+) -> Range<usize> {
+    if visible.is_empty() {
+        // Keep what has been built while the list is scrolled out of view.
+        return built.start.min(count)..built.end.min(count);
+    }
+    let margin = visible.start.saturating_sub(prefetch)..(visible.end + prefetch).min(count);
+    let (mut start, mut end) =
+        if built.is_empty() || built.start > visible.end || built.end < visible.start {
+            // Only a contiguous range of rows is kept, so after a jump the rows built previously
+            // are discarded, rather than building all of the rows in between.
+            (visible.start, visible.end)
+        } else {
+            (
+                built.start.max(margin.start).min(visible.start),
+                built.end.min(margin.end).max(visible.end),
+            )
+        };
+    if idle {
+        if end < margin.end {
+            end += 1;
+        } else if start > margin.start {
+            start -= 1;
+        }
+    }
+    start..end
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn test_visible_rows() {
+        // A list of 100 rows, 10 high.
+        let list = Rect::new(0., -200., 100., 800.);
+        assert_eq!(
+            visible_rows(list, Rect::new(0., 0., 100., 50.), 100),
+            20..25
+        );
+        // Partially visible rows are included.
+        assert_eq!(
+            visible_rows(list, Rect::new(0., 5., 100., 51.), 100),
+            20..26
+        );
+        // Clipped out entirely.
+        assert_eq!(
+            visible_rows(list, Rect::new(0., 900., 100., 950.), 100),
+            0..0
+        );
+        // A clip extending past the end of the list.
+        assert_eq!(
+            visible_rows(list, Rect::new(0., 750., 100., 900.), 100),
+            95..100
+        );
+        assert_eq!(visible_rows(list, Rect::new(0., 0., 100., 50.), 0), 0..0);
+    }
+
+    #[test]
+    fn test_next_rows() {
+        // Visible rows are built straight away, and the margin is filled in while idle, one
+        // row per frame, below the visible rows first.
+        assert_eq!(next_rows(&(0..0), &(20..25), 100, 2, false), 20..25);
+        assert_eq!(next_rows(&(0..0), &(20..25), 100, 2, true), 20..26);
+        assert_eq!(next_rows(&(20..26), &(20..25), 100, 2, true), 20..27);
+        assert_eq!(next_rows(&(20..27), &(20..25), 100, 2, true), 19..27);
+        assert_eq!(next_rows(&(19..27), &(20..25), 100, 2, true), 18..27);
+        assert_eq!(next_rows(&(18..27), &(20..25), 100, 2, true), 18..27);
+
+        // Without idle time, nothing beyond the visible rows is added.
+        assert_eq!(next_rows(&(20..25), &(20..25), 100, 2, false), 20..25);
+
+        // Scrolling keeps the built rows within the margin, and drops the rest.
+        assert_eq!(next_rows(&(18..27), &(23..28), 100, 2, false), 21..28);
+
+        // After a jump, only the visible rows are built.
+        assert_eq!(next_rows(&(18..27), &(60..65), 100, 2, false), 60..65);
+
+        // The margin is limited to the rows of the list.
+        assert_eq!(next_rows(&(95..100), &(95..100), 100, 2, true), 94..100);
+        assert_eq!(next_rows(&(0..5), &(0..5), 100, 2, true), 0..6);
+
+        // Rows are kept while scrolled out of view, unless the list has shrunk.
+        assert_eq!(next_rows(&(18..27), &(0..0), 100, 2, true), 18..27);
+        assert_eq!(next_rows(&(18..27), &(0..0), 20, 2, true), 18..20);
+    }
+
+    #[test]
+    fn test_built_rows() {
+        assert_eq!(built_rows(std::iter::empty()), 0..0);
+        assert_eq!(built_rows([22, 20, 21].into_iter()), 20..23);
+    }
+
+    #[test]
+    fn test_rows_to_build() {
+        let request = RowRequest {
+            keep: 20..25,
+            prefetch: 20..26,
+            attempt: 1,
+        };
+        let mut budget = FrameBudget::new(Duration::from_millis(4));
+        assert_eq!(rows_to_build(&request, 100, Some(&budget)), 20..26);
+        assert_eq!(rows_to_build(&request, 100, None), 20..26);
+
+        // Once the budget is used up, the prefetch is put off.
+        budget.spend(Duration::from_millis(5));
+        assert_eq!(rows_to_build(&request, 100, Some(&budget)), 20..25);
+
+        // The list has shrunk since the request was made.
+        assert_eq!(rows_to_build(&request, 22, Some(&budget)), 20..22);
+    }
+}
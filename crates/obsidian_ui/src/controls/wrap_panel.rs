@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use bevy::{prelude::*, ui};
+use bevy_reactor::*;
+
+use crate::hooks::CreateSizeSignal;
+
+/// A container which arranges its children in rows, wrapping onto a new row when the current
+/// row is full. Useful for tag clouds, swatch grids and asset tiles.
+#[derive(Clone, Default)]
+pub struct WrapPanel {
+    /// Spacing between children, both horizontally and vertically, in pixels.
+    pub gap: f32,
+
+    /// If set, children are placed in equal-width cells of at least this width; the cells
+    /// stretch so that each row fills the available width. Otherwise children keep their
+    /// natural widths.
+    pub cell_width: Option<f32>,
+
+    /// Cross-axis alignment of children within each row.
+    pub align: ui::AlignItems,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// The content of the panel.
+    pub children: ViewHandle,
+}
+
+impl ViewFactory for WrapPanel {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let gap = self.gap;
+        let cell_width = self.cell_width;
+        let align = self.align;
+        Element::<NodeBundle>::new()
+            .named("wrap_panel")
+            .with_styles((
+                move |ss: &mut StyleBuilder| {
+                    match cell_width {
+                        Some(width) => {
+                            ss.display(ui::Display::Grid).grid_template_columns(vec![
+                                ui::RepeatedGridTrack::minmax(
+                                    ui::GridTrackRepetition::AutoFill,
+                                    ui::MinTrackSizingFunction::Px(width),
+                                    ui::MaxTrackSizingFunction::Fraction(1.),
+                                ),
+                            ]);
+                        }
+                        None => {
+                            ss.display(ui::Display::Flex)
+                                .flex_direction(ui::FlexDirection::Row)
+                                .flex_wrap(ui::FlexWrap::Wrap);
+                        }
+                    }
+                    ss.align_items(align).gap(gap);
+                },
+                self.style.clone(),
+            ))
+            .with_child(&self.children)
+    }
+}
+
+fn style_masonry(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Row)
+        .align_items(ui::AlignItems::FlexStart);
+}
+
+fn style_masonry_column(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .align_items(ui::AlignItems::Stretch)
+        .flex_grow(1.)
+        .flex_basis(0)
+        .min_width(0);
+}
+
+/// A container which arranges a list of items into columns, with each item placed in the
+/// next column in turn. The number of columns is recomputed from the measured width of the
+/// container, so that each column is at least `column_width` pixels wide. Unlike a grid,
+/// items in a column are stacked directly below each other regardless of their heights.
+pub struct Masonry<Item: Send + Sync + Clone + PartialEq + 'static> {
+    /// The list of items to display.
+    pub items: Signal<Vec<Item>>,
+
+    /// Minimum width of each column, in pixels.
+    pub column_width: f32,
+
+    /// Spacing between columns and between items, in pixels.
+    pub gap: f32,
+
+    /// Additional styles to be applied to the container.
+    pub style: StyleHandle,
+
+    /// Function which produces the view for each item.
+    pub each: Arc<dyn Fn(&Item) -> ViewHandle + Send + Sync>,
+}
+
+impl<Item: Send + Sync + Clone + PartialEq + 'static> ViewFactory for Masonry<Item> {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        let id = cx.create_entity();
+        let size = cx.create_size_signal(id);
+        let items = self.items.clone();
+        let column_width = self.column_width.max(1.);
+        let gap = self.gap;
+        let each = self.each.clone();
+
+        let columns = cx.create_derived(move |cx| {
+            let width = size.get(cx).x;
+            (((width + gap) / (column_width + gap)).floor() as usize).max(1)
+        });
+
+        Element::<NodeBundle>::for_entity(id)
+            .named("masonry")
+            .with_styles((
+                style_masonry,
+                move |ss: &mut StyleBuilder| {
+                    ss.column_gap(gap);
+                },
+                self.style.clone(),
+            ))
+            .with_children(For::index(
+                move |cx| 0..columns.get(cx),
+                move |column, _| {
+                    let column = *column;
+                    let items = items.clone();
+                    let each = each.clone();
+                    Element::<NodeBundle>::new()
+                        .with_styles((style_masonry_column, move |ss: &mut StyleBuilder| {
+                            ss.row_gap(gap);
+                        }))
+                        .with_children(For::index(
+                            move |cx| {
+                                let count = columns.get(cx);
+                                items.get_clone(cx).into_iter().skip(column).step_by(count)
+                            },
+                            move |item, _| each(item),
+                        ))
+                },
+            ))
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+/// Which axes a drag operation is allowed to move along.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum DragAxis {
+    /// Movement along both axes.
+    #[default]
+    Both,
+    /// Horizontal movement only.
+    X,
+    /// Vertical movement only.
+    Y,
+}
+
+/// Constraints applied to the result of a drag operation: axis locking, snapping to a grid,
+/// clamping to bounds, and a precision mode which scales down the drag distance while a
+/// modifier key is held. Controls which support dragging, such as sliders, splitters and
+/// draggable panels, share this so that drags behave consistently.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DragConstraints {
+    /// Which axes the drag can move along.
+    pub axis: DragAxis,
+
+    /// If present, the result is snapped to multiples of this step. A step of zero along
+    /// an axis disables snapping on that axis.
+    pub grid: Option<Vec2>,
+
+    /// Minimum result value.
+    pub min: Vec2,
+
+    /// Maximum result value.
+    pub max: Vec2,
+
+    /// Factor applied to the drag distance in precision mode.
+    pub precision_scale: f32,
+}
+
+impl Default for DragConstraints {
+    fn default() -> Self {
+        Self {
+            axis: DragAxis::Both,
+            grid: None,
+            min: Vec2::NEG_INFINITY,
+            max: Vec2::INFINITY,
+            precision_scale: 0.1,
+        }
+    }
+}
+
+impl DragConstraints {
+    /// Restrict the drag to a single axis.
+    pub fn with_axis(mut self, axis: DragAxis) -> Self {
+        self.axis = axis;
+        self
+    }
+
+    /// Snap the result to multiples of `step`.
+    pub fn with_grid(mut self, step: Vec2) -> Self {
+        self.grid = Some(step);
+        self
+    }
+
+    /// Clamp the result to the given bounds.
+    pub fn with_bounds(mut self, min: Vec2, max: Vec2) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Set the factor applied to the drag distance in precision mode.
+    pub fn with_precision_scale(mut self, scale: f32) -> Self {
+        self.precision_scale = scale;
+        self
+    }
+
+    /// Compute the constrained result of dragging from `origin` by `distance`.
+    ///
+    /// Arguments:
+    /// * `origin` - The value at the start of the drag.
+    /// * `distance` - The distance dragged since the start of the drag, in value units.
+    /// * `precise` - Whether precision mode is active, see [`is_precision_drag`].
+    pub fn apply(&self, origin: Vec2, distance: Vec2, precise: bool) -> Vec2 {
+        let mut distance = if precise {
+            distance * self.precision_scale
+        } else {
+            distance
+        };
+        match self.axis {
+            DragAxis::Both => {}
+            DragAxis::X => distance.y = 0.,
+            DragAxis::Y => distance.x = 0.,
+        }
+        let mut result = origin + distance;
+        if let Some(step) = self.grid {
+            if step.x > 0. {
+                result.x = (result.x / step.x).round() * step.x;
+            }
+            if step.y > 0. {
+                result.y = (result.y / step.y).round() * step.y;
+            }
+        }
+        result.clamp(self.min, self.max)
+    }
+
+    /// Compute the constrained result of a one-dimensional drag, using the x components of
+    /// the constraints.
+    pub fn apply_1d(&self, origin: f32, distance: f32, precise: bool) -> f32 {
+        self.apply(Vec2::new(origin, 0.), Vec2::new(distance, 0.), precise)
+            .x
+    }
+}
+
+/// Returns true if the precision modifier key (either Shift key) is held.
+pub fn is_precision_drag(world: &World) -> bool {
+    world
+        .get_resource::<ButtonInput<KeyCode>>()
+        .is_some_and(|keys| keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis() {
+        let origin = Vec2::new(10., 10.);
+        let distance = Vec2::new(5., -3.);
+        let constraints = DragConstraints::default();
+        assert_eq!(
+            constraints.apply(origin, distance, false),
+            Vec2::new(15., 7.)
+        );
+        let x = constraints.with_axis(DragAxis::X);
+        assert_eq!(x.apply(origin, distance, false), Vec2::new(15., 10.));
+        let y = constraints.with_axis(DragAxis::Y);
+        assert_eq!(y.apply(origin, distance, false), Vec2::new(10., 7.));
+    }
+
+    #[test]
+    fn test_grid() {
+        let constraints = DragConstraints::default().with_grid(Vec2::new(10., 0.));
+        // A zero step disables snapping on that axis.
+        assert_eq!(
+            constraints.apply(Vec2::ZERO, Vec2::new(14., 14.), false),
+            Vec2::new(10., 14.)
+        );
+        assert_eq!(
+            constraints.apply(Vec2::ZERO, Vec2::new(-16., 0.), false),
+            Vec2::new(-20., 0.)
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let constraints = DragConstraints::default()
+            .with_grid(Vec2::splat(10.))
+            .with_bounds(Vec2::ZERO, Vec2::splat(25.));
+        // Bounds are applied after snapping, so the result never leaves them.
+        assert_eq!(
+            constraints.apply(Vec2::ZERO, Vec2::new(28., -5.), false),
+            Vec2::new(25., 0.)
+        );
+        assert_eq!(constraints.apply_1d(10., 12., false), 20.);
+        assert_eq!(constraints.apply_1d(10., 100., false), 25.);
+    }
+
+    #[test]
+    fn test_precision() {
+        let constraints = DragConstraints::default().with_precision_scale(0.5);
+        assert_eq!(constraints.apply_1d(10., 8., false), 18.);
+        assert_eq!(constraints.apply_1d(10., 8., true), 14.);
+    }
+
+    #[test]
+    fn test_is_precision_drag() {
+        let mut world = World::default();
+        assert!(!is_precision_drag(&world));
+        let mut keys = ButtonInput::<KeyCode>::default();
+        keys.press(KeyCode::ShiftRight);
+        world.insert_resource(keys);
+        assert!(is_precision_drag(&world));
+    }
+}
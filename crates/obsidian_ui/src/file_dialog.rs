@@ -0,0 +1,122 @@
+use std::path::Path;
+
+use bevy::{
+    asset::{
+        io::{
+            memory::{Dir, MemoryAssetReader},
+            AssetSource,
+        },
+        AssetPath,
+    },
+    prelude::*,
+};
+use bevy_reactor::*;
+
+use crate::controls::BrowseAssets;
+
+/// Name of the asset source which holds the files opened with the file dialog.
+pub const PICKED_FILES: &str = "picked";
+
+/// The files opened with the file dialog, which are loaded from memory by the asset server.
+#[derive(Resource)]
+struct PickedFiles {
+    dir: Dir,
+    /// Number of files picked so far, which is used to give each file a unique path, so that
+    /// picking a file again loads its current contents.
+    count: usize,
+}
+
+/// Plugin which responds to [`BrowseAssets`] by opening the platform's file dialog. In the
+/// browser, this is the browser's file picker. The chosen file is read into memory and
+/// loaded from the [`PICKED_FILES`] asset source, so this works the same way on every
+/// platform, including those where the asset server can't read arbitrary files.
+///
+/// This plugin registers an asset source, so it must be added before Bevy's `AssetPlugin`,
+/// which is part of `DefaultPlugins`.
+pub struct FileDialogPlugin;
+
+impl Plugin for FileDialogPlugin {
+    fn build(&self, app: &mut App) {
+        let dir = Dir::default();
+        let root = dir.clone();
+        app.register_asset_source(
+            PICKED_FILES,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: root.clone() })),
+        )
+        .insert_resource(PickedFiles { dir, count: 0 })
+        .add_systems(Update, open_file_dialog);
+    }
+}
+
+/// The short name of a type, without its module path or generic parameters.
+fn short_type_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+fn open_file_dialog(mut events: EventReader<BrowseAssets>, mut commands: Commands) {
+    for event in events.read().cloned() {
+        commands.add(move |world: &mut World| {
+            let dialog = rfd::AsyncFileDialog::new()
+                .set_title(format!("Choose {}", short_type_name(event.type_name)));
+            world.spawn_task(
+                async move {
+                    let file = dialog.pick_file().await?;
+                    Some((file.file_name(), file.read().await))
+                },
+                move |world, picked| {
+                    if let Some((name, contents)) = picked {
+                        let path = add_picked_file(world, &name, contents);
+                        let handle = (event.load)(world.resource::<AssetServer>(), path);
+                        world.run_callback(event.on_select, handle);
+                    }
+                },
+            );
+        });
+    }
+}
+
+/// Store the contents of a picked file in memory, and return the path it can be loaded from.
+fn add_picked_file(world: &mut World, name: &str, contents: Vec<u8>) -> AssetPath<'static> {
+    let mut picked = world.resource_mut::<PickedFiles>();
+    picked.count += 1;
+    // The name is kept last, so that the asset server can choose a loader by its extension.
+    let path = format!("{}/{}", picked.count, name);
+    picked.dir.insert_asset(Path::new(&path), contents);
+    AssetPath::from(format!("{}://{}", PICKED_FILES, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(
+            short_type_name("bevy_render::texture::image::Image"),
+            "Image"
+        );
+        assert_eq!(
+            short_type_name("bevy_pbr::pbr_material::StandardMaterial"),
+            "StandardMaterial"
+        );
+        assert_eq!(short_type_name("Foo<bar::Baz>"), "Foo");
+    }
+
+    #[test]
+    fn test_add_picked_file() {
+        let mut world = World::default();
+        world.insert_resource(PickedFiles {
+            dir: Dir::default(),
+            count: 0,
+        });
+        let first = add_picked_file(&mut world, "logo.png", vec![1, 2, 3]);
+        assert_eq!(first.to_string(), "picked://1/logo.png");
+        // Picking a file with the same name again gives it a new path.
+        let second = add_picked_file(&mut world, "logo.png", vec![4]);
+        assert_eq!(second.to_string(), "picked://2/logo.png");
+        let dir = &world.resource::<PickedFiles>().dir;
+        assert!(dir.get_asset(Path::new("2/logo.png")).is_some());
+    }
+}
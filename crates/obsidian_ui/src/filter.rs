@@ -0,0 +1,342 @@
+use std::{borrow::Cow, ops::Range};
+
+/// A single term within a [`FilterExpr`], such as `foo`, `type:mesh` or `-unused`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterTerm {
+    /// The field to match against, if the term was written as `field:value`.
+    pub field: Option<String>,
+
+    /// The text to search for, with its case folded.
+    pub value: String,
+
+    /// Whether the term was prefixed with `-`, meaning that matching items are excluded.
+    pub negated: bool,
+}
+
+impl FilterTerm {
+    fn matches(&self, item: &impl Filterable) -> bool {
+        let found = match self.field {
+            Some(ref field) => item
+                .filter_field(field)
+                .map(|v| contains_ignore_case(&v, &self.value))
+                .unwrap_or(false),
+            None => {
+                contains_ignore_case(&item.filter_text(), &self.value)
+                    || item.has_filter_tag(&self.value)
+            }
+        };
+        found != self.negated
+    }
+}
+
+/// A parsed search query, shared by searchable panels so that they all have the same
+/// filtering semantics. The query syntax is a whitespace-separated list of terms, all of which
+/// must match:
+///
+/// * `foo` matches items whose text contains "foo", or which have the tag "foo".
+/// * `type:mesh` matches items whose `type` field contains "mesh".
+/// * `-unused` excludes items that would otherwise match `unused`.
+/// * `"foo bar"` matches text containing a space; quotes can also be used for field values.
+///
+/// Matching is case-insensitive for all scripts, not just ASCII. An empty query matches
+/// everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilterExpr {
+    /// The list of terms in the query.
+    pub terms: Vec<FilterTerm>,
+}
+
+impl FilterExpr {
+    /// Parse a query string. Parsing never fails; malformed terms are treated as plain text.
+    pub fn parse(query: &str) -> Self {
+        let terms = tokenize(query)
+            .into_iter()
+            .filter_map(|token| {
+                let (negated, token) = match token.strip_prefix('-') {
+                    Some(rest) if !rest.is_empty() => (true, rest),
+                    _ => (false, token.as_str()),
+                };
+                let (field, value) = match token.split_once(':') {
+                    Some((field, value)) if !field.is_empty() => (Some(fold_case(field)), value),
+                    _ => (None, token),
+                };
+                if value.is_empty() {
+                    return None;
+                }
+                Some(FilterTerm {
+                    field,
+                    value: fold_case(value),
+                    negated,
+                })
+            })
+            .collect();
+        Self { terms }
+    }
+
+    /// True if the query has no terms, and will therefore match all items.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Returns true if the item satisfies every term in the query.
+    pub fn matches(&self, item: &impl Filterable) -> bool {
+        self.terms.iter().all(|term| term.matches(item))
+    }
+
+    /// Returns the byte ranges within `text` which match the query's plain text terms, for
+    /// highlighting search results. Ranges are sorted and do not overlap. Negated terms and
+    /// field terms are not highlighted.
+    pub fn highlight_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        let (folded, origin) = fold_case_with_origin(text);
+        let mut ranges: Vec<Range<usize>> = self
+            .terms
+            .iter()
+            .filter(|term| !term.negated && term.field.is_none())
+            .flat_map(|term| {
+                // Folding can change the length of the text, so map the match back to the
+                // characters of the original text which it covers.
+                folded
+                    .match_indices(term.value.as_str())
+                    .map(|(start, m)| origin[start].start..origin[start + m.len() - 1].end)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+
+        // Merge overlapping ranges.
+        let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+                _ => merged.push(range),
+            }
+        }
+        merged
+    }
+
+    /// Split `text` into a list of segments, each of which is flagged as to whether it
+    /// matches the query.
+    pub fn highlight_segments(&self, text: &str) -> Vec<(String, bool)> {
+        let mut segments = Vec::new();
+        let mut pos = 0;
+        for range in self.highlight_ranges(text) {
+            if range.start > pos {
+                segments.push((text[pos..range.start].to_string(), false));
+            }
+            segments.push((text[range.clone()].to_string(), true));
+            pos = range.end;
+        }
+        if pos < text.len() {
+            segments.push((text[pos..].to_string(), false));
+        }
+        segments
+    }
+}
+
+/// Trait for items which can be filtered by a [`FilterExpr`].
+pub trait Filterable {
+    /// The text matched by plain terms, typically the item's display name.
+    fn filter_text(&self) -> Cow<str>;
+
+    /// The value of a named field, such as `type` or `name`, used by `field:value` terms.
+    /// Returns `None` if the item has no such field.
+    fn filter_field(&self, _field: &str) -> Option<Cow<str>> {
+        None
+    }
+
+    /// Whether the item has a given tag, such as `unused`. Plain terms match if either the
+    /// text or a tag matches.
+    fn has_filter_tag(&self, _tag: &str) -> bool {
+        false
+    }
+}
+
+impl Filterable for str {
+    fn filter_text(&self) -> Cow<str> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl Filterable for String {
+    fn filter_text(&self) -> Cow<str> {
+        Cow::Borrowed(self)
+    }
+}
+
+/// Case-insensitive substring search. `needle` must already be folded with [`fold_case`].
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    fold_case(haystack).contains(needle)
+}
+
+/// Convert text to lower case so that it can be compared without regard to case. This uses
+/// the Unicode lowercase mapping, so some characters, such as `İ`, become more than one
+/// character.
+fn fold_case(text: &str) -> String {
+    text.chars().flat_map(char::to_lowercase).collect()
+}
+
+/// Fold the case of `text`, also returning, for each byte of the folded text, the byte range
+/// of the character in `text` it came from.
+fn fold_case_with_origin(text: &str) -> (String, Vec<Range<usize>>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut origin = Vec::with_capacity(text.len());
+    for (start, ch) in text.char_indices() {
+        let range = start..start + ch.len_utf8();
+        for lower in ch.to_lowercase() {
+            folded.push(lower);
+            origin.extend(std::iter::repeat(range.clone()).take(lower.len_utf8()));
+        }
+    }
+    (folded, origin)
+}
+
+/// Split a query into whitespace-separated tokens, keeping quoted sections together.
+/// The quote characters themselves are removed.
+fn tokenize(query: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for ch in query.chars() {
+        match ch {
+            '"' => quoted = !quoted,
+            ch if ch.is_whitespace() && !quoted => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            ch => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Item {
+        name: &'static str,
+        kind: &'static str,
+        used: bool,
+    }
+
+    impl Filterable for Item {
+        fn filter_text(&self) -> Cow<str> {
+            Cow::Borrowed(self.name)
+        }
+
+        fn filter_field(&self, field: &str) -> Option<Cow<str>> {
+            match field {
+                "name" => Some(Cow::Borrowed(self.name)),
+                "type" => Some(Cow::Borrowed(self.kind)),
+                _ => None,
+            }
+        }
+
+        fn has_filter_tag(&self, tag: &str) -> bool {
+            tag == "unused" && !self.used
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let expr = FilterExpr::parse(r#"name:Foo  type:mesh -unused "a b" - x:"#);
+        assert_eq!(
+            expr.terms,
+            vec![
+                FilterTerm {
+                    field: Some("name".into()),
+                    value: "foo".into(),
+                    negated: false,
+                },
+                FilterTerm {
+                    field: Some("type".into()),
+                    value: "mesh".into(),
+                    negated: false,
+                },
+                FilterTerm {
+                    field: None,
+                    value: "unused".into(),
+                    negated: true,
+                },
+                FilterTerm {
+                    field: None,
+                    value: "a b".into(),
+                    negated: false,
+                },
+                FilterTerm {
+                    field: None,
+                    value: "-".into(),
+                    negated: false,
+                },
+            ]
+        );
+        assert!(FilterExpr::parse("   ").is_empty());
+    }
+
+    #[test]
+    fn test_matches() {
+        let cube = Item {
+            name: "Cube",
+            kind: "Mesh",
+            used: true,
+        };
+        let old = Item {
+            name: "OldCube",
+            kind: "Mesh",
+            used: false,
+        };
+        let expr = FilterExpr::parse("cube type:mesh -unused");
+        assert!(expr.matches(&cube));
+        assert!(!expr.matches(&old));
+        assert!(FilterExpr::parse("unused").matches(&old));
+        assert!(!FilterExpr::parse("type:light").matches(&cube));
+        assert!(FilterExpr::default().matches(&cube));
+    }
+
+    #[test]
+    fn test_highlight() {
+        let expr = FilterExpr::parse("ub cu -be");
+        assert_eq!(expr.highlight_ranges("Cube cube"), vec![0..3, 5..8]);
+        assert_eq!(
+            expr.highlight_segments("A Cube"),
+            vec![
+                ("A ".to_string(), false),
+                ("Cub".to_string(), true),
+                ("e".to_string(), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unicode_case() {
+        let expr = FilterExpr::parse("ÉCOLE Straẞe");
+        assert_eq!(expr.terms[0].value, "école");
+        assert!(expr.matches(&"L'École de la straße".to_string()));
+        assert!(!expr.matches(&"ECOLE strasse".to_string()));
+        assert!(FilterExpr::parse("ΣΟΦΙΑ").matches(&"σοφια".to_string()));
+        assert_eq!(
+            FilterExpr::parse("école").highlight_ranges("Une ÉCOLE"),
+            vec![4..10]
+        );
+    }
+
+    #[test]
+    fn test_highlight_length_change() {
+        // 'İ' is two bytes, but lowercases to three: 'i' and a combining dot.
+        let expr = FilterExpr::parse("stan");
+        assert_eq!(expr.highlight_ranges("İstanbul"), vec![2..6]);
+        assert_eq!(
+            FilterExpr::parse("i").highlight_segments("İzmir"),
+            vec![
+                ("İ".to_string(), true),
+                ("zm".to_string(), false),
+                ("i".to_string(), true),
+                ("r".to_string(), false),
+            ]
+        );
+    }
+}
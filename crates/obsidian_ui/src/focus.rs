@@ -1,12 +1,13 @@
 use bevy::{
-    a11y::Focus,
+    a11y::{AccessibilityNode, Focus},
     app::{App, Plugin, Update},
     ecs::{
         component::Component,
         entity::Entity,
         event::{Event, EventReader, EventWriter},
         query::{Added, With, Without},
-        system::{Query, Res, ResMut, Resource, SystemParam},
+        system::{Query, Res, ResMut, Resource, SystemParam, SystemState},
+        world::World,
     },
     hierarchy::{Children, Parent},
     input::{
@@ -14,27 +15,33 @@ use bevy::{
         ButtonInput, ButtonState,
     },
     log::*,
+    text::Text,
     ui::Node,
     window::ReceivedCharacter,
 };
+#[cfg(feature = "picking")]
 use bevy_mod_picking::prelude::{EntityEvent, EventListenerPlugin};
 
-/// Bubbling event for key character input.
-#[derive(Clone, Event, EntityEvent)]
+/// Event for key character input, targeting the focused element. With the `picking`
+/// feature, the event bubbles up to the ancestors of the target.
+#[derive(Clone, Event)]
+#[cfg_attr(feature = "picking", derive(EntityEvent))]
 pub struct KeyCharEvent {
     /// The target of the event
-    #[target]
+    #[cfg_attr(feature = "picking", target)]
     pub target: Entity,
 
     /// Unicode value of the pressed key.
     pub key: char,
 }
 
-/// Bubbling event for key press.
-#[derive(Clone, Event, EntityEvent)]
+/// Event for key press, targeting the focused element. With the `picking` feature, the
+/// event bubbles up to the ancestors of the target.
+#[derive(Clone, Event)]
+#[cfg_attr(feature = "picking", derive(EntityEvent))]
 pub struct KeyPressEvent {
     /// The target of the event
-    #[target]
+    #[cfg_attr(feature = "picking", target)]
     pub target: Entity,
 
     /// Key code of the pressed key.
@@ -45,6 +52,9 @@ pub struct KeyPressEvent {
 
     /// Whether the shift key is held down.
     pub shift: bool,
+
+    /// Whether the command modifier is held down: Control, or Command on macOS.
+    pub command: bool,
 }
 
 /// A component which indicates that an entity wants to participate in tab navigation.
@@ -213,6 +223,138 @@ impl TabNavigation<'_, '_> {
     }
 }
 
+/// A problem with keyboard accessibility found by [`audit_focus_order`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusIssue {
+    /// The focusable entity has no accessible name, either from its [`AccessibilityNode`]
+    /// or from text content, so a screen reader can't describe it.
+    MissingName(Entity),
+
+    /// The entity has a non-negative [`TabIndex`], but is not inside any [`TabGroup`], so
+    /// it can't be reached with the keyboard.
+    Unreachable(Entity),
+
+    /// The modal [`TabGroup`] contains no focusable entities. Once focus is inside the
+    /// group, there is nothing to tab to, and no way to tab out.
+    FocusTrap(Entity),
+}
+
+impl FocusIssue {
+    /// The entity which has the problem.
+    pub fn entity(&self) -> Entity {
+        match self {
+            FocusIssue::MissingName(e) | FocusIssue::Unreachable(e) | FocusIssue::FocusTrap(e) => {
+                *e
+            }
+        }
+    }
+
+    /// A short description of the problem.
+    pub fn description(&self) -> &'static str {
+        match self {
+            FocusIssue::MissingName(_) => "Focusable has no accessible name",
+            FocusIssue::Unreachable(_) => "Focusable is not in a tab group",
+            FocusIssue::FocusTrap(_) => "Modal tab group has no focusables",
+        }
+    }
+}
+
+/// The results of [`audit_focus_order`]. These are also shown by the layout debug overlay,
+/// see [`LayoutDebugOverlay`](crate::layout_debug::LayoutDebugOverlay).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FocusAudit {
+    /// Focusable entities in the non-modal tab groups, in the order they are visited when
+    /// pressing the Tab key.
+    pub order: Vec<Entity>,
+
+    /// List of problems found.
+    pub issues: Vec<FocusIssue>,
+}
+
+impl FocusAudit {
+    /// Log each of the issues found as a warning.
+    pub fn log(&self) {
+        for issue in self.issues.iter() {
+            warn!("{}: {:?}", issue.description(), issue.entity());
+        }
+    }
+}
+
+/// Walk the focusable entities in tab order and report accessibility problems: focusables
+/// without an accessible name, focusables that can't be reached with the keyboard, and
+/// modal groups that trap the focus.
+#[allow(clippy::type_complexity)]
+pub fn audit_focus_order(world: &mut World) -> FocusAudit {
+    let mut state: SystemState<(
+        TabNavigation,
+        Query<(Entity, &TabIndex)>,
+        Query<&AccessibilityNode>,
+        Query<&Text>,
+        Query<&Children>,
+    )> = SystemState::new(world);
+    let (nav, tabindex, a11y, text, children) = state.get(world);
+    let mut audit = FocusAudit::default();
+
+    // Tab order of the non-modal groups.
+    let mut focusable: Vec<(Entity, TabIndex)> = Vec::new();
+    let mut tab_groups: Vec<(Entity, TabGroup)> = nav
+        .tabgroup
+        .iter()
+        .filter(|(_, tg, _)| !tg.modal)
+        .map(|(e, tg, _)| (e, *tg))
+        .collect();
+    tab_groups.sort_by(compare_tab_groups);
+    for (tg_entity, _) in tab_groups.iter() {
+        nav.gather_focusable(&mut focusable, *tg_entity);
+    }
+    focusable.sort_by(compare_tab_indices);
+    audit.order = focusable.iter().map(|(e, _)| *e).collect();
+
+    // Modal groups are reachable once focus is inside them.
+    let mut reachable = audit.order.clone();
+    for (tg_entity, tg, tg_children) in nav.tabgroup.iter() {
+        if tg.modal {
+            let mut group: Vec<(Entity, TabIndex)> = Vec::new();
+            for child in tg_children.iter() {
+                nav.gather_focusable(&mut group, *child);
+            }
+            if group.is_empty() {
+                audit.issues.push(FocusIssue::FocusTrap(tg_entity));
+            }
+            reachable.extend(group.iter().map(|(e, _)| *e));
+        }
+    }
+
+    for (entity, index) in tabindex.iter() {
+        if index.0 < 0 {
+            continue;
+        }
+        if !reachable.contains(&entity) {
+            audit.issues.push(FocusIssue::Unreachable(entity));
+        }
+        let named = a11y
+            .get(entity)
+            .is_ok_and(|node| node.name().is_some_and(|name| !name.is_empty()));
+        if !named && !has_text(entity, &text, &children) {
+            audit.issues.push(FocusIssue::MissingName(entity));
+        }
+    }
+
+    audit
+}
+
+/// Returns true if the entity or any of its descendants has non-empty text.
+fn has_text(entity: Entity, text: &Query<&Text>, children: &Query<&Children>) -> bool {
+    if let Ok(text) = text.get(entity) {
+        if text.sections.iter().any(|s| !s.value.trim().is_empty()) {
+            return true;
+        }
+    }
+    children
+        .get(entity)
+        .is_ok_and(|c| c.iter().any(|child| has_text(*child, text, children)))
+}
+
 fn compare_tab_groups(a: &(Entity, TabGroup), b: &(Entity, TabGroup)) -> std::cmp::Ordering {
     a.1.order.cmp(&b.1.order)
 }
@@ -265,6 +407,12 @@ fn handle_text_input(
                     key_code: ev.key_code,
                     repeat: !key.just_pressed(ev.key_code),
                     shift: key.pressed(KeyCode::ShiftLeft) || key.pressed(KeyCode::ShiftRight),
+                    command: key.any_pressed([
+                        KeyCode::ControlLeft,
+                        KeyCode::ControlRight,
+                        KeyCode::SuperLeft,
+                        KeyCode::SuperRight,
+                    ]),
                 };
                 press_writer.send(ev);
             }
@@ -286,13 +434,66 @@ pub struct KeyboardInputPlugin;
 
 impl Plugin for KeyboardInputPlugin {
     fn build(&self, app: &mut App) {
+        #[cfg(feature = "picking")]
         app.add_plugins((
             EventListenerPlugin::<KeyCharEvent>::default(),
             EventListenerPlugin::<KeyPressEvent>::default(),
-        ))
-        .init_resource::<FocusVisible>()
-        .add_event::<KeyPressEvent>()
-        .add_event::<KeyCharEvent>()
-        .add_systems(Update, (handle_auto_focus, handle_tab, handle_text_input));
+        ));
+        app.init_resource::<FocusVisible>()
+            .add_event::<KeyPressEvent>()
+            .add_event::<KeyCharEvent>()
+            .add_systems(Update, (handle_auto_focus, handle_tab, handle_text_input));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{hierarchy::BuildWorldChildren, text::TextStyle};
+
+    use super::*;
+
+    #[test]
+    fn test_audit_focus_order() {
+        let mut world = World::default();
+        let named = |label: &str| {
+            (
+                Node::default(),
+                Text::from_section(label, TextStyle::default()),
+            )
+        };
+        let second = world.spawn((named("Second"), TabIndex(1))).id();
+        let first = world.spawn((named("First"), TabIndex(0))).id();
+        let unnamed = world.spawn((Node::default(), TabIndex(0))).id();
+        let skipped = world.spawn((Node::default(), TabIndex(-1))).id();
+        world
+            .spawn((Node::default(), TabGroup::default()))
+            .push_children(&[second, first, unnamed, skipped]);
+
+        // Focusables outside of any tab group can't be reached.
+        let stray = world.spawn((named("Stray"), TabIndex(0))).id();
+
+        // A modal group with nothing to focus traps the focus.
+        let empty = world.spawn(Node::default()).id();
+        let modal = world
+            .spawn((
+                Node::default(),
+                TabGroup {
+                    order: 0,
+                    modal: true,
+                },
+            ))
+            .push_children(&[empty])
+            .id();
+
+        let audit = audit_focus_order(&mut world);
+        assert_eq!(audit.order, vec![first, unnamed, second]);
+        assert_eq!(audit.issues.len(), 3);
+        for issue in [
+            FocusIssue::MissingName(unnamed),
+            FocusIssue::Unreachable(stray),
+            FocusIssue::FocusTrap(modal),
+        ] {
+            assert!(audit.issues.contains(&issue), "missing {:?}", issue);
+        }
     }
 }
@@ -47,6 +47,18 @@ impl BistableTransitionState {
             BistableTransitionState::Exited => "exited",
         }
     }
+
+    /// Returns true if content should be animating towards, or resting in, its open
+    /// appearance. This is false for the one-frame `EnterStart` state, so that the opening
+    /// animation starts from the closed appearance.
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self,
+            BistableTransitionState::Entering
+                | BistableTransitionState::Entered
+                | BistableTransitionState::ExitStart
+        )
+    }
 }
 
 #[derive(Component, Default)]
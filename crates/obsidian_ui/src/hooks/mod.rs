@@ -1,7 +1,11 @@
+#[cfg(feature = "animation")]
 mod bistable_transition;
 mod focus_signal;
+mod size_signal;
 
+#[cfg(feature = "animation")]
 pub use bistable_transition::{
     BistableTransitionPlugin, BistableTransitionState, CreateBistableTransition,
 };
 pub use focus_signal::CreateFocusSignal;
+pub use size_signal::CreateSizeSignal;
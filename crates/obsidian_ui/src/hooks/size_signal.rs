@@ -0,0 +1,19 @@
+use bevy::{ecs::entity::Entity, math::Vec2, ui::Node};
+use bevy_reactor::{Cx, RunContextRead, RunContextSetup, Signal};
+
+/// Method to create a signal that tracks the measured size of a UI node.
+pub trait CreateSizeSignal {
+    /// Signal that returns the computed size of the target node, in logical pixels. Returns
+    /// zero until the node has been laid out.
+    fn create_size_signal(&mut self, target: Entity) -> Signal<Vec2>;
+}
+
+impl<'p, 'w, Props> CreateSizeSignal for Cx<'p, 'w, Props> {
+    fn create_size_signal(&mut self, target: Entity) -> Signal<Vec2> {
+        self.create_derived(move |cx| {
+            cx.use_component::<Node>(target)
+                .map(|node| node.size())
+                .unwrap_or_default()
+        })
+    }
+}
@@ -0,0 +1,960 @@
+use bevy::{
+    prelude::*, render::view::RenderLayers, sprite::Anchor, text::TextLayoutInfo, ui::UiSystem,
+    utils::HashMap, window::PrimaryWindow,
+};
+use bevy_reactor::*;
+
+use crate::focus::{audit_focus_order, FocusAudit};
+
+/// Plugin which adds the layout debug overlay. Press the toggle key (F12 by default) to show
+/// the bounds of the element under the cursor, in the style of a web browser's developer
+/// tools: the margin, border, padding and content boxes are outlined, rulers extend from the
+/// edges of the element to the edges of the window, and a label shows the element's name
+/// and size. With the `style-debug` feature, the label also lists which style function set
+/// each of the element's style properties, and which other styles it overrode.
+///
+/// The overlay is drawn with gizmos by a 2D camera which renders after every other camera.
+/// So that the UI isn't moved to that camera while the overlay is shown, the app's UI camera
+/// is marked with [`IsDefaultUiCamera`], unless some camera already is.
+///
+/// While the overlay is shown, it also displays the results of
+/// [`audit_focus_order`](crate::focus::audit_focus_order): a path connects the focusable
+/// elements in tab order, and elements with keyboard accessibility problems are outlined,
+/// with the problems listed in the label when the element is hovered.
+///
+/// The plugin also adds [`LayoutDiagnostics`], which can report common layout mistakes. It
+/// requires the [`ReactorPlugin`].
+pub struct LayoutDebugPlugin;
+
+impl Plugin for LayoutDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LayoutDebugOverlay>()
+            .init_resource::<LayoutDiagnostics>()
+            .init_resource::<OverlayContent>()
+            .insert_gizmo_group(
+                LayoutDebugGizmos,
+                GizmoConfig {
+                    render_layers: RenderLayers::layer(OVERLAY_LAYER),
+                    line_width: 1.,
+                    ..default()
+                },
+            )
+            .add_systems(Startup, create_overlay_effect)
+            .add_systems(Update, (toggle_layout_debug, update_overlay_camera).chain())
+            .add_systems(
+                PostUpdate,
+                (
+                    check_layout,
+                    find_hovered_element,
+                    audit_overlay_focus,
+                    draw_layout_debug,
+                )
+                    .chain()
+                    .after(UiSystem::Layout),
+            );
+    }
+}
+
+/// Resource which controls the layout debug overlay.
+#[derive(Resource)]
+pub struct LayoutDebugOverlay {
+    /// Whether the overlay is shown.
+    pub enabled: bool,
+
+    /// The key which toggles the overlay.
+    pub toggle_key: KeyCode,
+
+    /// Whether the overlay shows the focus order and keyboard accessibility problems.
+    pub show_focus_order: bool,
+
+    /// The topmost element under the cursor.
+    hovered: Option<Entity>,
+
+    /// The focus order audit, which is updated every frame while the overlay is shown.
+    focus_audit: FocusAudit,
+
+    /// The overlay camera and label, while the overlay is shown.
+    spawned: Option<(Entity, Entity)>,
+
+    /// The camera which was marked as the default UI camera when the overlay was shown.
+    pinned: Option<Entity>,
+}
+
+impl Default for LayoutDebugOverlay {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            toggle_key: KeyCode::F12,
+            show_focus_order: true,
+            hovered: None,
+            focus_audit: FocusAudit::default(),
+            spawned: None,
+            pinned: None,
+        }
+    }
+}
+
+/// Gizmo group for the overlay, which is only rendered by the overlay camera.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+struct LayoutDebugGizmos;
+
+/// Marks the text of the overlay's label.
+#[derive(Component)]
+struct LayoutDebugLabelText;
+
+/// Render layer of the overlay camera, and of everything it draws.
+const OVERLAY_LAYER: u8 = 31;
+
+/// Space between the label's text and the edges of its background.
+const LABEL_PADDING: Vec2 = Vec2::new(4., 2.);
+
+const MARGIN_COLOR: Color = Color::rgba(0.97, 0.6, 0.2, 0.9);
+const BORDER_COLOR: Color = Color::rgba(1.0, 0.85, 0.4, 0.9);
+const PADDING_COLOR: Color = Color::rgba(0.55, 0.75, 0.4, 0.9);
+const CONTENT_COLOR: Color = Color::rgba(0.4, 0.6, 0.9, 0.9);
+const RULER_COLOR: Color = Color::rgba(1.0, 0.2, 0.8, 0.6);
+const LABEL_BG_COLOR: Color = Color::rgba(0.1, 0.1, 0.12, 0.9);
+const ISSUE_COLOR: Color = Color::rgba(1.0, 0.1, 0.1, 0.9);
+const FOCUS_ORDER_COLOR: Color = Color::rgba(0.2, 0.9, 0.9, 0.8);
+const FOCUS_ISSUE_COLOR: Color = Color::rgba(0.9, 0.2, 1.0, 0.9);
+
+/// Radius of the dots which mark the focusable elements along the focus order path.
+const FOCUS_DOT_RADIUS: f32 = 3.;
+
+/// The nested boxes of an element, in logical window coordinates.
+#[derive(Clone, Debug, PartialEq)]
+struct DebugBoxes {
+    name: String,
+    margin: Rect,
+    border: Rect,
+    padding: Rect,
+    content: Rect,
+}
+
+/// What the overlay displays, in logical window coordinates. This is computed by an effect
+/// from the layout rects of the elements involved, and drawn every frame.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+struct OverlayContent {
+    /// The boxes of the hovered element.
+    hovered: Option<DebugBoxes>,
+    /// Additional lines for the hovered element's label: its focus order and accessibility
+    /// problems, and where its style properties came from, one line per property.
+    details: Vec<String>,
+    /// The bounds of elements with layout issues.
+    issues: Vec<Rect>,
+    /// The bounds of the focusable elements, in tab order.
+    focus_order: Vec<Rect>,
+    /// The bounds of elements with keyboard accessibility problems.
+    focus_issues: Vec<Rect>,
+}
+
+/// A common layout mistake detected by [`LayoutDiagnostics`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LayoutIssue {
+    /// The element extends outside of its parent, and the parent doesn't clip its content.
+    Overflow,
+    /// The element has a percentage width or height, but its parent's size on that axis is
+    /// `Auto` and isn't stretched by its own parent, so the percentage has nothing definite
+    /// to be relative to.
+    PercentOfAutoParent,
+    /// The element is a flex item with content, but has been laid out with zero width or
+    /// height, typically because it has shrunk to nothing or has no basis.
+    ZeroSizedFlexItem,
+}
+
+impl LayoutIssue {
+    fn description(&self, style: &Style) -> String {
+        match self {
+            LayoutIssue::Overflow => "overflows its parent".to_string(),
+            LayoutIssue::PercentOfAutoParent => format!(
+                "has a percentage size (width: {:?}, height: {:?}) inside a parent with auto size",
+                style.width, style.height
+            ),
+            LayoutIssue::ZeroSizedFlexItem => format!(
+                "is a flex item with zero size (flex_basis: {:?}, flex_shrink: {})",
+                style.flex_basis, style.flex_shrink
+            ),
+        }
+    }
+
+    /// The style properties of the element which are responsible for the issue.
+    fn properties(&self) -> &'static [&'static str] {
+        match self {
+            LayoutIssue::Overflow => &["width", "height", "min_width", "min_height", "margin"],
+            LayoutIssue::PercentOfAutoParent => &["width", "height"],
+            LayoutIssue::ZeroSizedFlexItem => &["flex_basis", "flex_shrink", "width", "height"],
+        }
+    }
+}
+
+/// Resource which controls the layout diagnostics. When enabled, the layout is checked each
+/// frame for common mistakes, and each new issue is logged as a warning, along with the path
+/// of names from the root of the UI to the element, and the style properties responsible.
+/// With the `style-debug` feature, the warning also names the style function which set each
+/// of those properties. If `highlight` is set, elements with issues are also outlined in the
+/// layout debug overlay.
+#[derive(Resource, Default)]
+pub struct LayoutDiagnostics {
+    /// Whether to check the layout.
+    pub enabled: bool,
+
+    /// Whether to outline elements with issues in the debug overlay.
+    pub highlight: bool,
+
+    /// The issues found during the last check.
+    issues: HashMap<Entity, Vec<LayoutIssue>>,
+}
+
+impl LayoutDiagnostics {
+    /// The elements which had issues during the last check.
+    pub fn issues(&self) -> impl Iterator<Item = (Entity, &[LayoutIssue])> {
+        self.issues
+            .iter()
+            .map(|(entity, issues)| (*entity, issues.as_slice()))
+    }
+}
+
+/// Returns true if `align` stretches an item, given the alignment of its container.
+fn is_stretched(align_self: AlignSelf, align_items: AlignItems) -> bool {
+    match align_self {
+        AlignSelf::Stretch => true,
+        AlignSelf::Auto => matches!(align_items, AlignItems::Default | AlignItems::Stretch),
+        _ => false,
+    }
+}
+
+/// Whether the size of an element is definite on each axis, so that percentage sizes of its
+/// children can be resolved against it. A size is definite if it is set explicitly, or if
+/// the element is stretched to fill its flex or grid parent on that axis.
+fn definite_size(style: &Style, parent: Option<&Style>) -> BVec2 {
+    let (stretch_x, stretch_y) = match parent {
+        Some(_) if style.position_type == PositionType::Absolute => (false, false),
+        Some(parent) if parent.display == Display::Flex => {
+            let stretched = is_stretched(style.align_self, parent.align_items);
+            match parent.flex_direction {
+                FlexDirection::Row | FlexDirection::RowReverse => (false, stretched),
+                FlexDirection::Column | FlexDirection::ColumnReverse => (stretched, false),
+            }
+        }
+        Some(parent) if parent.display == Display::Grid => (
+            match style.justify_self {
+                JustifySelf::Stretch => true,
+                JustifySelf::Auto => matches!(
+                    parent.justify_items,
+                    JustifyItems::Default | JustifyItems::Stretch
+                ),
+                _ => false,
+            },
+            is_stretched(style.align_self, parent.align_items),
+        ),
+        _ => (false, false),
+    };
+    BVec2::new(
+        style.width != Val::Auto || stretch_x,
+        style.height != Val::Auto || stretch_y,
+    )
+}
+
+/// Returns true if a percentage size on an axis can't be resolved against the parent.
+fn is_percent_of_auto(size: Val, parent_definite: bool) -> bool {
+    matches!(size, Val::Percent(_)) && !parent_definite
+}
+
+/// The layout of an element's parent, as needed to check the element.
+struct ParentLayout<'a> {
+    rect: Rect,
+    style: &'a Style,
+    /// Whether the parent's size is definite on each axis; see [`definite_size`].
+    definite: BVec2,
+}
+
+/// Check a single element for layout issues.
+fn find_layout_issues(
+    rect: Rect,
+    style: &Style,
+    has_children: bool,
+    parent: Option<ParentLayout>,
+) -> Vec<LayoutIssue> {
+    let mut issues = Vec::new();
+    let Some(ParentLayout {
+        rect: parent_rect,
+        style: parent_style,
+        definite,
+    }) = parent
+    else {
+        return issues;
+    };
+    const TOLERANCE: f32 = 0.5;
+    let overflow_x = parent_style.overflow.x.is_visible()
+        && (rect.min.x < parent_rect.min.x - TOLERANCE
+            || rect.max.x > parent_rect.max.x + TOLERANCE);
+    let overflow_y = parent_style.overflow.y.is_visible()
+        && (rect.min.y < parent_rect.min.y - TOLERANCE
+            || rect.max.y > parent_rect.max.y + TOLERANCE);
+    if style.position_type != PositionType::Absolute && (overflow_x || overflow_y) {
+        issues.push(LayoutIssue::Overflow);
+    }
+    if is_percent_of_auto(style.width, definite.x) || is_percent_of_auto(style.height, definite.y) {
+        issues.push(LayoutIssue::PercentOfAutoParent);
+    }
+    if parent_style.display == Display::Flex
+        && style.display != Display::None
+        && style.position_type != PositionType::Absolute
+        && has_children
+        && (rect.width() <= 0. || rect.height() <= 0.)
+    {
+        issues.push(LayoutIssue::ZeroSizedFlexItem);
+    }
+    issues
+}
+
+/// The path of names from the root of the hierarchy to `entity`.
+fn element_path(entity: Entity, names: &Query<(Option<&Name>, Option<&Parent>)>) -> String {
+    let mut path = Vec::new();
+    let mut current = Some(entity);
+    while let Some(entity) = current {
+        let Ok((name, parent)) = names.get(entity) else {
+            break;
+        };
+        path.push(name.map_or_else(|| format!("{:?}", entity), |name| name.to_string()));
+        current = parent.map(|parent| parent.get());
+    }
+    path.reverse();
+    path.join(" > ")
+}
+
+/// Show the name of a style function, without the module path.
+#[cfg(feature = "style-debug")]
+fn short_source(source: &str) -> &str {
+    let path = source.trim_end_matches("::{{closure}}");
+    let start = path.rfind("::").map_or(0, |index| index + 2);
+    &source[start..]
+}
+
+/// Describe which style functions set the properties responsible for `issue`, for example
+/// `" (width set by style_panel)"`. Empty if none of them were set by a style.
+#[cfg(feature = "style-debug")]
+fn issue_sources(issue: &LayoutIssue, provenance: Option<&StyleProvenance>) -> String {
+    let Some(provenance) = provenance else {
+        return String::new();
+    };
+    let sources: Vec<String> = issue
+        .properties()
+        .iter()
+        .filter_map(|property| {
+            let source = provenance.source(property)?;
+            Some(format!("{} set by {}", property, short_source(source)))
+        })
+        .collect();
+    if sources.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", sources.join(", "))
+    }
+}
+
+/// Check the layout of every element, and log issues which weren't present last time.
+#[allow(clippy::type_complexity)]
+pub(crate) fn check_layout(
+    mut diagnostics: ResMut<LayoutDiagnostics>,
+    nodes: Query<(
+        Entity,
+        &Node,
+        &GlobalTransform,
+        &Style,
+        Option<&Children>,
+        Option<&Parent>,
+    )>,
+    parents: Query<(&Node, &GlobalTransform, &Style, Option<&Parent>)>,
+    styles: Query<&Style>,
+    names: Query<(Option<&Name>, Option<&Parent>)>,
+    #[cfg(feature = "style-debug")] provenance: Query<&StyleProvenance>,
+) {
+    if !diagnostics.enabled {
+        diagnostics.issues.clear();
+        return;
+    }
+    let mut found = HashMap::<Entity, Vec<LayoutIssue>>::default();
+    for (entity, node, transform, style, children, parent) in nodes.iter() {
+        let parent = parent
+            .and_then(|parent| parents.get(parent.get()).ok())
+            .map(|(node, transform, style, grandparent)| ParentLayout {
+                rect: node.logical_rect(transform),
+                style,
+                definite: definite_size(
+                    style,
+                    grandparent.and_then(|grandparent| styles.get(grandparent.get()).ok()),
+                ),
+            });
+        let has_children = children.is_some_and(|children| !children.is_empty());
+        let issues = find_layout_issues(node.logical_rect(transform), style, has_children, parent);
+        if issues.is_empty() {
+            continue;
+        }
+        let previous = diagnostics.issues.get(&entity);
+        for issue in issues.iter() {
+            if !previous.is_some_and(|previous| previous.contains(issue)) {
+                #[cfg(feature = "style-debug")]
+                let sources = issue_sources(issue, provenance.get(entity).ok());
+                #[cfg(not(feature = "style-debug"))]
+                let sources = "";
+                warn!(
+                    "Layout: {} {}{}",
+                    element_path(entity, &names),
+                    issue.description(style),
+                    sources
+                );
+            }
+        }
+        found.insert(entity, issues);
+    }
+    diagnostics.issues = found;
+}
+
+/// Resolve a value in the same way as the layout, relative to the parent width, and convert
+/// it from UI pixels to logical window pixels.
+fn resolve(val: Val, parent_width: f32, viewport: Vec2, scale: f32) -> f32 {
+    val.resolve(parent_width, viewport).unwrap_or(0.) * scale
+}
+
+/// Grow `rect` by the given edges, resolved against the parent width. Negative values for
+/// `sign` shrink the rect instead.
+fn inset(
+    rect: Rect,
+    edges: UiRect,
+    sign: f32,
+    parent_width: f32,
+    viewport: Vec2,
+    scale: f32,
+) -> Rect {
+    let left = resolve(edges.left, parent_width, viewport, scale) * sign;
+    let right = resolve(edges.right, parent_width, viewport, scale) * sign;
+    let top = resolve(edges.top, parent_width, viewport, scale) * sign;
+    let bottom = resolve(edges.bottom, parent_width, viewport, scale) * sign;
+    let min = Vec2::new(rect.min.x - left, rect.min.y - top);
+    let max = Vec2::new(rect.max.x + right, rect.max.y + bottom);
+    Rect::from_corners(min, max.max(min))
+}
+
+/// Compute the margin, border, padding and content boxes of an element from its border box
+/// and style. The border box is in logical window pixels, while the parent width and
+/// viewport are in UI pixels, which are `scale` times smaller.
+fn debug_boxes(
+    name: String,
+    border: Rect,
+    style: &Style,
+    parent_width: f32,
+    viewport: Vec2,
+    scale: f32,
+) -> DebugBoxes {
+    let margin = inset(border, style.margin, 1., parent_width, viewport, scale);
+    let padding = inset(border, style.border, -1., parent_width, viewport, scale);
+    let content = inset(padding, style.padding, -1., parent_width, viewport, scale);
+    DebugBoxes {
+        name,
+        margin,
+        border,
+        padding,
+        content,
+    }
+}
+
+pub(crate) fn toggle_layout_debug(
+    mut overlay: ResMut<LayoutDebugOverlay>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    if keys.just_pressed(overlay.toggle_key) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+/// Spawn the overlay camera and label when the overlay is shown, and despawn them when it is
+/// hidden.
+pub(crate) fn update_overlay_camera(
+    mut commands: Commands,
+    mut overlay: ResMut<LayoutDebugOverlay>,
+    default_ui_camera: DefaultUiCamera,
+    marked: Query<(), With<IsDefaultUiCamera>>,
+) {
+    match (overlay.enabled, overlay.spawned) {
+        (true, None) => {
+            // Otherwise the overlay camera, which has the highest order, would become the
+            // default UI camera, and draw the UI over the overlay.
+            if marked.is_empty() {
+                overlay.pinned = default_ui_camera.get();
+                if let Some(camera) = overlay.pinned {
+                    commands.entity(camera).insert(IsDefaultUiCamera);
+                }
+            }
+            let camera = commands
+                .spawn((
+                    Camera2dBundle {
+                        camera: Camera {
+                            order: isize::MAX,
+                            clear_color: ClearColorConfig::None,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    RenderLayers::layer(OVERLAY_LAYER),
+                    Name::new("LayoutDebugCamera"),
+                ))
+                .id();
+            let label = commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: LABEL_BG_COLOR,
+                            anchor: Anchor::TopLeft,
+                            ..default()
+                        },
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    RenderLayers::layer(OVERLAY_LAYER),
+                    Name::new("LayoutDebugLabel"),
+                ))
+                .with_children(|label| {
+                    label.spawn((
+                        Text2dBundle {
+                            text: Text::from_section(
+                                "",
+                                TextStyle {
+                                    font_size: 12.,
+                                    color: Color::WHITE,
+                                    ..default()
+                                },
+                            ),
+                            text_anchor: Anchor::TopLeft,
+                            transform: Transform::from_xyz(LABEL_PADDING.x, -LABEL_PADDING.y, 1.),
+                            ..default()
+                        },
+                        RenderLayers::layer(OVERLAY_LAYER),
+                        LayoutDebugLabelText,
+                    ));
+                })
+                .id();
+            overlay.spawned = Some((camera, label));
+        }
+        (false, Some((camera, label))) => {
+            commands.entity(camera).despawn();
+            commands.entity(label).despawn_recursive();
+            if let Some(camera) = overlay.pinned.take() {
+                if let Some(mut camera) = commands.get_entity(camera) {
+                    camera.remove::<IsDefaultUiCamera>();
+                }
+            }
+            overlay.spawned = None;
+        }
+        _ => {}
+    }
+}
+
+/// Find the topmost element under the cursor.
+pub(crate) fn find_hovered_element(
+    mut overlay: ResMut<LayoutDebugOverlay>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    nodes: Query<(Entity, &Node, &GlobalTransform, &ViewVisibility)>,
+) {
+    let cursor = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .filter(|_| overlay.enabled);
+    let hovered = cursor.and_then(|cursor| {
+        // Node rects are in UI pixels, which are scaled by the UI scale.
+        let cursor = cursor / ui_scale.0;
+        nodes
+            .iter()
+            .filter(|(_, node, transform, visibility)| {
+                visibility.get() && node.logical_rect(transform).contains(cursor)
+            })
+            .max_by_key(|(_, node, ..)| node.stack_index())
+            .map(|(entity, ..)| entity)
+    });
+    if overlay.hovered != hovered {
+        overlay.hovered = hovered;
+    }
+}
+
+/// Audit the focus order while the overlay is shown.
+pub(crate) fn audit_overlay_focus(world: &mut World) {
+    let overlay = world.resource::<LayoutDebugOverlay>();
+    let audit = if overlay.enabled && overlay.show_focus_order {
+        audit_focus_order(world)
+    } else {
+        FocusAudit::default()
+    };
+    let mut overlay = world.resource_mut::<LayoutDebugOverlay>();
+    if overlay.focus_audit != audit {
+        overlay.focus_audit = audit;
+    }
+}
+
+/// The label lines describing an element's position in the focus order, and its keyboard
+/// accessibility problems.
+fn focus_lines(audit: &FocusAudit, entity: Entity) -> Vec<String> {
+    let position = audit.order.iter().position(|e| *e == entity);
+    position
+        .map(|index| format!("Tab order: {} of {}", index + 1, audit.order.len()))
+        .into_iter()
+        .chain(
+            audit
+                .issues
+                .iter()
+                .filter(|issue| issue.entity() == entity)
+                .map(|issue| issue.description().to_string()),
+        )
+        .collect()
+}
+
+/// Create the effect which computes the overlay content.
+fn create_overlay_effect(world: &mut World) {
+    let mut scope = TrackingScope::new(world.read_change_tick());
+    Cx::new((), world, &mut scope).create_effect(update_overlay_content);
+}
+
+/// Effect which computes what the overlay shows from the layout rects of the hovered element
+/// and of the elements with issues, so that the content only changes when they do.
+fn update_overlay_content(cx: &mut Cx) {
+    let enabled = *cx.use_resource_field(|overlay: &LayoutDebugOverlay| &overlay.enabled);
+    let hovered = *cx.use_resource_field(|overlay: &LayoutDebugOverlay| &overlay.hovered);
+    let content = if enabled {
+        let highlight =
+            *cx.use_resource_field(|diagnostics: &LayoutDiagnostics| &diagnostics.highlight);
+        let issues = if highlight {
+            cx.use_resource_field(|diagnostics: &LayoutDiagnostics| &diagnostics.issues)
+                .keys()
+                .filter_map(|entity| cx.use_node_rect(*entity))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let audit = cx
+            .use_resource_field(|overlay: &LayoutDebugOverlay| &overlay.focus_audit)
+            .clone();
+        let focus = hovered
+            .map(|entity| focus_lines(&audit, entity))
+            .unwrap_or_default();
+        #[cfg(feature = "style-debug")]
+        let provenance = hovered
+            .and_then(|entity| cx.use_component::<StyleProvenance>(entity))
+            .map(provenance_lines)
+            .unwrap_or_default();
+        #[cfg(not(feature = "style-debug"))]
+        let provenance = Vec::new();
+        OverlayContent {
+            hovered: hovered.and_then(|entity| hovered_boxes(cx, entity)),
+            details: [focus, provenance].concat(),
+            issues,
+            focus_order: audit
+                .order
+                .iter()
+                .filter_map(|entity| cx.use_node_rect(*entity))
+                .collect(),
+            focus_issues: audit
+                .issues
+                .iter()
+                .filter_map(|issue| cx.use_node_rect(issue.entity()))
+                .collect(),
+        }
+    } else {
+        OverlayContent::default()
+    };
+    cx.world_mut()
+        .resource_mut::<OverlayContent>()
+        .set_if_neq(content);
+}
+
+/// Compute the boxes of the hovered element from its layout rect and style.
+fn hovered_boxes(cx: &Cx, entity: Entity) -> Option<DebugBoxes> {
+    let border = cx.use_node_rect(entity)?;
+    let style = cx.use_component::<Style>(entity)?;
+    // Style values are in UI pixels, which are scaled by the UI scale.
+    let ui_scale = *cx.use_resource_field(|ui_scale: &UiScale| &ui_scale.0);
+    let viewport = cx.use_window_size() / ui_scale;
+    let parent_width = cx
+        .use_component::<Parent>(entity)
+        .and_then(|parent| cx.use_node_rect(parent.get()))
+        .map_or(viewport.x, |parent| parent.width() / ui_scale);
+    let name = cx
+        .use_component::<Name>(entity)
+        .map_or_else(|| format!("{:?}", entity), |name| name.to_string());
+    Some(debug_boxes(
+        name,
+        border,
+        style,
+        parent_width,
+        viewport,
+        ui_scale,
+    ))
+}
+
+/// Describe which style functions set each style property of an element.
+#[cfg(feature = "style-debug")]
+fn provenance_lines(provenance: &StyleProvenance) -> Vec<String> {
+    provenance
+        .iter()
+        .map(|(property, writers)| {
+            let (last, overridden) = writers.split_last().unwrap();
+            let mut line = format!("{}: {}", property, short_source(last));
+            if !overridden.is_empty() {
+                let overridden: Vec<&str> = overridden.iter().map(|s| short_source(s)).collect();
+                line.push_str(&format!(" (overrides {})", overridden.join(", ")));
+            }
+            line
+        })
+        .collect()
+}
+
+/// Draw the overlay content with gizmos. The world space of the overlay camera, a 2D camera,
+/// is in logical window pixels, with the origin at the center and y pointing up.
+pub(crate) fn draw_layout_debug(
+    mut gizmos: Gizmos<LayoutDebugGizmos>,
+    overlay: Res<LayoutDebugOverlay>,
+    content: Res<OverlayContent>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut labels: Query<(&mut Transform, &mut Sprite, &mut Visibility)>,
+    mut label_text: Query<(&mut Text, &TextLayoutInfo), With<LayoutDebugLabelText>>,
+) {
+    let Some((camera, label)) = overlay.spawned else {
+        return;
+    };
+    let Ok((camera, camera_transform)) = cameras.get(camera) else {
+        return;
+    };
+    let Some(window_size) = camera.logical_viewport_size() else {
+        return;
+    };
+    let to_world = |point: Vec2| camera.viewport_to_world_2d(camera_transform, point);
+
+    let mut rects: Vec<(Rect, Color)> = content
+        .issues
+        .iter()
+        .map(|issue| (*issue, ISSUE_COLOR))
+        .chain(
+            content
+                .focus_issues
+                .iter()
+                .map(|issue| (*issue, FOCUS_ISSUE_COLOR)),
+        )
+        .collect();
+    let mut lines: Vec<(Vec2, Vec2)> = Vec::new();
+    if let Some(boxes) = &content.hovered {
+        rects.extend([
+            (boxes.margin, MARGIN_COLOR),
+            (boxes.border, BORDER_COLOR),
+            (boxes.padding, PADDING_COLOR),
+            (boxes.content, CONTENT_COLOR),
+        ]);
+        // Rulers along the edges of the border box, extending to the edges of the window.
+        let border = boxes.border;
+        for y in [border.min.y, border.max.y] {
+            lines.push((Vec2::new(0., y), Vec2::new(window_size.x, y)));
+        }
+        for x in [border.min.x, border.max.x] {
+            lines.push((Vec2::new(x, 0.), Vec2::new(x, window_size.y)));
+        }
+    }
+    for (rect, color) in rects {
+        if let (Some(min), Some(max)) = (to_world(rect.min), to_world(rect.max)) {
+            gizmos.rect_2d((min + max) / 2., 0., (max - min).abs(), color);
+        }
+    }
+    for (start, end) in lines {
+        if let (Some(start), Some(end)) = (to_world(start), to_world(end)) {
+            gizmos.line_2d(start, end, RULER_COLOR);
+        }
+    }
+    // The focus order, as a path through the centers of the focusable elements.
+    let focus_path: Vec<Vec2> = content
+        .focus_order
+        .iter()
+        .filter_map(|rect| to_world(rect.center()))
+        .collect();
+    for center in focus_path.iter() {
+        gizmos.circle_2d(*center, FOCUS_DOT_RADIUS, FOCUS_ORDER_COLOR);
+    }
+    gizmos.linestrip_2d(focus_path, FOCUS_ORDER_COLOR);
+
+    let Ok((mut transform, mut background, mut visibility)) = labels.get_mut(label) else {
+        return;
+    };
+    let (Some(boxes), Some(position)) = (
+        &content.hovered,
+        content
+            .hovered
+            .as_ref()
+            .and_then(|boxes| to_world(Vec2::new(boxes.border.min.x, boxes.border.max.y + 4.))),
+    ) else {
+        visibility.set_if_neq(Visibility::Hidden);
+        return;
+    };
+    visibility.set_if_neq(Visibility::Inherited);
+    transform.translation = position.extend(10.);
+    if let Ok((mut text, layout)) = label_text.get_single_mut() {
+        let value = std::iter::once(format!(
+            "{}  {} \u{00d7} {}",
+            boxes.name,
+            boxes.border.width(),
+            boxes.border.height()
+        ))
+        .chain(content.details.iter().cloned())
+        .collect::<Vec<_>>()
+        .join("\n");
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+        // The text is laid out after this system, so the size lags by a frame.
+        let size = Some(layout.logical_size + LABEL_PADDING * 2.);
+        if background.custom_size != size {
+            background.custom_size = size;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::focus::FocusIssue;
+
+    #[test]
+    fn test_debug_boxes() {
+        let style = Style {
+            margin: UiRect::all(Val::Px(4.)),
+            border: UiRect::all(Val::Px(1.)),
+            padding: UiRect::horizontal(Val::Percent(10.)),
+            ..default()
+        };
+        let boxes = debug_boxes(
+            "panel".to_string(),
+            Rect::new(10., 10., 110., 60.),
+            &style,
+            200.,
+            Vec2::new(800., 600.),
+            1.,
+        );
+        assert_eq!(boxes.margin, Rect::new(6., 6., 114., 64.));
+        assert_eq!(boxes.padding, Rect::new(11., 11., 109., 59.));
+        assert_eq!(boxes.content, Rect::new(31., 11., 89., 59.));
+
+        // With a UI scale of 2, the same style covers twice as many window pixels.
+        let boxes = debug_boxes(
+            "panel".to_string(),
+            Rect::new(20., 20., 220., 120.),
+            &style,
+            200.,
+            Vec2::new(400., 300.),
+            2.,
+        );
+        assert_eq!(boxes.margin, Rect::new(12., 12., 228., 128.));
+        assert_eq!(boxes.padding, Rect::new(22., 22., 218., 118.));
+        assert_eq!(boxes.content, Rect::new(62., 22., 178., 118.));
+    }
+
+    #[test]
+    fn test_focus_lines() {
+        let first = Entity::from_raw(1);
+        let second = Entity::from_raw(2);
+        let audit = FocusAudit {
+            order: vec![first, second],
+            issues: vec![
+                FocusIssue::MissingName(second),
+                FocusIssue::Unreachable(Entity::from_raw(3)),
+            ],
+        };
+        assert_eq!(focus_lines(&audit, first), vec!["Tab order: 1 of 2"]);
+        assert_eq!(
+            focus_lines(&audit, second),
+            vec!["Tab order: 2 of 2", "Focusable has no accessible name"]
+        );
+        assert_eq!(
+            focus_lines(&audit, Entity::from_raw(3)),
+            vec!["Focusable is not in a tab group"]
+        );
+    }
+
+    #[test]
+    fn test_find_layout_issues() {
+        let parent_style = Style::default();
+        let parent = || {
+            Some(ParentLayout {
+                rect: Rect::new(0., 0., 100., 100.),
+                style: &parent_style,
+                definite: BVec2::FALSE,
+            })
+        };
+
+        let inside = Rect::new(10., 10., 90., 90.);
+        let style = Style::default();
+        assert!(find_layout_issues(inside, &style, true, parent()).is_empty());
+
+        let outside = Rect::new(10., 10., 120., 90.);
+        assert_eq!(
+            find_layout_issues(outside, &style, false, parent()),
+            vec![LayoutIssue::Overflow]
+        );
+
+        // Clipping parents may have overflowing content.
+        let clipping = Style {
+            overflow: Overflow::clip(),
+            ..default()
+        };
+        let clipping_parent = ParentLayout {
+            rect: Rect::new(0., 0., 100., 100.),
+            style: &clipping,
+            definite: BVec2::FALSE,
+        };
+        assert!(find_layout_issues(outside, &style, false, Some(clipping_parent)).is_empty());
+
+        let percent = Style {
+            width: Val::Percent(50.),
+            ..default()
+        };
+        assert_eq!(
+            find_layout_issues(inside, &percent, false, parent()),
+            vec![LayoutIssue::PercentOfAutoParent]
+        );
+
+        let collapsed = Rect::new(10., 10., 10., 90.);
+        assert_eq!(
+            find_layout_issues(collapsed, &style, true, parent()),
+            vec![LayoutIssue::ZeroSizedFlexItem]
+        );
+    }
+
+    #[test]
+    fn test_definite_size() {
+        let auto = Style::default();
+        let row = Style::default();
+        let column = Style {
+            flex_direction: FlexDirection::Column,
+            ..default()
+        };
+        assert_eq!(definite_size(&auto, None), BVec2::FALSE);
+
+        // Stretched flex items have a definite size on the cross axis only.
+        assert_eq!(definite_size(&auto, Some(&row)), BVec2::new(false, true));
+        assert_eq!(definite_size(&auto, Some(&column)), BVec2::new(true, false));
+        let centered = Style {
+            align_items: AlignItems::Center,
+            ..default()
+        };
+        assert_eq!(definite_size(&auto, Some(&centered)), BVec2::FALSE);
+
+        // Grid items are stretched on both axes by default.
+        let grid = Style {
+            display: Display::Grid,
+            ..default()
+        };
+        assert_eq!(definite_size(&auto, Some(&grid)), BVec2::TRUE);
+
+        let fixed = Style {
+            width: Val::Px(100.),
+            align_self: AlignSelf::Start,
+            ..default()
+        };
+        assert_eq!(definite_size(&fixed, Some(&row)), BVec2::new(true, false));
+    }
+}
@@ -3,12 +3,17 @@
 #![warn(missing_docs)]
 
 use bevy::{app::*, ui::UiMaterialPlugin};
+#[cfg(feature = "picking")]
 use bevy_mod_picking::prelude::EventListenerPlugin;
 use materials::{GradientRectMaterial, RoundedRectMaterial, SliderRectMaterial};
 
 /// Utilities for animation.
+#[cfg(feature = "animation")]
 pub mod animation;
 
+/// Copying and pasting text.
+pub mod clipboard;
+
 /// Module containing standard color definitions.
 #[allow(missing_docs)]
 pub mod colors;
@@ -16,6 +21,16 @@ pub mod colors;
 /// Module containing interactive and layout control widgets.
 pub mod controls;
 
+/// Search query parsing and matching, shared by searchable panels.
+pub mod filter;
+
+/// Constraints and snapping for drag interactions.
+pub mod drag;
+
+/// Opening assets with the platform's file dialog.
+#[cfg(feature = "file-dialog")]
+pub mod file_dialog;
+
 /// Module containing extensions to `Cx`.
 pub mod hooks;
 
@@ -24,8 +39,19 @@ pub mod materials;
 pub use materials::RoundedCorners;
 
 /// Utilities for managing scrolling views.
+#[cfg(feature = "picking")]
 pub mod scrolling;
 
+/// Changing numeric values by dragging on labels.
+#[cfg(feature = "picking")]
+pub mod scrub;
+
+/// Scripted input playback for demos and tutorials.
+pub mod simulated_input;
+
+/// Debug overlay which shows the layout boxes of the element under the cursor.
+pub mod layout_debug;
+
 /// Module containing standard sizes.
 pub mod size;
 
@@ -41,6 +67,7 @@ pub mod typography;
 /// Plugin for the Obsidian UI library.
 pub struct ObsidianUiPlugin;
 
+#[cfg(feature = "picking")]
 use scrolling::ScrollWheel;
 
 impl Plugin for ObsidianUiPlugin {
@@ -49,17 +76,39 @@ impl Plugin for ObsidianUiPlugin {
             UiMaterialPlugin::<RoundedRectMaterial>::default(),
             UiMaterialPlugin::<GradientRectMaterial>::default(),
             UiMaterialPlugin::<SliderRectMaterial>::default(),
-            hooks::BistableTransitionPlugin,
-            animation::AnimatedTransitionPlugin,
             focus::KeyboardInputPlugin,
         ))
-        .add_plugins(EventListenerPlugin::<ScrollWheel>::default())
-        .add_event::<ScrollWheel>()
-        .add_systems(
-            Update,
+        .init_resource::<clipboard::Clipboard>();
+        #[cfg(feature = "animation")]
+        app.add_plugins((
+            hooks::BistableTransitionPlugin,
+            animation::AnimatedTransitionPlugin,
+        ));
+        #[cfg(feature = "picking")]
+        app.add_plugins(EventListenerPlugin::<ScrollWheel>::default())
+            .add_event::<ScrollWheel>()
+            .add_systems(
+                Update,
+                (
+                    scrolling::handle_scroll_events,
+                    scrolling::update_scroll_positions,
+                    scrolling::cull_offscreen_content.after(scrolling::update_scroll_positions),
+                ),
+            );
+        #[cfg(feature = "inputs")]
+        app.add_event::<controls::BrowseAssets>()
+            .init_resource::<controls::EntityPickMode>()
+            .add_systems(Update, controls::pick_entity_on_click);
+        #[cfg(feature = "overlays")]
+        app.init_resource::<controls::CompletedTours>();
+        #[cfg(feature = "data-views")]
+        app.add_systems(Last, controls::update_virtual_rows);
+        #[cfg(feature = "previews")]
+        app.add_systems(
+            PostUpdate,
             (
-                scrolling::handle_scroll_events,
-                scrolling::update_scroll_positions,
+                controls::propagate_preview_layers,
+                controls::update_viewport_image_size.after(bevy::ui::UiSystem::Layout),
             ),
         );
     }
@@ -4,7 +4,7 @@ use bevy::render::render_resource::*;
 
 /// Options for rendering rounded corners.
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, Reflect)]
 pub enum RoundedCorners {
     None,
     #[default]
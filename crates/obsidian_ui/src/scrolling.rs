@@ -1,5 +1,6 @@
 use bevy::{input::mouse::MouseWheel, prelude::*, ui};
 use bevy_mod_picking::{focus::HoverMap, pointer::PointerId, prelude::EntityEvent};
+use bevy_reactor::PauseReactions;
 
 /// Mouse wheel entity event
 #[derive(Clone, Event, EntityEvent)]
@@ -58,6 +59,20 @@ impl ScrollArea {
 #[derive(Component, Default)]
 pub struct ScrollContent;
 
+/// Component which enables culling of offscreen content in a scroll area. Each top-level item
+/// of the scrolling content which lies entirely outside the visible region, expanded by
+/// `margin` pixels, is hidden and has its reactions paused until it is scrolled near view.
+#[derive(Component)]
+pub struct CullOffscreen {
+    /// Distance outside the visible region within which items are not culled, so that they
+    /// have a chance to catch up before they are scrolled into view.
+    pub margin: f32,
+}
+
+/// Marker component for culled scroll content, holding its visibility prior to culling.
+#[derive(Component)]
+pub struct Culled(Visibility);
+
 /// Marker component indicating this entity is the scrollbar on the X-axis.
 #[derive(Component)]
 pub struct ScrollBar {
@@ -163,6 +178,49 @@ pub(crate) fn update_scroll_positions(
     }
 }
 
+#[allow(clippy::type_complexity)]
+pub(crate) fn cull_offscreen_content(
+    mut commands: Commands,
+    query: Query<(&Node, &GlobalTransform, &Children, &CullOffscreen)>,
+    query_content: Query<&Children, With<ScrollContent>>,
+    mut query_items: Query<
+        (&Node, &GlobalTransform, &mut Visibility, Option<&Culled>),
+        Without<ScrollArea>,
+    >,
+) {
+    for (node, gt, children, cull) in query.iter() {
+        let area = node.logical_rect(gt);
+        if area.is_empty() {
+            // Not laid out yet.
+            continue;
+        }
+        let bounds = Rect::from_corners(area.min - cull.margin, area.max + cull.margin);
+        for content in children.iter().filter_map(|c| query_content.get(*c).ok()) {
+            for item in content.iter() {
+                let Ok((item_node, item_gt, mut visibility, culled)) = query_items.get_mut(*item)
+                else {
+                    continue;
+                };
+                let offscreen = item_node.logical_rect(item_gt).intersect(bounds).is_empty();
+                let item = *item;
+                match culled {
+                    None if offscreen => {
+                        commands.entity(item).insert(Culled(*visibility));
+                        *visibility = Visibility::Hidden;
+                        commands.add(move |world: &mut World| world.pause_reactions(item));
+                    }
+                    Some(culled) if !offscreen => {
+                        *visibility = culled.0;
+                        commands.entity(item).remove::<Culled>();
+                        commands.add(move |world: &mut World| world.resume_reactions(item));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
 pub(crate) fn handle_scroll_events(
     mut scroll_evr: EventReader<MouseWheel>,
     mut writer: EventWriter<ScrollWheel>,
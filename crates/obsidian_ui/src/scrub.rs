@@ -0,0 +1,247 @@
+use bevy::prelude::*;
+use bevy_mod_picking::{events::PointerCancel, prelude::*};
+use bevy_reactor::*;
+
+use crate::drag::{is_precision_drag, DragConstraints};
+
+#[derive(Clone, Copy, Default, PartialEq)]
+struct ScrubState {
+    active: bool,
+    origin: f32,
+    /// Horizontal distance the locked pointer has moved since the scrub began.
+    distance: f32,
+}
+
+/// Opt-in behavior which lets the user change a numeric value by dragging horizontally
+/// anywhere on an element, typically the label of a numeric row. While scrubbing, the pointer
+/// is locked (see [`PointerLock`]): the cursor is hidden and held in place, so that the drag
+/// is not limited by the width of the screen. Holding Shift scrubs in precision mode.
+///
+/// The scrub ends when the mouse button is released, when the pointer is cancelled, or when
+/// the pointer lock is lost, for example because the window lost focus.
+///
+/// ```ignore
+/// let label_id = cx.create_entity();
+/// Element::<NodeBundle>::for_entity(label_id)
+///     .insert(ScrubValue::new(value, on_change).with_speed(0.1).handlers(cx, label_id))
+/// ```
+#[derive(Clone, Copy)]
+pub struct ScrubValue {
+    value: Signal<f32>,
+    on_change: Callback<f32>,
+    speed: f32,
+    constraints: DragConstraints,
+}
+
+impl ScrubValue {
+    /// Create a scrubbing behavior for `value`. The callback is called with the new value as
+    /// the element is dragged.
+    pub fn new(value: impl IntoSignal<f32>, on_change: Callback<f32>) -> Self {
+        Self {
+            value: value.into_signal(),
+            on_change,
+            speed: 1.,
+            constraints: DragConstraints::default(),
+        }
+    }
+
+    /// Set the change in value per logical pixel dragged.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Set the constraints applied to the scrubbed value. Only the x components are used.
+    pub fn with_constraints(mut self, constraints: DragConstraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Create the pointer event handlers which implement scrubbing, to be inserted on the
+    /// element with the given id.
+    pub fn handlers(self, cx: &mut Cx, target: Entity) -> impl Bundle {
+        let scrub = self.create(cx, target);
+        (
+            On::<Pointer<DragStart>>::run(move |world: &mut World| {
+                let mut event = world
+                    .get_resource_mut::<ListenerInput<Pointer<DragStart>>>()
+                    .unwrap();
+                if event.button != PointerButton::Primary {
+                    return;
+                }
+                event.stop_propagation();
+                scrub.begin(world);
+            }),
+            On::<CapturedPointerUp>::run(move |world: &mut World| {
+                scrub.end(world);
+            }),
+            On::<Pointer<PointerCancel>>::run(move |world: &mut World| {
+                scrub.end(world);
+            }),
+        )
+    }
+
+    /// Create the state of a scrub of `target`, and the effect which applies the motion of the
+    /// locked pointer to the value.
+    fn create(self, cx: &mut Cx, target: Entity) -> Scrub {
+        let scrub = Scrub {
+            props: self,
+            target,
+            state: cx.create_mutable(ScrubState::default()),
+        };
+        cx.create_effect(move |cx| {
+            let delta = cx.use_pointer_delta();
+            let owner = cx.use_pointer_lock_owner();
+            let world = cx.world_mut();
+            let st = scrub.state.get(world);
+            if !st.active {
+                return;
+            }
+            if owner != Some(target) {
+                // The lock was released from outside, so the scrub can't continue.
+                scrub.end(world);
+                return;
+            }
+            if delta.x == 0. {
+                return;
+            }
+            let distance = st.distance + delta.x;
+            scrub.state.set(world, ScrubState { distance, ..st });
+            let new_value = self.constraints.apply_1d(
+                st.origin,
+                distance * self.speed,
+                is_precision_drag(world),
+            );
+            world.run_callback(self.on_change, new_value);
+        });
+        scrub
+    }
+}
+
+/// A scrub in progress, or ready to begin, on a particular element.
+#[derive(Clone, Copy)]
+struct Scrub {
+    props: ScrubValue,
+    target: Entity,
+    state: Mutable<ScrubState>,
+}
+
+impl Scrub {
+    fn begin(&self, world: &mut World) {
+        self.state.set(
+            world,
+            ScrubState {
+                active: true,
+                origin: self.props.value.get(world),
+                distance: 0.,
+            },
+        );
+        // The capture delivers the button release to the target, wherever the hidden cursor
+        // happens to be.
+        world.resource_mut::<PointerCapture>().capture(self.target);
+        world.resource_mut::<PointerLock>().lock(self.target);
+    }
+
+    fn end(&self, world: &mut World) {
+        let mut capture = world.resource_mut::<PointerCapture>();
+        if capture.target() == Some(self.target) {
+            capture.release();
+        }
+        let mut lock = world.resource_mut::<PointerLock>();
+        if lock.owner() == Some(self.target) {
+            lock.release();
+        }
+        self.state.set(world, ScrubState::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        input::mouse::MouseMotion,
+        window::{PrimaryWindow, WindowFocused},
+    };
+    use bevy_reactor::testing::TestHarness;
+
+    use super::*;
+
+    fn setup(harness: &mut TestHarness) -> (Scrub, Mutable<f32>, Entity) {
+        let world = harness.world_mut();
+        world.init_resource::<PointerCapture>();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), world, &mut scope);
+        let value = cx.create_mutable(1.);
+        let on_change = cx.create_callback(move |cx: &mut Cx<f32>| {
+            let new_value = cx.props;
+            value.set(cx, new_value);
+        });
+        let scrub = ScrubValue::new(value, on_change)
+            .with_speed(0.5)
+            .create(&mut cx, target);
+        (scrub, value, window)
+    }
+
+    fn move_pointer(harness: &mut TestHarness, dx: f32) {
+        harness.world_mut().send_event(MouseMotion {
+            delta: Vec2::new(dx, 0.),
+        });
+        harness.update();
+        // The new value is committed in the following frame.
+        harness.update();
+    }
+
+    #[test]
+    fn test_scrub() {
+        let mut harness = TestHarness::new();
+        let (scrub, value, _) = setup(&mut harness);
+        scrub.begin(harness.world_mut());
+        harness.update();
+        assert_eq!(
+            harness.world().resource::<PointerLock>().owner(),
+            Some(scrub.target)
+        );
+
+        move_pointer(&mut harness, 4.);
+        assert_eq!(value.get(harness.world()), 3.);
+
+        // The same motion again continues the scrub, rather than being ignored.
+        move_pointer(&mut harness, 4.);
+        assert_eq!(value.get(harness.world()), 5.);
+
+        scrub.end(harness.world_mut());
+        harness.update();
+        assert!(!harness.world().resource::<PointerLock>().is_locked());
+        assert_eq!(harness.world().resource::<PointerCapture>().target(), None);
+
+        // Motion after the scrub has ended is ignored.
+        move_pointer(&mut harness, 4.);
+        assert_eq!(value.get(harness.world()), 5.);
+    }
+
+    #[test]
+    fn test_scrub_blur() {
+        let mut harness = TestHarness::new();
+        let (scrub, value, window) = setup(&mut harness);
+        scrub.begin(harness.world_mut());
+        harness.update();
+        assert_eq!(
+            harness.world().resource::<PointerCapture>().target(),
+            Some(scrub.target)
+        );
+
+        // Losing focus releases the lock, which ends the scrub and releases the capture.
+        harness.world_mut().send_event(WindowFocused {
+            window,
+            focused: false,
+        });
+        harness.update_frames(2);
+        assert!(!harness.world().resource::<PointerLock>().is_locked());
+        assert_eq!(harness.world().resource::<PointerCapture>().target(), None);
+        assert!(!scrub.state.get(harness.world()).active);
+
+        move_pointer(&mut harness, 4.);
+        assert_eq!(value.get(harness.world()), 1.);
+    }
+}
@@ -0,0 +1,514 @@
+use std::{collections::VecDeque, time::Duration};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        mouse::MouseButtonInput,
+        ButtonState,
+    },
+    prelude::*,
+    ui,
+    window::{PrimaryWindow, ReceivedCharacter},
+};
+use bevy_reactor::testing::TestHarness;
+
+/// A single step in a [`SimulatedInput`] script.
+#[derive(Debug, Clone)]
+pub enum SimulatedStep {
+    /// Move the cursor to a position, in logical pixels, over the given duration.
+    MoveTo(Vec2, Duration),
+
+    /// Move the cursor to the center of a UI node over the given duration.
+    MoveToEntity(Entity, Duration),
+
+    /// Press and release a mouse button at the current cursor position.
+    Click(MouseButton),
+
+    /// Press and release a key, given by its physical key code and the logical key it
+    /// produces.
+    Key(KeyCode, Key),
+
+    /// Type a string, one character at a time, with the given interval between characters.
+    Type(String, Duration),
+
+    /// Do nothing for the given duration.
+    Wait(Duration),
+}
+
+/// Resource which plays back a script of simulated input events: moving the cursor,
+/// clicking and typing. This runs inside a real windowed app, and is intended for demo
+/// recordings and interactive tutorials. The simulated events are sent to the primary
+/// window, and are handled just like real input. The same scripts can drive controls in
+/// headless tests, see [`PlaySimulatedInput`].
+///
+/// Steps are queued using the builder methods, and are played back in order:
+///
+/// ```ignore
+/// fn start_demo(mut input: ResMut<SimulatedInput>) {
+///     input
+///         .move_to_entity(button, Duration::from_millis(500))
+///         .click()
+///         .type_text("Hello", Duration::from_millis(100));
+/// }
+/// ```
+#[derive(Resource, Default)]
+pub struct SimulatedInput {
+    steps: VecDeque<SimulatedStep>,
+    /// Time spent in the current step.
+    elapsed: Duration,
+    /// Cursor position at the start of the current step.
+    start: Option<Vec2>,
+    /// Current position of the simulated cursor.
+    cursor: Vec2,
+    /// Number of characters typed in the current step.
+    typed: usize,
+}
+
+impl SimulatedInput {
+    /// Queue a step.
+    pub fn push(&mut self, step: SimulatedStep) -> &mut Self {
+        self.steps.push_back(step);
+        self
+    }
+
+    /// Queue moving the cursor to a position in logical pixels.
+    pub fn move_to(&mut self, position: Vec2, duration: Duration) -> &mut Self {
+        self.push(SimulatedStep::MoveTo(position, duration))
+    }
+
+    /// Queue moving the cursor to the center of a UI node.
+    pub fn move_to_entity(&mut self, target: Entity, duration: Duration) -> &mut Self {
+        self.push(SimulatedStep::MoveToEntity(target, duration))
+    }
+
+    /// Queue a click of the left mouse button.
+    pub fn click(&mut self) -> &mut Self {
+        self.push(SimulatedStep::Click(MouseButton::Left))
+    }
+
+    /// Queue a key press. The logical key is the one the key produces on a US keyboard
+    /// layout, without modifiers. Panics if the key has no such logical key, such as a media
+    /// key; use [`press_key_as`](Self::press_key_as) for those.
+    pub fn press_key(&mut self, key_code: KeyCode) -> &mut Self {
+        let logical_key = logical_key(key_code)
+            .unwrap_or_else(|| panic!("No logical key for {:?}, use press_key_as", key_code));
+        self.press_key_as(key_code, logical_key)
+    }
+
+    /// Queue a key press which produces the given logical key.
+    pub fn press_key_as(&mut self, key_code: KeyCode, logical_key: Key) -> &mut Self {
+        self.push(SimulatedStep::Key(key_code, logical_key))
+    }
+
+    /// Queue typing a string, with the given interval between characters.
+    pub fn type_text(&mut self, text: &str, interval: Duration) -> &mut Self {
+        self.push(SimulatedStep::Type(text.to_string(), interval))
+    }
+
+    /// Queue a pause.
+    pub fn wait(&mut self, duration: Duration) -> &mut Self {
+        self.push(SimulatedStep::Wait(duration))
+    }
+
+    /// Returns true if all queued steps have been played.
+    pub fn is_finished(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The current position of the simulated cursor, in logical pixels.
+    pub fn cursor(&self) -> Vec2 {
+        self.cursor
+    }
+
+    fn next_step(&mut self) {
+        self.steps.pop_front();
+        self.elapsed = Duration::ZERO;
+        self.start = None;
+        self.typed = 0;
+    }
+
+    /// Advance the cursor towards `target`, returning the event for its movement. Moves on
+    /// to the next step when the cursor arrives.
+    fn move_cursor(&mut self, window: Entity, target: Vec2, duration: Duration) -> CursorMoved {
+        let start = *self.start.get_or_insert(self.cursor);
+        let t = if duration.is_zero() {
+            1.
+        } else {
+            (self.elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.)
+        };
+        let position = start.lerp(target, t);
+        let delta = position - self.cursor;
+        self.cursor = position;
+        if t >= 1. {
+            self.next_step();
+        }
+        CursorMoved {
+            window,
+            position,
+            delta: Some(delta),
+        }
+    }
+}
+
+/// The logical key which a key produces on a US keyboard layout, without modifiers.
+fn logical_key(key_code: KeyCode) -> Option<Key> {
+    let character = match key_code {
+        KeyCode::KeyA => "a",
+        KeyCode::KeyB => "b",
+        KeyCode::KeyC => "c",
+        KeyCode::KeyD => "d",
+        KeyCode::KeyE => "e",
+        KeyCode::KeyF => "f",
+        KeyCode::KeyG => "g",
+        KeyCode::KeyH => "h",
+        KeyCode::KeyI => "i",
+        KeyCode::KeyJ => "j",
+        KeyCode::KeyK => "k",
+        KeyCode::KeyL => "l",
+        KeyCode::KeyM => "m",
+        KeyCode::KeyN => "n",
+        KeyCode::KeyO => "o",
+        KeyCode::KeyP => "p",
+        KeyCode::KeyQ => "q",
+        KeyCode::KeyR => "r",
+        KeyCode::KeyS => "s",
+        KeyCode::KeyT => "t",
+        KeyCode::KeyU => "u",
+        KeyCode::KeyV => "v",
+        KeyCode::KeyW => "w",
+        KeyCode::KeyX => "x",
+        KeyCode::KeyY => "y",
+        KeyCode::KeyZ => "z",
+        KeyCode::Digit0 | KeyCode::Numpad0 => "0",
+        KeyCode::Digit1 | KeyCode::Numpad1 => "1",
+        KeyCode::Digit2 | KeyCode::Numpad2 => "2",
+        KeyCode::Digit3 | KeyCode::Numpad3 => "3",
+        KeyCode::Digit4 | KeyCode::Numpad4 => "4",
+        KeyCode::Digit5 | KeyCode::Numpad5 => "5",
+        KeyCode::Digit6 | KeyCode::Numpad6 => "6",
+        KeyCode::Digit7 | KeyCode::Numpad7 => "7",
+        KeyCode::Digit8 | KeyCode::Numpad8 => "8",
+        KeyCode::Digit9 | KeyCode::Numpad9 => "9",
+        KeyCode::Backquote => "`",
+        KeyCode::Backslash => "\\",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Comma => ",",
+        KeyCode::Equal => "=",
+        KeyCode::Minus | KeyCode::NumpadSubtract => "-",
+        KeyCode::Period | KeyCode::NumpadDecimal => ".",
+        KeyCode::Quote => "'",
+        KeyCode::Semicolon => ";",
+        KeyCode::Slash | KeyCode::NumpadDivide => "/",
+        KeyCode::NumpadAdd => "+",
+        KeyCode::NumpadMultiply => "*",
+        _ => {
+            return Some(match key_code {
+                KeyCode::Enter | KeyCode::NumpadEnter => Key::Enter,
+                KeyCode::Tab => Key::Tab,
+                KeyCode::Space => Key::Space,
+                KeyCode::Backspace => Key::Backspace,
+                KeyCode::Delete => Key::Delete,
+                KeyCode::Insert => Key::Insert,
+                KeyCode::Escape => Key::Escape,
+                KeyCode::Home => Key::Home,
+                KeyCode::End => Key::End,
+                KeyCode::PageUp => Key::PageUp,
+                KeyCode::PageDown => Key::PageDown,
+                KeyCode::ArrowUp => Key::ArrowUp,
+                KeyCode::ArrowDown => Key::ArrowDown,
+                KeyCode::ArrowLeft => Key::ArrowLeft,
+                KeyCode::ArrowRight => Key::ArrowRight,
+                KeyCode::ShiftLeft | KeyCode::ShiftRight => Key::Shift,
+                KeyCode::ControlLeft | KeyCode::ControlRight => Key::Control,
+                KeyCode::AltLeft | KeyCode::AltRight => Key::Alt,
+                KeyCode::SuperLeft | KeyCode::SuperRight => Key::Super,
+                KeyCode::CapsLock => Key::CapsLock,
+                KeyCode::ContextMenu => Key::ContextMenu,
+                KeyCode::F1 => Key::F1,
+                KeyCode::F2 => Key::F2,
+                KeyCode::F3 => Key::F3,
+                KeyCode::F4 => Key::F4,
+                KeyCode::F5 => Key::F5,
+                KeyCode::F6 => Key::F6,
+                KeyCode::F7 => Key::F7,
+                KeyCode::F8 => Key::F8,
+                KeyCode::F9 => Key::F9,
+                KeyCode::F10 => Key::F10,
+                KeyCode::F11 => Key::F11,
+                KeyCode::F12 => Key::F12,
+                _ => return None,
+            });
+        }
+    };
+    Some(Key::Character(character.into()))
+}
+
+/// Marker for a UI node which is moved to follow the simulated cursor, so that the cursor
+/// is visible in recordings.
+#[derive(Component, Default)]
+pub struct SimulatedCursor;
+
+#[allow(clippy::too_many_arguments)]
+fn play_simulated_input(
+    mut input: ResMut<SimulatedInput>,
+    time: Res<Time>,
+    window: Query<Entity, With<PrimaryWindow>>,
+    nodes: Query<(&Node, &GlobalTransform)>,
+    mut cursor_moved: EventWriter<CursorMoved>,
+    mut mouse_button: EventWriter<MouseButtonInput>,
+    mut keyboard: EventWriter<KeyboardInput>,
+    mut characters: EventWriter<ReceivedCharacter>,
+) {
+    // Headless apps, such as tests, have no window, but the events are still handled by
+    // systems which don't look at the window.
+    let window = window.get_single().unwrap_or(Entity::PLACEHOLDER);
+    let Some(step) = input.steps.front().cloned() else {
+        return;
+    };
+    input.elapsed += time.delta();
+    match step {
+        SimulatedStep::MoveTo(target, duration) => {
+            cursor_moved.send(input.move_cursor(window, target, duration));
+        }
+        SimulatedStep::MoveToEntity(entity, duration) => match nodes.get(entity) {
+            Ok((_, transform)) => {
+                let target = transform.translation().truncate();
+                cursor_moved.send(input.move_cursor(window, target, duration));
+            }
+            // The target has gone away, skip the step.
+            Err(_) => input.next_step(),
+        },
+        SimulatedStep::Click(button) => {
+            for state in [ButtonState::Pressed, ButtonState::Released] {
+                mouse_button.send(MouseButtonInput {
+                    button,
+                    state,
+                    window,
+                });
+            }
+            input.next_step();
+        }
+        SimulatedStep::Key(key_code, logical_key) => {
+            for state in [ButtonState::Pressed, ButtonState::Released] {
+                keyboard.send(KeyboardInput {
+                    key_code,
+                    logical_key: logical_key.clone(),
+                    state,
+                    window,
+                });
+            }
+            input.next_step();
+        }
+        SimulatedStep::Type(text, interval) => {
+            let due = if interval.is_zero() {
+                usize::MAX
+            } else {
+                (input.elapsed.as_secs_f64() / interval.as_secs_f64()) as usize + 1
+            };
+            let typed = input.typed;
+            for ch in text.chars().skip(typed).take(due.saturating_sub(typed)) {
+                characters.send(ReceivedCharacter {
+                    window,
+                    char: ch.to_string().into(),
+                });
+                input.typed += 1;
+            }
+            if input.typed >= text.chars().count() {
+                input.next_step();
+            }
+        }
+        SimulatedStep::Wait(duration) => {
+            if input.elapsed >= duration {
+                input.next_step();
+            }
+        }
+    }
+}
+
+fn update_simulated_cursor(
+    input: Res<SimulatedInput>,
+    mut cursors: Query<&mut Style, With<SimulatedCursor>>,
+) {
+    for mut style in cursors.iter_mut() {
+        style.left = ui::Val::Px(input.cursor.x);
+        style.top = ui::Val::Px(input.cursor.y);
+    }
+}
+
+/// Plugin which enables playback of [`SimulatedInput`] scripts. This is not included in the
+/// `ObsidianUiPlugin`, since it is only needed for demos.
+pub struct SimulatedInputPlugin;
+
+impl Plugin for SimulatedInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SimulatedInput>().add_systems(
+            PreUpdate,
+            (play_simulated_input, update_simulated_cursor)
+                .chain()
+                .before(bevy::input::InputSystem),
+        );
+    }
+}
+
+/// Method for playing [`SimulatedInput`] scripts in a [`TestHarness`], so that tests can
+/// drive controls the same way as demo scripts.
+pub trait PlaySimulatedInput {
+    /// Queue the steps added by `script`, and run frames until they have all been played,
+    /// plus one more frame so that the last events are handled. Panics if the steps are not
+    /// finished within `max_frames`.
+    fn play_input(&mut self, max_frames: usize, script: impl FnOnce(&mut SimulatedInput));
+}
+
+impl PlaySimulatedInput for TestHarness {
+    fn play_input(&mut self, max_frames: usize, script: impl FnOnce(&mut SimulatedInput)) {
+        if !self.world().contains_resource::<SimulatedInput>() {
+            self.app.add_plugins(SimulatedInputPlugin);
+        }
+        script(&mut self.world_mut().resource_mut::<SimulatedInput>());
+        for _ in 0..max_frames {
+            self.update();
+            if self.world().resource::<SimulatedInput>().is_finished() {
+                self.update();
+                return;
+            }
+        }
+        panic!("Simulated input not finished after {} frames", max_frames);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Events received by the app, in order.
+    #[derive(Resource, Default)]
+    struct Received {
+        cursor: Vec<Vec2>,
+        keys: Vec<(KeyCode, Key, ButtonState)>,
+        text: String,
+    }
+
+    fn record_input(
+        mut cursor_moved: EventReader<CursorMoved>,
+        mut keyboard: EventReader<KeyboardInput>,
+        mut characters: EventReader<ReceivedCharacter>,
+        mut received: ResMut<Received>,
+    ) {
+        for event in cursor_moved.read() {
+            received.cursor.push(event.position);
+        }
+        for event in keyboard.read() {
+            received
+                .keys
+                .push((event.key_code, event.logical_key.clone(), event.state));
+        }
+        for event in characters.read() {
+            received.text.push_str(&event.char);
+        }
+    }
+
+    fn harness() -> TestHarness {
+        let mut harness = TestHarness::new();
+        harness
+            .app
+            .init_resource::<Received>()
+            .add_systems(Update, record_input);
+        harness
+    }
+
+    #[test]
+    fn test_logical_key() {
+        assert_eq!(logical_key(KeyCode::KeyQ), Some(Key::Character("q".into())));
+        assert_eq!(
+            logical_key(KeyCode::Numpad7),
+            Some(Key::Character("7".into()))
+        );
+        assert_eq!(
+            logical_key(KeyCode::Backslash),
+            Some(Key::Character("\\".into()))
+        );
+        assert_eq!(logical_key(KeyCode::NumpadEnter), Some(Key::Enter));
+        assert_eq!(logical_key(KeyCode::MediaPlayPause), None);
+    }
+
+    #[test]
+    fn test_move_to() {
+        let mut harness = harness();
+        // Four frames at the harness's fixed frame time.
+        let duration = bevy_reactor::testing::TEST_FRAME_TIME * 4;
+        harness.play_input(10, |input| {
+            input.move_to(Vec2::new(80., 40.), duration);
+        });
+        let received = &harness.world().resource::<Received>().cursor;
+        assert_eq!(received.last(), Some(&Vec2::new(80., 40.)));
+        assert!(received.windows(2).all(|pair| pair[0].x < pair[1].x));
+        assert_eq!(
+            harness.world().resource::<SimulatedInput>().cursor(),
+            Vec2::new(80., 40.)
+        );
+    }
+
+    #[test]
+    fn test_move_to_missing_entity() {
+        let mut harness = harness();
+        let entity = harness.world_mut().spawn_empty().id();
+        harness.play_input(10, |input| {
+            input
+                .move_to_entity(entity, Duration::from_secs(1))
+                .move_to(Vec2::ONE, Duration::ZERO);
+        });
+        // The move to the entity, which is not a UI node, is skipped.
+        assert_eq!(
+            harness.world().resource::<Received>().cursor,
+            vec![Vec2::ONE]
+        );
+    }
+
+    #[test]
+    fn test_keys_and_text() {
+        let mut harness = harness();
+        harness.play_input(10, |input| {
+            input
+                .press_key(KeyCode::Enter)
+                .press_key_as(KeyCode::KeyQ, Key::Character("a".into()))
+                .type_text("héllo", Duration::ZERO);
+        });
+        let received = harness.world().resource::<Received>();
+        assert_eq!(
+            received.keys,
+            vec![
+                (KeyCode::Enter, Key::Enter, ButtonState::Pressed),
+                (KeyCode::Enter, Key::Enter, ButtonState::Released),
+                (
+                    KeyCode::KeyQ,
+                    Key::Character("a".into()),
+                    ButtonState::Pressed
+                ),
+                (
+                    KeyCode::KeyQ,
+                    Key::Character("a".into()),
+                    ButtonState::Released
+                ),
+            ]
+        );
+        assert_eq!(received.text, "héllo");
+    }
+
+    #[test]
+    fn test_type_text_interval() {
+        let mut harness = harness();
+        let interval = bevy_reactor::testing::TEST_FRAME_TIME * 2;
+        harness.app.add_plugins(SimulatedInputPlugin);
+        harness
+            .world_mut()
+            .resource_mut::<SimulatedInput>()
+            .type_text("abc", interval);
+        harness.update_frames(2);
+        // Characters are typed one per interval, not all at once.
+        let typed = harness.world().resource::<Received>().text.len();
+        assert!(typed < 3, "typed {} characters", typed);
+        harness.update_frames(8);
+        assert_eq!(harness.world().resource::<Received>().text, "abc");
+    }
+}
@@ -1,5 +1,7 @@
+use bevy::reflect::Reflect;
+
 /// Standard sizes for buttons and other widgets that have size variants.
-#[derive(Clone, Copy, PartialEq, Debug, Default)]
+#[derive(Clone, Copy, PartialEq, Debug, Default, Reflect)]
 #[allow(missing_docs)]
 pub enum Size {
     Xl,
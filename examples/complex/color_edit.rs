@@ -139,8 +139,6 @@ fn rgb_sliders(cx: &mut Cx) -> impl View {
                         Srgba::new(1.0, rgb.green, rgb.blue, 1.0),
                     ])
                 }),
-                min: Signal::Constant(0.),
-                max: Signal::Constant(255.),
                 value: cx.create_derived(|cx| cx.use_resource::<ColorEditState>().rgb.red * 255.0),
                 style: StyleHandle::new(style_slider),
                 precision: 1,
@@ -148,7 +146,8 @@ fn rgb_sliders(cx: &mut Cx) -> impl View {
                     cx.world_mut().resource_mut::<ColorEditState>().rgb.red = cx.props / 255.0;
                 })),
                 ..default()
-            },
+            }
+            .with_range(0., 255.),
             text_computed(|cx| {
                 format!("{:.0}", cx.use_resource::<ColorEditState>().rgb.red * 255.0)
             }),
@@ -160,8 +159,6 @@ fn rgb_sliders(cx: &mut Cx) -> impl View {
                         Srgba::new(rgb.red, 1.0, rgb.blue, 1.0),
                     ])
                 }),
-                min: Signal::Constant(0.),
-                max: Signal::Constant(255.),
                 value: cx
                     .create_derived(|cx| cx.use_resource::<ColorEditState>().rgb.green * 255.0),
                 style: StyleHandle::new(style_slider),
@@ -170,7 +167,8 @@ fn rgb_sliders(cx: &mut Cx) -> impl View {
                     cx.world_mut().resource_mut::<ColorEditState>().rgb.green = cx.props / 255.0;
                 })),
                 ..default()
-            },
+            }
+            .with_range(0., 255.),
             text_computed(|cx| {
                 format!(
                     "{:.0}",
@@ -185,8 +183,6 @@ fn rgb_sliders(cx: &mut Cx) -> impl View {
                         Srgba::new(rgb.red, rgb.green, 1.0, 1.0),
                     ])
                 }),
-                min: Signal::Constant(0.),
-                max: Signal::Constant(255.),
                 value: cx.create_derived(|cx| cx.use_resource::<ColorEditState>().rgb.blue * 255.0),
                 style: StyleHandle::new(style_slider),
                 precision: 1,
@@ -194,7 +190,8 @@ fn rgb_sliders(cx: &mut Cx) -> impl View {
                     cx.world_mut().resource_mut::<ColorEditState>().rgb.blue = cx.props / 255.0;
                 })),
                 ..default()
-            },
+            }
+            .with_range(0., 255.),
             text_computed(|cx| {
                 format!(
                     "{:.0}",
@@ -219,8 +216,6 @@ fn hsl_sliders(cx: &mut Cx) -> impl View {
                     Srgba::from(Hsla::new(300.0, 1.0, 0.5, 1.0)),
                     Srgba::from(Hsla::new(360.0, 1.0, 0.5, 1.0)),
                 ])),
-                min: Signal::Constant(0.),
-                max: Signal::Constant(360.),
                 value: cx.create_derived(|cx| cx.use_resource::<ColorEditState>().hsl.hue * 360.0),
                 style: StyleHandle::new(style_slider),
                 precision: 1,
@@ -228,7 +223,8 @@ fn hsl_sliders(cx: &mut Cx) -> impl View {
                     cx.world_mut().resource_mut::<ColorEditState>().hsl.hue = cx.props / 360.0;
                 })),
                 ..default()
-            },
+            }
+            .with_range(0., 360.),
             text_computed(|cx| {
                 format!("{:.0}", cx.use_resource::<ColorEditState>().hsl.hue * 360.0)
             }),
@@ -241,8 +237,6 @@ fn hsl_sliders(cx: &mut Cx) -> impl View {
                         Srgba::from(Hsla::new(hsl.hue, 1.0, hsl.lightness, 1.0)),
                     ])
                 }),
-                min: Signal::Constant(0.),
-                max: Signal::Constant(100.),
                 value: cx.create_derived(|cx| {
                     cx.use_resource::<ColorEditState>().hsl.saturation * 100.0
                 }),
@@ -255,7 +249,8 @@ fn hsl_sliders(cx: &mut Cx) -> impl View {
                         .saturation = cx.props / 100.0;
                 })),
                 ..default()
-            },
+            }
+            .with_range(0., 100.),
             text_computed(|cx| {
                 format!(
                     "{:.0}",
@@ -271,8 +266,6 @@ fn hsl_sliders(cx: &mut Cx) -> impl View {
                         Srgba::from(Hsla::new(hsl.hue, hsl.saturation, 1.0, 1.0)),
                     ])
                 }),
-                min: Signal::Constant(0.),
-                max: Signal::Constant(100.),
                 value: cx
                     .create_derived(|cx| cx.use_resource::<ColorEditState>().hsl.lightness * 100.0),
                 style: StyleHandle::new(style_slider),
@@ -284,7 +277,8 @@ fn hsl_sliders(cx: &mut Cx) -> impl View {
                         .lightness = cx.props / 100.0;
                 })),
                 ..default()
-            },
+            }
+            .with_range(0., 100.),
             text_computed(|cx| {
                 format!(
                     "{:.0}",
@@ -309,8 +303,6 @@ fn alpha_slider(cx: &mut Cx) -> impl View {
                     Srgba::new(rgb.red, rgb.green, rgb.blue, 1.0),
                 ])
             }),
-            min: Signal::Constant(0.),
-            max: Signal::Constant(255.),
             value: cx.create_derived(|cx| cx.use_resource::<ColorEditState>().rgb.alpha * 255.0),
             style: StyleHandle::new(style_slider),
             precision: 1,
@@ -318,7 +310,8 @@ fn alpha_slider(cx: &mut Cx) -> impl View {
                 cx.world_mut().resource_mut::<ColorEditState>().rgb.alpha = cx.props / 255.0;
             })),
             ..default()
-        },
+        }
+        .with_range(0., 255.),
         text_computed(|cx| {
             format!(
                 "{:.0}",
@@ -16,8 +16,9 @@ use color_edit::{color_edit, ColorEditState, ColorMode};
 use obsidian_ui::{
     colors,
     controls::{
-        Button, ButtonVariant, Checkbox, Dialog, DialogFooter, DialogHeader, ScrollView,
-        ScrollViewProps, Slider, Splitter, SplitterDirection, Swatch, TextInput, TextInputProps,
+        Button, ButtonVariant, Checkbox, Dialog, DialogFooter, DialogHeader, NumericRow,
+        ScrollView, ScrollViewProps, Slider, SpinBox, Splitter, SplitterDirection, Swatch,
+        TextInput, TextInputProps,
     },
     focus::TabGroup,
     size::Size,
@@ -28,7 +29,7 @@ use transform_overlay::TransformOverlay;
 use std::f32::consts::PI;
 
 use bevy::{
-    asset::io::{file::FileAssetReader, AssetSource},
+    asset::io::{AssetReader, AssetSource},
     prelude::*,
     render::{
         render_asset::RenderAssetUsages,
@@ -127,12 +128,21 @@ pub struct PanelWidth(f32);
 #[derive(Resource, Default)]
 pub struct SelectedShape(Option<Entity>);
 
+/// Reads the Obsidian UI assets from the filesystem, or over HTTP when running in a browser.
+fn obsidian_ui_asset_reader(path: &str) -> Box<dyn AssetReader> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let reader = bevy::asset::io::file::FileAssetReader::new(path);
+    #[cfg(target_arch = "wasm32")]
+    let reader = bevy::asset::io::wasm::HttpWasmAssetReader::new(path);
+    Box::new(reader)
+}
+
 fn main() {
     App::new()
         .register_asset_source(
             "obsidian_ui",
             AssetSource::build()
-                .with_reader(|| Box::new(FileAssetReader::new("crates/obsidian_ui/assets"))),
+                .with_reader(|| obsidian_ui_asset_reader("crates/obsidian_ui/assets")),
         )
         .init_resource::<SelectedShape>()
         .insert_resource(PanelWidth(200.))
@@ -244,13 +254,13 @@ fn ui_main(cx: &mut Cx<Entity>) -> impl View {
                             },
                             Button {
                                 children: "Close".into(),
-                                variant: Signal::Constant(ButtonVariant::Primary),
                                 autofocus: true,
                                 on_click: Some(cx.create_callback(move |cx| {
                                     checked_1.set(cx, false);
                                 })),
                                 ..default()
-                            },
+                            }
+                            .with_variant(ButtonVariant::Primary),
                         )
                             .fragment(),
                     },
@@ -311,8 +321,8 @@ fn ui_main(cx: &mut Cx<Entity>) -> impl View {
                         .with_styles(style_color_edit)
                         .with_children((
                             Slider {
-                                min: Signal::Constant(0.),
-                                max: Signal::Constant(255.),
+                                min: 0.0.into(),
+                                max: 255.0.into(),
                                 value: red.signal(),
                                 style: StyleHandle::new(style_slider),
                                 precision: 1,
@@ -321,6 +331,20 @@ fn ui_main(cx: &mut Cx<Entity>) -> impl View {
                                 })),
                                 ..default()
                             },
+                            NumericRow {
+                                label: "Red".into(),
+                                spin_box: SpinBox {
+                                    min: 0.0.into(),
+                                    max: 255.0.into(),
+                                    value: red.signal(),
+                                    precision: 1,
+                                    scrub_speed: 0.5,
+                                    on_change: Some(cx.create_callback(move |cx| {
+                                        red.set(cx, cx.props);
+                                    })),
+                                    ..default()
+                                },
+                            },
                             Swatch {
                                 color: rgb_color,
                                 size: Size::Md,
@@ -0,0 +1,398 @@
+//! Storybook-style preview of Obsidian UI controls. The sidebar lists the registered controls;
+//! the selected control is rendered in isolation, with a panel of knobs for adjusting its
+//! props. To add a control, append a [`Story`] to [`STORIES`].
+//!
+//! The knob panel is generated from the control's props struct by reflection. `bool` fields
+//! are shown as checkboxes, `f32` and `i32` fields as sliders, and unit enum fields as buttons
+//! which cycle through the variants; signal fields holding a `bool` or `f32` are fed by their
+//! knob, while the control is rebuilt when the knob of a plain field changes. Child views,
+//! styles and callbacks are skipped by the props' reflection, and any other fields are listed
+//! as read-only.
+
+use std::sync::Arc;
+
+use bevy::{
+    asset::io::{file::FileAssetReader, AssetSource},
+    prelude::*,
+    reflect::{
+        DynamicEnum, DynamicTypePath, DynamicVariant, Enum, ReflectRef, Struct, TypeInfo,
+        VariantInfo,
+    },
+    ui,
+};
+use bevy_mod_picking::DefaultPickingPlugins;
+use bevy_reactor::*;
+use obsidian_ui::{
+    colors,
+    controls::{Button, ButtonVariant, Checkbox, Slider},
+    typography, ObsidianUiPlugin,
+};
+
+/// The props of a control which can be previewed.
+trait StoryProps: Struct + ViewFactory + Send + Sync + 'static {}
+
+impl<P: Struct + ViewFactory + Send + Sync + 'static> StoryProps for P {}
+
+/// The range of the sliders for `f32` knobs.
+const F32_RANGE: (f32, f32) = (0., 100.);
+
+/// The range of the sliders for `i32` knobs.
+const I32_RANGE: (f32, f32) = (-1., 10.);
+
+/// A control registered with the preview.
+struct Story {
+    name: &'static str,
+    render: fn() -> ViewHandle,
+}
+
+const STORIES: &[Story] = &[
+    Story {
+        name: "Button",
+        render: || {
+            story(|| Button {
+                children: "Button".into(),
+                ..default()
+            })
+        },
+    },
+    Story {
+        name: "Checkbox",
+        render: || {
+            story(|| Checkbox {
+                label: "Checkbox".into(),
+                ..default()
+            })
+        },
+    },
+    Story {
+        name: "Slider",
+        render: || {
+            story(|| Slider {
+                value: Signal::Constant(50.),
+                max: Signal::Constant(100.),
+                precision: 1,
+                ..default()
+            })
+        },
+    },
+];
+
+fn main() {
+    App::new()
+        .register_asset_source(
+            "obsidian_ui",
+            AssetSource::build()
+                .with_reader(|| Box::new(FileAssetReader::new("crates/obsidian_ui/assets"))),
+        )
+        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPickingPlugins)
+        .add_plugins((ReactorPlugin::default(), ObsidianUiPlugin))
+        .add_systems(Startup, setup_view_root)
+        .add_systems(Update, bevy::window::close_on_esc)
+        .run();
+}
+
+fn setup_view_root(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+    commands.spawn(ViewRoot::new(preview.bind(())));
+}
+
+fn style_main(ss: &mut StyleBuilder) {
+    ss.position(ui::PositionType::Absolute)
+        .left(0)
+        .top(0)
+        .bottom(0)
+        .right(0)
+        .display(ui::Display::Flex)
+        .background_color(colors::U1);
+}
+
+fn style_sidebar(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .background_color(colors::U2)
+        .padding(8)
+        .gap(4)
+        .width(160);
+}
+
+fn style_stage(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_grow(1.)
+        .justify_content(ui::JustifyContent::Center)
+        .align_items(ui::AlignItems::Center);
+}
+
+fn style_read_only(ss: &mut StyleBuilder) {
+    ss.color(colors::U4);
+}
+
+fn style_knobs(ss: &mut StyleBuilder) {
+    ss.display(ui::Display::Flex)
+        .flex_direction(ui::FlexDirection::Column)
+        .background_color(colors::U2)
+        .padding(8)
+        .gap(8)
+        .width(220);
+}
+
+fn preview(cx: &mut Cx) -> impl View {
+    let selected = cx.create_mutable::<usize>(0);
+
+    Element::<NodeBundle>::new()
+        .with_styles((typography::text_default, style_main))
+        .with_children((
+            Element::<NodeBundle>::new()
+                .with_styles(style_sidebar)
+                .with_children(For::index(
+                    |_| 0..STORIES.len(),
+                    move |index, _| story_button.bind((*index, selected)),
+                )),
+            For::each(
+                move |cx| std::iter::once(selected.get(cx)),
+                |index| (STORIES[*index].render)(),
+            ),
+        ))
+}
+
+fn story_button(cx: &mut Cx<(usize, Mutable<usize>)>) -> impl View {
+    let (index, selected) = cx.props;
+    let variant = cx.create_derived(move |cx| {
+        if selected.get(cx) == index {
+            ButtonVariant::Selected
+        } else {
+            ButtonVariant::Default
+        }
+    });
+    ViewFactoryState::new(Button {
+        children: STORIES[index].name.into(),
+        variant,
+        on_click: Some(cx.create_callback(move |cx| selected.set(cx, index))),
+        ..default()
+    })
+}
+
+/// The knob for a field of a control's props.
+#[derive(Clone, Copy)]
+enum Knob {
+    /// A `Signal<bool>` field, which is fed by the knob's mutable.
+    BoolSignal(Mutable<bool>),
+    /// A `Signal<f32>` field, which is fed by the knob's mutable.
+    F32Signal(Mutable<f32>),
+    /// A `bool` field.
+    Bool(Mutable<bool>),
+    /// An `f32` field.
+    F32(Mutable<f32>),
+    /// An `i32` field.
+    I32(Mutable<i32>),
+    /// An enum field with only unit variants, holding the index of the selected variant.
+    Enum(Mutable<usize>, &'static [&'static str]),
+    /// A field which can't be edited.
+    ReadOnly,
+}
+
+/// The value of the knob of a plain field, which is copied into the props when the control is
+/// built.
+#[derive(Clone, PartialEq)]
+enum KnobValue {
+    Bool(bool),
+    F32(f32),
+    I32(i32),
+    Variant(&'static str),
+}
+
+impl Knob {
+    /// Create the knob for a field, starting from the field's value.
+    fn new<P>(cx: &mut Cx<P>, field: &dyn Reflect) -> Self {
+        if let Some(signal) = field.downcast_ref::<Signal<bool>>() {
+            Self::BoolSignal(cx.create_mutable(initial_value(signal)))
+        } else if let Some(signal) = field.downcast_ref::<Signal<f32>>() {
+            Self::F32Signal(cx.create_mutable(initial_value(signal)))
+        } else if let Some(value) = field.downcast_ref::<bool>() {
+            Self::Bool(cx.create_mutable(*value))
+        } else if let Some(value) = field.downcast_ref::<f32>() {
+            Self::F32(cx.create_mutable(*value))
+        } else if let Some(value) = field.downcast_ref::<i32>() {
+            Self::I32(cx.create_mutable(*value))
+        } else if let ReflectRef::Enum(value) = field.reflect_ref() {
+            match unit_variants(value) {
+                Some(names) => Self::Enum(cx.create_mutable(value.variant_index()), names),
+                None => Self::ReadOnly,
+            }
+        } else {
+            Self::ReadOnly
+        }
+    }
+
+    /// The value of the knob of a plain field. Signal fields are fed by their knob directly,
+    /// so they have no value to copy.
+    fn value(&self, cx: &Rcx) -> Option<KnobValue> {
+        match self {
+            Self::Bool(value) => Some(KnobValue::Bool(value.get(cx))),
+            Self::F32(value) => Some(KnobValue::F32(value.get(cx))),
+            Self::I32(value) => Some(KnobValue::I32(value.get(cx))),
+            Self::Enum(index, names) => Some(KnobValue::Variant(names[index.get(cx)])),
+            _ => None,
+        }
+    }
+
+    /// Set a field of the props from the knob.
+    fn apply(&self, field: &mut dyn Reflect, value: Option<&KnobValue>) {
+        match (self, value) {
+            (Self::BoolSignal(knob), _) => {
+                if let Some(signal) = field.downcast_mut::<Signal<bool>>() {
+                    *signal = knob.signal();
+                }
+            }
+            (Self::F32Signal(knob), _) => {
+                if let Some(signal) = field.downcast_mut::<Signal<f32>>() {
+                    *signal = knob.signal();
+                }
+            }
+            (_, Some(KnobValue::Bool(value))) => {
+                if let Some(field) = field.downcast_mut::<bool>() {
+                    *field = *value;
+                }
+            }
+            (_, Some(KnobValue::F32(value))) => {
+                if let Some(field) = field.downcast_mut::<f32>() {
+                    *field = *value;
+                }
+            }
+            (_, Some(KnobValue::I32(value))) => {
+                if let Some(field) = field.downcast_mut::<i32>() {
+                    *field = *value;
+                }
+            }
+            (_, Some(KnobValue::Variant(name))) => {
+                field.apply(&DynamicEnum::new(*name, DynamicVariant::Unit));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// The value of a signal field when the props are made. Signals other than constants can't
+/// be read here, so their knobs start from the default value.
+fn initial_value<T: Clone + Default>(signal: &Signal<T>) -> T {
+    match signal {
+        Signal::Constant(value) => value.clone(),
+        _ => T::default(),
+    }
+}
+
+/// The names of the variants of an enum, if all of them are unit variants.
+fn unit_variants(value: &dyn Enum) -> Option<&'static [&'static str]> {
+    match value.get_represented_type_info()? {
+        TypeInfo::Enum(info) if info.iter().all(|v| matches!(v, VariantInfo::Unit(_))) => {
+            Some(info.variant_names())
+        }
+        _ => None,
+    }
+}
+
+/// Renders a story: the control on a stage, next to the knob panel for its props.
+fn story<P: StoryProps>(make: fn() -> P) -> ViewHandle {
+    story_layout::<P>.bind(make).into()
+}
+
+fn story_layout<P: StoryProps>(cx: &mut Cx<fn() -> P>) -> impl View {
+    let make = cx.props;
+    let sample = make();
+    let fields: Vec<(String, String, Knob)> = sample
+        .iter_fields()
+        .enumerate()
+        .map(|(index, field)| {
+            let name = sample.name_at(index).unwrap_or_default().to_string();
+            let type_name = field.reflect_short_type_path().to_string();
+            (name, type_name, Knob::new(cx, field))
+        })
+        .collect();
+    let knobs: Arc<Vec<Knob>> = Arc::new(fields.iter().map(|(_, _, knob)| *knob).collect());
+    let controls: Vec<ViewHandle> = fields
+        .into_iter()
+        .map(|(name, type_name, knob)| knob_control(cx, name, type_name, knob))
+        .collect();
+    let count = controls.len();
+    let values = knobs.clone();
+    (
+        Element::<NodeBundle>::new()
+            .with_styles(style_stage)
+            .with_children(For::each(
+                move |cx| {
+                    std::iter::once(values.iter().map(|knob| knob.value(cx)).collect::<Vec<_>>())
+                },
+                move |values: &Vec<Option<KnobValue>>| -> ViewHandle {
+                    let mut props = make();
+                    for (index, (knob, value)) in knobs.iter().zip(values).enumerate() {
+                        if let Some(field) = props.field_at_mut(index) {
+                            knob.apply(field, value.as_ref());
+                        }
+                    }
+                    props.into()
+                },
+            )),
+        Element::<NodeBundle>::new()
+            .with_styles(style_knobs)
+            .with_children(For::index(
+                move |_| 0..count,
+                move |i, _| controls[*i].clone(),
+            )),
+    )
+}
+
+/// Generates the control for a knob.
+fn knob_control<P>(cx: &mut Cx<P>, name: String, type_name: String, knob: Knob) -> ViewHandle {
+    match knob {
+        Knob::BoolSignal(value) | Knob::Bool(value) => Checkbox {
+            on_change: Some(cx.create_callback(move |cx: &mut Cx<bool>| {
+                let checked = cx.props;
+                value.set(cx, checked);
+            })),
+            ..default()
+        }
+        .with_label(name)
+        .with_checked(value.signal())
+        .into(),
+        Knob::F32Signal(value) | Knob::F32(value) => Slider {
+            precision: 1,
+            formatted_value: Some(
+                cx.create_derived(move |cx| format!("{}: {:.1}", name, value.get(cx))),
+            ),
+            on_change: Some(cx.create_callback(move |cx: &mut Cx<f32>| {
+                let v = cx.props;
+                value.set(cx, v);
+            })),
+            ..default()
+        }
+        .with_value(value.signal())
+        .with_range(F32_RANGE.0, F32_RANGE.1)
+        .into(),
+        Knob::I32(value) => Slider {
+            formatted_value: Some(
+                cx.create_derived(move |cx| format!("{}: {}", name, value.get(cx))),
+            ),
+            on_change: Some(cx.create_callback(move |cx: &mut Cx<f32>| {
+                let v = cx.props;
+                value.set(cx, v.round() as i32);
+            })),
+            ..default()
+        }
+        .with_value(cx.create_derived(move |cx| value.get(cx) as f32))
+        .with_range(I32_RANGE.0, I32_RANGE.1)
+        .into(),
+        Knob::Enum(index, names) => Button {
+            children: text_computed(move |cx| format!("{}: {}", name, names[index.get(cx)])).into(),
+            on_click: Some(cx.create_callback(move |cx| {
+                let next = (index.get(cx) + 1) % names.len();
+                index.set(cx, next);
+            })),
+            ..default()
+        }
+        .into(),
+        Knob::ReadOnly => Element::<NodeBundle>::new()
+            .with_styles(style_read_only)
+            .with_child(&format!("{}: {} (read-only)", name, type_name).into())
+            .into(),
+    }
+}
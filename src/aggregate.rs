@@ -0,0 +1,137 @@
+use bevy::ecs::{entity::Entity, world::World};
+
+use crate::{
+    mutable::MutableNextCell, signal::Signal, Cx, Rcx, Reaction, ReactionHandle, RunContextSetup,
+    TrackingScope,
+};
+
+/// Reaction which computes an aggregate of the source collection, and updates the associated
+/// mutable only when the aggregate changes.
+pub(crate) struct AggregateReaction<C, A, F> {
+    source: Signal<C>,
+    aggregate: F,
+    value: A,
+}
+
+impl<
+        C: Send + Sync + 'static,
+        A: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> A + Send + Sync + 'static,
+    > Reaction for AggregateReaction<C, A, F>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let value = self
+            .source
+            .map(&Rcx::new(world, tracking), |c| (self.aggregate)(c));
+        if value != self.value {
+            self.value = value.clone();
+            MutableNextCell::set(world, owner, value);
+        }
+    }
+}
+
+/// Methods to create signals which summarize a collection. The collection is read by
+/// reference rather than cloned, and the aggregate is computed once per change to the
+/// collection. Readers of the resulting signal only react when the aggregate itself changes,
+/// so for example a "3 items selected" label does not re-render when the items are edited.
+pub trait CreateAggregate {
+    /// Signal containing the number of items in the collection.
+    fn create_len_signal<C>(&mut self, source: Signal<C>) -> Signal<usize>
+    where
+        C: Send + Sync + 'static,
+        for<'a> &'a C: IntoIterator,
+        for<'a> <&'a C as IntoIterator>::IntoIter: ExactSizeIterator;
+
+    /// Signal containing the result of folding the items of the collection with `reducer`,
+    /// starting from `init`. Use this for sums, counts of matching items, or "any selected"
+    /// flags.
+    fn create_reduced<C, I, A, F>(&mut self, source: Signal<C>, init: A, reducer: F) -> Signal<A>
+    where
+        C: Send + Sync + 'static,
+        I: 'static,
+        for<'a> &'a C: IntoIterator<Item = &'a I>,
+        A: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(A, &I) -> A + Send + Sync + 'static;
+}
+
+impl<'p, 'w, Props> CreateAggregate for Cx<'p, 'w, Props> {
+    fn create_len_signal<C>(&mut self, source: Signal<C>) -> Signal<usize>
+    where
+        C: Send + Sync + 'static,
+        for<'a> &'a C: IntoIterator,
+        for<'a> <&'a C as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        create_aggregate(self, source, |c: &C| c.into_iter().len())
+    }
+
+    fn create_reduced<C, I, A, F>(&mut self, source: Signal<C>, init: A, reducer: F) -> Signal<A>
+    where
+        C: Send + Sync + 'static,
+        I: 'static,
+        for<'a> &'a C: IntoIterator<Item = &'a I>,
+        A: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(A, &I) -> A + Send + Sync + 'static,
+    {
+        create_aggregate(self, source, move |c: &C| {
+            c.into_iter().fold(init.clone(), &reducer)
+        })
+    }
+}
+
+fn create_aggregate<Props, C, A, F>(
+    cx: &mut Cx<Props>,
+    source: Signal<C>,
+    aggregate: F,
+) -> Signal<A>
+where
+    C: Send + Sync + 'static,
+    A: PartialEq + Clone + Send + Sync + 'static,
+    F: Fn(&C) -> A + Send + Sync + 'static,
+{
+    let mut tracking = TrackingScope::new(cx.world_mut().read_change_tick());
+    let value = source.map(&Rcx::new(cx.world_mut(), &mut tracking), &aggregate);
+    let mutable = cx.create_mutable(value.clone());
+    cx.world_mut().entity_mut(mutable.id).insert((
+        ReactionHandle::new(AggregateReaction {
+            source,
+            aggregate,
+            value,
+        }),
+        tracking,
+    ));
+    Signal::Mutable(mutable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tracking_scope::run_reactions;
+
+    #[test]
+    fn test_aggregates() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let (items, len, total) = {
+            let mut cx = Cx::new((), &mut world, &mut owner);
+            let items = cx.create_mutable::<Vec<i32>>(vec![1, 2, 3]);
+            let len = cx.create_len_signal(items.signal());
+            let total = cx.create_reduced(items.signal(), 0, |acc, item| acc + item);
+            (items, len, total)
+        };
+        let mut len_scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(len.get(&Rcx::new(&world, &mut len_scope)), 3);
+        let mut total_scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(total.get(&Rcx::new(&world, &mut total_scope)), 6);
+
+        // Changing an item without changing the length only updates the total.
+        world.increment_change_tick();
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        items.set_clone(&mut cx, vec![1, 2, 5]);
+        crate::mutable::commit_mutables(&mut world);
+        run_reactions(&mut world);
+        crate::mutable::commit_mutables(&mut world);
+        assert!(!len_scope.dependencies_changed(&world));
+        assert!(total_scope.dependencies_changed(&world));
+        assert_eq!(total.get(&Rcx::new(&world, &mut total_scope)), 8);
+    }
+}
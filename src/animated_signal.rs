@@ -0,0 +1,160 @@
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    math::{Vec2, Vec3},
+    time::Time,
+};
+
+use crate::{
+    mutable::MutableNextCell, signal::Signal, Rcx, Reaction, ReactionHandle, RunContextSetup,
+    TrackingScope,
+};
+
+/// Differences smaller than this are treated as having reached the target value.
+const SETTLE_EPSILON: f32 = 0.0001;
+
+/// A value which can be smoothly animated towards a target value. See [`Signal::animated`].
+pub trait Animatable: Clone + PartialEq + Send + Sync + 'static {
+    /// Return the value a fraction `t` of the way from `self` to `target`.
+    fn interpolate(&self, target: &Self, t: f32) -> Self;
+
+    /// Returns true if `self` is close enough to `target` that the animation can snap to it.
+    fn is_near(&self, target: &Self) -> bool;
+}
+
+impl Animatable for f32 {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        self + (target - self) * t
+    }
+
+    fn is_near(&self, target: &Self) -> bool {
+        (self - target).abs() < SETTLE_EPSILON * target.abs().max(1.)
+    }
+}
+
+impl Animatable for Vec2 {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        self.lerp(*target, t)
+    }
+
+    fn is_near(&self, target: &Self) -> bool {
+        self.distance(*target) < SETTLE_EPSILON * target.length().max(1.)
+    }
+}
+
+impl Animatable for Vec3 {
+    fn interpolate(&self, target: &Self, t: f32) -> Self {
+        self.lerp(*target, t)
+    }
+
+    fn is_near(&self, target: &Self) -> bool {
+        self.distance(*target) < SETTLE_EPSILON * target.length().max(1.)
+    }
+}
+
+/// Reaction which moves the associated mutable towards the value of the source signal.
+pub(crate) struct AnimatedReaction<T> {
+    source: Signal<T>,
+    rate: f32,
+    current: T,
+}
+
+impl<T: Animatable> Reaction for AnimatedReaction<T> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = self.source.get_clone(&Rcx::new(world, tracking));
+        if self.current == target {
+            return;
+        }
+        let next = if self.rate > 0. {
+            let dt = world.resource::<Time>().delta_seconds();
+            let next = self
+                .current
+                .interpolate(&target, 1. - (-self.rate * dt).exp());
+            if next.is_near(&target) {
+                target
+            } else {
+                next
+            }
+        } else {
+            target
+        };
+        if next != self.current {
+            self.current = next.clone();
+            MutableNextCell::set(world, owner, next);
+        }
+        // Keep running each frame until the target is reached.
+        if self.current != target {
+            tracking.track_resource::<Time>(world);
+        }
+    }
+}
+
+impl<T: Animatable> Signal<T> {
+    /// Returns a signal which follows this one smoothly: when this signal changes, the
+    /// returned signal moves towards the new value a little each frame, rather than jumping
+    /// to it. `rate` is the speed of the approach, per second; larger values are snappier,
+    /// and zero disables the animation entirely.
+    ///
+    /// Because the animated value is itself a signal, anything can react to it; for example,
+    /// a camera transform can follow an animated pose.
+    pub fn animated<'p>(self, cx: &mut impl RunContextSetup<'p>, rate: f32) -> Signal<T> {
+        let world = cx.world_mut();
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        let init = self.get_clone(&Rcx::new(world, &mut tracking));
+        let mutable = cx.create_mutable(init.clone());
+        cx.world_mut().entity_mut(mutable.id).insert((
+            ReactionHandle::new(AnimatedReaction {
+                source: self,
+                rate,
+                current: init,
+            }),
+            tracking,
+        ));
+        Signal::Mutable(mutable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, Mutable};
+
+    #[test]
+    fn test_animated() {
+        let mut harness = TestHarness::new();
+        let mut scope = TrackingScope::new(harness.world().read_change_tick());
+        let (source, animated): (Mutable<f32>, Signal<f32>) = {
+            let mut cx = Cx::new((), harness.world_mut(), &mut scope);
+            let source = cx.create_mutable(0.);
+            (source, source.signal().animated(&mut cx, 10.))
+        };
+        let value = |harness: &TestHarness| animated.get(harness.world());
+        assert_eq!(value(&harness), 0.);
+
+        // The animated value approaches the target gradually.
+        harness.set(source, 10.);
+        harness.update();
+        let first = value(&harness);
+        assert!(first > 0. && first < 10.);
+        harness.update();
+        let second = value(&harness);
+        assert!(second > first && second < 10.);
+
+        // ...and eventually settles on it exactly.
+        harness.update_frames(200);
+        assert_eq!(value(&harness), 10.);
+    }
+
+    #[test]
+    fn test_animated_rate_zero() {
+        let mut harness = TestHarness::new();
+        let mut scope = TrackingScope::new(harness.world().read_change_tick());
+        let (source, animated) = {
+            let mut cx = Cx::new((), harness.world_mut(), &mut scope);
+            let source = cx.create_mutable(Vec2::ZERO);
+            (source, source.signal().animated(&mut cx, 0.))
+        };
+        harness.set(source, Vec2::ONE);
+        harness.update();
+        assert_eq!(animated.get(harness.world()), Vec2::ONE);
+    }
+}
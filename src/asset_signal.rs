@@ -0,0 +1,140 @@
+use bevy::{
+    asset::{Asset, AssetEvent, AssetId, Assets, Handle, LoadedFolder},
+    ecs::{
+        entity::Entity,
+        event::{Events, ManualEventReader},
+        world::World,
+    },
+};
+
+use crate::{
+    signal::Signal, Cx, Reaction, ReactionHandle, RunContextSetup, TrackingScope, WriteMutable,
+};
+
+/// Reaction which watches the asset event stream for additions and removals, and updates
+/// the list of asset ids.
+pub(crate) struct AssetIdsReaction<A: Asset> {
+    reader: ManualEventReader<AssetEvent<A>>,
+}
+
+impl<A: Asset> Reaction for AssetIdsReaction<A> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_resource::<Events<AssetEvent<A>>>(world);
+        let events = world.resource::<Events<AssetEvent<A>>>();
+
+        // The event resource changes every frame, so only rebuild the list if the set of
+        // assets actually changed.
+        let changed = self.reader.read(events).fold(false, |changed, ev| {
+            changed || matches!(ev, AssetEvent::Added { .. } | AssetEvent::Removed { .. })
+        });
+        if changed {
+            let ids = asset_ids::<A>(world);
+            world.write_mutable_clone(owner, ids);
+        }
+    }
+}
+
+/// Reaction which watches a loaded folder, and updates the list of handles of a given type
+/// within that folder.
+pub(crate) struct FolderHandlesReaction<A: Asset> {
+    folder: AssetId<LoadedFolder>,
+    reader: ManualEventReader<AssetEvent<LoadedFolder>>,
+    marker: std::marker::PhantomData<A>,
+}
+
+impl<A: Asset> Reaction for FolderHandlesReaction<A> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_resource::<Events<AssetEvent<LoadedFolder>>>(world);
+        let events = world.resource::<Events<AssetEvent<LoadedFolder>>>();
+        let changed = self.reader.read(events).fold(false, |changed, ev| {
+            changed
+                || ev.is_loaded_with_dependencies(self.folder)
+                || ev.is_modified(self.folder)
+                || ev.is_removed(self.folder)
+        });
+        if changed {
+            let handles = folder_handles::<A>(world, self.folder);
+            world.write_mutable_clone(owner, handles);
+        }
+    }
+}
+
+/// Returns the ids of all assets of type `A` currently in the world.
+fn asset_ids<A: Asset>(world: &World) -> Vec<AssetId<A>> {
+    match world.get_resource::<Assets<A>>() {
+        Some(assets) => assets.ids().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Returns the handles of type `A` contained within the given folder, if it has been loaded.
+fn folder_handles<A: Asset>(world: &World, folder: AssetId<LoadedFolder>) -> Vec<Handle<A>> {
+    match world
+        .get_resource::<Assets<LoadedFolder>>()
+        .and_then(|folders| folders.get(folder))
+    {
+        Some(folder) => folder
+            .handles
+            .iter()
+            .filter_map(|h| h.clone().try_typed::<A>().ok())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Methods to create signals that track collections of assets.
+pub trait CreateAssetSignal {
+    /// Signal that returns the ids of all assets of type `A`. The signal only changes when
+    /// an asset of that type is added or removed; modifications to the asset data don't
+    /// affect it.
+    fn create_asset_ids_signal<A: Asset>(&mut self) -> Signal<Vec<AssetId<A>>>;
+
+    /// Signal that returns the handles of all assets of type `A` within a folder loaded via
+    /// `AssetServer::load_folder`. Returns an empty list until the folder has finished loading.
+    fn create_folder_signal<A: Asset>(
+        &mut self,
+        folder: &Handle<LoadedFolder>,
+    ) -> Signal<Vec<Handle<A>>>;
+}
+
+impl<'p, 'w, Props> CreateAssetSignal for Cx<'p, 'w, Props> {
+    fn create_asset_ids_signal<A: Asset>(&mut self) -> Signal<Vec<AssetId<A>>> {
+        let world = self.world_mut();
+        let ids = asset_ids::<A>(world);
+        let reader = world
+            .resource::<Events<AssetEvent<A>>>()
+            .get_reader_current();
+        let mutable = self.create_mutable(ids);
+        let mut reaction = AssetIdsReaction::<A> { reader };
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        reaction.react(mutable.id, self.world_mut(), &mut tracking);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert((ReactionHandle::new(reaction), tracking));
+        mutable.signal()
+    }
+
+    fn create_folder_signal<A: Asset>(
+        &mut self,
+        folder: &Handle<LoadedFolder>,
+    ) -> Signal<Vec<Handle<A>>> {
+        let folder = folder.id();
+        let world = self.world_mut();
+        let handles = folder_handles::<A>(world, folder);
+        let reader = world
+            .resource::<Events<AssetEvent<LoadedFolder>>>()
+            .get_reader_current();
+        let mutable = self.create_mutable(handles);
+        let mut reaction = FolderHandlesReaction::<A> {
+            folder,
+            reader,
+            marker: std::marker::PhantomData,
+        };
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        reaction.react(mutable.id, self.world_mut(), &mut tracking);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert((ReactionHandle::new(reaction), tracking));
+        mutable.signal()
+    }
+}
@@ -1,6 +1,14 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
 use bevy::{ecs::system::SystemParam, prelude::*};
 
-use crate::{Cx, TrackingScope};
+use crate::{Cx, DespawnScopes, TrackingScope};
 
 pub(crate) trait CallbackFnRef<P> {
     fn call(&self, cx: &mut Cx<P>);
@@ -22,6 +30,16 @@ impl<P, F: FnMut(&mut Cx<P>)> CallbackFnMutRef<P> for F {
     }
 }
 
+pub(crate) trait CallbackFnReturnRef<P, R> {
+    fn call(&self, cx: &mut Cx<P>) -> R;
+}
+
+impl<P, R, F: Fn(&mut Cx<P>) -> R> CallbackFnReturnRef<P, R> for F {
+    fn call(&self, cx: &mut Cx<P>) -> R {
+        self(cx)
+    }
+}
+
 /// Contains a boxed, type-erased callback.
 #[derive(Component)]
 pub(crate) struct CallbackFnCell<P> {
@@ -33,6 +51,12 @@ pub(crate) struct CallbackFnMutCell<P> {
     pub(crate) inner: Option<Box<dyn CallbackFnMutRef<P> + Send + Sync>>,
 }
 
+/// Contains a boxed, type-erased callback which returns a value.
+#[derive(Component)]
+pub(crate) struct CallbackFnReturnCell<P, R> {
+    pub(crate) inner: Option<Box<dyn CallbackFnReturnRef<P, R> + Send + Sync>>,
+}
+
 /// Contains a reference to a callback. `P` is the type of the props.
 #[derive(PartialEq)]
 pub struct Callback<P = ()> {
@@ -47,6 +71,189 @@ impl<P> Clone for Callback<P> {
     }
 }
 
+/// Contains a reference to a callback which returns a value. `P` is the type of the props,
+/// and `R` is the type of the result. Unlike [`Callback`], these can only be run
+/// synchronously, since the caller needs the result.
+#[derive(PartialEq)]
+pub struct CallbackFn<P = (), R = ()> {
+    pub(crate) id: Entity,
+    pub(crate) marker: std::marker::PhantomData<(P, R)>,
+}
+
+impl<P, R> Copy for CallbackFn<P, R> {}
+impl<P, R> Clone for CallbackFn<P, R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Tracks the callbacks which are running. While any callback is running, the despawning of
+/// tracking scopes, including those of razed views, is deferred until the outermost callback
+/// returns. This allows a callback to close the view which owns it, such as a menu item which
+/// closes its own menu, and then continue to use the callback's captured mutables.
+#[derive(Resource, Default)]
+pub(crate) struct CallbackStack {
+    /// Number of callbacks which are running. This is shared with the [`CallbackGuard`] of
+    /// each running callback, so that it goes back down even if the callback panics.
+    depth: Arc<AtomicUsize>,
+    deferred_despawns: Vec<Entity>,
+}
+
+/// Marks a callback as running until it is dropped.
+struct CallbackGuard(Arc<AtomicUsize>);
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl CallbackStack {
+    /// If a callback is running, defer despawning the scope entity until it returns, and
+    /// return true.
+    pub(crate) fn defer_despawn(world: &mut World, scope_entity: Entity) -> bool {
+        match world.get_resource_mut::<CallbackStack>() {
+            Some(mut stack) if stack.depth.load(Ordering::Relaxed) > 0 => {
+                stack.deferred_despawns.push(scope_entity);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mark a callback as running. If a previous callback panicked, the entities it deferred
+    /// are despawned first, since its [`CallbackGuard`] was dropped without calling `exit`.
+    fn enter(world: &mut World) -> CallbackGuard {
+        Self::flush(world);
+        let depth = world
+            .get_resource_or_insert_with(CallbackStack::default)
+            .depth
+            .clone();
+        depth.fetch_add(1, Ordering::Relaxed);
+        CallbackGuard(depth)
+    }
+
+    /// Mark a callback as having returned. Once no callbacks are running, the deferred
+    /// entities are despawned.
+    fn exit(world: &mut World, guard: CallbackGuard) {
+        drop(guard);
+        Self::flush(world);
+    }
+
+    /// Despawn the deferred entities, unless a callback is running.
+    fn flush(world: &mut World) {
+        let Some(mut stack) = world.get_resource_mut::<CallbackStack>() else {
+            return;
+        };
+        if stack.depth.load(Ordering::Relaxed) == 0 {
+            let despawns = std::mem::take(&mut stack.deferred_despawns);
+            for scope_entity in despawns {
+                world.despawn_owned_recursive(scope_entity);
+            }
+        }
+    }
+}
+
+/// Invocations of callbacks which were made while the callback was already running, to be
+/// run when the current invocation returns. Props need not be `Send`, so this is a non-send
+/// resource; it is only accessed synchronously, from within [`invoke_callback`].
+struct QueuedCalls<P>(VecDeque<(Entity, P)>);
+
+/// Invoke a callback. If the callback is already running, for example because it triggered
+/// itself by way of another callback, the invocation is queued and run once the current
+/// invocation returns, rather than re-entering the callback.
+pub(crate) fn invoke_callback<P: 'static>(world: &mut World, callback: Callback<P>, props: P) {
+    let Some(props) = call_once(world, callback.id, props) else {
+        return;
+    };
+    match world.get_non_send_resource_mut::<QueuedCalls<P>>() {
+        Some(mut queue) => queue.0.push_back((callback.id, props)),
+        None => world.insert_non_send_resource(QueuedCalls(VecDeque::from([(callback.id, props)]))),
+    }
+}
+
+/// Call a callback, followed by any invocations of it which were queued while it ran.
+/// Returns the props if the callback is already running.
+fn call_once<P: 'static>(world: &mut World, id: Entity, props: P) -> Option<P> {
+    let tick = world.read_change_tick();
+    let mut tracking = TrackingScope::new(tick);
+    let Some(mut callback_entity) = world.get_entity_mut(id) else {
+        warn!("No callback found for {:?}", id);
+        return None;
+    };
+    if let Some(mut cell) = callback_entity.get_mut::<CallbackFnCell<P>>() {
+        let Some(callback_fn) = cell.inner.take() else {
+            return Some(props);
+        };
+        let guard = CallbackStack::enter(world);
+        let mut next = Some(props);
+        while let Some(props) = next {
+            callback_fn.call(&mut Cx::new(props, world, &mut tracking));
+            next = take_queued(world, id);
+        }
+        // The callback may have despawned its own entity.
+        if let Some(mut cell) = world.get_mut::<CallbackFnCell<P>>(id) {
+            cell.inner = Some(callback_fn);
+        }
+        CallbackStack::exit(world, guard);
+    } else if let Some(mut cell) = callback_entity.get_mut::<CallbackFnMutCell<P>>() {
+        let Some(mut callback_fn) = cell.inner.take() else {
+            return Some(props);
+        };
+        let guard = CallbackStack::enter(world);
+        let mut next = Some(props);
+        while let Some(props) = next {
+            callback_fn.call(&mut Cx::new(props, world, &mut tracking));
+            next = take_queued(world, id);
+        }
+        if let Some(mut cell) = world.get_mut::<CallbackFnMutCell<P>>(id) {
+            cell.inner = Some(callback_fn);
+        }
+        CallbackStack::exit(world, guard);
+    } else {
+        warn!("No callback found for {:?}", id);
+    }
+    None
+}
+
+/// Remove the first queued invocation of the given callback.
+fn take_queued<P: 'static>(world: &mut World, id: Entity) -> Option<P> {
+    let mut queue = world.get_non_send_resource_mut::<QueuedCalls<P>>()?;
+    let index = queue.0.iter().position(|(callback, _)| *callback == id)?;
+    let (_, props) = queue.0.remove(index)?;
+    if queue.0.is_empty() {
+        world.remove_non_send_resource::<QueuedCalls<P>>();
+    }
+    Some(props)
+}
+
+/// Invoke a callback which returns a value. Since the caller needs the result, a callback
+/// which is already running can't be queued; instead a warning is logged and `None` is
+/// returned.
+pub(crate) fn invoke_callback_fn<P: 'static, R: 'static>(
+    world: &mut World,
+    callback: CallbackFn<P, R>,
+    props: P,
+) -> Option<R> {
+    let tick = world.read_change_tick();
+    let mut tracking = TrackingScope::new(tick);
+    let Some(mut cell) = world.get_mut::<CallbackFnReturnCell<P, R>>(callback.id) else {
+        warn!("No callback found for {:?}", callback.id);
+        return None;
+    };
+    let Some(callback_fn) = cell.inner.take() else {
+        warn!("Callback {:?} was called while it was running", callback.id);
+        return None;
+    };
+    let guard = CallbackStack::enter(world);
+    let result = callback_fn.call(&mut Cx::new(props, world, &mut tracking));
+    if let Some(mut cell) = world.get_mut::<CallbackFnReturnCell<P, R>>(callback.id) {
+        cell.inner = Some(callback_fn);
+    }
+    CallbackStack::exit(world, guard);
+    Some(result)
+}
+
 /// An event which will call a callback with the given props.
 #[derive(Event)]
 #[allow(dead_code)] // For now
@@ -75,34 +282,97 @@ impl<'w, P: Send + Sync + 'static> CallDeferred<'w, P> {
 #[allow(dead_code)] // For now
 /// System that runs callbacks from an event listener.
 pub fn run_deferred_callbacks<P: 'static + Send + Sync>(world: &mut World) {
-    let tick = world.read_change_tick();
     let mut events = world.get_resource_mut::<Events<DeferredCall<P>>>().unwrap();
     let events = events.drain().collect::<Vec<_>>();
-    let mut tracking = TrackingScope::new(tick);
     for event in events {
-        let mut callback_entity = world.entity_mut(event.receiver);
-        if let Some(mut callback_cmp) = callback_entity.get_mut::<CallbackFnCell<P>>() {
-            let mut callback_fn = callback_cmp.inner.take();
-            let callback_box = callback_fn.as_ref().expect("Callback is not present");
-            let mut cx = Cx::new(event.props, world, &mut tracking);
-            callback_box.call(&mut cx);
-            let mut callback_entity = world.entity_mut(event.receiver);
-            callback_entity
-                .get_mut::<CallbackFnCell<P>>()
-                .unwrap()
-                .inner = callback_fn.take();
-        } else if let Some(mut callback_cmp) = callback_entity.get_mut::<CallbackFnMutCell<P>>() {
-            let mut callback_fn = callback_cmp.inner.take();
-            let callback_box = callback_fn.as_mut().expect("Callback is not present");
-            let mut cx = Cx::new(event.props, world, &mut tracking);
-            callback_box.call(&mut cx);
-            let mut callback_entity = world.entity_mut(event.receiver);
-            callback_entity
-                .get_mut::<CallbackFnMutCell<P>>()
-                .unwrap()
-                .inner = callback_fn.take();
-        } else {
-            warn!("No callback found for {:?}", event.receiver);
-        }
+        invoke_callback(
+            world,
+            Callback {
+                id: event.receiver,
+                marker: std::marker::PhantomData,
+            },
+            event.props,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReadMutable, RunContextSetup, RunContextWrite, WriteMutable};
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<i32>);
+
+    #[derive(Resource)]
+    struct Recursive(Callback);
+
+    #[test]
+    fn test_callback_razes_owner() {
+        let mut world = World::new();
+        let owner = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        let count = cx.create_mutable(0);
+        let close = cx.create_callback(move |cx: &mut Cx| {
+            // Closing the view which owns this callback doesn't invalidate its mutables.
+            cx.world_mut().despawn_owned_recursive(owner);
+            let value = count.get(cx);
+            count.set(cx, value + 1);
+        });
+        world.entity_mut(owner).insert(scope);
+
+        world.run_callback(close, ());
+        assert!(world.get_entity(owner).is_none());
+        assert!(world.get_entity(close.id).is_none());
+    }
+
+    #[test]
+    fn test_callback_reentry_is_queued() {
+        let mut world = World::new();
+        world.init_resource::<Log>();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        let callback = cx.create_callback_mut(|cx: &mut Cx| {
+            let world = cx.world_mut();
+            let count = world.resource::<Log>().0.len() as i32 + 1;
+            if count < 3 {
+                let callback = world.resource::<Recursive>().0;
+                world.run_callback(callback, ());
+            }
+            world.resource_mut::<Log>().0.push(count);
+        });
+        world.insert_resource(Recursive(callback));
+
+        world.run_callback(callback, ());
+        assert_eq!(world.resource::<Log>().0, vec![1, 2, 3]);
+    }
+
+    #[test]
+    #[cfg(panic = "unwind")]
+    fn test_callback_panic() {
+        let mut world = World::new();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let closed = world.spawn_empty().id();
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        let callback = cx.create_callback(move |cx: &mut Cx| {
+            cx.world_mut().despawn_owned_recursive(closed);
+            panic!("callback failed");
+        });
+        let other = cx.create_callback(|_cx: &mut Cx| {});
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            world.run_callback(callback, ());
+        }));
+        assert!(result.is_err());
+
+        // Once the callback has unwound, despawns are no longer deferred.
+        let owner = world.spawn_empty().id();
+        world.despawn_owned_recursive(owner);
+        assert!(world.get_entity(owner).is_none());
+
+        // The despawn deferred by the panicking callback completes when the next one runs.
+        assert!(world.get_entity(closed).is_some());
+        world.run_callback(other, ());
+        assert!(world.get_entity(closed).is_none());
     }
 }
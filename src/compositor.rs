@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use bevy::{
     prelude::*,
     render::{
@@ -29,6 +31,10 @@ pub struct Compositor {
 
     /// List of effects to be added to the image entity.
     effects: Vec<Box<dyn EntityEffect>>,
+
+    /// Number of effects at the start of `effects` which were added with
+    /// [`add_early_effect`](EffectTarget::add_early_effect).
+    early_effects: usize,
 }
 
 impl Compositor {
@@ -47,6 +53,7 @@ impl Compositor {
             image: None,
             image_entity: None,
             effects: Vec::new(),
+            early_effects: 0,
         }
     }
 
@@ -182,6 +189,11 @@ impl EffectTarget for Compositor {
     fn add_effect(&mut self, effect: Box<dyn EntityEffect>) {
         self.effects.push(effect);
     }
+
+    fn add_early_effect(&mut self, effect: Box<dyn EntityEffect>) {
+        self.effects.insert(self.early_effects, effect);
+        self.early_effects += 1;
+    }
 }
 
 impl From<Compositor> for ViewHandle {
@@ -192,7 +204,9 @@ impl From<Compositor> for ViewHandle {
 
 impl WithStyles for Compositor {
     fn with_styles<S: StyleTuple + 'static>(mut self, styles: S) -> Self {
-        self.add_effect(Box::new(ApplyStylesEffect { styles }));
+        self.add_effect(Box::new(ApplyStylesEffect {
+            styles: Arc::new(styles),
+        }));
         self
     }
 }
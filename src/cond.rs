@@ -10,7 +10,15 @@ pub enum CondState {
     False((ViewHandle, Entity)),
 }
 
+/// Component which records the display mode of a node hidden by a keep-alive [`Cond`], so
+/// that it can be restored when the branch is shown again.
+#[derive(Component)]
+pub(crate) struct KeptDisplay(Display);
+
 /// A conditional view which renders one of two children depending on the condition expression.
+///
+/// By default the inactive branch is razed; with [`keep_alive`](Self::keep_alive), it is
+/// hidden instead, so that it keeps its state.
 pub struct Cond<
     Test: 'static,
     Pos: Into<ViewHandle>,
@@ -22,6 +30,9 @@ pub struct Cond<
     pos: PosFn,
     neg: NegFn,
     state: CondState,
+    keep_alive: bool,
+    /// The inactive branch, hidden rather than razed, when `keep_alive` is set.
+    kept: Option<(ViewHandle, Entity)>,
 }
 
 impl<
@@ -39,9 +50,24 @@ impl<
             pos,
             neg,
             state: CondState::Unset,
+            keep_alive: false,
+            kept: None,
         }
     }
 
+    /// Keep the inactive branch alive when the condition changes, hiding it with
+    /// `Display::None` rather than razing it. This preserves the state of expensive
+    /// subtrees, such as text inputs, scroll positions and the results of async loads, when
+    /// the condition toggles frequently. Each branch is still only built the first time it
+    /// is shown.
+    ///
+    /// The hidden branch stays hidden if it changes while inactive: nodes which it adds are
+    /// hidden as they appear, and nodes whose styles are re-applied are hidden again.
+    pub fn keep_alive(mut self) -> Self {
+        self.keep_alive = true;
+        self
+    }
+
     fn build_branch_state<V: Into<ViewHandle>, Factory: Fn() -> V>(
         &self,
         branch: &Factory,
@@ -68,10 +94,18 @@ impl<
     > View for Cond<Test, Pos, PosFn, Neg, NegFn>
 {
     fn nodes(&self) -> NodeSpan {
-        match self.state {
-            CondState::Unset => NodeSpan::Empty,
-            CondState::True(ref true_state) => true_state.0.nodes(),
-            CondState::False(ref false_state) => false_state.0.nodes(),
+        let kept = self.kept.as_ref().map(|(view, _)| view.nodes());
+        match (&self.state, kept) {
+            (CondState::Unset, _) => NodeSpan::Empty,
+            (CondState::True(ref true_state), None) => true_state.0.nodes(),
+            (CondState::False(ref false_state), None) => false_state.0.nodes(),
+            // Kept branches stay in place, so that the order of nodes doesn't change.
+            (CondState::True(ref true_state), Some(kept)) => {
+                NodeSpan::Fragment(Box::new([true_state.0.nodes(), kept]))
+            }
+            (CondState::False(ref false_state), Some(kept)) => {
+                NodeSpan::Fragment(Box::new([kept, false_state.0.nodes()]))
+            }
         }
     }
 
@@ -89,49 +123,55 @@ impl<
     fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
         let re = Rcx::new(world, tracking);
         let cond = (self.test)(&re);
-        if cond {
-            match self.state {
-                CondState::True(_) => {
-                    // Already true, do nothing.
-                }
-                CondState::False((ref mut false_state, entity)) => {
-                    false_state.raze(entity, world);
-                    self.state = CondState::True(self.build_branch_state::<Pos, PosFn>(
-                        &self.pos,
-                        view_entity,
-                        world,
-                    ));
-                }
-                CondState::Unset => {
-                    self.state = CondState::True(self.build_branch_state::<Pos, PosFn>(
-                        &self.pos,
-                        view_entity,
-                        world,
-                    ));
-                }
+        if matches!(
+            (&self.state, cond),
+            (CondState::True(_), true) | (CondState::False(_), false)
+        ) {
+            // Already showing the right branch, do nothing.
+            return;
+        }
+
+        // Hide or raze the previous branch.
+        let previous = match std::mem::replace(&mut self.state, CondState::Unset) {
+            CondState::True(branch) | CondState::False(branch) => Some(branch),
+            CondState::Unset => None,
+        };
+        let kept = match previous {
+            Some((view, entity)) if self.keep_alive => {
+                set_branch_hidden(&view, true, world);
+                Some((view, entity))
             }
-        } else {
-            match self.state {
-                CondState::False(_) => {
-                    // Already false, do nothing.
-                }
-                CondState::True((ref mut true_state, entity)) => {
-                    true_state.raze(entity, world);
-                    self.state = CondState::False(self.build_branch_state::<Neg, NegFn>(
-                        &self.neg,
-                        view_entity,
-                        world,
-                    ));
-                }
-                CondState::Unset => {
-                    self.state = CondState::False(self.build_branch_state::<Neg, NegFn>(
-                        &self.neg,
-                        view_entity,
-                        world,
-                    ));
-                }
+            Some((view, entity)) => {
+                view.raze(entity, world);
+                None
             }
+            None => None,
+        };
+
+        // Show the kept branch, if there is one, or build the new branch.
+        let next = match self.kept.take() {
+            Some((view, entity)) => {
+                set_branch_hidden(&view, false, world);
+                (view, entity)
+            }
+            None if cond => self.build_branch_state::<Pos, PosFn>(&self.pos, view_entity, world),
+            None => self.build_branch_state::<Neg, NegFn>(&self.neg, view_entity, world),
+        };
+        self.kept = kept;
+        self.state = if cond {
+            CondState::True(next)
+        } else {
+            CondState::False(next)
+        };
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
+        // The kept branch may have replaced some of its nodes, which need hiding in turn. The
+        // parent still has to attach the new nodes, so the change is passed on.
+        if let Some((view, _)) = self.kept.as_ref() {
+            set_branch_hidden(view, true, world);
         }
+        false
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
@@ -140,11 +180,49 @@ impl<
             CondState::False((ref mut false_state, entity)) => false_state.raze(entity, world),
             CondState::Unset => {}
         }
+        if let Some((view, entity)) = self.kept.take() {
+            view.raze(entity, world);
+        }
         self.state = CondState::Unset;
         world.despawn_owned_recursive(view_entity);
     }
 }
 
+/// Hide or show the nodes of a kept branch, by setting their display mode.
+fn set_branch_hidden(view: &ViewHandle, hidden: bool, world: &mut World) {
+    let mut nodes = Vec::new();
+    view.nodes().flatten(&mut nodes);
+    for node in nodes {
+        let mut entt = world.entity_mut(node);
+        if hidden {
+            if entt.contains::<KeptDisplay>() {
+                continue;
+            }
+            let Some(mut style) = entt.get_mut::<Style>() else {
+                continue;
+            };
+            let display = std::mem::replace(&mut style.display, Display::None);
+            entt.insert(KeptDisplay(display));
+        } else if let Some(KeptDisplay(display)) = entt.take::<KeptDisplay>() {
+            if let Some(mut style) = entt.get_mut::<Style>() {
+                style.display = display;
+            }
+        }
+    }
+}
+
+/// System which keeps the nodes of hidden branches hidden when their styles are re-applied.
+/// The newly computed display mode is the one restored when the branch is shown again.
+pub(crate) fn keep_branches_hidden(
+    mut nodes: Query<(&mut Style, &mut KeptDisplay), Changed<Style>>,
+) {
+    for (mut style, mut kept) in nodes.iter_mut() {
+        if style.display != Display::None {
+            kept.0 = std::mem::replace(&mut style.display, Display::None);
+        }
+    }
+}
+
 /// Creates a conditional branch view.
 pub fn cond<
     Test: Send + Sync + Fn(&Rcx) -> bool,
@@ -172,3 +250,108 @@ impl<
         ViewHandle::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup, TextStatic,
+        ViewFactory,
+    };
+
+    #[derive(Resource, Default)]
+    struct Builds(usize);
+
+    struct Label(&'static str);
+
+    impl ViewFactory for Label {
+        fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+            cx.world_mut().resource_mut::<Builds>().0 += 1;
+            TextStatic::new(self.0.to_string())
+        }
+    }
+
+    fn toggle(cx: &mut Cx) -> impl View {
+        let flag = cx.create_mutable(true);
+        cx.register_signal("flag", flag.signal());
+        Cond::new(move |cx| flag.get(cx), || Label("On"), || Label("Off")).keep_alive()
+    }
+
+    #[test]
+    fn test_cond_keep_alive() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Builds>();
+        let root = harness.mount(toggle.bind(()));
+        assert_eq!(harness.nodes(root).len(), 1);
+
+        let flag = harness.mutable::<bool>("flag");
+        harness.set(flag, false);
+        let nodes = harness.nodes(root);
+        assert_eq!(nodes.len(), 2);
+        let display =
+            |harness: &TestHarness, node| harness.world().get::<Style>(node).unwrap().display;
+        assert_eq!(display(&harness, nodes[0]), Display::None);
+        assert_ne!(display(&harness, nodes[1]), Display::None);
+
+        // Toggling back shows the kept branch, without building it again.
+        harness.set(flag, true);
+        assert_eq!(harness.nodes(root), nodes);
+        assert_ne!(display(&harness, nodes[0]), Display::None);
+        assert_eq!(display(&harness, nodes[1]), Display::None);
+        assert_eq!(harness.world().resource::<Builds>().0, 2);
+    }
+
+    fn nested(cx: &mut Cx) -> impl View {
+        let outer = cx.create_mutable(false);
+        let inner = cx.create_mutable(true);
+        cx.register_signal("outer", outer);
+        cx.register_signal("inner", inner);
+        Cond::new(
+            move |cx| outer.get(cx),
+            || Label("On"),
+            move || Cond::new(move |cx| inner.get(cx), || Label("A"), || Label("B")),
+        )
+        .keep_alive()
+    }
+
+    #[test]
+    fn test_cond_keep_alive_changes() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Builds>();
+        let root = harness.mount(nested.bind(()));
+        let outer = harness.mutable::<bool>("outer");
+        let inner = harness.mutable::<bool>("inner");
+        let visible = |harness: &TestHarness| -> String {
+            harness
+                .nodes(root)
+                .into_iter()
+                .filter(|node| {
+                    harness.world().get::<Style>(*node).unwrap().display != Display::None
+                })
+                .filter_map(|node| harness.world().get::<Text>(node))
+                .map(|text| text.sections[0].value.clone())
+                .collect()
+        };
+        assert_eq!(visible(&harness), "A");
+
+        // Nodes which the hidden branch replaces are hidden too.
+        harness.set(outer, true);
+        assert_eq!(visible(&harness), "On");
+        harness.set(inner, false);
+        assert_eq!(harness.nodes(root).len(), 2);
+        assert_eq!(visible(&harness), "On");
+
+        // Re-applying the style of a hidden node doesn't show it.
+        let hidden = harness.nodes(root)[1];
+        harness
+            .world_mut()
+            .get_mut::<Style>(hidden)
+            .unwrap()
+            .display = Display::Flex;
+        harness.update();
+        assert_eq!(visible(&harness), "On");
+
+        harness.set(outer, false);
+        assert_eq!(visible(&harness), "B");
+    }
+}
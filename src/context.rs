@@ -0,0 +1,26 @@
+use bevy::{
+    ecs::{component::Component, entity::Entity, world::World},
+    hierarchy::Parent,
+};
+
+/// Component which holds a context value provided by a view, see [`Element::provide`].
+///
+/// [`Element::provide`]: crate::Element::provide
+#[derive(Component)]
+pub(crate) struct ContextValue<T: Send + Sync + 'static>(pub(crate) T);
+
+/// Search the view hierarchy, starting at `start` and proceeding upwards through the
+/// ancestors, for the nearest entity that provides a context value of type `T`.
+pub(crate) fn find_context<T: Send + Sync + 'static>(
+    world: &World,
+    start: Entity,
+) -> Option<Entity> {
+    let mut e = start;
+    loop {
+        let entt = world.get_entity(e)?;
+        if entt.contains::<ContextValue<T>>() {
+            return Some(e);
+        }
+        e = entt.get::<Parent>()?.get();
+    }
+}
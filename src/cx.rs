@@ -4,14 +4,29 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use bevy::prelude::*;
+use bevy::{
+    ecs::query::{QueryFilter, ROQueryItem, ReadOnlyQueryData},
+    prelude::*,
+};
 
 use crate::{
-    callback::{Callback, CallbackFnCell, CallbackFnMutCell},
-    derived::{Derived, DerivedCell, ReadDerived, ReadDerivedInternal},
+    callback::{
+        invoke_callback, invoke_callback_fn, Callback, CallbackFn, CallbackFnCell,
+        CallbackFnMutCell, CallbackFnReturnCell,
+    },
+    context::{find_context, ContextValue},
+    derived::{Derived, DerivedCell, ReadDerived, ReadDerivedInternal, SignalDeps},
+    local_state::{LocalStateKey, LocalStateSlots},
     mutable::{MutableCell, MutableNextCell, ReadMutable, WriteMutable},
+    reaction::DeferredReaction,
+    reaction_report::record_mutable_write,
+    reducer::{ReducerCell, ReducerStore},
+    selector::{Selector, SelectorCell},
+    store::{Store, StoreCell},
+    suspense::{SuspenseBoundary, SuspenseDeps},
     tracking_scope::TrackingScope,
-    Mutable, Reaction, ReactionHandle, Signal,
+    trigger::{Trigger, TriggerCell},
+    Mutable, Reaction, ReactionHandle, Signal, UseWindowMetrics, ViewRoot,
 };
 
 /// An immutable reactive context, used for reactive closures such as derived signals.
@@ -20,9 +35,53 @@ pub trait RunContextRead {
     /// adds the resource as a dependency of the current tracking scope.
     fn use_resource<T: Resource>(&self) -> &T;
 
+    /// Return a reference to a single field of the resource of the given type, as selected
+    /// by the projection function `f`. Unlike [`use_resource`](Self::use_resource), the
+    /// current tracking scope will only react when the projected value changes (as determined
+    /// by `PartialEq`), not when other parts of the resource are modified.
+    fn use_resource_field<
+        R: Resource,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        &self,
+        f: F,
+    ) -> &T;
+
     /// Return a reference to the Component `C` on the given entity. Calling this function
-    /// adds the component as a dependency of the current tracking scope.
+    /// adds the component as a dependency of the current tracking scope. The dependency is
+    /// fine-grained: only changes to that component on that entity (including insertion and
+    /// removal) will cause a reaction.
     fn use_component<C: Component>(&self, entity: Entity) -> Option<&C>;
+
+    /// Return a reference to the Component `C` on the given entity, without adding it as a
+    /// dependency of the current tracking scope. This is for callers which track the parts
+    /// of the component they depend on in some other way.
+    fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C>;
+
+    /// Return a value computed from the Component `C` on the given entity, as selected by the
+    /// projection function `f`. Unlike [`use_component`](Self::use_component), the current
+    /// tracking scope will only react when the projected value changes (as determined by
+    /// `PartialEq`), or when the component is removed.
+    fn use_component_field<
+        C: Component,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> T + Send + Sync + 'static,
+    >(
+        &self,
+        entity: Entity,
+        f: F,
+    ) -> Option<T>;
+
+    /// Return a reference to the resource of the given type. Unlike
+    /// [`use_resource`](Self::use_resource), the current tracking scope will only react when
+    /// `predicate`, called with the value of the resource at the time it was read and its
+    /// current value, returns true. This is useful for resources which are modified every
+    /// frame, such as `Time`, when only occasional changes matter.
+    fn use_resource_when<R: Resource + Clone, F: Fn(&R, &R) -> bool + Send + Sync + 'static>(
+        &self,
+        predicate: F,
+    ) -> &R;
 }
 
 /// A mutable reactive context. This allows write access to reactive data sources.
@@ -32,36 +91,32 @@ pub trait RunContextWrite: RunContextRead {
 
     /// Invoke a callback with the given props.
     ///
+    /// Callbacks may safely raze the view which owns them: while a callback is running, the
+    /// entities owned by razed views, including the callback itself and its mutables, are
+    /// kept alive until the outermost callback returns. If the callback is already running,
+    /// for example because it indirectly triggered itself, the invocation is queued and run
+    /// after the current one returns, rather than re-entering the callback.
+    ///
     /// Arguments:
     /// * `callback` - The callback to invoke.
     /// * `props` - The props to pass to the callback.
     fn run_callback<P: 'static>(&mut self, callback: Callback<P>, props: P) {
-        let world = self.world_mut();
-        let tick = world.read_change_tick();
-        let mut tracking = TrackingScope::new(tick);
-        let mut cx = Cx::new(props, world, &mut tracking);
-        let mut callback_entity = cx.world.entity_mut(callback.id);
-        if let Some(mut cell) = callback_entity.get_mut::<CallbackFnCell<P>>() {
-            let mut callback_fn = cell.inner.take();
-            let callback_box = callback_fn.as_ref().expect("Callback is not present");
-            callback_box.call(&mut cx);
-            let mut callback_entity = cx.world.entity_mut(callback.id);
-            callback_entity
-                .get_mut::<CallbackFnCell<P>>()
-                .unwrap()
-                .inner = callback_fn.take();
-        } else if let Some(mut cell) = callback_entity.get_mut::<CallbackFnMutCell<P>>() {
-            let mut callback_fn = cell.inner.take();
-            let callback_box = callback_fn.as_mut().expect("Callback is not present");
-            callback_box.call(&mut cx);
-            let mut callback_entity = cx.world.entity_mut(callback.id);
-            callback_entity
-                .get_mut::<CallbackFnMutCell<P>>()
-                .unwrap()
-                .inner = callback_fn.take();
-        } else {
-            warn!("No callback found for {:?}", callback.id);
-        }
+        invoke_callback(self.world_mut(), callback, props);
+    }
+
+    /// Invoke a callback which returns a value, and return the result. Returns `None` if the
+    /// callback no longer exists, or if it is already running, since the invocation can't be
+    /// queued.
+    ///
+    /// Arguments:
+    /// * `callback` - The callback to invoke.
+    /// * `props` - The props to pass to the callback.
+    fn run_callback_fn<P: 'static, R: 'static>(
+        &mut self,
+        callback: CallbackFn<P, R>,
+        props: P,
+    ) -> Option<R> {
+        invoke_callback_fn(self.world_mut(), callback, props)
     }
 }
 
@@ -87,6 +142,87 @@ pub trait RunContextSetup<'p> {
         }
     }
 
+    /// Create a new [`Trigger`] in this context. A trigger carries no data, but can be
+    /// notified to force the scopes that track it to re-run.
+    fn create_trigger(&mut self) -> Trigger {
+        let trigger = self.world_mut().spawn(TriggerCell).id();
+        self.add_owned(trigger);
+        Trigger { id: trigger }
+    }
+
+    /// Create a new [`Store`] in this context. A store holds a structured value, and allows
+    /// readers to subscribe to individual fields of it.
+    ///
+    /// Arguments:
+    /// * `init` - The initial value of the store.
+    fn create_store<T: Send + Sync + 'static>(&mut self, init: T) -> Store<T> {
+        let store = self.world_mut().spawn(StoreCell::new(init)).id();
+        self.add_owned(store);
+        Store {
+            id: store,
+            marker: PhantomData,
+        }
+    }
+
+    /// Create a new [`ReducerStore`], whose state is changed by dispatching actions which
+    /// are applied by `reducer`.
+    ///
+    /// Arguments:
+    /// * `init` - The initial state.
+    /// * `reducer` - Function which applies an action to the state.
+    fn create_reducer_store<
+        T: Clone + Send + Sync + 'static,
+        A: Send + Sync + 'static,
+        F: Fn(&mut T, &A) + Send + Sync + 'static,
+    >(
+        &mut self,
+        init: T,
+        reducer: F,
+    ) -> ReducerStore<T, A> {
+        let store = self
+            .world_mut()
+            .spawn((
+                StoreCell::new(init),
+                ReducerCell::<T, A> {
+                    reducer: Arc::new(reducer),
+                    middleware: Vec::new(),
+                },
+            ))
+            .id();
+        self.add_owned(store);
+        ReducerStore {
+            id: store,
+            marker: PhantomData,
+        }
+    }
+
+    /// Create a new [`Selector`] from a signal. Rather than subscribing to the signal as a
+    /// whole, readers test individual keys with [`Selector::is_selected`], and only react
+    /// when the result for their key changes. This is useful for lists with a single
+    /// selected item, where changing the selection should only update two rows.
+    ///
+    /// Arguments:
+    /// * `source` - The signal containing the selected key.
+    fn create_selector<K: PartialEq + Clone + Send + Sync + 'static>(
+        &mut self,
+        source: Signal<K>,
+    ) -> Selector<K> {
+        let selector = self.world_mut().spawn(SelectorCell::<K>(None)).id();
+        self.add_owned(selector);
+        self.create_effect(move |cx| {
+            let value = source.get_clone(cx);
+            if let Some(mut cell) = cx.world_mut().get_mut::<SelectorCell<K>>(selector) {
+                if cell.0.as_ref() != Some(&value) {
+                    cell.0 = Some(value);
+                }
+            }
+        });
+        Selector {
+            id: selector,
+            marker: PhantomData,
+        }
+    }
+
     /// Create a new [`Callback`] in this context. This holds a `Fn` within an entity.
     ///
     /// Arguments:
@@ -109,6 +245,33 @@ pub trait RunContextSetup<'p> {
         }
     }
 
+    /// Create a new [`CallbackFn`] in this context. This holds a `Fn` which returns a value,
+    /// for example a validation function which decides whether a dialog can be closed.
+    ///
+    /// Arguments:
+    /// * `callback` - The callback function to invoke. This will be called with a single
+    ///    parameter, which is a [`Cx`] object. The context may or may not have props.
+    fn create_callback_fn<
+        P: 'static,
+        R: 'static,
+        F: Send + Sync + 'static + Fn(&mut Cx<P>) -> R,
+    >(
+        &mut self,
+        callback: F,
+    ) -> CallbackFn<P, R> {
+        let callback = self
+            .world_mut()
+            .spawn(CallbackFnReturnCell::<P, R> {
+                inner: Some(Box::new(callback)),
+            })
+            .id();
+        self.add_owned(callback);
+        CallbackFn {
+            id: callback,
+            marker: PhantomData,
+        }
+    }
+
     /// Create a new [`CallbackFnMut`] in this context. This holds a `FnMut` within an entity.
     ///
     /// Arguments:
@@ -153,6 +316,31 @@ pub trait RunContextSetup<'p> {
         })
     }
 
+    /// Create a new [`Derived`] whose dependencies are given explicitly, rather than being
+    /// discovered by tracking the reads made by `compute`. The derived signal is invalidated
+    /// only when one of the signals in `deps` changes; anything read inside `compute` is
+    /// untracked. This is useful when the computation touches incidental data (such as the
+    /// current time) which should not cause it to re-run.
+    ///
+    /// Arguments:
+    /// * `deps` - A signal, or tuple of signals, which the output depends on.
+    /// * `compute` - The function that computes the output.
+    fn create_derived_with_deps<
+        R: 'static,
+        D: SignalDeps,
+        F: Send + Sync + 'static + Fn(&mut Rcx) -> R,
+    >(
+        &mut self,
+        deps: D,
+        compute: F,
+    ) -> Signal<R> {
+        self.create_derived(move |cx: &mut Rcx| {
+            deps.track(cx);
+            let mut untracked = TrackingScope::new(cx.world().read_change_tick());
+            compute(&mut Rcx::new(cx.world(), &mut untracked))
+        })
+    }
+
     // /// Create a new [`Memo`] in this context. This represents a readable signal which
     // /// is computed from other signals. The result is memoized, which means that downstream
     // /// dependants will not be notified unless the output changes.
@@ -198,15 +386,111 @@ pub trait RunContextSetup<'p> {
         self.add_owned(entity);
     }
 
-    // /// Register a cleanup function for the current tracking scope. This will be called when
-    // /// the scope is dropped, or (if it's an effect) just before the effect is re-run.
-    // ///
-    // /// Arguments:
-    // /// * `cleanup` - The function that performs the cleanup.
-    // fn on_cleanup<F: Send + Sync + 'static + FnOnce(&mut Cx<()>)>(&mut self, cleanup: F) {
-    //     let cleanup_action = Arc::new(Mutex::new(cleanup));
-    //     self.add_cleanup(cleanup_action);
-    // }
+    /// Keep a [`Mutable`] and a field of a component on the `target` entity in sync, in both
+    /// directions: changes to the mutable are written to the component, and changes to the
+    /// component are written back to the mutable. Each side is only written when its value
+    /// differs from the other, which prevents the two updates from looping. This is useful
+    /// for editor panels which edit fields such as a `Transform`'s translation.
+    ///
+    /// Nothing is written while the target is missing the component, or after it has been
+    /// despawned. If the component is inserted later, its value is copied into the mutable.
+    ///
+    /// Arguments:
+    /// * `mutable` - The mutable to bind.
+    /// * `target` - The entity containing the component.
+    /// * `field` - Function which selects the field within the component.
+    /// * `field_mut` - Function which selects the field for writing.
+    fn bind_component<
+        C: Component,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> &T + Send + Sync + 'static,
+        FM: Fn(&mut C) -> &mut T + Send + Sync + 'static,
+    >(
+        &mut self,
+        mutable: Mutable<T>,
+        target: Entity,
+        field: F,
+        field_mut: FM,
+    ) {
+        let field = Arc::new(field);
+
+        // Component to mutable.
+        let read_field = field.clone();
+        self.create_effect(move |cx| {
+            let Some(value) = cx.use_component::<C>(target).map(|c| read_field(c).clone()) else {
+                return;
+            };
+            if cx.world.read_mutable_map(&mutable, |v| *v != value) {
+                mutable.set_clone(cx, value);
+            }
+        });
+
+        // Mutable to component.
+        self.create_effect(move |cx| {
+            let value = mutable.get_clone(cx);
+            // The target may have been despawned, or may not have the component yet.
+            let Some(mut component) = cx.world_mut().get_mut::<C>(target) else {
+                return;
+            };
+            // Compare through a shared reference, so that the component is only marked as
+            // changed when it is actually written.
+            if *field(&*component) != value {
+                *field_mut(&mut *component) = value;
+            }
+        });
+    }
+
+    /// Create an effect with a debug name, which is shown in reaction traces. Otherwise
+    /// this is the same as [`create_effect`](Self::create_effect).
+    fn create_named_effect<F: Send + Sync + 'static + FnMut(&mut Cx<()>)>(
+        &mut self,
+        name: &str,
+        effect: F,
+    ) {
+        let ticks = self.world_mut().read_change_tick();
+        let action = Arc::new(Mutex::new(effect));
+        let mut scope = TrackingScope::new(ticks);
+        action.lock().unwrap()(&mut Cx::new((), self.world_mut(), &mut scope));
+        let entity = self
+            .world_mut()
+            .spawn((
+                scope,
+                ReactionHandle(action.clone()),
+                Name::new(name.to_string()),
+            ))
+            .id();
+        self.add_owned(entity);
+    }
+
+    /// Create an effect which runs after UI layout, rather than before it. Unlike
+    /// [`create_effect`](Self::create_effect), the effect is not run immediately; its first
+    /// run happens after the next layout pass. Use this for measuring the computed geometry
+    /// of nodes, for example to position a popover relative to its anchor. Note that changes
+    /// made to styles by the effect won't be laid out until the following frame.
+    ///
+    /// Arguments:
+    /// * `effect` - The function that computes the output. This will be called with a single
+    ///    parameter, which is a [`Cx`] object.
+    fn create_deferred_effect<F: Send + Sync + 'static + FnMut(&mut Cx<()>)>(&mut self, effect: F) {
+        let ticks = self.world_mut().read_change_tick();
+        let entity = self
+            .world_mut()
+            .spawn((
+                TrackingScope::new(ticks),
+                ReactionHandle::new(effect),
+                DeferredReaction::default(),
+            ))
+            .id();
+        self.add_owned(entity);
+    }
+
+    /// Register a cleanup function for the current tracking scope. This will be called when
+    /// the scope is despawned, or (if it's an effect) just before the effect is re-run. Use
+    /// this to tear down listeners, timers or auxiliary entities created by the effect.
+    ///
+    /// Arguments:
+    /// * `cleanup` - The function that performs the cleanup.
+    fn on_cleanup<F: Send + Sync + 'static + FnOnce(&mut World)>(&self, cleanup: F);
 }
 
 impl<F: Send + Sync + 'static + FnMut(&mut Cx<()>)> Reaction for F {
@@ -228,6 +512,15 @@ pub struct Cx<'p, 'w, Props = ()> {
 
     /// Set of reactive resources referenced by the presenter.
     pub(crate) tracking: RefCell<&'p mut TrackingScope>,
+
+    /// The view entity that this context belongs to, used for looking up context values.
+    owner: Option<Entity>,
+
+    /// The entity and key under which the presenter's persistent local state is stored.
+    local_state: Option<(Entity, LocalStateKey)>,
+
+    /// Index of the next local state slot.
+    local_state_slot: usize,
 }
 
 impl<'p, 'w, Props> Cx<'p, 'w, Props> {
@@ -236,6 +529,70 @@ impl<'p, 'w, Props> Cx<'p, 'w, Props> {
             props,
             world,
             tracking: RefCell::new(tracking),
+            owner: None,
+            local_state: None,
+            local_state_slot: 0,
+        }
+    }
+
+    /// Set the view entity that this context belongs to.
+    pub(crate) fn with_owner(mut self, owner: Entity) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    /// Set the entity on which persistent local state is stored, and the key which
+    /// identifies the presenter instance.
+    pub(crate) fn with_local_state(mut self, anchor: Entity, key: LocalStateKey) -> Self {
+        self.local_state = Some((anchor, key));
+        self
+    }
+
+    /// Return a [`Mutable`] holding local state which persists when the presenter is rebuilt
+    /// by its parent, such as an expanded flag or a scroll offset. State is matched to calls
+    /// by order, so like other hooks, `use_state` must be called the same number of times, in
+    /// the same order, each time the presenter runs. The `init` function is only called the
+    /// first time.
+    ///
+    /// Outside of a presenter bound with [`PresenterFn::bind`](crate::PresenterFn::bind),
+    /// this is equivalent to [`create_mutable`](RunContextSetup::create_mutable).
+    pub fn use_state<T: Send + Sync + 'static>(&mut self, init: impl FnOnce() -> T) -> Mutable<T> {
+        let Some((anchor, key)) = self.local_state else {
+            return self.create_mutable(init());
+        };
+        let slot = self.local_state_slot;
+        self.local_state_slot += 1;
+
+        let existing = self
+            .world
+            .get::<LocalStateSlots>(anchor)
+            .and_then(|slots| slots.0.get(&(key, slot)).copied())
+            .filter(|id| {
+                self.world
+                    .get::<MutableCell>(*id)
+                    .is_some_and(|cell| cell.0.is::<T>())
+            });
+        let id = match existing {
+            Some(id) => id,
+            None => {
+                let id = self.world.spawn(MutableCell(Box::new(init()))).id();
+                let mut anchor = self.world.entity_mut(anchor);
+                match anchor.get_mut::<LocalStateSlots>() {
+                    Some(mut slots) => {
+                        slots.0.insert((key, slot), id);
+                    }
+                    None => {
+                        let mut slots = LocalStateSlots::default();
+                        slots.0.insert((key, slot), id);
+                        anchor.insert(slots);
+                    }
+                }
+                id
+            }
+        };
+        Mutable {
+            id,
+            marker: PhantomData,
         }
     }
 
@@ -256,34 +613,96 @@ impl<'p, 'w, Props> Cx<'p, 'w, Props> {
         entity
     }
 
-    /// Return a reference to the Component `C` on the given entity.
+    /// Spawn an auxiliary [`Entity`] with the given bundle, such as a render target camera or
+    /// an audio sink. The entity is owned by the current tracking scope, and will be despawned
+    /// when the view is razed. Note that only the entity itself is despawned, not its children.
+    pub fn create_owned_entity_with<B: Bundle>(&mut self, bundle: B) -> Entity {
+        let entity = self.world_mut().spawn(bundle).id();
+        self.tracking.borrow_mut().add_owned(entity);
+        entity
+    }
+
+    /// Return a reference to the Component `C` on the given entity. Calling this function
+    /// adds the component as a dependency of the current tracking scope; the scope will react
+    /// when the component is changed, inserted or removed. Returns `None` if the entity does
+    /// not exist or does not have the component.
     pub fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
-        let component = self
-            .world
-            .components()
-            .component_id::<C>()
-            .expect("Unknown component type");
-
-        match self.world.get_entity(entity) {
-            Some(c) => {
-                self.tracking
-                    .borrow_mut()
-                    .track_component_id(entity, component);
-                c.get::<C>()
-            }
-            None => None,
+        self.tracking
+            .borrow_mut()
+            .track_component::<C>(entity, self.world);
+        self.world.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    /// Return a reference to the context value of type `T` provided by the nearest ancestor
+    /// view (see [`Element::provide`]). Returns `None` if no ancestor provides a value of
+    /// that type, or if this context is not associated with a view. Calling this function
+    /// adds the context value as a dependency of the current tracking scope.
+    pub fn use_context<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        let provider = find_context::<T>(self.world, self.owner?)?;
+        self.tracking
+            .borrow_mut()
+            .track_component::<ContextValue<T>>(provider, self.world);
+        self.world.get::<ContextValue<T>>(provider).map(|c| &c.0)
+    }
+
+    /// Returns the logical size of the viewport in which this view is displayed: that of the
+    /// camera given to the enclosing [`ViewRoot`](crate::ViewRoot) with `with_camera()`, or
+    /// else the primary window. The current scope will react when the viewport is resized.
+    /// In split-screen layouts, this is the size of the player's portion of the window.
+    pub fn use_viewport_size(&self) -> Vec2 {
+        let camera = self
+            .owner
+            .and_then(|owner| ViewRoot::find_target(self.world, owner).0);
+        match camera {
+            Some(camera) => self
+                .use_component_field(camera, |camera: &Camera| camera.logical_viewport_size())
+                .flatten()
+                .unwrap_or(Vec2::ZERO),
+            None => self.use_window_size(),
         }
     }
 
-    // /// Return a reference to the Component `C` on the given entity. This version does not
-    // /// add the component to the tracking scope, and is intended for components that update
-    // /// frequently.
-    // pub fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C> {
-    //     match self.bc.world.get_entity(entity) {
-    //         Some(c) => c.get::<C>(),
-    //         None => None,
-    //     }
-    // }
+    /// Register a dependency with the nearest enclosing [`Suspense`](crate::Suspense). The
+    /// suspense renders its fallback until `ready` becomes true. Does nothing if this view is
+    /// not within a suspense.
+    pub fn suspend_until(&mut self, ready: Signal<bool>) {
+        let Some(boundary) = self
+            .owner
+            .and_then(|owner| find_context::<SuspenseBoundary>(self.world, owner))
+        else {
+            return;
+        };
+        if let Some(mut deps) = self.world.get_mut::<SuspenseDeps>(boundary) {
+            deps.0.push(ready);
+        }
+    }
+
+    /// Register an asset with the nearest enclosing [`Suspense`](crate::Suspense), which
+    /// renders its fallback until the asset has been loaded.
+    pub fn suspend_until_loaded<A: Asset>(&mut self, handle: &Handle<A>) {
+        let id = handle.id();
+        let ready = self.create_derived(move |cx| cx.use_resource::<Assets<A>>().contains(id));
+        self.suspend_until(ready);
+    }
+
+    /// Run a query against the world, returning all of the matching rows. Calling this
+    /// function adds the query results as a dependency of the current tracking scope; the
+    /// scope will react when an entity starts or stops matching the query, or when any
+    /// component read by the query is changed on a matching entity.
+    ///
+    /// Because constructing a query requires mutable access to the world, this is only
+    /// available on [`Cx`], not on the read-only [`Rcx`].
+    pub fn use_query<D: ReadOnlyQueryData, F: QueryFilter + 'static>(
+        &mut self,
+    ) -> Vec<ROQueryItem<'_, D>> {
+        let mut state = self.world.query_filtered::<D, F>();
+        let entities = self.world.query_filtered::<Entity, F>();
+        let world: &World = self.world;
+        self.tracking
+            .borrow_mut()
+            .track_query(entities, state.component_access(), world);
+        state.iter(world).collect()
+    }
 
     // /// Return a reference to the Component `C` on the entity that contains the current
     // /// presenter invocation.
@@ -446,11 +865,63 @@ impl<'p, 'w, Props> RunContextRead for Cx<'p, 'w, Props> {
         self.world.resource::<T>()
     }
 
+    fn use_resource_field<
+        R: Resource,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        &self,
+        f: F,
+    ) -> &T {
+        let value = f(self.world.resource::<R>());
+        self.tracking.borrow_mut().track_resource_field(value, f);
+        value
+    }
+
     fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
         self.tracking
             .borrow_mut()
             .track_component::<C>(entity, self.world);
-        self.world.entity(entity).get::<C>()
+        self.world.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.world.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    fn use_component_field<
+        C: Component,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> T + Send + Sync + 'static,
+    >(
+        &self,
+        entity: Entity,
+        f: F,
+    ) -> Option<T> {
+        let mut tracking = self.tracking.borrow_mut();
+        match self.world.get_entity(entity).and_then(|e| e.get::<C>()) {
+            Some(component) => {
+                let value = f(component);
+                tracking.track_component_field(entity, value.clone(), f);
+                Some(value)
+            }
+            None => {
+                // React if the component is inserted later.
+                tracking.track_component::<C>(entity, self.world);
+                None
+            }
+        }
+    }
+
+    fn use_resource_when<R: Resource + Clone, F: Fn(&R, &R) -> bool + Send + Sync + 'static>(
+        &self,
+        predicate: F,
+    ) -> &R {
+        let value = self.world.resource::<R>();
+        self.tracking
+            .borrow_mut()
+            .track_resource_when(value.clone(), predicate);
+        value
     }
 }
 
@@ -468,6 +939,10 @@ impl<'p, 'w, Props> RunContextSetup<'p> for Cx<'p, 'w, Props> {
     fn add_owned(&self, entity: Entity) {
         self.tracking.borrow_mut().add_owned(entity);
     }
+
+    fn on_cleanup<F: Send + Sync + 'static + FnOnce(&mut World)>(&self, cleanup: F) {
+        self.tracking.borrow_mut().add_cleanup(Box::new(cleanup));
+    }
 }
 
 /// Immutable reactive context, used for reactive closures such as derived signals.
@@ -562,11 +1037,63 @@ impl<'p, 'w> RunContextRead for Rcx<'p, 'w> {
         self.world.resource::<T>()
     }
 
+    fn use_resource_field<
+        R: Resource,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        &self,
+        f: F,
+    ) -> &T {
+        let value = f(self.world.resource::<R>());
+        self.tracking.borrow_mut().track_resource_field(value, f);
+        value
+    }
+
     fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
         self.tracking
             .borrow_mut()
             .track_component::<C>(entity, self.world);
-        self.world.entity(entity).get::<C>()
+        self.world.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.world.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    fn use_component_field<
+        C: Component,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> T + Send + Sync + 'static,
+    >(
+        &self,
+        entity: Entity,
+        f: F,
+    ) -> Option<T> {
+        let mut tracking = self.tracking.borrow_mut();
+        match self.world.get_entity(entity).and_then(|e| e.get::<C>()) {
+            Some(component) => {
+                let value = f(component);
+                tracking.track_component_field(entity, value.clone(), f);
+                Some(value)
+            }
+            None => {
+                // React if the component is inserted later.
+                tracking.track_component::<C>(entity, self.world);
+                None
+            }
+        }
+    }
+
+    fn use_resource_when<R: Resource + Clone, F: Fn(&R, &R) -> bool + Send + Sync + 'static>(
+        &self,
+        predicate: F,
+    ) -> &R {
+        let value = self.world.resource::<R>();
+        self.tracking
+            .borrow_mut()
+            .track_resource_when(value.clone(), predicate);
+        value
     }
 }
 
@@ -635,9 +1162,10 @@ impl WriteMutable for World {
         let mut mutable_entity = self.entity_mut(mutable);
         if let Some(mut next) = mutable_entity.get_mut::<MutableNextCell>() {
             *next.0.as_mut().unwrap().downcast_mut::<T>().unwrap() = value;
+            record_mutable_write(self, mutable);
         } else if let Some(current_value) = mutable_entity.get_mut::<MutableCell>() {
             if *current_value.0.downcast_ref::<T>().unwrap() != value {
-                mutable_entity.insert(MutableNextCell(Some(Box::new(value))));
+                MutableNextCell::set(self, mutable, value);
             }
         }
     }
@@ -651,9 +1179,10 @@ impl WriteMutable for World {
         let mut mutable_entity = self.entity_mut(mutable);
         if let Some(mut next) = mutable_entity.get_mut::<MutableNextCell>() {
             *next.0.as_mut().unwrap().downcast_mut::<T>().unwrap() = value;
+            record_mutable_write(self, mutable);
         } else if let Some(current_value) = mutable_entity.get_mut::<MutableCell>() {
             if *current_value.0.downcast_ref::<T>().unwrap() != value {
-                mutable_entity.insert(MutableNextCell(Some(Box::new(value.clone()))));
+                MutableNextCell::set(self, mutable, value);
             }
         }
     }
@@ -745,8 +1274,42 @@ impl RunContextRead for World {
         self.resource::<T>()
     }
 
+    fn use_resource_field<
+        R: Resource,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        &self,
+        f: F,
+    ) -> &T {
+        f(self.resource::<R>())
+    }
+
     fn use_component<C: Component>(&self, entity: Entity) -> Option<&C> {
-        self.entity(entity).get::<C>()
+        self.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    fn use_component_untracked<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.get_entity(entity).and_then(|e| e.get::<C>())
+    }
+
+    fn use_component_field<
+        C: Component,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&C) -> T + Send + Sync + 'static,
+    >(
+        &self,
+        entity: Entity,
+        f: F,
+    ) -> Option<T> {
+        self.get_entity(entity).and_then(|e| e.get::<C>()).map(f)
+    }
+
+    fn use_resource_when<R: Resource + Clone, F: Fn(&R, &R) -> bool + Send + Sync + 'static>(
+        &self,
+        _predicate: F,
+    ) -> &R {
+        self.resource::<R>()
     }
 }
 
@@ -755,3 +1318,58 @@ impl RunContextWrite for World {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    #[test]
+    fn test_bind_component() {
+        let mut harness = TestHarness::new();
+        let world = harness.world_mut();
+        let target = world.spawn_empty().id();
+        let translation = {
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            let mut cx = Cx::new((), world, &mut scope);
+            let translation = cx.create_mutable(Vec3::ONE);
+            cx.bind_component(
+                translation,
+                target,
+                |t: &Transform| &t.translation,
+                |t| &mut t.translation,
+            );
+            translation
+        };
+
+        // Nothing is written while the target doesn't have the component.
+        harness.set(translation, Vec3::X);
+        assert!(harness.world().get::<Transform>(target).is_none());
+
+        // When the component is inserted, its value is copied into the mutable.
+        harness
+            .world_mut()
+            .entity_mut(target)
+            .insert(Transform::from_xyz(0., 2., 0.));
+        harness.update_frames(2);
+        assert_eq!(translation.get(harness.world()), Vec3::Y * 2.);
+
+        // Changes to the mutable are written to the component.
+        harness.set(translation, Vec3::Z);
+        harness.update();
+        assert_eq!(
+            harness
+                .world()
+                .get::<Transform>(target)
+                .unwrap()
+                .translation,
+            Vec3::Z
+        );
+
+        // Writing after the target is despawned is ignored.
+        harness.world_mut().despawn(target);
+        harness.set(translation, Vec3::X);
+        harness.update();
+        assert_eq!(translation.get(harness.world()), Vec3::X);
+    }
+}
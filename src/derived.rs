@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
 use bevy::prelude::*;
+use impl_trait_for_tuples::*;
 
-use crate::{Rcx, TrackingScope};
+use crate::{Rcx, Signal, TrackingScope};
 
 pub(crate) trait DerivedFnRef<R> {
     fn call(&self, cx: &mut Rcx) -> R;
@@ -32,6 +33,30 @@ impl<T> Clone for Derived<T> {
     }
 }
 
+/// A set of signals which can be declared as the explicit dependencies of a derived signal;
+/// see [`create_derived_with_deps`](crate::RunContextSetup::create_derived_with_deps).
+/// Implemented for single signals and tuples of signals.
+pub trait SignalDeps: Send + Sync + 'static {
+    /// Add each of the signals to the tracking scope of `cx`.
+    fn track(&self, cx: &Rcx);
+}
+
+impl<T: Send + Sync + 'static> SignalDeps for Signal<T> {
+    fn track(&self, cx: &Rcx) {
+        // Reading a derived signal is what tracks its own dependencies.
+        self.map(cx, |_| ());
+    }
+}
+
+#[impl_for_tuples(1, 16)]
+impl SignalDeps for Tuple {
+    for_tuples!( where #( Tuple: SignalDeps )* );
+
+    fn track(&self, cx: &Rcx) {
+        for_tuples!( #( self.Tuple.track(cx); )* );
+    }
+}
+
 /// An immutable reactive context, used for reactive closures such as derived signals.
 pub trait ReadDerived {
     /// Read the value of a derived signal using Copy semantics. This adds any dependencies of
@@ -78,3 +103,42 @@ pub(crate) trait ReadDerivedInternal {
     where
         R: Send + Sync + 'static;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cx, RunContextSetup};
+
+    #[test]
+    fn test_derived_with_deps() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let (a, b, sum) = {
+            let mut cx = Cx::new((), &mut world, &mut owner);
+            let a = cx.create_mutable::<i32>(1);
+            let b = cx.create_mutable::<i32>(2);
+            let sum = cx.create_derived_with_deps(a.signal(), move |cx| a.get(cx) + b.get(cx));
+            (a, b, sum)
+        };
+
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            assert_eq!(sum.get(&cx), 3);
+        }
+        assert!(!scope.dependencies_changed(&world));
+
+        // Reads inside the closure body are not tracked.
+        world.increment_change_tick();
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        b.set(&mut cx, 5);
+        crate::mutable::commit_mutables(&mut world);
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changing a declared dependency should trigger.
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        a.set(&mut cx, 5);
+        crate::mutable::commit_mutables(&mut world);
+        assert!(scope.dependencies_changed(&world));
+    }
+}
@@ -0,0 +1,97 @@
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{DespawnScopes, DisplayNodeChanged, Rcx, TrackingScope, View, ViewHandle};
+
+/// A view whose content is produced by a reactive closure. Whenever the dependencies of the
+/// closure change, it is called again, and the previous child view is replaced with the newly
+/// produced one. This is useful where the set of possible views is open-ended, such as routing
+/// between whole screens; when choosing between a fixed set of branches, prefer [`Cond`] or
+/// [`Switch`], which only rebuild when the chosen branch changes.
+///
+/// [`Cond`]: crate::Cond
+/// [`Switch`]: crate::Switch
+pub struct Dynamic<V: Into<ViewHandle>, F: Fn(&Rcx) -> V> {
+    view_fn: F,
+    state: Option<(ViewHandle, Entity)>,
+}
+
+impl<V: Into<ViewHandle>, F: Fn(&Rcx) -> V> Dynamic<V, F> {
+    /// Construct a new dynamic view from a closure which produces the child view.
+    pub fn new(view_fn: F) -> Self {
+        Self {
+            view_fn,
+            state: None,
+        }
+    }
+}
+
+impl<V: Into<ViewHandle>, F: Fn(&Rcx) -> V> View for Dynamic<V, F> {
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world.entity_mut(view_entity).insert(Name::new("Dynamic"));
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let view: ViewHandle = (self.view_fn)(&Rcx::new(world, tracking)).into();
+        if let Some((prev, entity)) = self.state.take() {
+            prev.raze(entity, world);
+        }
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.state = Some((view, entity));
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<V: 'static + Into<ViewHandle>, F: Send + Sync + 'static + Fn(&Rcx) -> V> From<Dynamic<V, F>>
+    for ViewHandle
+{
+    fn from(value: Dynamic<V, F>) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup};
+
+    fn router(cx: &mut Cx) -> impl View {
+        let screen = cx.create_mutable::<i32>(0);
+        cx.register_signal("screen", screen.signal());
+        Dynamic::new(move |cx| match screen.get(cx) {
+            0 => ViewHandle::from("Home"),
+            n => format!("Page {}", n).into(),
+        })
+    }
+
+    #[test]
+    fn test_dynamic() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(router.bind(()));
+        assert_eq!(harness.text(root), "Home");
+
+        let screen = harness.mutable::<i32>("screen");
+        harness.set(screen, 2);
+        assert_eq!(harness.text(root), "Page 2");
+        harness.set(screen, 0);
+        assert_eq!(harness.text(root), "Home");
+    }
+}
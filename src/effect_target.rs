@@ -1,8 +1,14 @@
-use std::sync::{Arc, Mutex};
+use std::{
+    panic::Location,
+    sync::{Arc, Mutex},
+};
 
 use bevy::ecs::{bundle::Bundle, entity::Entity, world::World};
 
-use crate::{tracking_scope::TrackingScope, Cx, Rcx, Reaction, ReactionHandle, ReactionTarget};
+use crate::{
+    reaction::DeferredReaction, tracking_scope::TrackingScope, write_conflicts::EffectLocation, Cx,
+    Rcx, Reaction, ReactionHandle, ReactionTarget, Signal,
+};
 
 /// A reactive effect that modifies a target entity.
 pub trait EntityEffect: Sync + Send {
@@ -24,8 +30,17 @@ where
     /// Add a reactive effct to the element.
     fn add_effect(&mut self, effect: Box<dyn EntityEffect>);
 
+    /// Add an effect which is started before the element's other effects, regardless of the
+    /// order in which they were added. This is for effects which set state that other
+    /// effects read when they start, such as style classes. Early effects are started in the
+    /// order in which they were added.
+    fn add_early_effect(&mut self, effect: Box<dyn EntityEffect>) {
+        self.add_effect(effect);
+    }
+
     /// Add a reaction to the element. This is a convenience method for adding a reactive
     /// effect that is already in the form of a `Reaction`.
+    #[track_caller]
     fn add_reaction<R: Reaction + Send + Sync + 'static>(&mut self, reaction: R) {
         self.add_effect(Box::new(RunReactionEffect::new(reaction)));
     }
@@ -63,6 +78,7 @@ where
     }
 
     /// Create a reactive effect which is attached to the element.
+    #[track_caller]
     fn create_effect<F: Send + Sync + 'static + FnMut(&mut Cx, Entity)>(
         mut self,
         effect: F,
@@ -71,6 +87,19 @@ where
         self
     }
 
+    /// Create a reactive effect which is attached to the element, and which runs after UI
+    /// layout. The first run happens after the element has been laid out, so the effect can
+    /// read the computed size and position of the display node.
+    fn create_deferred_effect<F: Send + Sync + 'static + FnMut(&mut Cx, Entity)>(
+        mut self,
+        effect: F,
+    ) -> Self {
+        self.add_effect(Box::new(DeferredReactionEffect::new(UpdateReaction::new(
+            effect,
+        ))));
+        self
+    }
+
     /// Add a static bundle to the element.
     fn insert<T: Bundle>(mut self, bundle: T) -> Self {
         self.add_effect(Box::new(InsertBundleEffect {
@@ -79,17 +108,32 @@ where
         self
     }
 
-    /// Add a static bundle to the element, if a condition is true.
-    fn insert_if<T: Bundle>(mut self, cond: bool, bundle: T) -> Self {
-        if cond {
-            self.add_effect(Box::new(InsertBundleEffect {
-                bundle: Some(bundle),
-            }));
+    /// Add a bundle to the element while a condition is true. The condition can be a constant
+    /// `bool`, a `Signal<bool>`, or a reactive closure; the bundle is inserted, using the
+    /// value returned by `factory`, whenever the condition becomes true, and removed when it
+    /// becomes false. This is useful for toggling behaviors such as `Pickable` or `Outline`.
+    #[track_caller]
+    fn insert_if<T: Bundle, C: InsertCondition, F: Send + Sync + 'static + Fn() -> T>(
+        mut self,
+        cond: C,
+        factory: F,
+    ) -> Self {
+        match cond.constant() {
+            Some(true) => self.add_effect(Box::new(InsertBundleEffect {
+                bundle: Some(factory()),
+            })),
+            Some(false) => {}
+            None => self.add_reaction(ConditionalBundleReaction {
+                cond,
+                factory,
+                inserted: false,
+            }),
         }
         self
     }
 
     /// Add a computed bundle to the element.
+    #[track_caller]
     fn insert_computed<T: Bundle, F: Send + Sync + 'static + FnMut(&mut Rcx) -> T>(
         mut self,
         factory: F,
@@ -99,6 +143,78 @@ where
     }
 }
 
+/// The condition for [`EffectTarget::insert_if`] and [`WithStyles::class`]: a constant `bool`,
+/// a `Signal<bool>`, or a closure which is evaluated reactively.
+///
+/// [`WithStyles::class`]: crate::WithStyles::class
+pub trait InsertCondition: Send + Sync + 'static {
+    /// The value of the condition, if it never changes.
+    fn constant(&self) -> Option<bool> {
+        None
+    }
+
+    /// Evaluate the condition, adding its dependencies to the current tracking scope.
+    fn test(&self, rcx: &Rcx) -> bool;
+}
+
+impl InsertCondition for bool {
+    fn constant(&self) -> Option<bool> {
+        Some(*self)
+    }
+
+    fn test(&self, _rcx: &Rcx) -> bool {
+        *self
+    }
+}
+
+impl InsertCondition for Signal<bool> {
+    fn constant(&self) -> Option<bool> {
+        match self {
+            Signal::Constant(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn test(&self, rcx: &Rcx) -> bool {
+        self.get(rcx)
+    }
+}
+
+impl<F: Send + Sync + 'static + Fn(&Rcx) -> bool> InsertCondition for F {
+    fn test(&self, rcx: &Rcx) -> bool {
+        self(rcx)
+    }
+}
+
+/// Inserts a bundle into the target entity while a condition is true, and removes it when the
+/// condition becomes false.
+pub struct ConditionalBundleReaction<B: Bundle, C: InsertCondition, F: Fn() -> B> {
+    cond: C,
+    factory: F,
+    inserted: bool,
+}
+
+impl<B: Bundle, C: InsertCondition, F: Sync + Send + Fn() -> B> Reaction
+    for ConditionalBundleReaction<B, C, F>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = world.entity(owner).get::<ReactionTarget>().unwrap().0;
+        let cond = self.cond.test(&Rcx::new(world, tracking));
+        if cond == self.inserted {
+            return;
+        }
+        self.inserted = cond;
+        let Some(mut entt) = world.get_entity_mut(target) else {
+            return;
+        };
+        if cond {
+            entt.insert((self.factory)());
+        } else {
+            entt.remove::<B>();
+        }
+    }
+}
+
 /// Inserts a static, pre-constructed bundle into the target entity. No reactivity.
 pub struct InsertBundleEffect<B: Bundle> {
     pub(crate) bundle: Option<B>,
@@ -114,12 +230,15 @@ impl<B: Bundle> EntityEffect for InsertBundleEffect<B> {
 /// Effect that runs a reaction function (reactively).
 pub struct RunReactionEffect<R> {
     reaction: Arc<Mutex<R>>,
+    location: &'static Location<'static>,
 }
 
 impl<R> RunReactionEffect<R> {
+    #[track_caller]
     pub(crate) fn new(reaction: R) -> Self {
         Self {
             reaction: Arc::new(Mutex::new(reaction)),
+            location: Location::caller(),
         }
     }
 }
@@ -139,6 +258,7 @@ impl<R: Reaction + Send + Sync + 'static> EntityEffect for RunReactionEffect<R>
             .spawn((
                 ReactionHandle(self.reaction.clone()),
                 ReactionTarget(target),
+                EffectLocation(self.location),
             ))
             .id();
 
@@ -154,6 +274,34 @@ impl<R: Reaction + Send + Sync + 'static> EntityEffect for RunReactionEffect<R>
     }
 }
 
+/// Effect that runs a reaction function after UI layout.
+pub struct DeferredReactionEffect<R> {
+    reaction: Arc<Mutex<R>>,
+}
+
+impl<R> DeferredReactionEffect<R> {
+    pub(crate) fn new(reaction: R) -> Self {
+        Self {
+            reaction: Arc::new(Mutex::new(reaction)),
+        }
+    }
+}
+
+impl<R: Reaction + Send + Sync + 'static> EntityEffect for DeferredReactionEffect<R> {
+    // The reaction is not run here; it will first run after the next layout pass.
+    fn start(&mut self, target: Entity, world: &mut World, parent_scope: &mut TrackingScope) {
+        let reaction_id = world
+            .spawn((
+                ReactionHandle(self.reaction.clone()),
+                ReactionTarget(target),
+                TrackingScope::new(world.read_change_tick()),
+                DeferredReaction::default(),
+            ))
+            .id();
+        parent_scope.add_owned(reaction_id);
+    }
+}
+
 /// Calls a closure which computes a bundle reactively, returns the bundle as a result.
 /// This is then inserted into the target.
 pub struct ComputedBundleReaction<B: Bundle, F: FnMut(&mut Rcx) -> B> {
@@ -256,3 +404,34 @@ impl<F: FnMut(&mut Cx, Entity)> Reaction for UpdateReaction<F> {
         (self.effect)(&mut cx, target);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{testing::TestHarness, Element, PresenterFn, RegisterSignal, View};
+
+    #[derive(Component)]
+    struct Highlighted;
+
+    fn item(cx: &mut Cx) -> impl View {
+        let selected = cx.create_mutable(false);
+        cx.register_signal("selected", selected.signal());
+        Element::<NodeBundle>::new().insert_if(selected.signal(), || Highlighted)
+    }
+
+    #[test]
+    fn test_insert_if() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(item.bind(()));
+        let element = harness.nodes(root)[0];
+        assert!(harness.world().get::<Highlighted>(element).is_none());
+
+        let selected = harness.mutable::<bool>("selected");
+        harness.set(selected, true);
+        assert!(harness.world().get::<Highlighted>(element).is_some());
+        harness.set(selected, false);
+        assert!(harness.world().get::<Highlighted>(element).is_none());
+    }
+}
@@ -1,13 +1,19 @@
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
 
 use bevy::prelude::*;
 
 use crate::{
+    context::ContextValue,
     effect_target::{EffectTarget, EntityEffect},
+    mount::{LifecycleFn, MountPhase, PendingEffects, PendingMounts, SharedEffects},
     node_span::NodeSpan,
     parent_view::{ChildView, ParentView},
+    pause::DisplayNodeView,
     view::View,
-    DespawnScopes, TrackingScope, ViewHandle,
+    DespawnScopes, ViewHandle,
 };
 
 /// A basic UI element
@@ -26,7 +32,21 @@ where
     children: Vec<ChildView>,
 
     /// List of effects to be added to the element.
-    effects: Vec<Box<dyn EntityEffect>>,
+    effects: SharedEffects,
+
+    /// Number of effects at the start of `effects` which were added with
+    /// [`add_early_effect`](EffectTarget::add_early_effect).
+    early_effects: usize,
+
+    /// Functions to call once the element has been mounted, along with the phase in which
+    /// to call them.
+    on_mount: Vec<(MountPhase, LifecycleFn)>,
+
+    /// Functions to call when the element is razed.
+    on_unmount: Vec<LifecycleFn>,
+
+    /// Functions which insert context values into the view entity.
+    providers: Vec<LifecycleFn>,
 
     marker: PhantomData<B>,
 }
@@ -38,7 +58,11 @@ impl<B: Bundle + Default> Element<B> {
             debug_name: String::new(),
             display: None,
             children: Vec::new(),
-            effects: Vec::new(),
+            effects: Arc::new(Mutex::new(Vec::new())),
+            early_effects: 0,
+            on_mount: Vec::new(),
+            on_unmount: Vec::new(),
+            providers: Vec::new(),
             marker: PhantomData,
         }
     }
@@ -49,7 +73,11 @@ impl<B: Bundle + Default> Element<B> {
             debug_name: String::new(),
             display: Some(node),
             children: Vec::new(),
-            effects: Vec::new(),
+            effects: Arc::new(Mutex::new(Vec::new())),
+            early_effects: 0,
+            on_mount: Vec::new(),
+            on_unmount: Vec::new(),
+            providers: Vec::new(),
             marker: PhantomData,
         }
     }
@@ -60,6 +88,48 @@ impl<B: Bundle + Default> Element<B> {
         self
     }
 
+    /// Register a function to be called once the element's display node and all of its
+    /// children have been built, for one-time setup such as setting focus or starting an
+    /// animation. Unlike an effect, this is not reactive. The function is passed the
+    /// display entity. A parent's mount functions are called before those of its children.
+    pub fn on_mount<F: Fn(&mut World, Entity) + Send + Sync + 'static>(self, f: F) -> Self {
+        self.on_mount_phase(MountPhase::Build, f)
+    }
+
+    /// Like [`on_mount`](Self::on_mount), but allows choosing the [`MountPhase`] in which the
+    /// function is called. Use [`MountPhase::Layout`] to measure the display node.
+    pub fn on_mount_phase<F: Fn(&mut World, Entity) + Send + Sync + 'static>(
+        mut self,
+        phase: MountPhase,
+        f: F,
+    ) -> Self {
+        self.on_mount.push((phase, Arc::new(f)));
+        self
+    }
+
+    /// Register a function to be called when the element is razed. This is called after the
+    /// element's children have been razed, but while the display node still exists. The
+    /// function is passed the display entity.
+    pub fn on_unmount<F: Fn(&mut World, Entity) + Send + Sync + 'static>(mut self, f: F) -> Self {
+        self.on_unmount.push(Arc::new(f));
+        self
+    }
+
+    /// Provide a context value to all descendant views, which they can retrieve using
+    /// [`Cx::use_context`]. Values are resolved through the view hierarchy, so a descendant
+    /// will see the value provided by its nearest ancestor. To provide a value that changes
+    /// over time, provide a signal or mutable.
+    ///
+    /// [`Cx::use_context`]: crate::Cx::use_context
+    pub fn provide<T: Clone + Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.providers.push(Arc::new(move |world, view_entity| {
+            world
+                .entity_mut(view_entity)
+                .insert(ContextValue(value.clone()));
+        }));
+        self
+    }
+
     // pub fn insert_computed_ref<
     //     T: Component,
     //     F1: Send + Sync + 'static + FnMut() -> T,
@@ -91,7 +161,15 @@ impl<B: Bundle + Default> Element<B> {
 
 impl<B: Bundle + Default> EffectTarget for Element<B> {
     fn add_effect(&mut self, effect: Box<dyn EntityEffect>) {
-        self.effects.push(effect);
+        self.effects.lock().unwrap().push(effect);
+    }
+
+    fn add_early_effect(&mut self, effect: Box<dyn EntityEffect>) {
+        self.effects
+            .lock()
+            .unwrap()
+            .insert(self.early_effects, effect);
+        self.early_effects += 1;
     }
 }
 
@@ -115,31 +193,43 @@ impl<B: Bundle + Default> View for Element<B> {
 
     fn build(&mut self, view_entity: Entity, world: &mut World) {
         world.entity_mut(view_entity).insert(Name::new("Element"));
+        let outermost = PendingEffects::begin(world);
+
+        // Context values must be present before any descendants are built.
+        for provider in self.providers.iter() {
+            provider(world, view_entity);
+        }
 
         // Build display entity if it doesn't already exist.
         let display = match self.display {
             Some(display) => {
-                world
-                    .entity_mut(display)
-                    .insert((B::default(), Name::new(self.debug_name.clone())));
+                world.entity_mut(display).insert((
+                    B::default(),
+                    Name::new(self.debug_name.clone()),
+                    DisplayNodeView(view_entity),
+                ));
                 display
             }
             None => {
                 let entity = world
-                    .spawn((B::default(), Name::new(self.debug_name.clone())))
+                    .spawn((
+                        B::default(),
+                        Name::new(self.debug_name.clone()),
+                        DisplayNodeView(view_entity),
+                    ))
                     .id();
                 self.display = Some(entity);
                 entity
             }
         };
 
-        // Insert components from effects.
-        if !self.effects.is_empty() {
-            let mut tracking = TrackingScope::new(world.read_change_tick());
-            for effect in self.effects.iter_mut() {
-                effect.start(display, world, &mut tracking);
-            }
-            world.entity_mut(view_entity).insert(tracking);
+        // Queue effects and mount functions before building children, so that they run
+        // before the children's effects and mount functions.
+        if !self.effects.lock().unwrap().is_empty() {
+            PendingEffects::push(world, view_entity, display, self.effects.clone());
+        }
+        for (phase, on_mount) in self.on_mount.iter() {
+            PendingMounts::push(world, *phase, display, on_mount.clone());
         }
 
         // Build child nodes.
@@ -148,11 +238,21 @@ impl<B: Bundle + Default> View for Element<B> {
         }
 
         self.attach_children(world);
+
+        // Start the queued effects once the whole hierarchy has been built and attached, so
+        // that effects never observe a partially-built subtree.
+        if outermost {
+            PendingEffects::finish(world);
+        }
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
         assert!(self.display.is_some());
+        // Children are razed before the parent.
         self.raze_children(world);
+        for on_unmount in self.on_unmount.iter() {
+            on_unmount(world, self.display.unwrap());
+        }
 
         // Delete the display node.
         world.entity_mut(self.display.unwrap()).remove_parent();
@@ -0,0 +1,378 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::mount::PendingEffects;
+use crate::node_span::NodeSpan;
+use crate::{
+    DespawnScopes, DisplayNodeChanged, Mutable, TrackingScope, View, ViewHandle, WriteMutable,
+};
+
+/// Function which produces the fallback view from an error message.
+type FallbackFn = Arc<dyn Fn(&str) -> ViewHandle + Send + Sync>;
+
+/// Component on the view entity of an [`ErrorBoundary`] whose child was built successfully,
+/// holding the child's view entity. Panics in reactions owned by the child are routed to the
+/// boundary.
+#[derive(Component)]
+struct BoundaryChild(Entity);
+
+/// Component inserted on the view entity of an [`ErrorBoundary`] when one of its child's
+/// reactions panics, holding the panic message.
+#[derive(Component)]
+struct BoundaryError(String);
+
+/// A view which catches failures in its child. If building the child panics, for example
+/// because `ViewFactory::create` or a derived computation evaluated during construction
+/// panicked, the partially-built child is torn down and the fallback view is rendered in its
+/// place, rather than taking down the whole app. The same happens if one of the child's
+/// reactions panics after it has been built; in that case the child is razed, and the
+/// fallback is shown from the following frame.
+///
+/// Once the fallback is shown, panics in its reactions are passed on to the next enclosing
+/// boundary, if any.
+///
+/// Panics can only be caught when they unwind. When they abort instead, which is the default
+/// for `wasm32-unknown-unknown`, the boundary has no effect.
+pub struct ErrorBoundary {
+    child: ViewHandle,
+    fallback: FallbackFn,
+    error: Option<Mutable<Option<String>>>,
+    state: Option<(ViewHandle, Entity)>,
+}
+
+impl ErrorBoundary {
+    /// Construct a new `ErrorBoundary` around the given child view. By default, nothing is
+    /// rendered if the child fails.
+    pub fn new(child: impl Into<ViewHandle>) -> Self {
+        Self {
+            child: child.into(),
+            fallback: Arc::new(|_| ViewHandle::default()),
+            error: None,
+            state: None,
+        }
+    }
+
+    /// Set the view which is rendered if the child fails. The function is passed the error
+    /// message.
+    pub fn with_fallback<V: Into<ViewHandle>, F: Fn(&str) -> V + Send + Sync + 'static>(
+        mut self,
+        fallback: F,
+    ) -> Self {
+        self.fallback = Arc::new(move |message| fallback(message).into());
+        self
+    }
+
+    /// Set a mutable which receives the error message if the child fails. This allows other
+    /// parts of the UI to react to the failure, for example to show a status indicator.
+    pub fn with_error(mut self, error: Mutable<Option<String>>) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Replace the failed child with the fallback view.
+    fn show_fallback(&mut self, view_entity: Entity, world: &mut World, message: String) {
+        world.entity_mut(view_entity).remove::<BoundaryChild>();
+        if let Some(error) = self.error {
+            world.write_mutable_clone(error.id(), Some(message.clone()));
+        }
+        let fallback = (self.fallback)(&message);
+        let entity = ViewHandle::spawn(&fallback, view_entity, world);
+        self.state = Some((fallback, entity));
+    }
+}
+
+/// Extract a message from a panic payload.
+#[cfg(panic = "unwind")]
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "view construction panicked".to_string()
+    }
+}
+
+/// Run `f`, returning the panic message if it panics.
+#[cfg(panic = "unwind")]
+fn catch_panic(f: impl FnOnce()) -> Result<(), String> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f))
+        .map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Run `f`. Panics abort the process, so there is nothing to catch.
+#[cfg(not(panic = "unwind"))]
+fn catch_panic(f: impl FnOnce()) -> Result<(), String> {
+    f();
+    Ok(())
+}
+
+/// Returns true if `entity` is `root`, or is reachable from it through child views and
+/// owned entities such as effects.
+#[cfg(panic = "unwind")]
+fn owns(world: &World, root: Entity, entity: Entity) -> bool {
+    let mut stack = vec![root];
+    while let Some(next) = stack.pop() {
+        if next == entity {
+            return true;
+        }
+        let Some(entt) = world.get_entity(next) else {
+            continue;
+        };
+        if let Some(children) = entt.get::<Children>() {
+            stack.extend(children.iter().copied());
+        }
+        if let Some(scope) = entt.get::<TrackingScope>() {
+            stack.extend_from_slice(scope.owned());
+        }
+    }
+    false
+}
+
+/// Find the innermost error boundary whose child owns the given reaction. This is only
+/// needed when a reaction panics, so it searches the boundaries rather than having every
+/// reaction keep track of its boundary.
+#[cfg(panic = "unwind")]
+fn find_boundary(world: &mut World, scope_entity: Entity) -> Option<Entity> {
+    let mut query = world.query::<(Entity, &BoundaryChild)>();
+    let world: &World = world;
+    let boundaries: Vec<(Entity, Entity)> = query
+        .iter(world)
+        .map(|(boundary, child)| (boundary, child.0))
+        .filter(|(_, child)| owns(world, *child, scope_entity))
+        .collect();
+    // Enclosing boundaries own the inner ones, so the innermost boundary is the one whose
+    // child owns none of the others.
+    boundaries
+        .iter()
+        .find(|(boundary, child)| {
+            !boundaries
+                .iter()
+                .any(|(other, _)| other != boundary && owns(world, *child, *other))
+        })
+        .map(|(boundary, _)| *boundary)
+}
+
+/// Run a reaction, routing a panic to the innermost [`ErrorBoundary`] which owns the
+/// reaction. Panics in reactions outside of any boundary are resumed.
+#[cfg(panic = "unwind")]
+pub(crate) fn catch_reaction_panic<R: ?Sized>(
+    world: &mut World,
+    scope_entity: Entity,
+    reaction: &Mutex<R>,
+    react: impl FnOnce(&mut R, &mut World),
+) {
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        react(&mut *reaction.lock().unwrap(), world)
+    }));
+    let Err(payload) = result else {
+        return;
+    };
+    // The reaction's state may be inconsistent, but it is about to be razed along with the
+    // rest of the boundary's child, which needs to lock it.
+    reaction.clear_poison();
+    match find_boundary(world, scope_entity) {
+        Some(boundary) => {
+            let message = panic_message(payload.as_ref());
+            error!("Reaction failed: {}", message);
+            world.entity_mut(boundary).insert(BoundaryError(message));
+        }
+        None => std::panic::resume_unwind(payload),
+    }
+}
+
+/// Run a reaction. Panics abort the process, so there is nothing to catch.
+#[cfg(not(panic = "unwind"))]
+pub(crate) fn catch_reaction_panic<R: ?Sized>(
+    world: &mut World,
+    _scope_entity: Entity,
+    reaction: &Mutex<R>,
+    react: impl FnOnce(&mut R, &mut World),
+) {
+    react(&mut *reaction.lock().unwrap(), world)
+}
+
+impl View for ErrorBoundary {
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world
+            .entity_mut(view_entity)
+            .insert(Name::new("ErrorBoundary"));
+        let child = self.child.clone();
+        let entity = world.spawn(child.clone()).set_parent(view_entity).id();
+        let building = PendingEffects::is_building(world);
+        match catch_panic(|| child.build(entity, world)) {
+            Ok(()) => {
+                world.entity_mut(view_entity).insert(BoundaryChild(entity));
+                self.state = Some((child, entity));
+            }
+            Err(message) => {
+                error!("View construction failed: {}", message);
+                // The child's state is unknown, so tear down whatever was built.
+                world.despawn_owned_recursive(entity);
+                if !building && PendingEffects::is_building(world) {
+                    // The build which would have started the child's effects was unwound.
+                    PendingEffects::finish(world);
+                }
+                self.show_fallback(view_entity, world, message);
+            }
+        }
+
+        world.init_component::<BoundaryError>();
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        tracking.track_component::<BoundaryError>(view_entity, world);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let error = world.entity_mut(view_entity).take::<BoundaryError>();
+        tracking.track_component::<BoundaryError>(view_entity, world);
+        let Some(BoundaryError(message)) = error else {
+            return;
+        };
+        if let Some((view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        self.show_fallback(view_entity, world, message);
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, _world: &mut World) -> bool {
+        // The boundary has no display nodes of its own, so the parent view re-attaches the
+        // nodes of the child or fallback.
+        false
+    }
+}
+
+impl From<ErrorBoundary> for ViewHandle {
+    fn from(value: ErrorBoundary) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cx::Cx, testing::TestHarness, text_computed, Element, ParentView, PresenterFn,
+        RegisterSignal, RunContextSetup,
+    };
+
+    struct Panicky;
+
+    impl View for Panicky {
+        fn nodes(&self) -> NodeSpan {
+            NodeSpan::Empty
+        }
+
+        fn build(&mut self, _view_entity: Entity, _world: &mut World) {
+            panic!("boom");
+        }
+
+        fn raze(&mut self, _view_entity: Entity, _world: &mut World) {}
+    }
+
+    #[test]
+    #[cfg(panic = "unwind")]
+    fn test_error_boundary() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let error = Cx::new((), &mut world, &mut scope).create_mutable::<Option<String>>(None);
+        let parent = world.spawn_empty().id();
+
+        let view: ViewHandle = ErrorBoundary::new(ViewHandle::new(Panicky))
+            .with_error(error)
+            .into();
+        ViewHandle::spawn(&view, parent, &mut world);
+        crate::mutable::commit_mutables(&mut world);
+
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        assert_eq!(error.get_clone(&mut cx), Some("boom".to_string()));
+    }
+
+    fn fragile(cx: &mut Cx) -> impl View {
+        let broken = cx.create_mutable(false);
+        let error = cx.create_mutable::<Option<String>>(None);
+        cx.register_signal("broken", broken);
+        cx.register_signal("error", error);
+        ErrorBoundary::new(text_computed(move |cx| {
+            if broken.get(cx) {
+                panic!("broken");
+            }
+            "Fine".to_string()
+        }))
+        .with_error(error)
+        .with_fallback(|message| format!("Failed: {}", message))
+    }
+
+    #[test]
+    #[cfg(panic = "unwind")]
+    fn test_error_boundary_reaction() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(fragile.bind(()));
+        assert_eq!(harness.text(root), "Fine");
+
+        // A panic in the child's reaction replaces the child with the fallback.
+        let broken = harness.mutable::<bool>("broken");
+        harness.set(broken, true);
+        harness.update_frames(2);
+        assert_eq!(harness.text(root), "Failed: broken");
+        assert_eq!(harness.nodes(root).len(), 1);
+        let error = harness.mutable::<Option<String>>("error");
+        assert_eq!(
+            error.get_clone(harness.world_mut()),
+            Some("broken".to_string())
+        );
+    }
+    fn nested(cx: &mut Cx) -> impl View {
+        let broken = cx.create_mutable(false);
+        let outer_error = cx.create_mutable::<Option<String>>(None);
+        cx.register_signal("broken", broken);
+        cx.register_signal("outer_error", outer_error);
+        ErrorBoundary::new(
+            Element::<NodeBundle>::new().with_children((
+                "Outer ",
+                ErrorBoundary::new(text_computed(move |cx| {
+                    if broken.get(cx) {
+                        panic!("broken");
+                    }
+                    "Inner".to_string()
+                }))
+                .with_fallback(|message| format!("Inner failed: {}", message)),
+            )),
+        )
+        .with_error(outer_error)
+        .with_fallback(|message| format!("Outer failed: {}", message))
+    }
+
+    #[test]
+    #[cfg(panic = "unwind")]
+    fn test_error_boundary_nested() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(nested.bind(()));
+        assert_eq!(harness.text(root), "Outer Inner");
+
+        // Only the innermost boundary enclosing the reaction falls back.
+        let broken = harness.mutable::<bool>("broken");
+        harness.set(broken, true);
+        harness.update_frames(2);
+        assert_eq!(harness.text(root), "Outer Inner failed: broken");
+        let outer_error = harness.mutable::<Option<String>>("outer_error");
+        assert_eq!(outer_error.get_clone(harness.world_mut()), None);
+    }
+}
@@ -0,0 +1,66 @@
+use bevy::ecs::{
+    entity::Entity,
+    event::{Event, Events, ManualEventReader},
+    world::World,
+};
+
+use crate::{
+    mutable::{MutableCell, MutableNextCell},
+    signal::Signal,
+    Cx, Reaction, ReactionHandle, RunContextSetup, TrackingScope,
+};
+
+/// Reaction which reads new events from the event stream and publishes them to the
+/// associated mutable.
+pub(crate) struct EventsReaction<E: Event + Clone> {
+    reader: ManualEventReader<E>,
+}
+
+impl<E: Event + Clone> Reaction for EventsReaction<E> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_resource::<Events<E>>(world);
+        let events: Vec<E> = self
+            .reader
+            .read(world.resource::<Events<E>>())
+            .cloned()
+            .collect();
+
+        // Events don't need to be comparable, so we bypass the equality check that
+        // `write_mutable` does. Every batch of new events is a change; an empty batch is only
+        // a change if the previous batch was not empty.
+        let was_empty = world
+            .get::<MutableCell>(owner)
+            .and_then(|cell| cell.0.downcast_ref::<Vec<E>>())
+            .map(|prev| prev.is_empty())
+            .unwrap_or(true);
+        if !events.is_empty() || !was_empty {
+            MutableNextCell::set(world, owner, events);
+        }
+    }
+}
+
+/// Method to create a signal that tracks a stream of Bevy events.
+pub trait CreateEventSignal {
+    /// Signal that returns the events of type `E` that were sent since the previous update.
+    /// The signal changes whenever new events arrive, even if they are identical to the
+    /// previous events, and reverts to an empty list once there are no more new events.
+    /// Events sent before the signal was created are not included.
+    fn create_event_signal<E: Event + Clone>(&mut self) -> Signal<Vec<E>>;
+}
+
+impl<'p, 'w, Props> CreateEventSignal for Cx<'p, 'w, Props> {
+    fn create_event_signal<E: Event + Clone>(&mut self) -> Signal<Vec<E>> {
+        let reader = self
+            .world_mut()
+            .resource::<Events<E>>()
+            .get_reader_current();
+        let mutable = self.create_mutable::<Vec<E>>(Vec::new());
+        let mut reaction = EventsReaction::<E> { reader };
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        reaction.react(mutable.id, self.world_mut(), &mut tracking);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert((ReactionHandle::new(reaction), tracking));
+        Signal::Mutable(mutable)
+    }
+}
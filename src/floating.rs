@@ -0,0 +1,303 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    Cx, EffectTarget, Element, ParentView, Portal, StyleBuilder, View, ViewFactory, ViewHandle,
+    WithStyles,
+};
+
+/// Which side of the anchor a [`Floating`] view is placed on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Placement {
+    /// Above the anchor.
+    Top,
+    /// Below the anchor.
+    #[default]
+    Bottom,
+    /// To the left of the anchor.
+    Left,
+    /// To the right of the anchor.
+    Right,
+}
+
+impl Placement {
+    fn flipped(self) -> Self {
+        match self {
+            Placement::Top => Placement::Bottom,
+            Placement::Bottom => Placement::Top,
+            Placement::Left => Placement::Right,
+            Placement::Right => Placement::Left,
+        }
+    }
+}
+
+/// How a [`Floating`] view is aligned with the anchor along the side it is placed on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloatAlign {
+    /// Align the left or top edges.
+    #[default]
+    Start,
+    /// Center on the anchor.
+    Center,
+    /// Align the right or bottom edges.
+    End,
+}
+
+/// Component which positions a floating node relative to its anchor after layout.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct FloatingPosition {
+    anchor: Entity,
+    placement: Placement,
+    align: FloatAlign,
+    offset: f32,
+}
+
+/// A view which displays its content in an overlay, positioned next to an anchor entity.
+/// After layout, the content is flipped to the opposite side of the anchor if it doesn't fit
+/// in the window, and clamped to stay within the window. This is the shared positioning
+/// machinery for tooltips, popovers, dropdown menus and context menus.
+///
+/// The content is hidden until it has been positioned, so that it doesn't flash at the
+/// wrong location when first shown.
+///
+/// ```ignore
+/// Floating::new(button_id, menu_items)
+///     .with_placement(Placement::Bottom)
+///     .with_align(FloatAlign::End)
+///     .with_offset(4.)
+/// ```
+pub struct Floating {
+    content: ViewHandle,
+    position: FloatingPosition,
+}
+
+impl Floating {
+    /// Construct a new `Floating` view, which places `content` next to `anchor`.
+    pub fn new(anchor: Entity, content: impl Into<ViewHandle>) -> Self {
+        Self {
+            content: content.into(),
+            position: FloatingPosition {
+                anchor,
+                placement: Placement::default(),
+                align: FloatAlign::default(),
+                offset: 0.,
+            },
+        }
+    }
+
+    /// Set which side of the anchor the content is placed on. The default is below.
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.position.placement = placement;
+        self
+    }
+
+    /// Set how the content is aligned with the anchor.
+    pub fn with_align(mut self, align: FloatAlign) -> Self {
+        self.position.align = align;
+        self
+    }
+
+    /// Set the gap between the anchor and the content, in pixels.
+    pub fn with_offset(mut self, offset: f32) -> Self {
+        self.position.offset = offset;
+        self
+    }
+}
+
+impl ViewFactory for Floating {
+    fn create(&self, _cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        Portal::new(
+            Element::<NodeBundle>::new()
+                .with_styles(|ss: &mut StyleBuilder| {
+                    ss.position(PositionType::Absolute);
+                })
+                .insert((self.position, Visibility::Hidden))
+                .with_child(&self.content),
+        )
+    }
+}
+
+/// Compute the top-left position of a floating node of the given size, placed next to
+/// `anchor` within a window of size `bounds`.
+fn float_position(anchor: Rect, size: Vec2, bounds: Vec2, position: &FloatingPosition) -> Vec2 {
+    let place = |placement: Placement| -> Vec2 {
+        let align = |start: f32, end: f32, extent: f32| match position.align {
+            FloatAlign::Start => start,
+            FloatAlign::Center => (start + end - extent) * 0.5,
+            FloatAlign::End => end - extent,
+        };
+        match placement {
+            Placement::Top => Vec2::new(
+                align(anchor.min.x, anchor.max.x, size.x),
+                anchor.min.y - position.offset - size.y,
+            ),
+            Placement::Bottom => Vec2::new(
+                align(anchor.min.x, anchor.max.x, size.x),
+                anchor.max.y + position.offset,
+            ),
+            Placement::Left => Vec2::new(
+                anchor.min.x - position.offset - size.x,
+                align(anchor.min.y, anchor.max.y, size.y),
+            ),
+            Placement::Right => Vec2::new(
+                anchor.max.x + position.offset,
+                align(anchor.min.y, anchor.max.y, size.y),
+            ),
+        }
+    };
+    // Amount by which a position overflows the window along the placement axis.
+    let overflow = |pos: Vec2, placement: Placement| match placement {
+        Placement::Top | Placement::Bottom => {
+            (-pos.y).max(0.) + (pos.y + size.y - bounds.y).max(0.)
+        }
+        Placement::Left | Placement::Right => {
+            (-pos.x).max(0.) + (pos.x + size.x - bounds.x).max(0.)
+        }
+    };
+
+    let mut pos = place(position.placement);
+    let flipped_placement = position.placement.flipped();
+    let flipped = place(flipped_placement);
+    if overflow(pos, position.placement) > overflow(flipped, flipped_placement) {
+        pos = flipped;
+    }
+    pos.clamp(Vec2::ZERO, (bounds - size).max(Vec2::ZERO))
+}
+
+/// Positions each floating node next to its anchor, using the layout computed this frame.
+/// Nodes are kept within the viewport of their target camera if they have one, otherwise
+/// within the primary window. Layout sizes are divided by [`UiScale`], so the bounds are
+/// converted to match.
+pub(crate) fn update_floating_positions(
+    mut floats: Query<(
+        &FloatingPosition,
+        &Node,
+        &mut Style,
+        &mut Visibility,
+        Option<&TargetCamera>,
+    )>,
+    anchors: Query<(&Node, &GlobalTransform)>,
+    cameras: Query<&Camera>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+) {
+    let window_bounds = windows
+        .get_single()
+        .ok()
+        .map(|window| Vec2::new(window.width(), window.height()));
+    for (position, node, mut style, mut visibility, target) in floats.iter_mut() {
+        let bounds = match target {
+            Some(target) => cameras
+                .get(target.0)
+                .ok()
+                .and_then(|camera| camera.logical_viewport_size()),
+            None => window_bounds,
+        };
+        let Some(bounds) = bounds else {
+            continue;
+        };
+        let bounds = bounds / ui_scale.0;
+        let Ok((anchor_node, anchor_transform)) = anchors.get(position.anchor) else {
+            // The anchor has gone away.
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        };
+        let anchor = anchor_node.logical_rect(anchor_transform);
+        let pos = float_position(anchor, node.size(), bounds, position);
+        let (left, top) = (Val::Px(pos.x), Val::Px(pos.y));
+        if style.left != left || style.top != top {
+            style.left = left;
+            style.top = top;
+        } else if *visibility == Visibility::Hidden {
+            // Shown once the new position has been laid out.
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::RunSystemOnce, window::WindowResolution};
+
+    use super::*;
+
+    fn position(placement: Placement, align: FloatAlign) -> FloatingPosition {
+        FloatingPosition {
+            anchor: Entity::PLACEHOLDER,
+            placement,
+            align,
+            offset: 4.,
+        }
+    }
+
+    #[test]
+    fn test_float_position() {
+        let bounds = Vec2::new(800., 600.);
+        let size = Vec2::new(100., 50.);
+        let anchor = Rect::new(200., 100., 300., 120.);
+
+        let below = position(Placement::Bottom, FloatAlign::Start);
+        assert_eq!(
+            float_position(anchor, size, bounds, &below),
+            Vec2::new(200., 124.)
+        );
+
+        let right = position(Placement::Right, FloatAlign::Center);
+        assert_eq!(
+            float_position(anchor, size, bounds, &right),
+            Vec2::new(304., 85.)
+        );
+
+        // Flips above when there isn't room below.
+        let anchor = Rect::new(200., 560., 300., 580.);
+        assert_eq!(
+            float_position(anchor, size, bounds, &below),
+            Vec2::new(200., 506.)
+        );
+
+        // Clamps to the window when aligned past its edge.
+        let anchor = Rect::new(760., 100., 790., 120.);
+        let end = position(Placement::Bottom, FloatAlign::Start);
+        assert_eq!(
+            float_position(anchor, size, bounds, &end),
+            Vec2::new(700., 124.)
+        );
+    }
+
+    #[test]
+    fn test_floating_ui_scale() {
+        let mut world = World::new();
+        world.insert_resource(UiScale(2.));
+        world.spawn((
+            Window {
+                resolution: WindowResolution::new(800., 600.),
+                ..default()
+            },
+            PrimaryWindow,
+        ));
+        let anchor = world
+            .spawn((
+                Node::default(),
+                GlobalTransform::from_translation(Vec3::new(700., 500., 0.)),
+            ))
+            .id();
+        let float = world
+            .spawn((
+                FloatingPosition {
+                    anchor,
+                    ..position(Placement::Bottom, FloatAlign::Start)
+                },
+                Node::default(),
+                Style::default(),
+                Visibility::Hidden,
+            ))
+            .id();
+        world.run_system_once(update_floating_positions);
+
+        // The window is 400 by 300 in UI units, so the node is kept within that.
+        let style = world.get::<Style>(float).unwrap();
+        assert_eq!(style.left, Val::Px(400.));
+        assert_eq!(style.top, Val::Px(300.));
+    }
+}
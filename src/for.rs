@@ -1,3 +1,5 @@
+use std::{hash::Hash, iter::Enumerate};
+
 use crate::ViewHandle;
 
 use super::{ForEach, ForIndex, Rcx};
@@ -5,6 +7,7 @@ use super::{ForEach, ForIndex, Rcx};
 /// A namespace that contains constructor functions for various kinds of for-loops:
 /// * `For::each()`
 /// * `For::keyed()`
+/// * `For::each_indexed()`
 /// * `For::index()`
 pub struct For;
 
@@ -45,6 +48,29 @@ impl For {
         ForEach::new(item_fn, cmp, each)
     }
 
+    /// Construct a keyed for loop for an array of items. `key` produces a unique key for each
+    /// item, and `each` produces a child view for each item. During rebuilds, child views are
+    /// matched with the previous items by key, so rows are preserved, along with their local
+    /// state, across insertions, removals and reorders. A row is not rebuilt when its item
+    /// changes but its key does not; rows which display changing data should read it from
+    /// signals.
+    pub fn keyed<
+        Item: Clone + 'static,
+        ItemIter: Iterator<Item = Item>,
+        ItemFn: Fn(&Rcx) -> ItemIter,
+        K: PartialEq + Hash,
+        KeyFn: Fn(&Item) -> K + Clone + Send + Sync + 'static,
+        V: Into<ViewHandle>,
+        F: Fn(&Item) -> V + Send,
+    >(
+        item_fn: ItemFn,
+        key: KeyFn,
+        each: F,
+    ) -> ForEach<Item, ItemIter, ItemFn, impl Fn(&Item, &Item) -> bool, V, F> {
+        let cmp_key = key.clone();
+        ForEach::new(item_fn, move |a, b| cmp_key(a) == cmp_key(b), each).memo_by_key(key)
+    }
+
     /// Construct an unkeyed for loop for an array of items. The callback is called once for each
     /// array element; its argument is the item, which must be equals-comparable, and it's result
     /// is a View. During rebuild, the list of child views may be re-ordered based on a comparison
@@ -61,4 +87,35 @@ impl For {
     ) -> ForEach<Item, ItemIter, ItemFn, impl Fn(&Item, &Item) -> bool, V, F> {
         ForEach::new(item_fn, |a, b| a == b, each)
     }
+
+    /// Construct an unkeyed for loop for an array of items, where each child view also
+    /// receives the item's position in the array. The callback's arguments are the array index
+    /// and the item, which must be equals-comparable. Like `For::each()`, child views are
+    /// matched with the previous items by comparison, but a child view is also rebuilt when
+    /// its item has moved to a different index, so that row numbering, striping and similar
+    /// position-dependent content stays up to date.
+    #[allow(clippy::type_complexity)]
+    pub fn each_indexed<
+        Item: Clone + PartialEq,
+        ItemIter: Iterator<Item = Item>,
+        ItemFn: Fn(&Rcx) -> ItemIter,
+        V: Into<ViewHandle>,
+        F: Fn(usize, &Item) -> V + Send,
+    >(
+        item_fn: ItemFn,
+        each: F,
+    ) -> ForEach<
+        (usize, Item),
+        Enumerate<ItemIter>,
+        impl Fn(&Rcx) -> Enumerate<ItemIter>,
+        impl Fn(&(usize, Item), &(usize, Item)) -> bool,
+        V,
+        impl Fn(&(usize, Item)) -> V + Send,
+    > {
+        ForEach::new(
+            move |cx: &Rcx| item_fn(cx).enumerate(),
+            |a: &(usize, Item), b: &(usize, Item)| a == b,
+            move |(index, item): &(usize, Item)| each(*index, item),
+        )
+    }
 }
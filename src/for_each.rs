@@ -1,8 +1,10 @@
+use std::hash::{Hash, Hasher};
 use std::ops::Range;
 
 use bevy::ecs::entity::Entity;
 use bevy::ecs::world::World;
 use bevy::hierarchy::Parent;
+use bevy::utils::HashMap;
 
 use crate::{lcs::lcs, View};
 use crate::{DespawnScopes, DisplayNodeChanged, Rcx, TrackingScope, ViewHandle};
@@ -41,6 +43,16 @@ pub struct ForEach<
     each: F,
     fallback: Option<ViewHandle>,
     fallback_ent: Option<Entity>,
+    memo_key: Option<MemoKeyFn<Item>>,
+}
+
+/// Function which computes the key used to match list items when memoizing.
+type MemoKeyFn<Item> = Box<dyn Fn(&Item) -> u64 + Send + Sync>;
+
+fn hash_key<K: Hash>(key: &K) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
 }
 
 #[allow(clippy::needless_range_loop)]
@@ -61,6 +73,7 @@ impl<
             cmp,
             fallback: None,
             fallback_ent: None,
+            memo_key: None,
         }
     }
 
@@ -70,29 +83,101 @@ impl<
         self
     }
 
+    /// Memoize the child views by key. When the list is updated, each item is matched with
+    /// a previous item having the same key and an equal value, regardless of position; the
+    /// matched child views are reused without calling the view function again. Items are
+    /// only built or razed when no match is found. This is much cheaper than the default
+    /// diffing for large lists which are refreshed wholesale, such as inspectors that poll
+    /// their data source.
+    pub fn memo_by_key<K: Hash, KF: Fn(&Item) -> K + Send + Sync + 'static>(
+        mut self,
+        key: KF,
+    ) -> Self
+    where
+        Item: 'static,
+    {
+        self.memo_key = Some(Box::new(move |item: &Item| hash_key(&key(item))));
+        self
+    }
+
+    /// Memoize the child views using the hash of each item as the key; see
+    /// [`memo_by_key`](Self::memo_by_key).
+    pub fn memo_by_hash(mut self) -> Self
+    where
+        Item: Hash + 'static,
+    {
+        self.memo_key = Some(Box::new(hash_key::<Item>));
+        self
+    }
+
+    /// Rebuild the list by matching items by memo key. Returns true if any child views were
+    /// added, removed or moved.
+    fn build_memoized(
+        &mut self,
+        world: &mut World,
+        view_entity: Entity,
+        next_items: &[Item],
+        out: &mut Vec<ListItem<Item>>,
+    ) -> bool {
+        let key = self.memo_key.as_ref().unwrap();
+        let prev_ids: Vec<Entity> = self.items.iter().map(|item| item.id).collect();
+        let mut prev: HashMap<u64, Vec<ListItem<Item>>> = HashMap::default();
+        for item in self.items.drain(..) {
+            prev.entry(key(&item.value)).or_default().push(item);
+        }
+
+        for next in next_items {
+            let reused = prev.get_mut(&key(next)).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .position(|p| (self.cmp)(&p.value, next))
+                    .map(|index| candidates.remove(index))
+            });
+            match reused {
+                Some(item) => out.push(item),
+                None => {
+                    let view = (self.each)(next).into();
+                    out.push(ListItem {
+                        id: ViewHandle::spawn(&view, view_entity, world),
+                        view,
+                        value: next.clone(),
+                    });
+                }
+            }
+        }
+
+        // Raze items which were not reused.
+        for unused in prev.into_values().flatten() {
+            unused.view.raze(unused.id, world);
+        }
+
+        !out.iter().map(|item| item.id).eq(prev_ids)
+    }
+
     /// Uses the sequence of key values to match the previous array items with the updated
-    /// array items. Matching items are patched, other items are inserted or deleted.
+    /// array items. Matching items are copied over; items which are only in the previous
+    /// array are moved to `removed`, and items which are only in the updated array are left
+    /// as `None` in `out`, so that the caller can decide whether to reuse or build them.
+    /// Since `out` is built in order, each entry in `out` corresponds to the item in
+    /// `next_items` at the same index.
     ///
     /// # Arguments
     ///
-    /// * `bc` - [`BuildContext`] used to build individual elements.
     /// * `prev_state` - Array of view state elements from previous update.
     /// * `prev_range` - The range of elements we are comparing in `prev_state`.
-    /// * `next_state` - Array of view state elements to be built.
-    /// * `next_range` - The range of elements we are comparing in `next_state`.
-    #[allow(clippy::too_many_arguments)]
+    /// * `next_items` - Array of items to be built.
+    /// * `next_range` - The range of elements we are comparing in `next_items`.
+    /// * `out` - The reconciled list of view state elements.
+    /// * `removed` - Previous elements which were not matched.
     fn build_recursive(
         &self,
-        world: &mut World,
-        view_entity: Entity,
         prev_state: &[ListItem<Item>],
         prev_range: Range<usize>,
         next_items: &[Item],
         next_range: Range<usize>,
-        out: &mut Vec<ListItem<Item>>,
-    ) -> bool {
-        let mut changed = false;
-
+        out: &mut Vec<Option<ListItem<Item>>>,
+        removed: &mut Vec<ListItem<Item>>,
+    ) {
         // Look for longest common subsequence.
         // prev_start and next_start are *relative to the slice*.
         let (prev_start, next_start, lcs_length) = lcs(
@@ -103,23 +188,9 @@ impl<
 
         // If there was nothing in common
         if lcs_length == 0 {
-            // Raze old elements
-            for i in prev_range {
-                let prev = &prev_state[i];
-                prev.view.raze(prev.id, world);
-                changed = true;
-            }
-            // Build new elements
-            for i in next_range {
-                changed = true;
-                let view = (self.each)(&next_items[i]).into();
-                out.push(ListItem {
-                    id: ViewHandle::spawn(&view, view_entity, world),
-                    view,
-                    value: next_items[i].clone(),
-                });
-            }
-            return changed;
+            removed.extend(prev_state[prev_range].iter().cloned());
+            out.extend(next_range.map(|_| None));
+            return;
         }
 
         // Adjust prev_start and next_start to be relative to the entire state array.
@@ -130,40 +201,26 @@ impl<
         if prev_start > prev_range.start {
             if next_start > next_range.start {
                 // Both prev and next have entries before lcs, so recurse
-                changed |= self.build_recursive(
-                    world,
-                    view_entity,
+                self.build_recursive(
                     prev_state,
                     prev_range.start..prev_start,
                     next_items,
                     next_range.start..next_start,
                     out,
+                    removed,
                 )
             } else {
                 // Deletions
-                for i in prev_range.start..prev_start {
-                    let prev = &prev_state[i];
-                    prev.view.raze(prev.id, world);
-                    changed = true;
-                }
+                removed.extend(prev_state[prev_range.start..prev_start].iter().cloned());
             }
         } else if next_start > next_range.start {
             // Insertions
-            for i in next_range.start..next_start {
-                let view = (self.each)(&next_items[i]).into();
-                out.push(ListItem {
-                    id: ViewHandle::spawn(&view, view_entity, world),
-                    view,
-                    value: next_items[i].clone(),
-                });
-                changed = true;
-            }
+            out.extend((next_range.start..next_start).map(|_| None));
         }
 
         // For items that match, copy over the view and value.
         for i in 0..lcs_length {
-            let prev = &prev_state[prev_start + i];
-            out.push(prev.clone());
+            out.push(Some(prev_state[prev_start + i].clone()));
         }
 
         // Stuff that follows the LCS.
@@ -172,34 +229,90 @@ impl<
         if prev_end < prev_range.end {
             if next_end < next_range.end {
                 // Both prev and next have entries after lcs, so recurse
-                changed |= self.build_recursive(
-                    world,
-                    view_entity,
+                self.build_recursive(
                     prev_state,
                     prev_end..prev_range.end,
                     next_items,
                     next_end..next_range.end,
                     out,
+                    removed,
                 );
             } else {
                 // Deletions
-                for i in prev_end..prev_range.end {
-                    let prev = &prev_state[i];
-                    prev.view.raze(prev.id, world);
-                    changed = true;
-                }
+                removed.extend(prev_state[prev_end..prev_range.end].iter().cloned());
             }
         } else if next_end < next_range.end {
             // Insertions
-            for i in next_end..next_range.end {
-                let view = (self.each)(&next_items[i]).into();
-                out.push(ListItem {
-                    id: ViewHandle::spawn(&view, view_entity, world),
-                    view,
-                    value: next_items[i].clone(),
-                });
-                changed = true;
+            out.extend((next_end..next_range.end).map(|_| None));
+        }
+    }
+
+    /// Rebuild the list by diffing the previous items with the updated items. Items which
+    /// were moved to a different position are reused rather than razed and rebuilt, so their
+    /// entities, and any state such as focus, are preserved; the parent's children are then
+    /// re-ordered to match. Returns true if any child views were added, removed or moved.
+    ///
+    /// Items can only be compared for equality, so finding moved items takes time
+    /// proportional to the number of inserted items times the number of removed ones. For
+    /// large lists whose items are often replaced, [`memo_by_key`](Self::memo_by_key) is
+    /// faster.
+    fn build_diffed(
+        &mut self,
+        world: &mut World,
+        view_entity: Entity,
+        next_items: &[Item],
+        out: &mut Vec<ListItem<Item>>,
+    ) -> bool {
+        let mut matched: Vec<Option<ListItem<Item>>> = Vec::with_capacity(next_items.len());
+        let mut removed: Vec<ListItem<Item>> = Vec::new();
+        self.build_recursive(
+            &self.items,
+            0..self.items.len(),
+            next_items,
+            0..next_items.len(),
+            &mut matched,
+            &mut removed,
+        );
+        let mut changed = !removed.is_empty();
+
+        // Removed items are taken out of their slots when they are reused, rather than being
+        // removed from the list, so that the remaining items don't have to be shifted. Moved
+        // items are usually found in order, so the search starts after the leading empty
+        // slots.
+        let mut remaining = removed.len();
+        let mut first = 0;
+        let mut removed: Vec<Option<ListItem<Item>>> = removed.into_iter().map(Some).collect();
+        for (next, slot) in next_items.iter().zip(matched) {
+            if let Some(item) = slot {
+                out.push(item);
+                continue;
+            }
+            changed = true;
+            // Reuse an item that was moved from elsewhere in the list.
+            if remaining > 0 {
+                let moved = removed[first..]
+                    .iter_mut()
+                    .find(|p| p.as_ref().is_some_and(|p| (self.cmp)(&p.value, next)));
+                if let Some(item) = moved.and_then(Option::take) {
+                    remaining -= 1;
+                    while removed.get(first).is_some_and(Option::is_none) {
+                        first += 1;
+                    }
+                    out.push(item);
+                    continue;
+                }
             }
+            let view = (self.each)(next).into();
+            out.push(ListItem {
+                id: ViewHandle::spawn(&view, view_entity, world),
+                view,
+                value: next.clone(),
+            });
+        }
+
+        // Raze items which were not reused.
+        for unused in removed.into_iter().flatten() {
+            unused.view.raze(unused.id, world);
         }
 
         changed
@@ -237,18 +350,11 @@ impl<
         let items: Vec<Item> = iter.collect();
         let mut next_state: Vec<ListItem<Item>> = Vec::with_capacity(hint);
         let next_len = items.len();
-        let prev_len = self.items.len();
-        let mut changed = false;
-
-        self.build_recursive(
-            world,
-            view_entity,
-            &self.items,
-            0..prev_len,
-            &items,
-            0..next_len,
-            &mut next_state,
-        );
+        let mut changed = if self.memo_key.is_some() {
+            self.build_memoized(world, view_entity, &items, &mut next_state)
+        } else {
+            self.build_diffed(world, view_entity, &items, &mut next_state)
+        };
 
         // Handle fallback
         if let Some(ref mut fallback) = self.fallback {
@@ -300,6 +406,70 @@ impl<
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::{
+        testing::TestHarness, Cx, Element, For, ParentView, PresenterFn, RegisterSignal,
+        TextStatic, View, ViewFactory,
+    };
+
+    #[derive(Resource, Default)]
+    struct Builds(usize);
+
+    struct Row(i32);
+
+    impl ViewFactory for Row {
+        fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+            cx.world_mut().resource_mut::<Builds>().0 += 1;
+            TextStatic::new(self.0.to_string())
+        }
+    }
+
+    fn list(cx: &mut Cx) -> impl View {
+        let items = cx.create_mutable(vec![1, 2, 3]);
+        cx.register_signal("items", items.signal());
+        Element::<NodeBundle>::new().with_children(For::each(
+            move |cx| items.get_clone(cx).into_iter(),
+            |item| Row(*item),
+        ))
+    }
+
+    #[test]
+    fn test_for_each_move() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Builds>();
+        let root = harness.mount(list.bind(()));
+        let element = harness.nodes(root)[0];
+        let children = |harness: &TestHarness| -> Vec<Entity> {
+            harness
+                .world()
+                .get::<Children>(element)
+                .unwrap()
+                .iter()
+                .copied()
+                .collect()
+        };
+        let before = children(&harness);
+        assert_eq!(before.len(), 3);
+        assert_eq!(harness.text(root), "123");
+
+        // Moving an item re-orders the existing rows rather than rebuilding them.
+        let items = harness.mutable::<Vec<i32>>("items");
+        harness.set(items, vec![3, 1, 2]);
+        assert_eq!(harness.text(root), "312");
+        assert_eq!(children(&harness), vec![before[2], before[0], before[1]]);
+        assert_eq!(harness.world().resource::<Builds>().0, 3);
+
+        // New items are still built, and missing items razed.
+        harness.set(items, vec![2, 4]);
+        assert_eq!(harness.text(root), "24");
+        assert_eq!(children(&harness)[0], before[1]);
+        assert_eq!(harness.world().resource::<Builds>().0, 4);
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 //     use bevy::ecs::world::World;
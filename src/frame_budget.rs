@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+/// The default time per frame available for reactions and deferrable work.
+pub(crate) const DEFAULT_FRAME_BUDGET: Duration = Duration::from_millis(4);
+
+/// Resource which tracks how much of the frame's time budget has been used. The time taken by
+/// reactions is always charged against the budget, as each reaction finishes; work which can
+/// be put off until a later frame, such as building rows of a virtualized list ahead of time,
+/// should only be done while time [`remains`](Self::remaining). Work done outside of a
+/// reaction should charge the time it takes with [`spend`](Self::spend). This keeps optional
+/// work from making busy frames hitch.
+///
+/// The budget is reset at the start of each frame. The limit is set with
+/// [`ReactorPlugin::with_frame_budget`](crate::ReactorPlugin::with_frame_budget).
+#[derive(Resource, Debug, Clone)]
+pub struct FrameBudget {
+    /// Time per frame available for reactions and deferrable work.
+    pub limit: Duration,
+
+    /// Time used so far this frame.
+    spent: Duration,
+}
+
+impl Default for FrameBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_FRAME_BUDGET)
+    }
+}
+
+impl FrameBudget {
+    /// Construct a budget with the given time per frame.
+    pub fn new(limit: Duration) -> Self {
+        Self {
+            limit,
+            spent: Duration::ZERO,
+        }
+    }
+
+    /// Time used so far this frame.
+    pub fn spent(&self) -> Duration {
+        self.spent
+    }
+
+    /// Time left this frame. This is zero once the budget has been exceeded.
+    pub fn remaining(&self) -> Duration {
+        self.limit.saturating_sub(self.spent)
+    }
+
+    /// Charge time against this frame's budget.
+    pub fn spend(&mut self, time: Duration) {
+        self.spent += time;
+    }
+}
+
+pub(crate) fn reset_frame_budget(mut budget: ResMut<FrameBudget>) {
+    budget.spent = Duration::ZERO;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_budget() {
+        let mut budget = FrameBudget::new(Duration::from_millis(4));
+        assert_eq!(budget.remaining(), Duration::from_millis(4));
+        budget.spend(Duration::from_millis(3));
+        assert_eq!(budget.remaining(), Duration::from_millis(1));
+        budget.spend(Duration::from_millis(3));
+        assert_eq!(budget.spent(), Duration::from_millis(6));
+        assert_eq!(budget.remaining(), Duration::ZERO);
+    }
+}
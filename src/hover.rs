@@ -5,7 +5,8 @@ use bevy::{
 use bevy_mod_picking::{focus::HoverMap, pointer::PointerId};
 
 use crate::{
-    signal::Signal, Cx, Reaction, ReactionHandle, RunContextSetup, TrackingScope, WriteMutable,
+    pointer_capture::PointerCapture, signal::Signal, Cx, Reaction, ReactionHandle, RunContextSetup,
+    TrackingScope, WriteMutable,
 };
 
 pub(crate) struct HoverReaction {
@@ -16,13 +17,19 @@ impl Reaction for HoverReaction {
     fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
         tracking.track_resource::<HoverMap>(world);
         let hover_map = world.get_resource::<HoverMap>().unwrap();
+        // Only track the capture target, not the cursor position.
+        let capture = world.resource::<PointerCapture>().target();
+        tracking.track_resource_field(&capture, |c: &PointerCapture| &c.target);
 
         // "Hovering" is defined as "the mouse is over this element or one of its descendants."
-        let is_hovering = match hover_map.get(&PointerId::Mouse) {
-            Some(map) => map
+        // While the pointer is captured, only the capturing element and its ancestors are
+        // considered to be hovered.
+        let is_hovering = match (capture, hover_map.get(&PointerId::Mouse)) {
+            (Some(captured), _) => is_descendant(world, &captured, &self.target),
+            (None, Some(map)) => map
                 .iter()
                 .any(|(ha, _)| is_descendant(world, ha, &self.target)),
-            None => false,
+            (None, None) => false,
         };
 
         // TODO: Direct access to mutable by entity id is kind of a cheat.
@@ -0,0 +1,112 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use bevy::{
+    app::{App, Plugin},
+    asset::{
+        io::{AssetReader, AssetReaderError, AssetSource, PathStream, Reader, VecReader},
+        AssetApp,
+    },
+    utils::BoxedFuture,
+};
+
+/// Plugin which lets the asset server load assets over HTTP, from paths such as
+/// `https://example.com/logo.png`. This allows images and other assets to be fetched from a
+/// server, for example with [`CreateAssetSignal`](crate::CreateAssetSignal), or with
+/// [`Suspense`](crate::Suspense) while they load. Requests are made with the browser's
+/// `fetch` API on the web.
+///
+/// This plugin registers the `http` and `https` asset sources, so it must be added before
+/// Bevy's `AssetPlugin`, which is part of `DefaultPlugins`.
+pub struct HttpAssetPlugin;
+
+impl Plugin for HttpAssetPlugin {
+    fn build(&self, app: &mut App) {
+        for scheme in ["http", "https"] {
+            app.register_asset_source(
+                scheme,
+                AssetSource::build().with_reader(move || Box::new(HttpAssetReader { scheme })),
+            );
+        }
+    }
+}
+
+/// Asset reader which fetches assets from a web server.
+struct HttpAssetReader {
+    /// The URL scheme, which is the name of the asset source.
+    scheme: &'static str,
+}
+
+impl HttpAssetReader {
+    /// The URL of the asset at `path`, which is everything after the scheme.
+    fn url(&self, path: &Path) -> String {
+        format!(
+            "{}://{}",
+            self.scheme,
+            path.to_string_lossy().replace('\\', "/")
+        )
+    }
+}
+
+/// Fetch the contents of a URL.
+async fn fetch(url: String, path: PathBuf) -> Result<Vec<u8>, AssetReaderError> {
+    let response = ehttp::fetch_async(ehttp::Request::get(url))
+        .await
+        .map_err(|error| AssetReaderError::Io(Arc::new(std::io::Error::other(error))))?;
+    match response.status {
+        200..=299 => Ok(response.bytes),
+        404 => Err(AssetReaderError::NotFound(path)),
+        status => Err(AssetReaderError::HttpError(status)),
+    }
+}
+
+impl AssetReader for HttpAssetReader {
+    fn read<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        Box::pin(async move {
+            let bytes = fetch(self.url(path), path.to_path_buf()).await?;
+            let reader: Box<Reader> = Box::new(VecReader::new(bytes));
+            Ok(reader)
+        })
+    }
+
+    fn read_meta<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<Reader<'a>>, AssetReaderError>> {
+        // Servers don't provide asset meta files, so default settings are always used.
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_path_buf())) })
+    }
+
+    fn read_directory<'a>(
+        &'a self,
+        path: &'a Path,
+    ) -> BoxedFuture<'a, Result<Box<PathStream>, AssetReaderError>> {
+        Box::pin(async move { Err(AssetReaderError::NotFound(path.to_path_buf())) })
+    }
+
+    fn is_directory<'a>(
+        &'a self,
+        _path: &'a Path,
+    ) -> BoxedFuture<'a, Result<bool, AssetReaderError>> {
+        Box::pin(async move { Ok(false) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let reader = HttpAssetReader { scheme: "https" };
+        assert_eq!(
+            reader.url(Path::new("example.com/images/logo.png")),
+            "https://example.com/images/logo.png"
+        );
+    }
+}
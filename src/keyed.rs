@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, switch::CaseBody, DespawnScopes, DisplayNodeChanged, Rcx, TrackingScope,
+    View, ViewHandle,
+};
+
+/// A group of child views, identified by keys, which are displayed in the order given by a
+/// reactive list of keys. When the order changes, the existing children are re-attached in
+/// the new order rather than being rebuilt, so that toolbars and tab strips can reorder their
+/// entries cheaply. Children whose keys are missing from the list are razed, and built again
+/// if their key reappears; keys with no matching child are ignored.
+///
+/// ```ignore
+/// KeyedChildren::new(move |cx| tab_order.get_clone(cx))
+///     .child("scene", || tab("Scene"))
+///     .child("assets", || tab("Assets"))
+///     .child("console", || tab("Console"))
+/// ```
+pub struct KeyedChildren<K, F> {
+    order_fn: F,
+    children: Vec<(K, Arc<dyn CaseBody>)>,
+    /// The built children, in display order, as indices into `children` with their views.
+    built: Vec<(usize, ViewHandle, Entity)>,
+}
+
+impl<K: PartialEq + Send + Sync + 'static, F: Fn(&Rcx) -> Vec<K> + Send + Sync + 'static>
+    KeyedChildren<K, F>
+{
+    /// Construct a new `KeyedChildren`, ordered by the keys returned by `order_fn`.
+    pub fn new(order_fn: F) -> Self {
+        Self {
+            order_fn,
+            children: Vec::new(),
+            built: Vec::new(),
+        }
+    }
+
+    /// Add a child view, which is displayed when `key` is present in the list of keys.
+    pub fn child<V: Into<ViewHandle>, FV: Send + Sync + 'static + Fn() -> V>(
+        mut self,
+        key: K,
+        view: FV,
+    ) -> Self {
+        self.children.push((key, Arc::new(view)));
+        self
+    }
+}
+
+impl<K: PartialEq + Send + Sync + 'static, F: Fn(&Rcx) -> Vec<K> + Send + Sync + 'static> View
+    for KeyedChildren<K, F>
+{
+    fn nodes(&self) -> NodeSpan {
+        let child_spans: Vec<NodeSpan> =
+            self.built.iter().map(|(_, view, _)| view.nodes()).collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let keys = (self.order_fn)(&Rcx::new(world, tracking));
+        let mut order: Vec<usize> = Vec::with_capacity(keys.len());
+        for key in keys.iter() {
+            if let Some(index) = self.children.iter().position(|(k, _)| k == key) {
+                if !order.contains(&index) {
+                    order.push(index);
+                }
+            }
+        }
+
+        let previous: Vec<usize> = self.built.iter().map(|(index, _, _)| *index).collect();
+        if order == previous {
+            return;
+        }
+
+        // Raze children which are no longer displayed, and re-use the rest in the new order.
+        let mut built = std::mem::take(&mut self.built);
+        for (index, view, entity) in built.iter() {
+            if !order.contains(index) {
+                view.raze(*entity, world);
+            }
+        }
+        built.retain(|(index, _, _)| order.contains(index));
+        for index in order {
+            match built.iter().position(|(i, _, _)| *i == index) {
+                Some(pos) => self.built.push(built.swap_remove(pos)),
+                None => {
+                    let (view, entity) = self.children[index].1.build(view_entity, world);
+                    self.built.push((index, view, entity));
+                }
+            }
+        }
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        for (_, view, entity) in self.built.drain(..) {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<K: PartialEq + Send + Sync + 'static, F: Fn(&Rcx) -> Vec<K> + Send + Sync + 'static>
+    From<KeyedChildren<K, F>> for ViewHandle
+{
+    fn from(value: KeyedChildren<K, F>) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, Element, ParentView, PresenterFn, RegisterSignal};
+
+    fn tabs(cx: &mut Cx) -> impl View {
+        let order = cx.create_mutable(vec!["a", "b", "c"]);
+        cx.register_signal("order", order.signal());
+        Element::<NodeBundle>::new().with_children(
+            KeyedChildren::new(move |cx| order.get_clone(cx))
+                .child("a", || "A")
+                .child("b", || "B")
+                .child("c", || "C"),
+        )
+    }
+
+    #[test]
+    fn test_keyed_children() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(tabs.bind(()));
+        let element = harness.nodes(root)[0];
+        let children = |harness: &TestHarness| -> Vec<Entity> {
+            harness
+                .world()
+                .get::<Children>(element)
+                .map_or(Vec::new(), |children| children.to_vec())
+        };
+        let before = children(&harness);
+        assert_eq!(harness.text(root), "ABC");
+
+        // Reordering re-attaches the existing nodes.
+        let order = harness.mutable::<Vec<&'static str>>("order");
+        harness.set(order, vec!["c", "a", "b"]);
+        assert_eq!(harness.text(root), "CAB");
+        assert_eq!(children(&harness), vec![before[2], before[0], before[1]]);
+
+        // Missing keys are razed, unknown keys ignored.
+        harness.set(order, vec!["b", "x"]);
+        assert_eq!(harness.text(root), "B");
+        assert_eq!(children(&harness), vec![before[1]]);
+    }
+}
@@ -0,0 +1,99 @@
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{DespawnScopes, DisplayNodeChanged, Rcx, TrackingScope, View, ViewHandle};
+
+/// A view which defers building its content until a condition first becomes true, such as a
+/// tab panel being selected or a tree node being expanded. This avoids paying the cost of
+/// constructing content which may never be shown. Once built, the content is kept, even if
+/// the condition later becomes false; use [`Cond`](crate::Cond) to tear content down again.
+pub struct Lazy<Test: Fn(&Rcx) -> bool, V: Into<ViewHandle>, F: Fn() -> V> {
+    test: Test,
+    view_fn: F,
+    state: Option<(ViewHandle, Entity)>,
+}
+
+impl<Test: Fn(&Rcx) -> bool, V: Into<ViewHandle>, F: Fn() -> V> Lazy<Test, V, F> {
+    /// Construct a new `Lazy` view, which builds the view returned by `view_fn` once `test`
+    /// returns true.
+    pub fn new(test: Test, view_fn: F) -> Self {
+        Self {
+            test,
+            view_fn,
+            state: None,
+        }
+    }
+}
+
+impl<Test: Fn(&Rcx) -> bool, V: Into<ViewHandle>, F: Fn() -> V> View for Lazy<Test, V, F> {
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world.entity_mut(view_entity).insert(Name::new("Lazy"));
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        // Once built, there are no further dependencies, so this won't run again.
+        if self.state.is_some() || !(self.test)(&Rcx::new(world, tracking)) {
+            return;
+        }
+        let view: ViewHandle = (self.view_fn)().into();
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.state = Some((view, entity));
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<
+        Test: Fn(&Rcx) -> bool + Send + Sync + 'static,
+        V: Into<ViewHandle> + 'static,
+        F: Fn() -> V + Send + Sync + 'static,
+    > From<Lazy<Test, V, F>> for ViewHandle
+{
+    fn from(value: Lazy<Test, V, F>) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup};
+
+    fn tree_node(cx: &mut Cx) -> impl View {
+        let expanded = cx.create_mutable(false);
+        cx.register_signal("expanded", expanded.signal());
+        Lazy::new(move |cx| expanded.get(cx), || "Children")
+    }
+
+    #[test]
+    fn test_lazy() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(tree_node.bind(()));
+        assert_eq!(harness.text(root), "");
+
+        let expanded = harness.mutable::<bool>("expanded");
+        harness.set(expanded, true);
+        assert_eq!(harness.text(root), "Children");
+
+        // Content is kept once it has been built.
+        harness.set(expanded, false);
+        assert_eq!(harness.text(root), "Children");
+    }
+}
@@ -2,35 +2,96 @@
 
 #![warn(missing_docs)]
 
+// Lets code generated by `bevy_reactor_macros` refer to this crate from within it.
+extern crate self as bevy_reactor;
+
+mod aggregate;
+#[cfg(feature = "animation")]
+mod animated_signal;
+mod asset_signal;
 mod callback;
 mod compositor;
 mod cond;
+mod context;
 mod cx;
 mod derived;
+mod dynamic;
 mod effect_target;
 mod element;
+mod error_boundary;
+mod event_signal;
+mod floating;
 mod r#for;
 mod for_each;
 mod for_index;
 mod fragment;
+mod frame_budget;
+#[cfg(feature = "picking")]
 mod hover;
+#[cfg(feature = "http")]
+mod http_asset;
+mod keyed;
+mod lazy;
 mod lcs;
+mod local_state;
+mod material_param;
+mod memo;
+mod mount;
 mod mutable;
+mod node_rect;
 mod node_span;
 mod parent_view;
+mod pause;
+#[cfg(feature = "persist")]
+mod persist;
 mod plugin;
+#[cfg(feature = "picking")]
+mod pointer_capture;
+mod pointer_lock;
 mod portal;
 mod presenter;
 mod reaction;
+mod reaction_report;
+mod reducer;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod selection_highlight;
+mod selection_model;
+mod selector;
 mod signal;
+mod signal_param;
+mod signal_registry;
+mod slots;
+mod store;
 mod style;
+mod suspense;
 mod switch;
+mod synced;
+mod task_signal;
+pub mod testing;
 mod text;
+mod text_measure;
+mod timer_signal;
 mod tracking_scope;
+mod transition;
+mod trigger;
 mod view;
+mod view_list;
+mod when_resource;
+mod window_metrics;
+mod world_anchor;
+mod write_conflicts;
 
+pub use aggregate::CreateAggregate;
+#[cfg(feature = "animation")]
+pub use animated_signal::Animatable;
+pub use asset_signal::CreateAssetSignal;
+pub use bevy_reactor_macros::styles;
+pub use bevy_reactor_macros::view;
+pub use bevy_reactor_macros::StoreFields;
 pub use callback::CallDeferred;
 pub use callback::Callback;
+pub use callback::CallbackFn;
 pub use compositor::Compositor;
 pub use cond::cond;
 pub use cond::Cond;
@@ -41,41 +102,128 @@ pub use cx::RunContextSetup;
 pub use cx::RunContextWrite;
 pub use derived::Derived;
 pub use derived::ReadDerived;
+pub use derived::SignalDeps;
+pub use dynamic::Dynamic;
 pub use effect_target::EffectTarget;
 pub use effect_target::EntityEffect;
+pub use effect_target::InsertCondition;
 pub use element::Element;
+pub use error_boundary::ErrorBoundary;
+pub use event_signal::CreateEventSignal;
+pub use floating::FloatAlign;
+pub use floating::Floating;
+pub use floating::Placement;
 pub use for_each::ForEach;
 pub use for_index::ForIndex;
 pub use fragment::Fragment;
+pub use frame_budget::FrameBudget;
+#[cfg(feature = "picking")]
 pub use hover::CreateHoverSignal;
+#[cfg(feature = "http")]
+pub use http_asset::HttpAssetPlugin;
+pub use keyed::KeyedChildren;
+pub use lazy::Lazy;
+pub use material_param::BindMaterialParam;
+pub use material_param::MaterialParamPlugin;
+pub use memo::Memo;
+pub use mount::MountPhase;
 pub use mutable::Mutable;
 pub use mutable::ReadMutable;
+pub use mutable::SignalSetter;
 pub use mutable::WriteMutable;
+pub use node_rect::UseNodeRect;
 pub use node_span::NodeSpan;
+pub use parent_view::ChildArray;
 pub use parent_view::ChildView;
 pub use parent_view::ChildViewTuple;
 pub use parent_view::ParentView;
+pub use pause::PauseReactions;
+pub use pause::ReactionsPaused;
+#[cfg(feature = "persist")]
+pub use persist::PersistPlugin;
+#[cfg(feature = "persist")]
+pub use persist::PersistedState;
 pub use plugin::ReactorPlugin;
+pub use plugin::ReactorSet;
+#[cfg(feature = "picking")]
+pub use pointer_capture::CapturedPointerMove;
+#[cfg(feature = "picking")]
+pub use pointer_capture::CapturedPointerUp;
+#[cfg(feature = "picking")]
+pub use pointer_capture::PointerCapture;
+pub use pointer_lock::PointerLock;
+pub use pointer_lock::UsePointerLock;
+pub use portal::OverlayStack;
 pub use portal::Portal;
+pub use portal::OVERLAY_Z_INDEX_BASE;
 pub use presenter::*;
 pub use r#for::For;
 pub use reaction::*;
+pub use reaction_report::PassReport;
+pub use reaction_report::ReactionReport;
+pub use reducer::ReducerStore;
+pub use reducer::StoreAction;
+#[cfg(feature = "scripting")]
+pub use scripting::ScriptEngine;
+#[cfg(feature = "scripting")]
+pub use scripting::ScriptNode;
+#[cfg(feature = "scripting")]
+pub use scripting::ScriptView;
+pub use selection_highlight::CreateSelectionHighlight;
+pub use selection_highlight::Selected;
+pub use selection_model::CreateSelectionModel;
+pub use selection_model::SelectionModel;
+pub use selector::Selector;
+pub use signal::IntoSignal;
 pub use signal::Signal;
+pub use signal_param::SignalKey;
+pub use signal_param::SignalReader;
+pub use signal_param::SignalWriter;
+pub use signal_registry::RegisterSignal;
+pub use signal_registry::SignalName;
+pub use signal_registry::Signals;
+pub use slots::Slots;
+pub use store::Store;
+pub use store::StoreField;
+pub use style::ElementClasses;
 pub use style::StyleBuilder;
 pub use style::StyleBuilderBackground;
 pub use style::StyleBuilderBorderColor;
 pub use style::StyleBuilderFont;
 pub use style::StyleBuilderLayout;
 pub use style::StyleBuilderOutline;
+#[cfg(feature = "picking")]
 pub use style::StyleBuilderPointerEvents;
 pub use style::StyleBuilderZIndex;
+pub use style::StyleDynSource;
 pub use style::StyleHandle;
+#[cfg(feature = "style-debug")]
+pub use style::StyleProvenance;
 pub use style::StyleTuple;
 pub use style::WithStyles;
+pub use suspense::Suspense;
 pub use switch::Case;
 pub use switch::Switch;
+pub use switch::SwitchOn;
+pub use synced::CreateSynced;
+pub use synced::SyncSource;
+pub use synced::Synced;
+pub use task_signal::CreateTaskSignal;
+pub use task_signal::SpawnTask;
+pub use task_signal::TaskFuture;
 pub use text::*;
+pub use text_measure::MeasureText;
+pub use timer_signal::CreateTimerSignal;
 pub use tracking_scope::DespawnScopes;
 pub use tracking_scope::TrackingScope;
+pub use transition::CreateTransition;
+pub use transition::Transition;
+pub use trigger::Trigger;
 pub use view::*;
+pub use view_list::ViewList;
+pub use when_resource::WhenResource;
+pub use window_metrics::UseWindowMetrics;
+pub use window_metrics::WindowMetrics;
+pub use world_anchor::ScreenPosition;
+pub use world_anchor::UseScreenPosition;
 // pub use style::StyleBuilderTextureAtlas;
@@ -0,0 +1,53 @@
+use std::any::TypeId;
+
+use bevy::{
+    ecs::{component::Component, entity::Entity},
+    utils::HashMap,
+};
+
+/// Identifies a presenter instance for the purpose of persisting local state: the type of
+/// the presenter function, and the key assigned with [`crate::Bind::with_key`].
+pub(crate) type LocalStateKey = (TypeId, u64);
+
+/// Component which holds the persistent local state of the presenters which are children of
+/// this view entity. Each call to [`crate::Cx::use_state`] occupies a numbered slot, and the
+/// slot holds the entity of the mutable containing the state. Because the component lives on
+/// the parent view, the state survives when the parent rebuilds the presenter, and is
+/// despawned along with the parent.
+#[derive(Component, Default)]
+pub(crate) struct LocalStateSlots(pub(crate) HashMap<(LocalStateKey, usize), Entity>);
+
+#[cfg(test)]
+mod tests {
+    use std::any::TypeId;
+
+    use bevy::ecs::world::World;
+
+    use crate::{cx::Cx, DespawnScopes, TrackingScope};
+
+    #[test]
+    fn test_state_survives_rebuild() {
+        let mut world = World::default();
+        let parent = world
+            .spawn(TrackingScope::new(world.read_change_tick()))
+            .id();
+        let key = (TypeId::of::<()>(), 0);
+
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope).with_local_state(parent, key);
+        let expanded = cx.use_state(|| false);
+        expanded.set(&mut cx, true);
+        crate::mutable::commit_mutables(&mut world);
+
+        // A rebuilt presenter gets the same state back, and `init` is not called.
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope).with_local_state(parent, key);
+        let rebuilt = cx.use_state(|| false);
+        assert_eq!(rebuilt.id(), expanded.id());
+        assert!(rebuilt.get(&cx));
+
+        // The state is released along with the parent view.
+        world.despawn_owned_recursive(parent);
+        assert!(world.get_entity(expanded.id()).is_none());
+    }
+}
@@ -0,0 +1,205 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{Cx, ReactorSet, RunContextRead, RunContextSetup, RunContextWrite, Signal};
+
+/// A deferred write to a single material.
+type MaterialWrite<M> = Box<dyn FnOnce(&mut M) + Send + Sync>;
+
+/// Resource holding the material writes made by bound parameters during the current frame.
+#[derive(Resource)]
+pub(crate) struct MaterialParamQueue<M: Asset> {
+    writes: Vec<(AssetId<M>, MaterialWrite<M>)>,
+}
+
+impl<M: Asset> Default for MaterialParamQueue<M> {
+    fn default() -> Self {
+        Self { writes: Vec::new() }
+    }
+}
+
+/// Plugin which applies bound material parameters for materials of type `M`. Add it for each
+/// material type which has bound parameters, including `StandardMaterial`; parameters bound
+/// for a material type without the plugin are ignored, with a warning.
+pub struct MaterialParamPlugin<M: Asset>(PhantomData<M>);
+
+impl<M: Asset> Default for MaterialParamPlugin<M> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<M: Asset> Plugin for MaterialParamPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MaterialParamQueue<M>>()
+            .add_systems(PostUpdate, apply_material_params::<M>.after(ReactorSet));
+    }
+}
+
+/// Applies the queued writes, so that each material is only modified, and uploaded to the
+/// GPU, once per frame no matter how many of its parameters changed.
+pub(crate) fn apply_material_params<M: Asset>(
+    mut queue: ResMut<MaterialParamQueue<M>>,
+    materials: Option<ResMut<Assets<M>>>,
+) {
+    if queue.writes.is_empty() {
+        return;
+    }
+    let Some(mut materials) = materials else {
+        queue.writes.clear();
+        return;
+    };
+    let mut batches: HashMap<AssetId<M>, Vec<MaterialWrite<M>>> = HashMap::default();
+    for (id, write) in queue.writes.drain(..) {
+        batches.entry(id).or_default().push(write);
+    }
+    for (id, writes) in batches {
+        if let Some(material) = materials.get_mut(id) {
+            for write in writes {
+                write(material);
+            }
+        }
+    }
+}
+
+/// Methods for driving the parameters of a material from signals.
+pub trait BindMaterialParam {
+    /// Create an effect which writes the value of `value` into the material of type `M` used
+    /// by the `target` entity, whenever the value changes or the entity's material is
+    /// replaced. Writes are batched, so that several parameters bound to the same material
+    /// only modify it once per frame.
+    ///
+    /// The write modifies the material asset itself, so it affects every entity which uses
+    /// the same material handle. To change the parameters of a single entity, give it its
+    /// own copy of the material first.
+    ///
+    /// [`MaterialParamPlugin::<M>`](MaterialParamPlugin) must be added to the app; otherwise
+    /// a warning is logged and the parameter is not applied.
+    ///
+    /// Arguments:
+    /// * `target` - The entity whose `Handle<M>` selects the material.
+    /// * `value` - The signal to read.
+    /// * `apply` - Function which writes the value into the material.
+    fn bind_material_param<
+        M: Asset,
+        T: Clone + Send + Sync + 'static,
+        F: Fn(&mut M, &T) + Send + Sync + 'static,
+    >(
+        &mut self,
+        target: Entity,
+        value: Signal<T>,
+        apply: F,
+    );
+}
+
+impl<'p, 'w, Props> BindMaterialParam for Cx<'p, 'w, Props> {
+    fn bind_material_param<
+        M: Asset,
+        T: Clone + Send + Sync + 'static,
+        F: Fn(&mut M, &T) + Send + Sync + 'static,
+    >(
+        &mut self,
+        target: Entity,
+        value: Signal<T>,
+        apply: F,
+    ) {
+        if !self
+            .world_mut()
+            .contains_resource::<MaterialParamQueue<M>>()
+        {
+            warn!(
+                "bind_material_param: MaterialParamPlugin::<{}> has not been added to the app, \
+                so the parameter will not be applied",
+                std::any::type_name::<M>()
+            );
+            return;
+        }
+        let apply = Arc::new(apply);
+        self.create_effect(move |cx| {
+            let value = value.get_clone(cx);
+            let Some(id) = cx
+                .use_component::<Handle<M>>(target)
+                .map(|handle| handle.id())
+            else {
+                return;
+            };
+            let apply = apply.clone();
+            if let Some(mut queue) = cx.world_mut().get_resource_mut::<MaterialParamQueue<M>>() {
+                queue
+                    .writes
+                    .push((id, Box::new(move |material| apply(material, &value))));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::reflect::TypePath;
+
+    use super::*;
+    use crate::{testing::TestHarness, EmptyView, PresenterFn, RegisterSignal, View};
+
+    #[derive(Asset, TypePath, Default)]
+    struct TestMaterial {
+        roughness: f32,
+        tint: f32,
+    }
+
+    fn inspector(cx: &mut Cx<Entity>) -> impl View {
+        let target = cx.props;
+        let roughness = cx.create_mutable(0.5);
+        cx.bind_material_param(target, roughness.signal(), |m: &mut TestMaterial, v| {
+            m.roughness = *v
+        });
+        cx.bind_material_param(target, Signal::Constant(2.), |m: &mut TestMaterial, v| {
+            m.tint = *v
+        });
+        cx.register_signal("roughness", roughness.signal());
+        EmptyView
+    }
+
+    #[test]
+    fn test_bind_material_param() {
+        let mut harness = TestHarness::new();
+        harness
+            .app
+            .init_asset::<TestMaterial>()
+            .add_plugins(MaterialParamPlugin::<TestMaterial>::default());
+        let handle = harness
+            .world_mut()
+            .resource_mut::<Assets<TestMaterial>>()
+            .add(TestMaterial::default());
+        let target = harness.world_mut().spawn(handle.clone()).id();
+        harness.mount(inspector.bind(target));
+
+        let roughness = harness.mutable::<f32>("roughness");
+        let materials = harness.world().resource::<Assets<TestMaterial>>();
+        assert_eq!(materials.get(&handle).unwrap().roughness, 0.5);
+        assert_eq!(materials.get(&handle).unwrap().tint, 2.);
+
+        harness.set(roughness, 0.25);
+        let materials = harness.world().resource::<Assets<TestMaterial>>();
+        assert_eq!(materials.get(&handle).unwrap().roughness, 0.25);
+    }
+
+    #[test]
+    fn test_missing_plugin() {
+        let mut harness = TestHarness::new();
+        harness.app.init_asset::<TestMaterial>();
+        let handle = harness
+            .world_mut()
+            .resource_mut::<Assets<TestMaterial>>()
+            .add(TestMaterial::default());
+        let target = harness.world_mut().spawn(handle.clone()).id();
+        harness.mount(inspector.bind(target));
+
+        // Without the plugin the parameters are not applied, and nothing is queued.
+        let materials = harness.world().resource::<Assets<TestMaterial>>();
+        assert_eq!(materials.get(&handle).unwrap().roughness, 0.);
+        assert!(!harness
+            .world()
+            .contains_resource::<MaterialParamQueue<TestMaterial>>());
+    }
+}
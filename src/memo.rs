@@ -0,0 +1,151 @@
+use std::sync::Arc;
+
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{DespawnScopes, DisplayNodeChanged, Rcx, TrackingScope, View, ViewFactory, ViewHandle};
+
+/// A view which wraps a [`ViewFactory`] produced by a reactive closure, and only rebuilds the
+/// factory's view when the factory's properties change. Whenever the dependencies of the
+/// closure change, it is called again, and the new factory is compared with the previous
+/// one; if they are equal, the new factory is discarded and the existing view is kept.
+///
+/// This is useful for expensive subtrees, such as the contents of a dialog, whose inputs
+/// are read reactively but rarely change.
+///
+/// ```ignore
+/// Memo::new(move |cx| TextLabel::new(title.get_clone(cx)))
+/// ```
+pub struct Memo<VF: ViewFactory, F: Fn(&Rcx) -> VF, Cmp: Fn(&VF, &VF) -> bool> {
+    factory_fn: F,
+    cmp: Cmp,
+    state: Option<(Arc<VF>, ViewHandle, Entity)>,
+}
+
+impl<VF: ViewFactory + PartialEq, F: Fn(&Rcx) -> VF> Memo<VF, F, fn(&VF, &VF) -> bool> {
+    /// Construct a new `Memo` which compares factories using `PartialEq`.
+    pub fn new(factory_fn: F) -> Self {
+        Self::with_comparator(factory_fn, VF::eq)
+    }
+}
+
+impl<VF: ViewFactory, F: Fn(&Rcx) -> VF, Cmp: Fn(&VF, &VF) -> bool> Memo<VF, F, Cmp> {
+    /// Construct a new `Memo` which compares factories using a custom comparator, for
+    /// factories whose properties don't implement `PartialEq`, or where only some of the
+    /// properties affect the output. The comparator should return true if the factories
+    /// would produce the same view.
+    pub fn with_comparator(factory_fn: F, cmp: Cmp) -> Self {
+        Self {
+            factory_fn,
+            cmp,
+            state: None,
+        }
+    }
+}
+
+impl<VF: ViewFactory + Send + Sync + 'static, F: Fn(&Rcx) -> VF, Cmp: Fn(&VF, &VF) -> bool> View
+    for Memo<VF, F, Cmp>
+{
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((_, ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world.entity_mut(view_entity).insert(Name::new("Memo"));
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let factory = (self.factory_fn)(&Rcx::new(world, tracking));
+        if let Some((ref prev, _, _)) = self.state {
+            if (self.cmp)(prev, &factory) {
+                return;
+            }
+        }
+        if let Some((_, view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        let factory = Arc::new(factory);
+        let view = ViewHandle::from(factory.clone());
+        let entity = ViewHandle::spawn(&view, view_entity, world);
+        self.state = Some((factory, view, entity));
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((_, view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<
+        VF: ViewFactory + Send + Sync + 'static,
+        F: Fn(&Rcx) -> VF + Send + Sync + 'static,
+        Cmp: Fn(&VF, &VF) -> bool + Send + Sync + 'static,
+    > From<Memo<VF, F, Cmp>> for ViewHandle
+{
+    fn from(value: Memo<VF, F, Cmp>) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup, TextStatic,
+    };
+
+    #[derive(Resource, Default)]
+    struct Builds(usize);
+
+    #[derive(PartialEq)]
+    struct Label(String);
+
+    impl ViewFactory for Label {
+        fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+            cx.world_mut().resource_mut::<Builds>().0 += 1;
+            TextStatic::new(self.0.clone())
+        }
+    }
+
+    fn parent(cx: &mut Cx) -> impl View {
+        let count = cx.create_mutable::<i32>(0);
+        cx.register_signal("count", count.signal());
+        Memo::new(move |cx| {
+            let parity = if count.get(cx) % 2 == 0 {
+                "Even"
+            } else {
+                "Odd"
+            };
+            Label(parity.to_string())
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Builds>();
+        let root = harness.mount(parent.bind(()));
+        assert_eq!(harness.text(root), "Even");
+        assert_eq!(harness.world().resource::<Builds>().0, 1);
+
+        // Equal props reuse the existing view.
+        let count = harness.mutable::<i32>("count");
+        harness.set(count, 2);
+        assert_eq!(harness.text(root), "Even");
+        assert_eq!(harness.world().resource::<Builds>().0, 1);
+
+        harness.set(count, 3);
+        assert_eq!(harness.text(root), "Odd");
+        assert_eq!(harness.world().resource::<Builds>().0, 2);
+    }
+}
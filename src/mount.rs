@@ -0,0 +1,187 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use bevy::prelude::*;
+
+use crate::{effect_target::EntityEffect, TrackingScope};
+
+/// A function called with one of an element's entities during its lifecycle.
+pub(crate) type LifecycleFn = Arc<dyn Fn(&mut World, Entity) + Send + Sync + 'static>;
+
+/// Determines when a mount callback runs, relative to the construction and layout of the
+/// view hierarchy.
+///
+/// Views are mounted with the following ordering guarantees:
+/// * An element's effects are started before those of its children, but not until the entire
+///   view hierarchy being built has been constructed.
+/// * Mount callbacks are not called until the entire view hierarchy being built has been
+///   constructed and its display nodes attached, so they never observe a half-built subtree.
+/// * Within a phase, a parent's mount callbacks are called before those of its children.
+/// * When a view is razed, its children are razed (and their unmount callbacks called)
+///   before the parent's unmount callbacks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MountPhase {
+    /// Run after the view hierarchy has been built, but before UI layout. Computed sizes
+    /// and positions of nodes are not yet valid.
+    #[default]
+    Build,
+
+    /// Run after the first UI layout pass following the construction of the view, so that
+    /// the computed size and position of the display nodes can be measured.
+    Layout,
+}
+
+/// Queue of mount callbacks waiting for their phase to arrive.
+#[derive(Resource, Default)]
+pub(crate) struct PendingMounts {
+    build: Vec<(Entity, LifecycleFn)>,
+    layout: Vec<(Entity, LifecycleFn)>,
+}
+
+impl PendingMounts {
+    /// Queue a mount callback. Callbacks are run in the order they were queued, so parents
+    /// should queue their callbacks before building their children.
+    pub(crate) fn push(world: &mut World, phase: MountPhase, target: Entity, f: LifecycleFn) {
+        let mut pending = world.get_resource_or_insert_with(PendingMounts::default);
+        match phase {
+            MountPhase::Build => pending.build.push((target, f)),
+            MountPhase::Layout => pending.layout.push((target, f)),
+        }
+    }
+}
+
+fn run_pending(world: &mut World, take: fn(&mut PendingMounts) -> Vec<(Entity, LifecycleFn)>) {
+    loop {
+        let Some(mut pending) = world.get_resource_mut::<PendingMounts>() else {
+            return;
+        };
+        let queued = take(&mut pending);
+        if queued.is_empty() {
+            return;
+        }
+        for (target, f) in queued {
+            // The view may have been razed before the callback had a chance to run.
+            if world.get_entity(target).is_some() {
+                f(world, target);
+            }
+        }
+    }
+}
+
+/// System which runs mount callbacks for views built during this frame.
+pub(crate) fn run_build_mounts(world: &mut World) {
+    run_pending(world, |pending| std::mem::take(&mut pending.build));
+}
+
+/// System which runs mount callbacks which were waiting for UI layout.
+pub(crate) fn run_layout_mounts(world: &mut World) {
+    run_pending(world, |pending| std::mem::take(&mut pending.layout));
+}
+
+/// The effects of an element, shared with the queue of effects waiting to be started.
+pub(crate) type SharedEffects = Arc<Mutex<Vec<Box<dyn EntityEffect>>>>;
+
+/// Queue of element effects waiting to be started. Effects are started once the outermost
+/// view being built is complete, in the order they were queued, so that a parent's effects
+/// start before those of its children, and no effect observes a half-built subtree.
+#[derive(Resource, Default)]
+pub(crate) struct PendingEffects {
+    building: bool,
+    queue: VecDeque<(Entity, Entity, SharedEffects)>,
+}
+
+impl PendingEffects {
+    /// Called at the start of building an element. Returns true if this is the outermost
+    /// build, in which case the caller must call [`PendingEffects::finish`] once its subtree
+    /// has been built.
+    pub(crate) fn begin(world: &mut World) -> bool {
+        let mut pending = world.get_resource_or_insert_with(PendingEffects::default);
+        !std::mem::replace(&mut pending.building, true)
+    }
+
+    /// Returns true if a view hierarchy is currently being built.
+    pub(crate) fn is_building(world: &World) -> bool {
+        world
+            .get_resource::<PendingEffects>()
+            .is_some_and(|pending| pending.building)
+    }
+
+    /// Queue the effects of an element. The effects' reactions are owned by `view_entity`, and
+    /// they modify `display`.
+    pub(crate) fn push(
+        world: &mut World,
+        view_entity: Entity,
+        display: Entity,
+        effects: SharedEffects,
+    ) {
+        world
+            .resource_mut::<PendingEffects>()
+            .queue
+            .push_back((view_entity, display, effects));
+    }
+
+    /// Start all queued effects, including those of any views built while doing so.
+    pub(crate) fn finish(world: &mut World) {
+        loop {
+            let next = world.resource_mut::<PendingEffects>().queue.pop_front();
+            let Some((view_entity, display, effects)) = next else {
+                break;
+            };
+            // The view may have been razed before its effects had a chance to start.
+            if world.get_entity(view_entity).is_none() || world.get_entity(display).is_none() {
+                continue;
+            }
+            let mut tracking = TrackingScope::new(world.read_change_tick());
+            for effect in effects.lock().unwrap().iter_mut() {
+                effect.start(display, world, &mut tracking);
+            }
+            world.entity_mut(view_entity).insert(tracking);
+        }
+        world.resource_mut::<PendingEffects>().building = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, EffectTarget, Element, ParentView, RunContextWrite};
+
+    #[derive(Resource, Default)]
+    struct Log(Vec<&'static str>);
+
+    fn logged(name: &'static str) -> Element<NodeBundle> {
+        Element::<NodeBundle>::new()
+            .create_effect(move |cx: &mut Cx, _| {
+                cx.world_mut().resource_mut::<Log>().0.push(name);
+            })
+            .on_mount(move |world, _| world.resource_mut::<Log>().0.push(name))
+    }
+
+    #[test]
+    fn test_mount_order() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Log>();
+        harness.mount(
+            logged("parent")
+                .create_effect(|cx: &mut Cx, display| {
+                    // The children are attached before any effects start.
+                    let children = cx
+                        .world_mut()
+                        .get::<Children>(display)
+                        .map_or(0, |c| c.len());
+                    assert_eq!(children, 2);
+                })
+                .with_children((
+                    logged("first").with_children(logged("grandchild")),
+                    logged("second"),
+                )),
+        );
+
+        // Effects run first, then mount callbacks, parents before children in both cases.
+        let order = ["parent", "first", "grandchild", "second"];
+        let expected: Vec<&str> = order.iter().chain(order.iter()).copied().collect();
+        assert_eq!(harness.world().resource::<Log>().0, expected);
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{signal::Signal, RunContextWrite};
+use crate::{
+    reaction_report::{commit_mutable_writes, record_mutable_write},
+    signal::Signal,
+    RunContextWrite,
+};
 use bevy::prelude::*;
 use std::any::Any;
 
@@ -25,6 +29,16 @@ pub(crate) struct MutableCell(pub(crate) Box<dyn Any + Send + Sync + 'static>);
 #[derive(Component)]
 pub(crate) struct MutableNextCell(pub(crate) Option<Box<dyn Any + Send + Sync + 'static>>);
 
+impl MutableNextCell {
+    /// Set the value which `mutable` will take when mutables are next committed.
+    pub(crate) fn set<T: Send + Sync + 'static>(world: &mut World, mutable: Entity, value: T) {
+        world
+            .entity_mut(mutable)
+            .insert(MutableNextCell(Some(Box::new(value))));
+        record_mutable_write(world, mutable);
+    }
+}
+
 /// Contains a reference to a reactive mutable variable.
 #[derive(PartialEq)]
 pub struct Mutable<T> {
@@ -37,6 +51,14 @@ impl<T> Mutable<T> {
     pub fn id(&self) -> Entity {
         self.id
     }
+
+    /// Give the mutable a debug name, which is shown in reaction traces.
+    pub fn named<R: RunContextWrite>(self, cx: &mut R, name: &str) -> Self {
+        cx.world_mut()
+            .entity_mut(self.id)
+            .insert(Name::new(name.to_string()));
+        self
+    }
 }
 
 impl<T> Copy for Mutable<T> {}
@@ -121,6 +143,73 @@ where
     }
 }
 
+impl<T> Mutable<T>
+where
+    T: PartialEq + Send + Sync + 'static,
+{
+    /// Split this [`Mutable`] into a read-only [`Signal`] and a write-only [`SignalSetter`].
+    /// This allows a parent to hand the setter to a child control while the rest of the
+    /// code only has read access, in the manner of a controlled component.
+    pub fn split(self) -> (Signal<T>, SignalSetter<T>) {
+        (
+            Signal::Mutable(self),
+            SignalSetter {
+                id: self.id,
+                marker: std::marker::PhantomData,
+            },
+        )
+    }
+}
+
+/// The write half of a [`Mutable`] which has been split using [`Mutable::split`].
+#[derive(PartialEq)]
+pub struct SignalSetter<T> {
+    pub(crate) id: Entity,
+    pub(crate) marker: std::marker::PhantomData<T>,
+}
+
+impl<T> SignalSetter<T> {
+    /// The entity that holds the mutable value.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+}
+
+impl<T> Copy for SignalSetter<T> {}
+impl<T> Clone for SignalSetter<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> SignalSetter<T>
+where
+    T: PartialEq + Copy + Send + Sync + 'static,
+{
+    /// Set the value of the underlying [`Mutable`] with Copy semantics.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `value`: The new value.
+    pub fn set<R: WriteMutable>(&self, cx: &mut R, value: T) {
+        cx.write_mutable(self.id, value);
+    }
+}
+
+impl<T> SignalSetter<T>
+where
+    T: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Set the value of the underlying [`Mutable`] with Clone semantics.
+    ///
+    /// Arguments:
+    /// * `cx`: The reactive context.
+    /// * `value`: The new value.
+    pub fn set_clone<R: WriteMutable>(&self, cx: &mut R, value: T) {
+        cx.write_mutable_clone(self.id, value);
+    }
+}
+
 /// Trait for low-level read-access to mutables given an entity id.
 pub trait ReadMutable {
     /// Read the value of a mutable variable using Copy semantics. Calling this function adds the
@@ -162,6 +251,7 @@ pub trait WriteMutable {
 }
 
 pub(crate) fn commit_mutables(world: &mut World) {
+    commit_mutable_writes(world);
     for (mut sig_val, mut sig_next) in world
         .query::<(&mut MutableCell, &mut MutableNextCell)>()
         .iter_mut(world)
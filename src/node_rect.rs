@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use crate::RunContextRead;
+
+/// Methods for reading the layout of UI nodes reactively.
+pub trait UseNodeRect {
+    /// Returns the border box of a UI node after layout, in logical window pixels, taking
+    /// [`UiScale`] into account. The current scope will react when the node is laid out at a
+    /// different position or size, or when the UI scale changes. Returns `None` if the entity
+    /// is not a UI node.
+    fn use_node_rect(&self, entity: Entity) -> Option<Rect>;
+}
+
+impl<R: RunContextRead> UseNodeRect for R {
+    fn use_node_rect(&self, entity: Entity) -> Option<Rect> {
+        let size = self.use_component_field(entity, |node: &Node| node.size())?;
+        let center = self.use_component_field(entity, |transform: &GlobalTransform| {
+            transform.translation().truncate()
+        })?;
+        let scale = *self.use_resource_field(|ui_scale: &UiScale| &ui_scale.0);
+        Some(Rect::from_center_size(center * scale, size * scale))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, RunContextSetup, TrackingScope};
+
+    #[test]
+    fn test_use_node_rect() {
+        let mut harness = TestHarness::new();
+        let world = harness.world_mut();
+        world.insert_resource(UiScale(2.));
+        let node = world
+            .spawn((
+                Node::default(),
+                GlobalTransform::from_translation(Vec3::new(50., 20., 0.)),
+            ))
+            .id();
+        let rect = {
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            let mut cx = Cx::new((), world, &mut scope);
+            let rect = cx.create_mutable(None);
+            cx.create_effect(move |cx| {
+                let value = cx.use_node_rect(node);
+                rect.set(cx, value);
+            });
+            rect
+        };
+        harness.update_frames(2);
+        assert_eq!(
+            rect.get(harness.world()),
+            Some(Rect::from_center_size(Vec2::new(100., 40.), Vec2::ZERO))
+        );
+
+        // Moving the node updates the rect.
+        harness
+            .world_mut()
+            .entity_mut(node)
+            .insert(GlobalTransform::from_translation(Vec3::new(60., 20., 0.)));
+        harness.update_frames(2);
+        assert_eq!(
+            rect.get(harness.world()),
+            Some(Rect::from_center_size(Vec2::new(120., 40.), Vec2::ZERO))
+        );
+    }
+}
@@ -59,6 +59,20 @@ pub trait ParentView: Sized {
         self
     }
 
+    /// Insert a child view at the given position among this element's children. If `index`
+    /// is greater than the number of children, the view is appended.
+    fn insert_child_at(mut self, index: usize, view: &ViewHandle) -> Self {
+        let index = index.min(self.children().len());
+        self.children_mut().insert(
+            index,
+            ChildView {
+                view: view.clone(),
+                entity: None,
+            },
+        );
+        self
+    }
+
     /// Raze all child views.
     fn raze_children(&mut self, world: &mut World) {
         // Raze all child views
@@ -123,3 +137,179 @@ impl ChildViewTuple for Tuple {
         ViewHandle::new(Fragment::new(self))
     }
 }
+
+/// A list of child views whose length and types are only known at runtime, such as children
+/// built in a loop, or included conditionally. It can be passed anywhere a tuple of children
+/// is accepted, such as [`ParentView::with_children`].
+#[derive(Default, Clone)]
+pub struct ChildArray(Vec<ViewHandle>);
+
+impl ChildArray {
+    /// Construct an empty list of children.
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Add a child view to the end of the list.
+    pub fn push(&mut self, view: impl Into<ViewHandle>) {
+        self.0.push(view.into());
+    }
+
+    /// Add the child view returned by `view_fn` if `condition` is true. The view is only
+    /// constructed when it is added.
+    pub fn push_if<V: Into<ViewHandle>>(&mut self, condition: bool, view_fn: impl FnOnce() -> V) {
+        if condition {
+            self.0.push(view_fn().into());
+        }
+    }
+
+    /// Add a child view, returning the list, for use in builder chains.
+    pub fn with(mut self, view: impl Into<ViewHandle>) -> Self {
+        self.push(view);
+        self
+    }
+
+    /// Add the child view returned by `view_fn` if `condition` is true, returning the list.
+    pub fn with_if<V: Into<ViewHandle>>(
+        mut self,
+        condition: bool,
+        view_fn: impl FnOnce() -> V,
+    ) -> Self {
+        self.push_if(condition, view_fn);
+        self
+    }
+
+    /// The number of child views.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if there are no child views.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl From<Vec<ViewHandle>> for ChildArray {
+    fn from(views: Vec<ViewHandle>) -> Self {
+        Self(views)
+    }
+}
+
+impl<V: Into<ViewHandle>> FromIterator<V> for ChildArray {
+    fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+        Self(iter.into_iter().map(|view| view.into()).collect())
+    }
+}
+
+impl<V: Into<ViewHandle>> Extend<V> for ChildArray {
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        self.0.extend(iter.into_iter().map(|view| view.into()));
+    }
+}
+
+impl ChildViewTuple for ChildArray {
+    fn get_handles(self, out: &mut Vec<ViewHandle>) {
+        out.extend(self.0);
+    }
+
+    fn to_vec(self) -> Vec<ViewHandle> {
+        self.0
+    }
+
+    fn fragment(self) -> ViewHandle {
+        ViewHandle::new(Fragment::new(self))
+    }
+}
+
+impl ChildViewTuple for Vec<ViewHandle> {
+    fn get_handles(self, out: &mut Vec<ViewHandle>) {
+        out.extend(self);
+    }
+
+    fn to_vec(self) -> Vec<ViewHandle> {
+        self
+    }
+
+    fn fragment(self) -> ViewHandle {
+        ViewHandle::new(Fragment::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{testing::TestHarness, view, Cx, Element, PresenterFn, RegisterSignal, View};
+
+    #[derive(Resource)]
+    struct Labels(Vec<&'static str>, bool);
+
+    fn toolbar(cx: &mut Cx) -> impl View {
+        let labels = cx.world().resource::<Labels>();
+        let show_help = labels.1;
+        let buttons: ChildArray = labels.0.iter().copied().collect();
+        Element::<NodeBundle>::new().with_children((
+            buttons.with_if(show_help, || "Help"),
+            vec![ViewHandle::from("!")],
+        ))
+    }
+
+    fn menu(_cx: &mut Cx) -> impl View {
+        Element::<NodeBundle>::new()
+            .with_children(("Open", "Close"))
+            .insert_child_at(1, &"Save".into())
+            .insert_child_at(10, &"Quit".into())
+    }
+
+    #[test]
+    fn test_insert_child_at() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(menu.bind(()));
+        assert_eq!(harness.text(root), "OpenSaveCloseQuit");
+    }
+
+    #[test]
+    fn test_child_array() {
+        let mut harness = TestHarness::new();
+        harness
+            .world_mut()
+            .insert_resource(Labels(vec!["Cut", "Copy", "Paste"], true));
+        let root = harness.mount(toolbar.bind(()));
+        assert_eq!(harness.text(root), "CutCopyPasteHelp!");
+    }
+
+    fn counter(cx: &mut Cx) -> impl View {
+        let count = cx.create_mutable(0);
+        cx.register_signal("count", count.signal());
+        view! {
+            <NodeBundle>
+                "Count: "
+                {move |cx| count.get(cx).to_string()}
+                if move |cx| count.get(cx) > 1 {
+                    " many "
+                } else {
+                    " few "
+                }
+                for i in move |cx| 0..count.get(cx) {
+                    <NodeBundle>{i.to_string()}</NodeBundle>
+                }
+            </NodeBundle>
+        }
+    }
+
+    #[test]
+    fn test_view_macro() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(counter.bind(()));
+        assert_eq!(harness.text(root), "Count: 0 few ");
+
+        let count = harness.mutable::<i32>("count");
+        harness.set(count, 3);
+        assert_eq!(harness.text(root), "Count: 3 many 012");
+
+        harness.set(count, 1);
+        assert_eq!(harness.text(root), "Count: 1 few 0");
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::{
+    ecs::{component::Component, entity::Entity, world::World},
+    hierarchy::{Children, Parent},
+};
+
+use crate::{node_span::NodeSpan, TrackingScope, ViewHandle};
+
+/// Marker component which suspends a tracking scope: while present, the scope does not react
+/// to changes in its dependencies. Changes are not lost; if any dependency changed while the
+/// scope was paused, it reacts once the marker is removed.
+#[derive(Component)]
+pub struct ReactionsPaused;
+
+/// Component on an element's display node which identifies the view entity that produced it.
+#[derive(Component)]
+pub(crate) struct DisplayNodeView(pub(crate) Entity);
+
+/// Methods for suspending the reactions of the views which produced a part of the display
+/// tree, for example while it is scrolled out of sight.
+pub trait PauseReactions {
+    /// Pause the reactions of the views which produced the given display node, including its
+    /// child views and any effects or derived signals they own.
+    fn pause_reactions(&mut self, display_node: Entity);
+
+    /// Resume the reactions paused by [`PauseReactions::pause_reactions`].
+    fn resume_reactions(&mut self, display_node: Entity);
+}
+
+impl PauseReactions for World {
+    fn pause_reactions(&mut self, display_node: Entity) {
+        set_reactions_paused(self, display_node, true);
+    }
+
+    fn resume_reactions(&mut self, display_node: Entity) {
+        set_reactions_paused(self, display_node, false);
+    }
+}
+
+fn set_reactions_paused(world: &mut World, display_node: Entity, paused: bool) {
+    let Some(mut root) = world.get::<DisplayNodeView>(display_node).map(|v| v.0) else {
+        return;
+    };
+
+    // Include views which wrap the element without producing any other nodes, such as the
+    // presenter which created it.
+    while let Some(parent) = world.get::<Parent>(root).map(|p| p.get()) {
+        match world.get::<ViewHandle>(parent).map(|view| view.nodes()) {
+            Some(NodeSpan::Node(node)) if node == display_node => root = parent,
+            _ => break,
+        }
+    }
+
+    let mut stack = vec![root];
+    while let Some(entity) = stack.pop() {
+        let Some(mut entt) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        if let Some(children) = entt.get::<Children>() {
+            stack.extend(children.iter().copied());
+        }
+        let Some(scope) = entt.get::<TrackingScope>() else {
+            continue;
+        };
+        stack.extend(scope.owned().iter().copied());
+        if paused {
+            entt.insert(ReactionsPaused);
+        } else {
+            entt.remove::<ReactionsPaused>();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, Element, PresenterFn, RegisterSignal, RunContextSetup,
+        TextComputed,
+    };
+
+    fn counter(cx: &mut Cx) -> impl crate::View {
+        let count = cx.create_mutable::<i32>(0);
+        cx.register_signal("count", count.signal());
+        Element::<NodeBundle>::new().with_children(TextComputed::new(move |cx| {
+            format!("Count: {}", count.get(cx))
+        }))
+    }
+
+    #[test]
+    fn test_pause_reactions() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(counter.bind(()));
+        let count = harness.mutable::<i32>("count");
+        let node = harness.nodes(root)[0];
+
+        harness.world_mut().pause_reactions(node);
+        harness.set(count, 1);
+        assert_eq!(harness.text(root), "Count: 0");
+
+        // Changes made while paused are applied on resume.
+        harness.world_mut().resume_reactions(node);
+        harness.update();
+        assert_eq!(harness.text(root), "Count: 1");
+    }
+}
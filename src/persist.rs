@@ -0,0 +1,249 @@
+use std::{
+    any::Any,
+    io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{app::AppExit, ecs::component::Tick, prelude::*, utils::HashMap};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{mutable::MutableCell, Mutable, RunContextWrite};
+
+/// Function which serializes the value of a mutable.
+type SerializeFn = fn(&(dyn Any + Send + Sync)) -> Option<String>;
+
+/// A mutable registered for persistence.
+struct PersistEntry {
+    key: String,
+    mutable: Entity,
+    /// Change tick at which the value was last captured.
+    tick: Tick,
+    serialize: SerializeFn,
+}
+
+fn serialize_value<T: Serialize + 'static>(value: &(dyn Any + Send + Sync)) -> Option<String> {
+    ron::to_string(value.downcast_ref::<T>()?).ok()
+}
+
+/// Read saved state from a file.
+#[cfg(not(target_arch = "wasm32"))]
+fn read_state(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path).ok()
+}
+
+/// Write saved state to a file.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_state(path: &Path, text: &str) -> io::Result<()> {
+    std::fs::write(path, text)
+}
+
+/// The browser's local storage, which takes the place of the filesystem on the web.
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> io::Result<web_sys::Storage> {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::Unsupported, "no local storage"))
+}
+
+/// Read saved state from the browser's local storage, using the path as the key.
+#[cfg(target_arch = "wasm32")]
+fn read_state(path: &Path) -> Option<String> {
+    local_storage()
+        .ok()?
+        .get_item(&path.to_string_lossy())
+        .ok()
+        .flatten()
+}
+
+/// Write saved state to the browser's local storage, using the path as the key.
+#[cfg(target_arch = "wasm32")]
+fn write_state(path: &Path, text: &str) -> io::Result<()> {
+    local_storage()?
+        .set_item(&path.to_string_lossy(), text)
+        .map_err(|_| io::Error::other("local storage is full"))
+}
+
+/// Resource holding persisted UI state: the serialized values of mutables which have been
+/// registered with [`Mutable::persist`], keyed by name. Values are restored when a mutable
+/// is registered, and the latest values are written back to disk when the app exits. The
+/// last known value of a mutable is kept after it is despawned, so that state such as the
+/// size of a closed panel is still saved.
+///
+/// On the web, state is kept in the browser's local storage, using the path as the key.
+/// Since closing a browser tab doesn't send `AppExit`, the state is saved whenever it
+/// changes instead.
+#[derive(Resource, Default)]
+pub struct PersistedState {
+    path: Option<PathBuf>,
+    values: HashMap<String, String>,
+    entries: Vec<PersistEntry>,
+    /// True if values have been captured since the state was last saved.
+    dirty: bool,
+}
+
+impl PersistedState {
+    /// Load persisted state from a file. A missing or unreadable file results in empty
+    /// state, since there may be no saved state on the first run.
+    pub fn load(path: impl AsRef<Path>) -> Self {
+        let path = path.as_ref();
+        let values = read_state(path)
+            .and_then(|text| match ron::from_str(&text) {
+                Ok(values) => Some(values),
+                Err(err) => {
+                    warn!("Ignoring persisted state in {:?}: {}", path, err);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        Self {
+            path: Some(path.to_path_buf()),
+            values,
+            entries: Vec::new(),
+            dirty: false,
+        }
+    }
+
+    /// Write the persisted state to the file it was loaded from. Does nothing for state
+    /// which was not loaded from a file.
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let text = ron::ser::to_string_pretty(&self.values, Default::default())
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        write_state(path, &text)
+    }
+
+    /// Capture the current values of all registered mutables which have changed.
+    fn capture(&mut self, world: &World) {
+        let this_run = world.read_change_tick();
+        self.entries.retain_mut(|entry| {
+            let Some(entity) = world.get_entity(entry.mutable) else {
+                // Keep the last captured value, but stop tracking.
+                return false;
+            };
+            if let (Some(cell), Some(ticks)) = (
+                entity.get::<MutableCell>(),
+                entity.get_change_ticks::<MutableCell>(),
+            ) {
+                if ticks.is_changed(entry.tick, this_run) {
+                    if let Some(text) = (entry.serialize)(cell.0.as_ref()) {
+                        if self.values.get(&entry.key) != Some(&text) {
+                            self.values.insert(entry.key.clone(), text);
+                            self.dirty = true;
+                        }
+                    }
+                    entry.tick = this_run;
+                }
+            }
+            true
+        });
+    }
+}
+
+impl<T> Mutable<T>
+where
+    T: Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    /// Register this mutable for persistence under the given key. If a value was saved
+    /// under this key by a previous run, it replaces the current value of the mutable.
+    /// Requires the [`PersistPlugin`]; otherwise this does nothing.
+    pub fn persist<R: RunContextWrite>(self, cx: &mut R, key: &str) -> Self {
+        let world = cx.world_mut();
+        let tick = world.read_change_tick();
+        let Some(mut state) = world.get_resource_mut::<PersistedState>() else {
+            warn!("Mutable::persist() requires PersistPlugin");
+            return self;
+        };
+        let restored = state
+            .values
+            .get(key)
+            .and_then(|text| ron::from_str::<T>(text).ok());
+        state.entries.push(PersistEntry {
+            key: key.to_string(),
+            mutable: self.id,
+            tick,
+            serialize: serialize_value::<T>,
+        });
+        if let Some(value) = restored {
+            if let Some(mut cell) = world.get_mut::<MutableCell>(self.id) {
+                cell.0 = Box::new(value);
+            }
+        }
+        self
+    }
+}
+
+fn capture_persisted_state(world: &mut World) {
+    world.resource_scope(|world, mut state: Mut<PersistedState>| {
+        state.capture(world);
+    });
+}
+
+fn save_persisted_state(mut exit: EventReader<AppExit>, mut state: ResMut<PersistedState>) {
+    let exiting = exit.read().next().is_some();
+    // There is no reliable exit event in the browser.
+    let save = exiting || (cfg!(target_arch = "wasm32") && state.dirty);
+    if save {
+        if let Err(err) = state.save() {
+            error!("Failed to save persisted state: {}", err);
+        }
+        state.dirty = false;
+    }
+}
+
+/// Plugin which enables persistence of mutables registered with [`Mutable::persist`]. State
+/// is loaded from the given file at startup, and saved to it when the app exits. On the web,
+/// the browser's local storage is used instead of a file; see [`PersistedState`].
+pub struct PersistPlugin {
+    path: PathBuf,
+}
+
+impl PersistPlugin {
+    /// Construct a new `PersistPlugin` which stores state in the given file.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Plugin for PersistPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PersistedState::load(&self.path))
+            .add_systems(
+                Last,
+                (capture_persisted_state, save_persisted_state).chain(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{cx::Cx, RunContextSetup, TrackingScope};
+
+    #[test]
+    fn test_persist_restore() {
+        let mut world = World::default();
+        let mut state = PersistedState::default();
+        state
+            .values
+            .insert("width".to_string(), "250.0".to_string());
+        world.insert_resource(state);
+
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        let width = cx.create_mutable::<f32>(100.).persist(&mut cx, "width");
+        assert_eq!(width.get(&cx), 250.);
+
+        // Changes are captured, and kept after the mutable is despawned.
+        width.set(&mut cx, 300.);
+        world.increment_change_tick();
+        crate::mutable::commit_mutables(&mut world);
+        capture_persisted_state(&mut world);
+        world.despawn(width.id());
+        capture_persisted_state(&mut world);
+        let state = world.resource::<PersistedState>();
+        assert_eq!(state.values.get("width").map(String::as_str), Some("300.0"));
+        assert!(state.entries.is_empty());
+    }
+}
@@ -1,32 +1,246 @@
-use bevy::prelude::*;
+use std::time::Duration;
 
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    prelude::*,
+    ui::UiSystem,
+};
+
+#[cfg(feature = "picking")]
+use bevy_mod_picking::prelude::EventListenerPlugin;
+
+#[cfg(feature = "picking")]
+use crate::pointer_capture::{
+    dispatch_captured_pointer, CapturedPointerMove, CapturedPointerUp, PointerCapture,
+};
+#[cfg(feature = "scripting")]
+use crate::scripting::ScriptEngine;
 use crate::{
-    attach_child_views, build_added_view_roots, compositor::update_compositor_size,
-    mutable::commit_mutables, tracking_scope::run_reactions, update_text_styles,
+    attach_child_views, build_added_view_roots,
+    compositor::update_compositor_size,
+    cond::keep_branches_hidden,
+    floating::update_floating_positions,
+    frame_budget::{reset_frame_budget, FrameBudget, DEFAULT_FRAME_BUDGET},
+    mount::{run_build_mounts, run_layout_mounts},
+    mutable::commit_mutables,
+    pointer_lock::{update_pointer_lock, PointerLock},
+    portal::{prune_overlay_stack, OverlayStack},
+    reaction_report::{finish_reaction_report, ReactionReport},
+    signal_registry::{prune_dangling_signals, Signals},
+    text_measure::add_text_measure_cache,
+    tracking_scope::{run_deferred_reactions, run_reactions, ReactorTrace},
+    update_text_styles,
+    window_metrics::{update_window_metrics, WindowMetrics},
+    world_anchor::{update_screen_positions, update_world_anchors},
+    write_conflicts::{report_write_conflicts, WriteConflicts},
 };
 
-/// Plugin that adds the reactive UI system to the app.
-pub struct ReactorPlugin;
+/// System set containing the processing of reactions: committing mutables, building new
+/// view roots, running reactions and re-attaching changed display nodes. Game systems which
+/// modify state displayed by the UI can be ordered before this set so that the changes are
+/// reflected in the same frame.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ReactorSet;
+
+/// An individual reaction pass within [`ReactorSet`].
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+struct ReactorPass(usize);
+
+/// Plugin that adds the reactive UI system to the app. The plugin can be added with its
+/// default settings as `app.add_plugins(ReactorPlugin)`, or configured starting from
+/// `ReactorPlugin::default()`.
+pub struct ReactorPlugin {
+    trace: bool,
+    write_conflicts: bool,
+    reaction_report: bool,
+    /// The schedule in which reactions are processed, or `None` for `Update`.
+    schedule: Option<InternedScheduleLabel>,
+    passes: usize,
+    text_measure_cache: bool,
+    frame_budget: Duration,
+}
+
+/// The [`ReactorPlugin`] with its default settings, so that the plugin can still be added by
+/// name, as `app.add_plugins(ReactorPlugin)`, as it could before it had settings.
+#[allow(non_upper_case_globals)]
+pub const ReactorPlugin: ReactorPlugin = ReactorPlugin::DEFAULT;
+
+impl Default for ReactorPlugin {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl ReactorPlugin {
+    const DEFAULT: Self = Self {
+        trace: false,
+        write_conflicts: false,
+        reaction_report: false,
+        schedule: None,
+        passes: 1,
+        text_measure_cache: false,
+        frame_budget: DEFAULT_FRAME_BUDGET,
+    };
+
+    /// Enable the reaction trace log. When enabled, every reaction is logged each frame,
+    /// along with the signals, components and resources whose changes triggered it. Give
+    /// signals and effects debug names with `.named()` to make the log easier to read.
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Enable warnings about conflicting writes. When enabled, the components changed by each
+    /// element effect, such as `create_effect()`, `insert_computed()` and styles, are
+    /// recorded; if two different effects set the same component of the same entity to
+    /// different values in several consecutive frames, a warning is logged with the source
+    /// locations where the effects were created. Such conflicts typically show up as
+    /// flickering. Values are compared through reflection, so only components whose types
+    /// are registered with the app are checked.
+    pub fn with_write_conflict_warnings(mut self, enabled: bool) -> Self {
+        self.write_conflicts = enabled;
+        self
+    }
+
+    /// Enable the reaction report. When enabled, the [`ReactionReport`] resource is updated
+    /// at the end of each frame with the number of reaction passes which ran, the changes
+    /// which triggered the reactions in each pass, and the longest chain of reactions which
+    /// triggered each other through mutables.
+    pub fn with_reaction_report(mut self, enabled: bool) -> Self {
+        self.reaction_report = enabled;
+        self
+    }
+
+    /// Set the schedule in which reactions are processed. The default is `Update`. Use
+    /// `PostUpdate` to process reactions after game logic has run. In `Update` and
+    /// `PostUpdate`, reactions are processed before UI layout, so their changes are laid out
+    /// in the same frame; in a schedule which runs after `PostUpdate`, such as `Last`, the
+    /// changes are not laid out until the following frame.
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = Some(schedule.intern());
+        self
+    }
+
+    /// Set the number of times reactions are processed per frame. A mutable written by a
+    /// reaction is normally not seen until the following frame; additional passes allow
+    /// such chains of updates to settle within a single frame. The default is one pass.
+    pub fn with_passes(mut self, passes: usize) -> Self {
+        self.passes = passes.max(1);
+        self
+    }
+
+    /// Enable the shared text measurement cache. Text nodes with the same content, font, size
+    /// and scale share a single measurement, and the text layout for each width constraint
+    /// is computed once rather than once per node. This speeds up layout of UIs with many
+    /// identical labels, such as table cells and tree nodes. Cached measurements are
+    /// discarded when their font asset changes.
+    pub fn with_text_measure_cache(mut self, enabled: bool) -> Self {
+        self.text_measure_cache = enabled;
+        self
+    }
+
+    /// Set the time per frame available for reactions and deferrable work, such as building
+    /// rows of a virtualized list ahead of time. Reactions always run, but work which can be
+    /// put off is only done while the [`FrameBudget`] has time remaining. The default is 4ms.
+    pub fn with_frame_budget(mut self, limit: Duration) -> Self {
+        self.frame_budget = limit;
+        self
+    }
+}
 
 impl Plugin for ReactorPlugin {
     fn build(&self, app: &mut App) {
-        app
-            //.register_asset_loader(TextureAtlasLoader)
-            // .add_event::<DeferredCall<f32>>()
+        let schedule = self.schedule.unwrap_or_else(|| Update.intern());
+        if self.trace {
+            app.init_resource::<ReactorTrace>();
+        }
+        if self.write_conflicts {
+            app.init_resource::<WriteConflicts>();
+        }
+        if self.reaction_report {
+            app.init_resource::<ReactionReport>();
+        }
+        #[cfg(feature = "picking")]
+        app.init_resource::<PointerCapture>()
+            .add_plugins((
+                EventListenerPlugin::<CapturedPointerMove>::default(),
+                EventListenerPlugin::<CapturedPointerUp>::default(),
+            ))
+            .add_event::<CapturedPointerMove>()
+            .add_event::<CapturedPointerUp>()
             .add_systems(
-                Update,
+                PreUpdate,
+                dispatch_captured_pointer.after(bevy::input::InputSystem),
+            );
+        #[cfg(feature = "scripting")]
+        app.init_resource::<ScriptEngine>();
+        if self.text_measure_cache {
+            add_text_measure_cache(app);
+        }
+        app.insert_resource(FrameBudget::new(self.frame_budget))
+            .add_systems(First, reset_frame_budget);
+        app.init_resource::<WindowMetrics>()
+            .init_resource::<OverlayStack>()
+            .init_resource::<Signals>()
+            .init_resource::<PointerLock>()
+            .add_systems(
+                PreUpdate,
                 (
-                    (
-                        // run_deferred_callbacks::<f32>,
-                        commit_mutables,
-                        build_added_view_roots,
-                        run_reactions,
-                        attach_child_views,
-                        update_text_styles,
-                    )
-                        .chain(),
-                    update_compositor_size,
+                    update_window_metrics,
+                    update_pointer_lock.after(bevy::input::InputSystem),
                 ),
+            )
+            .configure_sets(schedule, ReactorSet.before(UiSystem::Layout));
+        for pass in 0..self.passes {
+            // Each pass gets its own system instances, so that change detection in each
+            // pass only sees changes made since that pass last ran.
+            app.add_systems(
+                schedule,
+                (
+                    commit_mutables,
+                    build_added_view_roots,
+                    run_reactions,
+                    attach_child_views,
+                    run_build_mounts,
+                )
+                    .chain()
+                    .in_set(ReactorPass(pass))
+                    .in_set(ReactorSet),
             );
+            if pass > 0 {
+                app.configure_sets(schedule, ReactorPass(pass).after(ReactorPass(pass - 1)));
+            }
+        }
+        app.add_systems(
+            schedule,
+            (
+                update_text_styles,
+                keep_branches_hidden,
+                prune_overlay_stack,
+            )
+                .after(ReactorPass(self.passes - 1))
+                .in_set(ReactorSet),
+        )
+        .add_systems(Update, update_compositor_size)
+        .add_systems(
+            PostUpdate,
+            (
+                (run_layout_mounts, run_deferred_reactions).chain(),
+                update_floating_positions,
+            )
+                .after(UiSystem::Layout),
+        )
+        .add_systems(
+            PostUpdate,
+            (update_screen_positions, update_world_anchors).before(UiSystem::Layout),
+        )
+        .add_systems(
+            Last,
+            (
+                prune_dangling_signals,
+                report_write_conflicts,
+                finish_reaction_report,
+            ),
+        );
     }
 }
@@ -0,0 +1,93 @@
+use bevy::{
+    input::{mouse::MouseButtonInput, ButtonState},
+    prelude::*,
+};
+use bevy_mod_picking::prelude::EntityEvent;
+
+/// Resource which records the element, if any, that has captured the mouse pointer. While
+/// the pointer is captured, the capturing element receives [`CapturedPointerMove`] and
+/// [`CapturedPointerUp`] events for all pointer motion, even when the cursor leaves the
+/// element's bounds or the window, and other elements are not considered to be hovered.
+/// Controls typically capture the pointer when a drag starts; the capture is released
+/// automatically when the left mouse button is released.
+#[derive(Resource, Default)]
+pub struct PointerCapture {
+    pub(crate) target: Option<Entity>,
+    origin: Vec2,
+    position: Vec2,
+}
+
+impl PointerCapture {
+    /// Capture the pointer on behalf of `target`. Distances reported by subsequent
+    /// [`CapturedPointerMove`] events are measured from the cursor position at this time.
+    pub fn capture(&mut self, target: Entity) {
+        self.target = Some(target);
+        self.origin = self.position;
+    }
+
+    /// Release the pointer capture.
+    pub fn release(&mut self) {
+        self.target = None;
+    }
+
+    /// The element which has captured the pointer, if any.
+    pub fn target(&self) -> Option<Entity> {
+        self.target
+    }
+}
+
+/// Event sent to the capturing element when the pointer moves.
+#[derive(Clone, Event, EntityEvent)]
+pub struct CapturedPointerMove {
+    /// Event target
+    #[target]
+    pub target: Entity,
+
+    /// Current cursor position, in logical pixels.
+    pub position: Vec2,
+
+    /// Distance the cursor has moved since the capture began.
+    pub distance: Vec2,
+}
+
+/// Event sent to the capturing element when the left mouse button is released, just before
+/// the capture is released.
+#[derive(Clone, Event, EntityEvent)]
+pub struct CapturedPointerUp {
+    /// Event target
+    #[target]
+    pub target: Entity,
+
+    /// Cursor position, in logical pixels.
+    pub position: Vec2,
+}
+
+pub(crate) fn dispatch_captured_pointer(
+    mut capture: ResMut<PointerCapture>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut mouse_button: EventReader<MouseButtonInput>,
+    mut moves: EventWriter<CapturedPointerMove>,
+    mut ups: EventWriter<CapturedPointerUp>,
+) {
+    for ev in cursor_moved.read() {
+        capture.position = ev.position;
+        if let Some(target) = capture.target {
+            moves.send(CapturedPointerMove {
+                target,
+                position: ev.position,
+                distance: ev.position - capture.origin,
+            });
+        }
+    }
+
+    for ev in mouse_button.read() {
+        if ev.button == MouseButton::Left && ev.state == ButtonState::Released {
+            if let Some(target) = capture.target.take() {
+                ups.send(CapturedPointerUp {
+                    target,
+                    position: capture.position,
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::{
+    input::mouse::MouseMotion,
+    prelude::*,
+    window::{CursorGrabMode, PrimaryWindow, WindowFocused},
+};
+
+use crate::RunContextRead;
+
+/// Resource which locks the mouse pointer on behalf of an interaction, such as scrubbing a
+/// value, orbiting a preview viewport, or fine adjustment in a color picker. While locked,
+/// the cursor is hidden and held in place (or confined to the window, on platforms which
+/// don't support locking), and the relative motion of the mouse is reported each frame by
+/// [`delta`](Self::delta), unaffected by the edges of the screen.
+///
+/// The cursor is restored to its previous state, and its original position, when the lock
+/// is released, when the window loses focus, or when the owner entity is despawned.
+#[derive(Resource, Default)]
+pub struct PointerLock {
+    owner: Option<Entity>,
+    /// A sequence number, incremented whenever the motion is updated, paired with the
+    /// motion during the current frame. The sequence number ensures that reactions see a
+    /// change even when the mouse moves by the same amount in consecutive frames.
+    motion: (u64, Vec2),
+    /// The cursor state to restore on release: grab mode, visibility and position.
+    restore: Option<(CursorGrabMode, bool, Option<Vec2>)>,
+}
+
+impl PointerLock {
+    /// Lock the pointer on behalf of `owner`. The lock takes effect at the start of the
+    /// next frame.
+    pub fn lock(&mut self, owner: Entity) {
+        self.owner = Some(owner);
+    }
+
+    /// Release the pointer lock, restoring the cursor.
+    pub fn release(&mut self) {
+        self.owner = None;
+    }
+
+    /// The entity which has locked the pointer, if any.
+    pub fn owner(&self) -> Option<Entity> {
+        self.owner
+    }
+
+    /// Returns true if the pointer is locked.
+    pub fn is_locked(&self) -> bool {
+        self.owner.is_some()
+    }
+
+    /// The relative motion of the mouse during the current frame, in logical pixels. This is
+    /// zero when the pointer is not locked.
+    pub fn delta(&self) -> Vec2 {
+        self.motion.1
+    }
+}
+
+/// Methods for reading the pointer lock reactively.
+pub trait UsePointerLock {
+    /// Returns the relative motion of the locked pointer during the current frame. The
+    /// current scope will react each frame in which the pointer moves while locked.
+    fn use_pointer_delta(&self) -> Vec2;
+
+    /// Returns the entity which has locked the pointer, if any. The current scope will react
+    /// when the pointer is locked or released, including when the lock is released
+    /// automatically because the window lost focus.
+    fn use_pointer_lock_owner(&self) -> Option<Entity>;
+}
+
+impl<R: RunContextRead> UsePointerLock for R {
+    fn use_pointer_delta(&self) -> Vec2 {
+        self.use_resource_field(|lock: &PointerLock| &lock.motion).1
+    }
+
+    fn use_pointer_lock_owner(&self) -> Option<Entity> {
+        *self.use_resource_field(|lock: &PointerLock| &lock.owner)
+    }
+}
+
+/// System which applies the pointer lock to the primary window, and accumulates the motion
+/// of the locked pointer.
+pub(crate) fn update_pointer_lock(
+    mut lock: ResMut<PointerLock>,
+    mut motion: EventReader<MouseMotion>,
+    mut focus: EventReader<WindowFocused>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    entities: Query<()>,
+) {
+    let Ok(mut window) = windows.get_single_mut() else {
+        motion.clear();
+        return;
+    };
+
+    // Release the lock if its owner has gone away, or the user has switched windows.
+    if let Some(owner) = lock.owner {
+        if entities.get(owner).is_err() || focus.read().any(|ev| !ev.focused) {
+            lock.owner = None;
+        }
+    } else {
+        focus.clear();
+    }
+
+    match (lock.owner, lock.restore) {
+        (Some(_), None) => {
+            lock.restore = Some((
+                window.cursor.grab_mode,
+                window.cursor.visible,
+                window.cursor_position(),
+            ));
+            window.cursor.grab_mode = CursorGrabMode::Locked;
+            window.cursor.visible = false;
+        }
+        (None, Some((grab_mode, visible, position))) => {
+            window.cursor.grab_mode = grab_mode;
+            window.cursor.visible = visible;
+            if position.is_some() {
+                window.set_cursor_position(position);
+            }
+            lock.restore = None;
+        }
+        _ => {}
+    }
+
+    // Mouse motion is reported in physical pixels.
+    let delta = if lock.owner.is_some() {
+        motion.read().map(|ev| ev.delta).sum::<Vec2>() / window.scale_factor()
+    } else {
+        motion.clear();
+        Vec2::ZERO
+    };
+    // Only write when there is motion to report, or motion to clear, so that a stationary
+    // pointer doesn't trigger reactions every frame.
+    if delta != Vec2::ZERO || lock.motion.1 != Vec2::ZERO {
+        let sequence = lock.motion.0.wrapping_add(1);
+        lock.motion = (sequence, delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{Rcx, TrackingScope};
+
+    #[test]
+    fn test_pointer_lock() {
+        let mut world = World::default();
+        world.init_resource::<PointerLock>();
+        world.init_resource::<Events<MouseMotion>>();
+        world.init_resource::<Events<WindowFocused>>();
+        let window = world.spawn((Window::default(), PrimaryWindow)).id();
+        let owner = world.spawn_empty().id();
+
+        world.resource_mut::<PointerLock>().lock(owner);
+        world.send_event(MouseMotion {
+            delta: Vec2::new(3., 0.),
+        });
+        world.send_event(MouseMotion {
+            delta: Vec2::new(2., 1.),
+        });
+        world.run_system_once(update_pointer_lock);
+        assert_eq!(world.resource::<PointerLock>().delta(), Vec2::new(5., 1.));
+        let cursor = &world.get::<Window>(window).unwrap().cursor;
+        assert_eq!(cursor.grab_mode, CursorGrabMode::Locked);
+        assert!(!cursor.visible);
+
+        // Losing focus releases the lock and restores the cursor.
+        world.send_event(WindowFocused {
+            window,
+            focused: false,
+        });
+        world.run_system_once(update_pointer_lock);
+        assert!(!world.resource::<PointerLock>().is_locked());
+        assert_eq!(world.resource::<PointerLock>().delta(), Vec2::ZERO);
+        let cursor = &world.get::<Window>(window).unwrap().cursor;
+        assert_eq!(cursor.grab_mode, CursorGrabMode::None);
+        assert!(cursor.visible);
+    }
+
+    #[test]
+    fn test_steady_motion() {
+        let mut world = World::default();
+        world.init_resource::<PointerLock>();
+        world.init_resource::<Events<MouseMotion>>();
+        world.init_resource::<Events<WindowFocused>>();
+        world.spawn((Window::default(), PrimaryWindow));
+        let owner = world.spawn_empty().id();
+        world.resource_mut::<PointerLock>().lock(owner);
+
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        world.run_system_once(update_pointer_lock);
+        Rcx::new(&world, &mut scope).use_pointer_delta();
+
+        // The same motion in consecutive frames is reported as a change each frame.
+        for _ in 0..2 {
+            world.increment_change_tick();
+            world.send_event(MouseMotion {
+                delta: Vec2::new(2., 0.),
+            });
+            world.run_system_once(update_pointer_lock);
+            assert!(scope.dependencies_changed(&world));
+            scope = TrackingScope::new(world.read_change_tick());
+            assert_eq!(
+                Rcx::new(&world, &mut scope).use_pointer_delta(),
+                Vec2::new(2., 0.)
+            );
+        }
+
+        // When the motion stops, the scope reacts once more to see the zero delta.
+        world.increment_change_tick();
+        world.run_system_once(update_pointer_lock);
+        assert!(scope.dependencies_changed(&world));
+        scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(Rcx::new(&world, &mut scope).use_pointer_delta(), Vec2::ZERO);
+        world.increment_change_tick();
+        world.run_system_once(update_pointer_lock);
+        assert!(!scope.dependencies_changed(&world));
+    }
+}
@@ -1,20 +1,185 @@
-use bevy::prelude::*;
+use bevy::{ecs::entity::Entities, prelude::*};
 
-use crate::{node_span::NodeSpan, view::View, DespawnScopes, ViewHandle};
+use crate::{node_span::NodeSpan, view::View, DespawnScopes, ViewHandle, ViewRoot};
+
+/// The global z-index of the lowest overlay layer. Each open portal is assigned the next
+/// index above this, so that overlays stack in the order they were opened.
+pub const OVERLAY_Z_INDEX_BASE: i32 = 1000;
+
+/// Resource, shared by all view roots, which tracks the open portals in the order they
+/// were opened. Popups and dialogs from any root consult this so that, for example, only
+/// the topmost overlay is dismissed when the user presses Escape, even when several roots
+/// (such as a game HUD and an editor overlay) have overlays open at once.
+///
+/// Portals which display their content inside another UI node, with [`Portal::to`], are not
+/// overlays, and are not tracked.
+#[derive(Resource, Default)]
+pub struct OverlayStack {
+    /// Each open portal, along with its z-index layer.
+    portals: Vec<(Entity, i32)>,
+}
+
+impl OverlayStack {
+    /// The view entity of the most recently opened portal which is still open.
+    pub fn top(&self) -> Option<Entity> {
+        self.portals.last().map(|(portal, _)| *portal)
+    }
+
+    /// Returns true if the given portal view entity is the topmost overlay.
+    pub fn is_top(&self, portal: Entity) -> bool {
+        self.top() == Some(portal)
+    }
+
+    /// Returns true if `node` is one of the display nodes of the topmost overlay, or a
+    /// descendant of one. Overlays use this to decide whether to respond to dismissal, such
+    /// as a press of the Escape key.
+    pub fn is_top_node(&self, world: &World, node: Entity) -> bool {
+        let mut e = node;
+        loop {
+            if let Some(OverlayNode(portal)) = world.get::<OverlayNode>(e) {
+                return self.is_top(*portal);
+            }
+            e = match world.get::<Parent>(e) {
+                Some(parent) => parent.get(),
+                None => return false,
+            };
+        }
+    }
+
+    /// Iterate over the open portals, from bottom to top.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.portals.iter().map(|(portal, _)| *portal)
+    }
+
+    /// Add a portal to the top of the stack, in a layer above all the open portals.
+    fn push(&mut self, portal: Entity) {
+        let layer = self.portals.last().map_or(0, |(_, layer)| layer + 1);
+        self.portals.push((portal, layer));
+    }
+
+    fn layer(&self, portal: Entity) -> i32 {
+        self.portals
+            .iter()
+            .find(|(p, _)| *p == portal)
+            .map_or(0, |(_, layer)| *layer)
+    }
+
+    fn remove(&mut self, portal: Entity) {
+        self.portals.retain(|(p, _)| *p != portal);
+    }
+}
+
+/// Component which records the portal that a top-level overlay node belongs to.
+#[derive(Component)]
+pub(crate) struct OverlayNode(Entity);
+
+/// System which removes portals whose view entities have been despawned without being
+/// razed, for example when their view root was despawned directly.
+pub(crate) fn prune_overlay_stack(mut stack: ResMut<OverlayStack>, entities: &Entities) {
+    if stack
+        .portals
+        .iter()
+        .any(|(portal, _)| !entities.contains(*portal))
+    {
+        stack
+            .portals
+            .retain(|(portal, _)| entities.contains(*portal));
+    }
+}
+
+/// Where a [`Portal`] places its display nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+enum PortalTarget {
+    /// At the top level, using the camera of the enclosing view root.
+    #[default]
+    Root,
+    /// As children of the given entity.
+    Entity(Entity),
+    /// At the top level, rendered by the given camera.
+    Camera(Entity),
+}
 
 /// A `Portal` represents a view that is displayed with no parent, causing it's location to
 /// be relative to the window rather than any parent view.
+///
+/// By default, the portal's display nodes use the camera and render layers of the
+/// [`ViewRoot`] which contains the portal, so that overlays appear in the same window as the
+/// view which opened them, and are placed in a global z-index layer above all view roots.
+/// Use [`Portal::to`] or [`Portal::to_camera`] to display the content elsewhere.
 pub struct Portal {
     view: ViewHandle,
     entity: Option<Entity>,
+    target: PortalTarget,
+    attached: Vec<Entity>,
 }
 
 impl Portal {
-    /// Construct a new `Fragment`.
+    /// Construct a new `Portal`.
     pub fn new(view: impl Into<ViewHandle>) -> Self {
         Self {
             view: view.into(),
             entity: None,
+            target: PortalTarget::Root,
+            attached: Vec::new(),
+        }
+    }
+
+    /// Display the content as children of the given UI node, rather than at the top level.
+    /// This allows content to be placed in a dedicated layer, such as a container for toasts
+    /// which sits below modal dialogs. The target node is responsible for positioning and
+    /// z-ordering the content.
+    pub fn to(mut self, target: Entity) -> Self {
+        self.target = PortalTarget::Entity(target);
+        self
+    }
+
+    /// Display the content at the top level of the given camera's UI, for example a camera
+    /// for another window, or one which renders a world-space UI panel to a texture.
+    pub fn to_camera(mut self, camera: Entity) -> Self {
+        self.target = PortalTarget::Camera(camera);
+        self
+    }
+
+    /// Apply the target camera, render layers and overlay z-index to the display nodes, or
+    /// parent them to the target entity.
+    fn attach_nodes(&mut self, view_entity: Entity, world: &mut World) {
+        let nodes = self.view.nodes();
+        let mut flat: Vec<Entity> = Vec::with_capacity(nodes.count());
+        nodes.flatten(&mut flat);
+
+        let (camera, layers) = match self.target {
+            PortalTarget::Root => ViewRoot::find_target(world, view_entity),
+            PortalTarget::Camera(camera) => (Some(camera), None),
+            PortalTarget::Entity(target) => {
+                for node in self.attached.drain(..) {
+                    if !flat.contains(&node) {
+                        if let Some(mut entt) = world.get_entity_mut(node) {
+                            entt.remove_parent();
+                        }
+                    }
+                }
+                if let Some(mut entt) = world.get_entity_mut(target) {
+                    entt.push_children(&flat);
+                }
+                self.attached = flat;
+                return;
+            }
+        };
+        let layer = world
+            .get_resource_or_insert_with(OverlayStack::default)
+            .layer(view_entity);
+        for node in flat {
+            let mut entt = world.entity_mut(node);
+            entt.insert((
+                ZIndex::Global(OVERLAY_Z_INDEX_BASE + layer),
+                OverlayNode(view_entity),
+            ));
+            if let Some(camera) = camera {
+                entt.insert(TargetCamera(camera));
+            }
+            if let Some(layers) = layers {
+                entt.insert(layers);
+            }
         }
     }
 }
@@ -27,17 +192,27 @@ impl View for Portal {
     fn build(&mut self, view_entity: Entity, world: &mut World) {
         assert!(self.entity.is_none());
         world.entity_mut(view_entity).insert(Name::new("Portal"));
+        if !matches!(self.target, PortalTarget::Entity(_)) {
+            world
+                .get_resource_or_insert_with(OverlayStack::default)
+                .push(view_entity);
+        }
         self.entity = Some(ViewHandle::spawn(&self.view, view_entity, world));
+        self.attach_nodes(view_entity, world);
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
         self.view.raze(self.entity.unwrap(), world);
         self.entity = None;
+        self.attached.clear();
+        if let Some(mut stack) = world.get_resource_mut::<OverlayStack>() {
+            stack.remove(view_entity);
+        }
         world.despawn_owned_recursive(view_entity);
     }
 
-    fn children_changed(&mut self, _view_entity: Entity, _world: &mut World) -> bool {
-        // info!("children_changed handled");
+    fn children_changed(&mut self, view_entity: Entity, world: &mut World) -> bool {
+        self.attach_nodes(view_entity, world);
         true
     }
 }
@@ -47,3 +222,77 @@ impl From<Portal> for ViewHandle {
         ViewHandle::new(value)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cond, Cx, Element, ParentView, PresenterFn, RegisterSignal,
+        RunContextSetup, TextStatic,
+    };
+
+    fn overlays(cx: &mut Cx) -> impl View {
+        let first = cx.create_mutable(true);
+        let second = cx.create_mutable(true);
+        cx.register_signal("first", first);
+        cx.register_signal("second", second);
+        Element::<NodeBundle>::new().with_children((
+            Cond::new(
+                move |cx| first.get(cx),
+                || Portal::new(TextStatic::new("first".to_string())),
+                || (),
+            ),
+            Cond::new(
+                move |cx| second.get(cx),
+                || Portal::new(TextStatic::new("second".to_string())),
+                || (),
+            ),
+        ))
+    }
+
+    fn find_text(harness: &mut TestHarness, content: &str) -> Entity {
+        let mut texts = harness.world_mut().query::<(Entity, &Text)>();
+        texts
+            .iter(harness.world())
+            .find(|(_, text)| text.sections[0].value == content)
+            .map(|(entity, _)| entity)
+            .unwrap()
+    }
+
+    fn layer(harness: &TestHarness, node: Entity) -> i32 {
+        match harness.world().get::<ZIndex>(node) {
+            Some(ZIndex::Global(z)) => z - OVERLAY_Z_INDEX_BASE,
+            _ => panic!("Overlay node has no global z-index"),
+        }
+    }
+
+    #[test]
+    fn test_overlay_stack() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(overlays.bind(()));
+        let first = find_text(&mut harness, "first");
+        let second = find_text(&mut harness, "second");
+        let stack = harness.world().resource::<OverlayStack>();
+        assert_eq!(stack.iter().count(), 2);
+        assert!(stack.is_top_node(harness.world(), second));
+        assert!(!stack.is_top_node(harness.world(), first));
+        assert_eq!(layer(&harness, second), 1);
+
+        // Closing an overlay removes it from the stack, and reopening it puts it in a new
+        // layer above the ones still open.
+        let first_open = harness.mutable::<bool>("first");
+        harness.set(first_open, false);
+        assert_eq!(harness.world().resource::<OverlayStack>().iter().count(), 1);
+        harness.set(first_open, true);
+        let first = find_text(&mut harness, "first");
+        assert_eq!(layer(&harness, first), 2);
+        let stack = harness.world().resource::<OverlayStack>();
+        assert!(stack.is_top_node(harness.world(), first));
+        assert!(!stack.is_top_node(harness.world(), second));
+
+        // Overlays whose root is despawned without being razed are removed.
+        harness.world_mut().entity_mut(root).despawn_recursive();
+        harness.update();
+        assert_eq!(harness.world().resource::<OverlayStack>().iter().count(), 0);
+    }
+}
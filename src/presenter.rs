@@ -1,6 +1,8 @@
+use std::any::TypeId;
+
 use bevy::{
     ecs::{entity::Entity, world::World},
-    hierarchy::BuildWorldChildren,
+    hierarchy::{BuildWorldChildren, Parent},
 };
 
 use crate::{node_span::NodeSpan, Cx, DespawnScopes, TrackingScope, View, ViewHandle};
@@ -51,6 +53,10 @@ pub struct Bind<F: 'static, P: PresenterFn<F>> {
 
     /// Display nodes.
     nodes: NodeSpan,
+
+    /// Distinguishes the local state of this presenter from siblings using the same
+    /// presenter function.
+    key: u64,
 }
 
 impl<F: 'static, P: PresenterFn<F>> Bind<F, P> {
@@ -60,8 +66,17 @@ impl<F: 'static, P: PresenterFn<F>> Bind<F, P> {
             props: Some(props),
             inner: None,
             nodes: NodeSpan::Empty,
+            key: 0,
         }
     }
+
+    /// Set the key used to identify this presenter's local state (see [`Cx::use_state`]).
+    /// This is only needed when several siblings use the same presenter function, such as
+    /// the rows of a list, in which case each should be given a distinct key.
+    pub fn with_key(mut self, key: u64) -> Self {
+        self.key = key;
+        self
+    }
 }
 
 impl<F: 'static, P: PresenterFn<F>> View for Bind<F, P> {
@@ -73,7 +88,12 @@ impl<F: 'static, P: PresenterFn<F>> View for Bind<F, P> {
         assert!(self.inner.is_none());
         assert!(self.props.is_some());
         let mut tracking = TrackingScope::new(world.read_change_tick());
-        let mut cx = Cx::new(self.props.take().unwrap(), world, &mut tracking);
+        let mut cx =
+            Cx::new(self.props.take().unwrap(), world, &mut tracking).with_owner(view_entity);
+        // Local state is stored on the parent view, so that it outlives this presenter.
+        if let Some(parent) = cx.world_mut().get::<Parent>(view_entity).map(Parent::get) {
+            cx = cx.with_local_state(parent, (TypeId::of::<P>(), self.key));
+        }
         let mut view = self.presenter.call(&mut cx);
         self.props = Some(cx.props);
         let inner = world.spawn(tracking).set_parent(view_entity).id();
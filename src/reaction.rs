@@ -43,3 +43,18 @@ impl ReactionHandle {
 /// id is not known until the reaction is started. This component tracks the target entity.
 #[derive(Component)]
 pub struct ReactionTarget(pub Entity);
+
+/// Marks a reaction which runs after UI layout rather than before it. Deferred reactions
+/// are not run when they are created; instead their first run happens after the next layout
+/// pass, so that they can measure the computed size and position of nodes.
+#[derive(Component)]
+pub(crate) struct DeferredReaction {
+    /// True if the reaction has not run yet.
+    pub(crate) pending: bool,
+}
+
+impl Default for DeferredReaction {
+    fn default() -> Self {
+        Self { pending: true }
+    }
+}
@@ -0,0 +1,250 @@
+use std::fmt;
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::tracking_scope::{entity_label, TrackingScope};
+
+/// Summary of the reaction processing during the last frame, for finding cascades of
+/// reactions that trigger each other and multiply the cost of updating the UI. Enable it with
+/// [`ReactorPlugin::with_reaction_report`](crate::ReactorPlugin::with_reaction_report), and
+/// read it from a system, or log it with `info!("{}", report)`.
+///
+/// A chain is a sequence of reactions in which each reaction was triggered by a mutable
+/// written by the one before it. Chains are followed across passes and frames, so a chain
+/// longer than the number of reactor passes means the update was spread over several frames.
+#[derive(Resource, Default)]
+pub struct ReactionReport {
+    /// The passes which ran during the last frame, in order.
+    pub passes: Vec<PassReport>,
+
+    /// The longest chain of reactions which finished during the last frame.
+    pub longest_chain: Vec<String>,
+
+    /// The report for the frame in progress.
+    current: Vec<PassReport>,
+    current_chain: Vec<String>,
+
+    /// The chain which led to each mutable written since mutables were last committed.
+    writers: HashMap<Entity, Vec<String>>,
+
+    /// The chain which led to each mutable at the last commit. These are the mutables which
+    /// trigger the reactions of the following passes.
+    committed: HashMap<Entity, Vec<String>>,
+
+    /// The chain leading to the reaction which is running, including the reaction itself.
+    active: Option<Vec<String>>,
+}
+
+/// Summary of one pass of reactions.
+#[derive(Clone, Debug, Default)]
+pub struct PassReport {
+    /// True if this was a pass of deferred reactions, which run after layout.
+    pub deferred: bool,
+
+    /// The number of reactions which ran.
+    pub reactions: usize,
+
+    /// The changes which triggered the reactions, with the number of reactions triggered by
+    /// each, most frequent first.
+    pub triggers: Vec<(String, usize)>,
+}
+
+impl ReactionReport {
+    /// The total number of reactions which ran during the last frame.
+    pub fn reactions(&self) -> usize {
+        self.passes.iter().map(|pass| pass.reactions).sum()
+    }
+}
+
+impl fmt::Display for ReactionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} reactions in {} passes",
+            self.reactions(),
+            self.passes.len()
+        )?;
+        for (index, pass) in self.passes.iter().enumerate() {
+            writeln!(
+                f,
+                "  pass {}{}: {} reactions",
+                index,
+                if pass.deferred { " (deferred)" } else { "" },
+                pass.reactions
+            )?;
+            for (trigger, count) in pass.triggers.iter() {
+                writeln!(f, "    {} x {}", count, trigger)?;
+            }
+        }
+        if self.longest_chain.len() > 1 {
+            writeln!(f, "  longest chain: {}", self.longest_chain.join(" -> "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The state of the report during a pass of reactions.
+pub(crate) struct PassRecorder {
+    report: PassReport,
+    /// The chain leading to each reaction in the pass, including the reaction itself.
+    chains: HashMap<Entity, Vec<String>>,
+}
+
+/// Note that `mutable` has been written. If a reaction is running, it is recorded as the
+/// writer, so that the reactions which the mutable triggers extend its chain.
+pub(crate) fn record_mutable_write(world: &mut World, mutable: Entity) {
+    let Some(mut report) = world.get_resource_mut::<ReactionReport>() else {
+        return;
+    };
+    let report = report.bypass_change_detection();
+    if let Some(chain) = &report.active {
+        let chain = chain.clone();
+        report.writers.insert(mutable, chain);
+    }
+}
+
+/// Note that the mutables written since the last commit are being committed.
+pub(crate) fn commit_mutable_writes(world: &mut World) {
+    let Some(mut report) = world.get_resource_mut::<ReactionReport>() else {
+        return;
+    };
+    let report = report.bypass_change_detection();
+    report.committed = std::mem::take(&mut report.writers);
+}
+
+/// Start recording a pass of reactions, if the report is enabled.
+pub(crate) fn begin_pass(
+    world: &mut World,
+    changed: &HashSet<Entity>,
+    deferred: bool,
+) -> Option<PassRecorder> {
+    let report = world.get_resource::<ReactionReport>()?;
+    let mut triggers = HashMap::<String, usize>::default();
+    let mut chains = HashMap::default();
+    for entity in changed.iter() {
+        let Some(scope) = world.get::<TrackingScope>(*entity) else {
+            continue;
+        };
+        let changes = scope.describe_changes(world);
+        if changes.is_empty() {
+            *triggers.entry("first run".to_string()).or_default() += 1;
+        }
+        for change in changes {
+            *triggers.entry(change).or_default() += 1;
+        }
+
+        // Extend the longest chain which led to a mutable that triggered this reaction.
+        let mut chain = scope
+            .changed_mutables(world)
+            .filter_map(|mutable| report.committed.get(&mutable))
+            .max_by_key(|chain| chain.len())
+            .cloned()
+            .unwrap_or_default();
+        chain.push(entity_label(world, *entity));
+        chains.insert(*entity, chain);
+    }
+
+    let mut triggers: Vec<(String, usize)> = triggers.into_iter().collect();
+    triggers.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Some(PassRecorder {
+        report: PassReport {
+            deferred,
+            reactions: changed.len(),
+            triggers,
+        },
+        chains,
+    })
+}
+
+impl PassRecorder {
+    /// Start recording the mutables written by a reaction which is about to run.
+    pub(crate) fn before_reaction(&self, world: &mut World, reaction: Entity) {
+        world
+            .resource_mut::<ReactionReport>()
+            .bypass_change_detection()
+            .active = self.chains.get(&reaction).cloned();
+    }
+
+    /// Stop recording the mutables written by the reaction which has just run.
+    pub(crate) fn after_reaction(&self, world: &mut World) {
+        world
+            .resource_mut::<ReactionReport>()
+            .bypass_change_detection()
+            .active = None;
+    }
+}
+
+/// Finish recording a pass of reactions.
+pub(crate) fn end_pass(world: &mut World, recorder: Option<PassRecorder>) {
+    let Some(recorder) = recorder else {
+        return;
+    };
+    let mut report = world.resource_mut::<ReactionReport>();
+    if let Some(chain) = recorder
+        .chains
+        .into_values()
+        .max_by_key(|chain| chain.len())
+    {
+        if chain.len() > report.current_chain.len() {
+            report.current_chain = chain;
+        }
+    }
+    if recorder.report.reactions > 0 {
+        report.current.push(recorder.report);
+    }
+}
+
+/// Publish the report for the frame which has just finished.
+pub(crate) fn finish_reaction_report(report: Option<ResMut<ReactionReport>>) {
+    let Some(mut report) = report else {
+        return;
+    };
+    report.passes = std::mem::take(&mut report.current);
+    report.longest_chain = std::mem::take(&mut report.current_chain);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, EmptyView, PresenterFn, RegisterSignal, View};
+
+    fn cascade(cx: &mut Cx) -> impl View {
+        let source = cx.create_mutable(0);
+        let doubled = cx.create_mutable(0);
+        let quadrupled = cx.create_mutable(0);
+        cx.create_named_effect("double", move |cx| {
+            let value = source.get(cx);
+            doubled.set(cx, value * 2);
+        });
+        cx.create_named_effect("quadruple", move |cx| {
+            let value = doubled.get(cx);
+            quadrupled.set(cx, value * 2);
+        });
+        cx.register_signal("source", source.signal());
+        EmptyView
+    }
+
+    #[test]
+    fn test_reaction_report() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<ReactionReport>();
+        harness.mount(cascade.bind(()));
+        let source = harness.mutable::<i32>("source");
+
+        // With a single pass, the second effect runs in the following frame.
+        harness.set(source, 1);
+        let report = harness.world().resource::<ReactionReport>();
+        assert!(report
+            .passes
+            .iter()
+            .any(|pass| pass.triggers.iter().any(|(t, _)| t.contains("mutable"))));
+        harness.update();
+        let report = harness.world().resource::<ReactionReport>();
+        assert_eq!(report.longest_chain.len(), 2);
+        assert!(report.longest_chain[0].contains("double"));
+        assert!(report.longest_chain[1].contains("quadruple"));
+    }
+}
@@ -0,0 +1,223 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use bevy::ecs::{component::Component, entity::Entity, world::World};
+
+use crate::{store::StoreCell, RunContextRead, RunContextSetup, RunContextWrite, Signal, Store};
+
+/// Function which applies an action to the state of a [`ReducerStore`].
+type ReducerFn<T, A> = Arc<dyn Fn(&mut T, &A) + Send + Sync>;
+
+/// Function which observes the actions dispatched to a [`ReducerStore`].
+type MiddlewareFn<T, A> = Arc<dyn Fn(&mut World, StoreAction<T, A>) + Send + Sync>;
+
+/// Contains the reducer and middleware of a [`ReducerStore`].
+#[derive(Component)]
+pub(crate) struct ReducerCell<T, A> {
+    pub(crate) reducer: ReducerFn<T, A>,
+    pub(crate) middleware: Vec<MiddlewareFn<T, A>>,
+}
+
+/// An action which has been processed by a [`ReducerStore`], as seen by middleware.
+pub struct StoreAction<'a, T, A> {
+    /// The action that was dispatched.
+    pub action: &'a A,
+    /// The state before the action was applied.
+    pub before: &'a T,
+    /// The state after the action was applied.
+    pub after: &'a T,
+}
+
+/// A [`Store`] whose state can only be changed by dispatching actions, which are applied by a
+/// single reducer function. State is read through selectors, which only react when the
+/// selected value changes. Middleware can observe every action along with the state before and
+/// after it, for logging, undo integration or persistence.
+///
+/// ```ignore
+/// let doc = cx.create_reducer_store(Document::default(), |doc, action: &DocAction| {
+///     match action {
+///         DocAction::Rename(name) => doc.name = name.clone(),
+///         DocAction::AddLayer => doc.layers.push(Layer::default()),
+///     }
+/// });
+/// doc.add_middleware(cx.world_mut(), |_world, ev| info!("{:?}", ev.action));
+/// let layer_count = doc.selector(cx, |doc| doc.layers.len());
+/// doc.dispatch(cx, DocAction::AddLayer);
+/// ```
+#[derive(PartialEq, Eq, Debug)]
+pub struct ReducerStore<T, A> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<(T, A)>,
+}
+
+impl<T, A> ReducerStore<T, A> {
+    /// The entity that holds the store state.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+}
+
+impl<T, A> Copy for ReducerStore<T, A> {}
+impl<T, A> Clone for ReducerStore<T, A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static, A: Send + Sync + 'static> ReducerStore<T, A> {
+    /// The underlying [`Store`], for reading individual fields of the state. Fields should
+    /// not be written directly, as this bypasses the reducer and middleware.
+    pub fn store(&self) -> Store<T> {
+        Store {
+            id: self.id,
+            marker: PhantomData,
+        }
+    }
+
+    /// Read a value computed from the state. The current tracking scope will only react when
+    /// the result of `selector` changes.
+    pub fn select<R: RunContextRead, V: PartialEq + Clone + Send + Sync + 'static>(
+        &self,
+        cx: &R,
+        selector: impl Fn(&T) -> V + Send + Sync + 'static,
+    ) -> V {
+        self.store().root().map(cx, selector)
+    }
+
+    /// Create a signal containing a value computed from the state. Readers of the signal only
+    /// react when the result of `selector` changes.
+    pub fn selector<'p, V: PartialEq + Clone + Send + Sync + 'static>(
+        &self,
+        cx: &mut impl RunContextSetup<'p>,
+        selector: impl Fn(&T) -> V + Send + Sync + 'static,
+    ) -> Signal<V> {
+        let store = *self;
+        let selector = Arc::new(selector);
+        cx.create_derived(move |cx| {
+            let selector = selector.clone();
+            store.select(cx, move |state| selector(state))
+        })
+    }
+
+    /// Apply an action to the state using the store's reducer, then pass it to each
+    /// middleware in the order they were added.
+    pub fn dispatch<W: RunContextWrite>(&self, cx: &mut W, action: A) {
+        let world = cx.world_mut();
+        let (reducer, middleware) = {
+            let cell = world
+                .get::<ReducerCell<T, A>>(self.id)
+                .expect("Store has been despawned");
+            (cell.reducer.clone(), cell.middleware.clone())
+        };
+        let mut state = world.get_mut::<StoreCell<T>>(self.id).unwrap();
+        if middleware.is_empty() {
+            reducer(&mut state.value, &action);
+            state.touch(&[]);
+            return;
+        }
+        let before = state.value.clone();
+        reducer(&mut state.value, &action);
+        state.touch(&[]);
+        let after = state.value.clone();
+        for observer in middleware {
+            observer(
+                world,
+                StoreAction {
+                    action: &action,
+                    before: &before,
+                    after: &after,
+                },
+            );
+        }
+    }
+
+    /// Replace the state without going through the reducer or middleware. This is intended
+    /// for restoring a previously recorded state, such as when undoing or loading a saved
+    /// document.
+    pub fn restore<W: RunContextWrite>(&self, cx: &mut W, state: T) {
+        let mut cell = cx
+            .world_mut()
+            .get_mut::<StoreCell<T>>(self.id)
+            .expect("Store has been despawned");
+        cell.value = state;
+        cell.touch(&[]);
+    }
+
+    /// Add a middleware function which is called after each action has been applied.
+    pub fn add_middleware(
+        &self,
+        world: &mut World,
+        middleware: impl Fn(&mut World, StoreAction<T, A>) + Send + Sync + 'static,
+    ) {
+        world
+            .get_mut::<ReducerCell<T, A>>(self.id)
+            .expect("Store has been despawned")
+            .middleware
+            .push(Arc::new(middleware));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::{system::Resource, world::World};
+
+    use crate::{cx::Cx, tracking_scope::TrackingScope, Rcx, RunContextSetup};
+
+    #[derive(Clone, Default)]
+    struct Document {
+        name: String,
+        layers: Vec<String>,
+    }
+
+    enum DocAction {
+        Rename(String),
+        AddLayer(String),
+    }
+
+    #[derive(Resource, Default)]
+    struct History(Vec<usize>);
+
+    #[test]
+    fn test_reducer_store() {
+        let mut world = World::default();
+        world.init_resource::<History>();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        let doc =
+            cx.create_reducer_store(
+                Document::default(),
+                |doc, action: &DocAction| match action {
+                    DocAction::Rename(name) => doc.name = name.clone(),
+                    DocAction::AddLayer(layer) => doc.layers.push(layer.clone()),
+                },
+            );
+        doc.add_middleware(cx.world_mut(), |world, ev| {
+            world
+                .resource_mut::<History>()
+                .0
+                .push(ev.before.layers.len());
+        });
+
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(
+            doc.select(&Rcx::new(&world, &mut scope), |d| d.layers.len()),
+            0
+        );
+
+        // Renaming doesn't change the number of layers.
+        world.increment_change_tick();
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        doc.dispatch(&mut cx, DocAction::Rename("Sketch".to_string()));
+        assert!(!scope.dependencies_changed(&world));
+
+        let mut name_scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(
+            doc.select(&Rcx::new(&world, &mut name_scope), |d| d.name.clone()),
+            "Sketch"
+        );
+
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        doc.dispatch(&mut cx, DocAction::AddLayer("Background".to_string()));
+        assert!(scope.dependencies_changed(&world));
+        assert_eq!(world.resource::<History>().0, vec![0, 0]);
+    }
+}
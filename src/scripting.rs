@@ -0,0 +1,416 @@
+use bevy::{prelude::*, utils::HashMap};
+#[cfg(feature = "picking")]
+use bevy_mod_picking::prelude::{Click, On, Pointer};
+use rhai::{Array, Engine, Scope, AST};
+
+#[cfg(feature = "picking")]
+use crate::RunContextWrite;
+use crate::{
+    node_span::NodeSpan, style::TextStyleChanged, Callback, DespawnScopes, Rcx, RunContextRead,
+    Signals, TrackingScope, View,
+};
+
+/// A display node produced by a script. Scripts build a tree of these using the functions
+/// `div(children)` and `text(string)`, along with chainable methods such as `.row()`,
+/// `.gap(px)` and `.on_click("callback")`.
+#[derive(Clone, Debug, Default)]
+pub struct ScriptNode {
+    text: Option<String>,
+    name: Option<String>,
+    style: Style,
+    background: Option<Color>,
+    on_click: Option<String>,
+    children: Vec<ScriptNode>,
+}
+
+impl ScriptNode {
+    fn div(children: Array) -> Self {
+        Self {
+            children: children
+                .into_iter()
+                .map(|child| {
+                    if child.is::<ScriptNode>() {
+                        child.cast::<ScriptNode>()
+                    } else {
+                        Self::text(child.to_string())
+                    }
+                })
+                .collect(),
+            ..default()
+        }
+    }
+
+    fn text(text: String) -> Self {
+        Self {
+            text: Some(text),
+            ..default()
+        }
+    }
+}
+
+/// Resource holding the script engine used by [`ScriptView`], along with the callbacks which
+/// have been exposed to scripts. Scripts read signals from the [`Signals`] registry.
+#[derive(Resource)]
+pub struct ScriptEngine {
+    engine: Engine,
+    callbacks: HashMap<String, Callback>,
+}
+
+impl Default for ScriptEngine {
+    fn default() -> Self {
+        let mut engine = Engine::new();
+        engine
+            .register_type_with_name::<ScriptNode>("Node")
+            .register_fn("div", ScriptNode::div)
+            .register_fn("text", |text: &str| ScriptNode::text(text.to_string()))
+            .register_fn("name", |node: &mut ScriptNode, name: &str| {
+                node.name = Some(name.to_string());
+                node.clone()
+            })
+            .register_fn("row", |node: &mut ScriptNode| {
+                node.style.flex_direction = FlexDirection::Row;
+                node.clone()
+            })
+            .register_fn("column", |node: &mut ScriptNode| {
+                node.style.flex_direction = FlexDirection::Column;
+                node.clone()
+            })
+            .register_fn("gap", |node: &mut ScriptNode, gap: f64| {
+                node.style.row_gap = Val::Px(gap as f32);
+                node.style.column_gap = Val::Px(gap as f32);
+                node.clone()
+            })
+            .register_fn("padding", |node: &mut ScriptNode, padding: f64| {
+                node.style.padding = UiRect::all(Val::Px(padding as f32));
+                node.clone()
+            })
+            .register_fn("background", |node: &mut ScriptNode, color: &str| {
+                node.background = Color::hex(color).ok();
+                node.clone()
+            })
+            .register_fn("on_click", |node: &mut ScriptNode, callback: &str| {
+                node.on_click = Some(callback.to_string());
+                node.clone()
+            });
+        Self {
+            engine,
+            callbacks: HashMap::default(),
+        }
+    }
+}
+
+impl ScriptEngine {
+    /// The underlying script engine, for registering additional functions and types.
+    pub fn engine_mut(&mut self) -> &mut Engine {
+        &mut self.engine
+    }
+
+    /// Make a callback available to scripts, which can attach it to a node with
+    /// `.on_click("name")`.
+    pub fn expose_callback(&mut self, name: impl Into<String>, callback: Callback) {
+        self.callbacks.insert(name.into(), callback);
+    }
+}
+
+/// A view whose display nodes are defined by a script. The script must evaluate to a node,
+/// for example:
+///
+/// ```text
+/// div([
+///     text("Score: " + signals.score),
+///     div(["Reset"]).padding(4.0).background("#444").on_click("reset"),
+/// ]).column().gap(4.0)
+/// ```
+///
+/// Every signal in the [`Signals`] registry is readable by scripts as `signals.<name>`, and
+/// the view updates when any of them changes. Note that script integers are `i64` and script
+/// floats are `f64`; signals of other numeric types will appear to scripts as opaque values.
+///
+/// When the script's output changes, the existing display nodes are patched to match it:
+/// nodes whose text or style changed are updated in place, and nodes are only spawned or
+/// despawned where the shape of the tree changed.
+///
+/// Script and evaluation errors are logged and shown in place of the script's output.
+pub struct ScriptView {
+    source: String,
+    ast: Option<Result<AST, String>>,
+    root: Option<Entity>,
+    tree: Option<SpawnedNode>,
+}
+
+/// A display node spawned for a [`ScriptNode`], recording the node it was spawned from so that
+/// later output can be compared against it. The recorded node has no children; those are
+/// held in `children` instead.
+struct SpawnedNode {
+    entity: Entity,
+    node: ScriptNode,
+    children: Vec<SpawnedNode>,
+}
+
+impl ScriptView {
+    /// Construct a new script view from the script's source text.
+    pub fn new(source: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            ast: None,
+            root: None,
+            tree: None,
+        }
+    }
+
+    fn evaluate(&self, world: &World, tracking: &mut TrackingScope) -> Result<ScriptNode, String> {
+        let Some(script) = world.get_resource::<ScriptEngine>() else {
+            return Err("ScriptEngine resource not found".to_string());
+        };
+        let ast = match self.ast.as_ref().unwrap() {
+            Ok(ast) => ast,
+            Err(err) => return Err(err.clone()),
+        };
+        let cx = Rcx::new(world, tracking);
+        let signals = cx.use_resource::<Signals>().script_values(&cx);
+        let mut scope = Scope::new();
+        scope.push_constant("signals", signals);
+        script
+            .engine
+            .eval_ast_with_scope::<ScriptNode>(&mut scope, ast)
+            .map_err(|err| err.to_string())
+    }
+
+    fn rebuild(&mut self, world: &mut World, tracking: &mut TrackingScope) {
+        let root = self.root.unwrap();
+        let node = self.evaluate(world, tracking).unwrap_or_else(|err| {
+            error!("Script error: {}", err);
+            ScriptNode::text(err)
+        });
+        let tree = match self.tree.take() {
+            Some(tree) => patch_script_node(world, tree, node),
+            None => spawn_script_node(world, node),
+        };
+        world.entity_mut(root).replace_children(&[tree.entity]);
+        self.tree = Some(tree);
+    }
+}
+
+fn spawn_script_node(world: &mut World, mut node: ScriptNode) -> SpawnedNode {
+    let children = std::mem::take(&mut node.children);
+    let entity = match node.text {
+        Some(ref text) => world
+            .spawn((
+                TextBundle {
+                    text: Text::from_section(text.clone(), TextStyle::default()),
+                    ..default()
+                },
+                TextStyleChanged,
+            ))
+            .id(),
+        None => world
+            .spawn(NodeBundle {
+                style: node.style.clone(),
+                background_color: node.background.unwrap_or(Color::NONE).into(),
+                ..default()
+            })
+            .id(),
+    };
+    set_name(world, entity, &node.name);
+    set_on_click(world, entity, &node.on_click);
+    let children: Vec<SpawnedNode> = children
+        .into_iter()
+        .map(|child| spawn_script_node(world, child))
+        .collect();
+    let ids: Vec<Entity> = children.iter().map(|child| child.entity).collect();
+    world.entity_mut(entity).push_children(&ids);
+    SpawnedNode {
+        entity,
+        node,
+        children,
+    }
+}
+
+/// Update the display nodes spawned for `old` so that they match `new`. A node is only
+/// replaced if it changed between being a text node and a container.
+fn patch_script_node(world: &mut World, old: SpawnedNode, mut new: ScriptNode) -> SpawnedNode {
+    if old.node.text.is_some() != new.text.is_some() {
+        world.entity_mut(old.entity).despawn_recursive();
+        return spawn_script_node(world, new);
+    }
+    let entity = old.entity;
+    match new.text {
+        Some(ref text) => {
+            if old.node.text.as_ref() != Some(text) {
+                let mut entt = world.entity_mut(entity);
+                entt.get_mut::<Text>().unwrap().sections[0].value = text.clone();
+            }
+        }
+        None => {
+            if old.node.style != new.style {
+                world.entity_mut(entity).insert(new.style.clone());
+            }
+            if old.node.background != new.background {
+                world
+                    .entity_mut(entity)
+                    .insert(BackgroundColor(new.background.unwrap_or(Color::NONE)));
+            }
+        }
+    }
+    if old.node.name != new.name {
+        set_name(world, entity, &new.name);
+    }
+    if old.node.on_click != new.on_click {
+        set_on_click(world, entity, &new.on_click);
+    }
+
+    // Patch children pairwise, then spawn or despawn the difference in length.
+    let old_ids: Vec<Entity> = old.children.iter().map(|child| child.entity).collect();
+    let mut new_children = std::mem::take(&mut new.children).into_iter();
+    let mut children = Vec::with_capacity(new_children.len());
+    for old_child in old.children {
+        match new_children.next() {
+            Some(new_child) => children.push(patch_script_node(world, old_child, new_child)),
+            None => world.entity_mut(old_child.entity).despawn_recursive(),
+        }
+    }
+    for new_child in new_children {
+        children.push(spawn_script_node(world, new_child));
+    }
+    let ids: Vec<Entity> = children.iter().map(|child| child.entity).collect();
+    if ids != old_ids {
+        world.entity_mut(entity).replace_children(&ids);
+    }
+    SpawnedNode {
+        entity,
+        node: new,
+        children,
+    }
+}
+
+fn set_name(world: &mut World, entity: Entity, name: &Option<String>) {
+    let mut entt = world.entity_mut(entity);
+    match name {
+        Some(name) => {
+            entt.insert(Name::new(name.clone()));
+        }
+        None => {
+            entt.remove::<Name>();
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "picking"), allow(unused_variables))]
+fn set_on_click(world: &mut World, entity: Entity, on_click: &Option<String>) {
+    #[cfg(feature = "picking")]
+    {
+        let mut entt = world.entity_mut(entity);
+        match on_click.clone() {
+            Some(callback) => {
+                entt.insert(On::<Pointer<Click>>::run(move |world: &mut World| {
+                    let found = world
+                        .resource::<ScriptEngine>()
+                        .callbacks
+                        .get(&callback)
+                        .copied();
+                    match found {
+                        Some(cb) => world.run_callback(cb, ()),
+                        None => warn!("Script callback '{}' not found", callback),
+                    }
+                }));
+            }
+            None => {
+                entt.remove::<On<Pointer<Click>>>();
+            }
+        }
+    }
+}
+
+impl View for ScriptView {
+    fn nodes(&self) -> NodeSpan {
+        NodeSpan::Node(self.root.unwrap())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.root.is_none());
+        self.ast = Some(match world.get_resource::<ScriptEngine>() {
+            Some(script) => script
+                .engine
+                .compile(&self.source)
+                .map_err(|err| err.to_string()),
+            None => Err("ScriptEngine resource not found".to_string()),
+        });
+        world.init_resource::<Signals>();
+        self.root = Some(world.spawn(NodeBundle::default()).id());
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.rebuild(world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        self.rebuild(world, tracking);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        let root = self.root.take().expect("Razing unbuilt ScriptView");
+        self.tree = None;
+        world.entity_mut(root).remove_parent();
+        world.entity_mut(root).despawn_recursive();
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, Mutable, RegisterSignal, RunContextSetup};
+
+    fn mount_script(harness: &mut TestHarness, source: &str) -> (Entity, Mutable<i64>) {
+        let world = harness.world_mut();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), world, &mut scope);
+        let score = cx.create_mutable::<i64>(0);
+        cx.register_signal("score", score);
+        (harness.mount(ScriptView::new(source)), score)
+    }
+
+    #[test]
+    fn test_script_patch_in_place() {
+        let mut harness = TestHarness::new();
+        let (root, score) = mount_script(
+            &mut harness,
+            r#"div([text("Score: " + signals.score), div(["Reset"]).name("reset")])"#,
+        );
+        assert_eq!(harness.text(root), "Score: 0Reset");
+        let nodes = harness.descendants(root);
+
+        // Changing a signal updates the text without respawning any nodes.
+        harness.set(score, 5);
+        assert_eq!(harness.text(root), "Score: 5Reset");
+        assert_eq!(harness.descendants(root), nodes);
+        assert!(harness.find_by_name(root, "reset").is_some());
+    }
+
+    #[test]
+    fn test_script_patch_structure() {
+        let mut harness = TestHarness::new();
+        let (root, score) = mount_script(
+            &mut harness,
+            r#"
+                let items = [text("first")];
+                for i in 0..signals.score { items.push(text("item " + i)); }
+                div(items)
+            "#,
+        );
+        assert_eq!(harness.text(root), "first");
+        let first = harness.descendants(root)[2];
+
+        // New children are appended, keeping the existing ones.
+        harness.set(score, 2);
+        assert_eq!(harness.text(root), "firstitem 0item 1");
+        let nodes = harness.descendants(root);
+        assert_eq!(nodes.len(), 5);
+        assert_eq!(nodes[2], first);
+
+        // Removed children are despawned.
+        harness.set(score, 1);
+        assert_eq!(harness.text(root), "firstitem 0");
+        assert_eq!(harness.descendants(root), nodes[..4]);
+        assert!(harness.world().get_entity(nodes[4]).is_none());
+    }
+}
@@ -0,0 +1,130 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{Cx, IntoSignal, RunContextSetup, RunContextWrite};
+
+/// Marker component added to entities which are in a highlighted selection. Systems which
+/// draw selection visuals, such as outlines or gizmos, can query for this.
+#[derive(Component, Clone, Copy, Default, Debug)]
+pub struct Selected;
+
+/// Methods for displaying a selection on the entities in a scene.
+pub trait CreateSelectionHighlight {
+    /// Create an effect which inserts `highlight` on each entity in `selection`, and removes
+    /// it again when the entity is deselected, or when the current scope is despawned. This
+    /// keeps the selection visuals in the viewport in sync with any other view of the same
+    /// selection, such as an outliner or an inspector.
+    ///
+    /// The highlight is typically the [`Selected`] marker, an outline component, or a
+    /// tinted material handle. If the entity already has a component of the same type, such
+    /// as its regular material, the previous value is saved and restored when the entity is
+    /// deselected. Only entities whose selection state changed are updated.
+    ///
+    /// Arguments:
+    /// * `selection` - The selected entities: either a [`SelectionModel`](crate::SelectionModel)
+    ///   or a signal containing a list of entities.
+    /// * `highlight` - The component to insert on selected entities.
+    fn create_selection_highlight<C: Component + Clone>(
+        &mut self,
+        selection: impl IntoSignal<Vec<Entity>>,
+        highlight: C,
+    );
+}
+
+/// Remove the highlight from an entity, restoring the component it replaced, if any.
+fn restore<C: Component>(world: &mut World, entity: Entity, prior: Option<C>) {
+    if let Some(mut entt) = world.get_entity_mut(entity) {
+        entt.remove::<C>();
+        if let Some(prior) = prior {
+            entt.insert(prior);
+        }
+    }
+}
+
+impl<'p, 'w, Props> CreateSelectionHighlight for Cx<'p, 'w, Props> {
+    fn create_selection_highlight<C: Component + Clone>(
+        &mut self,
+        selection: impl IntoSignal<Vec<Entity>>,
+        highlight: C,
+    ) {
+        let selection = selection.into_signal();
+        // The entities which currently have the highlight, along with the value of the
+        // component which the highlight replaced.
+        let highlighted = Arc::new(Mutex::new(HashMap::<Entity, Option<C>>::new()));
+        let current = highlighted.clone();
+        self.on_cleanup(move |world| {
+            for (entity, prior) in current.lock().unwrap().drain() {
+                restore(world, entity, prior);
+            }
+        });
+        self.create_effect(move |cx| {
+            let selection = selection.get_clone(cx);
+            let mut highlighted = highlighted.lock().unwrap();
+            let world = cx.world_mut();
+            let deselected: Vec<Entity> = highlighted
+                .keys()
+                .filter(|entity| !selection.contains(entity))
+                .copied()
+                .collect();
+            for entity in deselected {
+                let prior = highlighted.remove(&entity).unwrap();
+                restore(world, entity, prior);
+            }
+            for entity in selection.iter() {
+                if highlighted.contains_key(entity) {
+                    continue;
+                }
+                if let Some(mut entt) = world.get_entity_mut(*entity) {
+                    let prior = entt.take::<C>();
+                    entt.insert(highlight.clone());
+                    highlighted.insert(*entity, prior);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, CreateSelectionModel, EmptyView, PresenterFn, RegisterSignal, View,
+    };
+
+    #[derive(Component, Clone, Copy, PartialEq, Debug)]
+    struct Tint(u32);
+
+    fn viewport(cx: &mut Cx) -> impl View {
+        let selection = cx.create_selection_model::<Entity>();
+        cx.create_selection_highlight(selection, Selected);
+        cx.create_selection_highlight(selection, Tint(1));
+        cx.register_signal("selection", selection.signal());
+        EmptyView
+    }
+
+    #[test]
+    fn test_selection_highlight() {
+        let mut harness = TestHarness::new();
+        let a = harness.world_mut().spawn(Tint(0)).id();
+        let b = harness.world_mut().spawn_empty().id();
+        harness.mount(viewport.bind(()));
+        let selection = harness.mutable::<Vec<Entity>>("selection");
+
+        harness.set(selection, vec![a, b]);
+        assert!(harness.world().get::<Selected>(a).is_some());
+        assert!(harness.world().get::<Selected>(b).is_some());
+        assert_eq!(harness.world().get::<Tint>(a), Some(&Tint(1)));
+        assert_eq!(harness.world().get::<Tint>(b), Some(&Tint(1)));
+
+        // Deselecting restores the component which the highlight replaced.
+        harness.set(selection, vec![b]);
+        assert!(harness.world().get::<Selected>(a).is_none());
+        assert!(harness.world().get::<Selected>(b).is_some());
+        assert_eq!(harness.world().get::<Tint>(a), Some(&Tint(0)));
+        assert_eq!(harness.world().get::<Tint>(b), Some(&Tint(1)));
+
+        harness.set(selection, Vec::new());
+        assert_eq!(harness.world().get::<Tint>(b), None);
+    }
+}
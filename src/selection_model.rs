@@ -0,0 +1,89 @@
+use crate::{Cx, Mutable, ReadMutable, RunContextSetup, Signal, WriteMutable};
+
+/// A reactive set of selected items, such as the entities selected in a scene editor. A
+/// single model can be shared between all of the views which display or edit the selection,
+/// for example an outliner, an inspector and the viewport's
+/// [selection highlight](crate::CreateSelectionHighlight), so that they stay in sync.
+///
+/// Items are kept in the order in which they were selected.
+///
+/// Create a selection model with [`CreateSelectionModel::create_selection_model`].
+#[derive(PartialEq)]
+pub struct SelectionModel<K> {
+    items: Mutable<Vec<K>>,
+}
+
+impl<K> Copy for SelectionModel<K> {}
+impl<K> Clone for SelectionModel<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: PartialEq + Clone + Send + Sync + 'static> SelectionModel<K> {
+    /// A signal containing the list of selected items.
+    pub fn signal(&self) -> Signal<Vec<K>> {
+        self.items.signal()
+    }
+
+    /// The list of selected items.
+    pub fn get<R: ReadMutable>(&self, cx: &mut R) -> Vec<K> {
+        self.items.get_clone(cx)
+    }
+
+    /// Returns true if `key` is selected.
+    pub fn is_selected<R: ReadMutable>(&self, cx: &R, key: &K) -> bool {
+        cx.read_mutable_map(&self.items, |items| items.contains(key))
+    }
+
+    /// Replace the selection with the given items.
+    pub fn set<R: WriteMutable>(&self, cx: &mut R, items: Vec<K>) {
+        self.items.set_clone(cx, items);
+    }
+
+    /// Replace the selection with a single item.
+    pub fn select<R: WriteMutable>(&self, cx: &mut R, key: K) {
+        self.set(cx, vec![key]);
+    }
+
+    /// Add `key` to the selection if it is not selected, or remove it if it is.
+    pub fn toggle<R: ReadMutable + WriteMutable>(&self, cx: &mut R, key: K) {
+        let mut items = self.get(cx);
+        match items.iter().position(|item| *item == key) {
+            Some(index) => {
+                items.remove(index);
+            }
+            None => items.push(key),
+        }
+        self.set(cx, items);
+    }
+
+    /// Deselect all items.
+    pub fn clear<R: WriteMutable>(&self, cx: &mut R) {
+        self.set(cx, Vec::new());
+    }
+}
+
+impl<K> From<SelectionModel<K>> for Signal<Vec<K>> {
+    fn from(model: SelectionModel<K>) -> Self {
+        Signal::Mutable(model.items)
+    }
+}
+
+/// Method to create a [`SelectionModel`].
+pub trait CreateSelectionModel {
+    /// Create a selection model which is initially empty.
+    fn create_selection_model<K: PartialEq + Clone + Send + Sync + 'static>(
+        &mut self,
+    ) -> SelectionModel<K>;
+}
+
+impl<'p, 'w, Props> CreateSelectionModel for Cx<'p, 'w, Props> {
+    fn create_selection_model<K: PartialEq + Clone + Send + Sync + 'static>(
+        &mut self,
+    ) -> SelectionModel<K> {
+        SelectionModel {
+            items: self.create_mutable(Vec::new()),
+        }
+    }
+}
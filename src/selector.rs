@@ -0,0 +1,47 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::{component::Component, entity::Entity};
+
+use crate::RunContextRead;
+
+/// Component which holds the current value of a selector's source signal. The value is
+/// `None` until the selector's effect has run for the first time.
+#[derive(Component)]
+pub(crate) struct SelectorCell<K>(pub(crate) Option<K>);
+
+/// A keyed view of a selection signal, created by
+/// [`create_selector`](crate::RunContextSetup::create_selector). Testing a key with
+/// [`is_selected`](Selector::is_selected) only subscribes to whether that key is selected,
+/// so when the selection moves from one row of a list to another, only those two rows react,
+/// rather than every row in the list.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Selector<K> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<K>,
+}
+
+impl<K> Selector<K> {
+    /// The entity that holds the selector state.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+}
+
+impl<K> Copy for Selector<K> {}
+impl<K> Clone for Selector<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: PartialEq + Clone + Send + Sync + 'static> Selector<K> {
+    /// Returns true if `key` is equal to the current value of the source signal. The current
+    /// tracking scope will only react when the result of this test changes.
+    pub fn is_selected<R: RunContextRead>(&self, cx: &R, key: &K) -> bool {
+        let key = key.clone();
+        cx.use_component_field::<SelectorCell<K>, _, _>(self.id, move |cell| {
+            cell.0.as_ref() == Some(&key)
+        })
+        .unwrap_or(false)
+    }
+}
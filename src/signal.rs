@@ -1,4 +1,8 @@
-use crate::{derived::ReadDerived, mutable::ReadMutable, Derived, Mutable};
+use bevy::{core::Name, reflect::impl_reflect_value};
+
+use crate::{
+    derived::ReadDerived, mutable::ReadMutable, Derived, Mutable, RunContextSetup, RunContextWrite,
+};
 
 /// What type of reactive node underlies this signal. "Signals" in this framework represent
 /// any kind of reactive data source, including mutable variables, derived signals, and memoized
@@ -33,6 +37,10 @@ where
     }
 }
 
+// Signals are reflected as opaque values, so that structs of props holding signals can
+// derive `Reflect`.
+impl_reflect_value!((in bevy_reactor) Signal<T: Clone + Send + Sync>());
+
 impl<T> Signal<T>
 where
     T: Copy + Send + Sync + 'static,
@@ -78,6 +86,137 @@ where
     }
 }
 
+impl<T> Signal<T> {
+    /// Give the signal a debug name, which is shown in reaction traces. Has no effect on
+    /// constant signals.
+    pub fn named<R: RunContextWrite>(self, cx: &mut R, name: &str) -> Self {
+        let id = match self {
+            Signal::Mutable(ref mutable) => mutable.id,
+            Signal::Derived(ref derived) => derived.id,
+            Signal::Memo | Signal::Constant(_) => return self,
+        };
+        cx.world_mut()
+            .entity_mut(id)
+            .insert(Name::new(name.to_string()));
+        self
+    }
+}
+
+impl<T> Signal<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Construct a signal which always has the given value. This is equivalent to
+    /// `Signal::Constant(value)`.
+    pub fn constant(value: T) -> Self {
+        Self::Constant(value)
+    }
+
+    /// Derive a signal by applying `f` to the value of this signal. If this signal is a
+    /// constant, the result is also a constant, and no derived signal is created.
+    pub fn derive<'p, U: Send + Sync + 'static, F: Fn(&T) -> U + Send + Sync + 'static>(
+        self,
+        cx: &mut impl RunContextSetup<'p>,
+        f: F,
+    ) -> Signal<U> {
+        match self {
+            Signal::Constant(value) => Signal::Constant(f(&value)),
+            signal => cx.create_derived(move |rcx| signal.map(rcx, &f)),
+        }
+    }
+}
+
+/// Adapters for optional data sources, such as a maybe-loaded asset or a maybe-selected
+/// entity, so that they can be passed to controls which expect a concrete value.
+impl<T> Signal<Option<T>>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    /// A signal containing this signal's value, or `default` if it is `None`.
+    pub fn unwrap_or<'p>(self, cx: &mut impl RunContextSetup<'p>, default: T) -> Signal<T> {
+        self.derive(cx, move |value| {
+            value.as_ref().cloned().unwrap_or_else(|| default.clone())
+        })
+    }
+
+    /// A signal containing this signal's value, or the default value of `T` if it is
+    /// `None`.
+    pub fn unwrap_or_default<'p>(self, cx: &mut impl RunContextSetup<'p>) -> Signal<T>
+    where
+        T: Default,
+    {
+        self.derive(cx, |value| value.as_ref().cloned().unwrap_or_default())
+    }
+
+    /// A signal which is true when this signal's value is `Some`.
+    pub fn is_some<'p>(self, cx: &mut impl RunContextSetup<'p>) -> Signal<bool> {
+        self.derive(cx, |value| value.is_some())
+    }
+}
+
+/// Adapters for fallible data sources.
+impl<T, E> Signal<Result<T, E>>
+where
+    T: Clone + Send + Sync + 'static,
+    E: Send + Sync + 'static,
+{
+    /// A signal containing the success value of this signal, or `None` if it is an error.
+    pub fn ok<'p>(self, cx: &mut impl RunContextSetup<'p>) -> Signal<Option<T>> {
+        self.derive(cx, |value| value.as_ref().ok().cloned())
+    }
+
+    /// A signal containing `f` applied to the success value of this signal, or `None` if it
+    /// is an error.
+    pub fn ok_map<'p, U: Send + Sync + 'static, F: Fn(&T) -> U + Send + Sync + 'static>(
+        self,
+        cx: &mut impl RunContextSetup<'p>,
+        f: F,
+    ) -> Signal<Option<U>> {
+        self.derive(cx, move |value| value.as_ref().ok().map(&f))
+    }
+
+    /// A signal containing the success value of this signal, or `default` if it is an
+    /// error.
+    pub fn unwrap_or<'p>(self, cx: &mut impl RunContextSetup<'p>, default: T) -> Signal<T> {
+        self.derive(cx, move |value| {
+            value
+                .as_ref()
+                .ok()
+                .cloned()
+                .unwrap_or_else(|| default.clone())
+        })
+    }
+}
+
+/// A constant value converts into a constant signal, so that props of type `Signal<T>` can be
+/// given a plain value with `.into()`.
+impl<T> From<T> for Signal<T> {
+    fn from(value: T) -> Self {
+        Signal::Constant(value)
+    }
+}
+
+impl<T> From<Mutable<T>> for Signal<T> {
+    fn from(mutable: Mutable<T>) -> Self {
+        Signal::Mutable(mutable)
+    }
+}
+
+/// A value which can be converted into a [`Signal`]: a plain value, which becomes a constant,
+/// a [`Mutable`], or another signal. Functions which accept `impl IntoSignal<T>` can be
+/// called with either a constant or a reactive value, so that callers don't have to wrap
+/// constants in `Signal::Constant`.
+pub trait IntoSignal<T> {
+    /// Convert this value into a signal.
+    fn into_signal(self) -> Signal<T>;
+}
+
+impl<T, S: Into<Signal<T>>> IntoSignal<T> for S {
+    fn into_signal(self) -> Signal<T> {
+        self.into()
+    }
+}
+
 /// Implement default if T has a default.
 impl<T> Default for Signal<T>
 where
@@ -87,3 +226,31 @@ where
         Self::Constant(Default::default())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::{Cx, TrackingScope};
+
+    #[test]
+    fn test_option_adapters() {
+        let mut world = World::new();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        let selected = cx.create_mutable::<Option<i32>>(None);
+        let value = selected.signal().unwrap_or(&mut cx, 5);
+        let is_some = selected.signal().is_some(&mut cx);
+        assert_eq!(value.get(&cx), 5);
+        assert!(!is_some.get(&cx));
+
+        let loaded = cx.create_mutable(Some(2)).signal();
+        assert_eq!(loaded.unwrap_or(&mut cx, 5).get(&cx), 2);
+        assert!(loaded.is_some(&mut cx).get(&cx));
+
+        // Constants stay constant.
+        let constant = Signal::constant(Err::<i32, ()>(())).unwrap_or(&mut cx, 7);
+        assert!(matches!(constant, Signal::Constant(7)));
+    }
+}
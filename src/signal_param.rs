@@ -0,0 +1,177 @@
+use std::marker::PhantomData;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    mutable::{MutableCell, MutableNextCell},
+    Mutable, RunContextWrite, Signals,
+};
+
+/// Trait for types which name a [`Mutable`] that can be accessed from ordinary systems via
+/// [`SignalReader`] and [`SignalWriter`]. The key type is usually an empty struct:
+///
+/// ```ignore
+/// struct SelectedTool;
+///
+/// impl SignalKey for SelectedTool {
+///     type Value = Tool;
+/// }
+/// ```
+pub trait SignalKey: Send + Sync + 'static {
+    /// The type of value held by the signal.
+    type Value: PartialEq + Clone + Send + Sync + 'static;
+}
+
+/// The name under which the mutable for the key `K` is registered in [`Signals`].
+fn key_name<K: SignalKey>() -> &'static str {
+    std::any::type_name::<K>()
+}
+
+impl<T> Mutable<T>
+where
+    T: PartialEq + Clone + Send + Sync + 'static,
+{
+    /// Register this mutable under the key `K`, so that systems can access it with
+    /// [`SignalReader<K>`] and [`SignalWriter<K>`]. The mutable is stored in the [`Signals`]
+    /// registry, under the type name of `K`. Registering another mutable under the same key
+    /// replaces this one.
+    pub fn register<K: SignalKey<Value = T>, R: RunContextWrite>(self, cx: &mut R) -> Self {
+        cx.world_mut()
+            .get_resource_or_insert_with(Signals::default)
+            .insert_named(key_name::<K>(), self.signal());
+        self
+    }
+}
+
+/// System parameter which reads the [`Mutable`] registered under the key `K`.
+#[derive(SystemParam)]
+pub struct SignalReader<'w, 's, K: SignalKey> {
+    signals: Option<Res<'w, Signals>>,
+    cells: Query<'w, 's, Ref<'static, MutableCell>>,
+    marker: PhantomData<K>,
+}
+
+impl<'w, 's, K: SignalKey> SignalReader<'w, 's, K> {
+    /// The current value of the signal, or `None` if no mutable is registered under `K` or
+    /// it has been despawned.
+    pub fn get(&self) -> Option<K::Value> {
+        let entity = self
+            .signals
+            .as_ref()?
+            .mutable_entity::<K::Value>(key_name::<K>())?;
+        let cell = self.cells.get(entity).ok()?;
+        cell.0.downcast_ref::<K::Value>().cloned()
+    }
+
+    /// Returns true if the value has changed since the last time this system ran, or if the
+    /// registry has changed, which may mean that a different mutable is registered under `K`.
+    pub fn is_changed(&self) -> bool {
+        let Some(signals) = self.signals.as_ref() else {
+            return false;
+        };
+        signals.is_changed()
+            || signals
+                .mutable_entity::<K::Value>(key_name::<K>())
+                .and_then(|entity| self.cells.get(entity).ok())
+                .is_some_and(|cell| cell.is_changed())
+    }
+}
+
+/// System parameter which writes the [`Mutable`] registered under the key `K`. Writes are
+/// deferred in the same way as other writes to mutables: the new value is committed, and
+/// reactions which depend on it run, the next time reactions are processed. A system can use
+/// a [`SignalReader`] for the same key alongside the writer.
+#[derive(SystemParam)]
+pub struct SignalWriter<'w, 's, K: SignalKey> {
+    signals: Option<Res<'w, Signals>>,
+    cells: Query<'w, 's, (&'static MutableCell, Option<&'static mut MutableNextCell>)>,
+    commands: Commands<'w, 's>,
+    marker: PhantomData<K>,
+}
+
+impl<'w, 's, K: SignalKey> SignalWriter<'w, 's, K> {
+    fn entity(&self) -> Option<Entity> {
+        self.signals
+            .as_ref()?
+            .mutable_entity::<K::Value>(key_name::<K>())
+    }
+
+    /// The value of the signal, including any write which has not yet been committed, or
+    /// `None` if no mutable is registered under `K` or it has been despawned.
+    pub fn get(&self) -> Option<K::Value> {
+        let (cell, next) = self.cells.get(self.entity()?).ok()?;
+        next.and_then(|next| next.0.as_ref())
+            .unwrap_or(&cell.0)
+            .downcast_ref::<K::Value>()
+            .cloned()
+    }
+
+    /// Set the value of the signal. Does nothing if the value being set matches the existing
+    /// value, or if no mutable is registered under `K`.
+    pub fn set(&mut self, value: K::Value) {
+        let Some(entity) = self.entity() else {
+            return;
+        };
+        let Ok((cell, next)) = self.cells.get_mut(entity) else {
+            return;
+        };
+        match next {
+            Some(mut next) => next.0 = Some(Box::new(value)),
+            None => {
+                if cell.0.downcast_ref::<K::Value>() != Some(&value) {
+                    self.commands
+                        .entity(entity)
+                        .insert(MutableNextCell(Some(Box::new(value))));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::{cx::Cx, mutable::commit_mutables, Rcx, RunContextSetup, TrackingScope};
+
+    struct Counter;
+
+    impl SignalKey for Counter {
+        type Value = i32;
+    }
+
+    #[test]
+    fn test_signal_writer() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let mut cx = Cx::new((), &mut world, &mut scope);
+        let counter = cx.create_mutable::<i32>(1).register::<Counter, _>(&mut cx);
+        assert!(world.resource::<Signals>().contains(key_name::<Counter>()));
+
+        // The write is deferred until mutables are committed, like any other write.
+        world.run_system_once(
+            |mut writer: SignalWriter<Counter>, reader: SignalReader<Counter>| {
+                let value = writer.get().unwrap();
+                writer.set(value + 1);
+                assert_eq!(writer.get(), Some(2));
+                assert_eq!(reader.get(), Some(1));
+            },
+        );
+        world.run_system_once(|reader: SignalReader<Counter>| {
+            assert_eq!(reader.get(), Some(1));
+        });
+
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        assert_eq!(counter.get(&Rcx::new(&world, &mut tracking)), 1);
+        world.increment_change_tick();
+        commit_mutables(&mut world);
+        assert!(tracking.dependencies_changed(&world));
+        world.run_system_once(|reader: SignalReader<Counter>| {
+            assert_eq!(reader.get(), Some(2));
+        });
+
+        let cx = Cx::new((), &mut world, &mut scope);
+        assert_eq!(counter.get(&cx), 2);
+    }
+}
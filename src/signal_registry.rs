@@ -0,0 +1,250 @@
+use std::{any::Any, marker::PhantomData};
+
+use bevy::{prelude::*, utils::HashMap};
+
+#[cfg(feature = "scripting")]
+use crate::Rcx;
+use crate::{IntoSignal, RunContextWrite, Signal};
+
+/// A typed name for a signal in the [`Signals`] registry. Declaring names as constants lets
+/// code which doesn't share any other types agree on both the name and the value type:
+///
+/// ```ignore
+/// const SCORE: SignalName<i32> = SignalName::new("game.score");
+/// ```
+pub struct SignalName<T> {
+    name: &'static str,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> SignalName<T> {
+    /// Construct a new signal name.
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            marker: PhantomData,
+        }
+    }
+
+    /// The string form of the name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Copy for SignalName<T> {}
+impl<T> Clone for SignalName<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+struct SignalEntry {
+    /// The `Signal<T>`, type-erased.
+    signal: Box<dyn Any + Send + Sync>,
+    /// The entity holding the signal's value, used to detect dangling entries.
+    entity: Option<Entity>,
+    /// True if the signal is a [`Mutable`](crate::Mutable), and so can be written.
+    mutable: bool,
+    type_name: &'static str,
+    /// Reads the signal as a script value.
+    #[cfg(feature = "scripting")]
+    to_script: Box<dyn Fn(&Rcx) -> rhai::Dynamic + Send + Sync>,
+}
+
+/// Resource which holds signals registered under string names, so that they can be looked up
+/// from anywhere: views, systems, scripts, or plugins which can't share Rust types directly.
+/// Reading the registry with `cx.use_resource::<Signals>()` makes the caller react when
+/// entries are added or removed.
+///
+/// This is the single registry for named signals: mutables registered under a
+/// [`SignalKey`](crate::SignalKey) for [`SignalReader`](crate::SignalReader) and
+/// [`SignalWriter`](crate::SignalWriter) are stored here under the key's type name, and
+/// [script views](crate::ScriptView) can read every registered signal as `signals.<name>`.
+///
+/// Registered signals are not owned by the registry; when the owner of a signal is despawned,
+/// its entry is removed and a warning is logged.
+#[derive(Resource, Default)]
+pub struct Signals {
+    entries: HashMap<String, SignalEntry>,
+}
+
+impl Signals {
+    /// Register `signal` under the given name, replacing any previous entry.
+    pub fn insert<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        name: SignalName<T>,
+        signal: Signal<T>,
+    ) {
+        self.insert_named(name.name, signal);
+    }
+
+    /// Register `signal` under a name given as a string.
+    pub fn insert_named<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        signal: Signal<T>,
+    ) {
+        let entity = match signal {
+            Signal::Mutable(ref mutable) => Some(mutable.id),
+            Signal::Derived(ref derived) => Some(derived.id),
+            Signal::Memo | Signal::Constant(_) => None,
+        };
+        let name = name.into();
+        let previous = self.entries.insert(
+            name.clone(),
+            SignalEntry {
+                mutable: matches!(signal, Signal::Mutable(_)),
+                #[cfg(feature = "scripting")]
+                to_script: {
+                    let signal = signal.clone();
+                    Box::new(move |cx| rhai::Dynamic::from(signal.get_clone(cx)))
+                },
+                signal: Box::new(signal),
+                entity,
+                type_name: std::any::type_name::<T>(),
+            },
+        );
+        if let Some(previous) = previous {
+            if previous.entity.is_some() && previous.entity != entity {
+                warn!("Signal '{}' was registered more than once", name);
+            }
+        }
+    }
+
+    /// Look up the signal registered under the given name.
+    pub fn get<T: Clone + Send + Sync + 'static>(&self, name: SignalName<T>) -> Option<Signal<T>> {
+        self.get_named(name.name)
+    }
+
+    /// Look up a signal by a string name. Returns `None` if there is no such signal, or if it
+    /// was registered with a different value type, in which case a warning is logged.
+    pub fn get_named<T: Clone + Send + Sync + 'static>(&self, name: &str) -> Option<Signal<T>> {
+        let entry = self.entries.get(name)?;
+        match entry.signal.downcast_ref::<Signal<T>>() {
+            Some(signal) => Some(signal.clone()),
+            None => {
+                warn!(
+                    "Signal '{}' has type {}, not {}",
+                    name,
+                    entry.type_name,
+                    std::any::type_name::<T>()
+                );
+                None
+            }
+        }
+    }
+
+    /// The entity of the [`Mutable`](crate::Mutable) registered under the given name, if the
+    /// signal is a mutable of type `T`.
+    pub(crate) fn mutable_entity<T: 'static>(&self, name: &str) -> Option<Entity> {
+        let entry = self.entries.get(name)?;
+        if entry.mutable && entry.signal.is::<Signal<T>>() {
+            entry.entity
+        } else {
+            None
+        }
+    }
+
+    /// The values of all registered signals, as a script object map.
+    #[cfg(feature = "scripting")]
+    pub(crate) fn script_values(&self, cx: &Rcx) -> rhai::Map {
+        self.entries
+            .iter()
+            .map(|(name, entry)| (name.as_str().into(), (entry.to_script)(cx)))
+            .collect()
+    }
+
+    /// Returns true if a signal is registered under the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Remove the signal registered under the given name. Returns true if there was one.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.entries.remove(name).is_some()
+    }
+}
+
+/// Method for registering signals in the [`Signals`] registry.
+pub trait RegisterSignal {
+    /// Register `signal` under the given name, replacing any previous entry. This is
+    /// shorthand for inserting into the [`Signals`] resource, which is created if it doesn't
+    /// exist.
+    fn register_signal<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        signal: impl IntoSignal<T>,
+    );
+}
+
+impl<R: RunContextWrite> RegisterSignal for R {
+    fn register_signal<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        name: impl Into<String>,
+        signal: impl IntoSignal<T>,
+    ) {
+        self.world_mut()
+            .get_resource_or_insert_with(Signals::default)
+            .insert_named(name, signal.into_signal());
+    }
+}
+
+/// Removes entries whose signals have been despawned.
+pub(crate) fn prune_dangling_signals(world: &mut World) {
+    let dangling: Vec<String> = {
+        let Some(signals) = world.get_resource::<Signals>() else {
+            return;
+        };
+        signals
+            .entries
+            .iter()
+            .filter(|(_, entry)| entry.entity.is_some_and(|e| world.get_entity(e).is_none()))
+            .map(|(name, _)| name.clone())
+            .collect()
+    };
+    if dangling.is_empty() {
+        return;
+    }
+    let mut signals = world.resource_mut::<Signals>();
+    for name in dangling {
+        warn!(
+            "Signal '{}' was despawned while still registered; removing it",
+            name
+        );
+        signals.entries.remove(&name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cx, RunContextSetup, TrackingScope};
+
+    const COUNT: SignalName<i32> = SignalName::new("count");
+
+    #[test]
+    fn test_signal_registry() {
+        let mut world = World::default();
+        world.init_resource::<Signals>();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let count = {
+            let mut cx = Cx::new((), &mut world, &mut scope);
+            let count = cx.create_mutable::<i32>(3);
+            cx.world_mut()
+                .resource_mut::<Signals>()
+                .insert(COUNT, count.signal());
+            count
+        };
+
+        let signals = world.resource::<Signals>();
+        assert!(signals.get(COUNT).is_some());
+        assert!(signals.get_named::<String>("count").is_none());
+        assert!(signals.get_named::<i32>("other").is_none());
+
+        // Despawning the signal removes the dangling entry.
+        world.despawn(count.id);
+        prune_dangling_signals(&mut world);
+        assert!(!world.resource::<Signals>().contains("count"));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::ViewHandle;
+
+/// A set of named content regions passed to a composite control, such as the header, body
+/// and actions of a dialog. This lets a control accept several pieces of content without
+/// needing a separate field for each one.
+///
+/// ```ignore
+/// Dialog {
+///     slots: Slots::new()
+///         .set(Dialog::HEADER, "Rename Layer")
+///         .set(Dialog::FOOTER, (cancel_button, ok_button).fragment()),
+///     ..default()
+/// }
+/// ```
+#[derive(Clone, Default)]
+pub struct Slots {
+    slots: Vec<(&'static str, ViewHandle)>,
+}
+
+impl Slots {
+    /// Construct an empty set of slots.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content of the named slot, replacing any previous content.
+    pub fn set(mut self, name: &'static str, view: impl Into<ViewHandle>) -> Self {
+        let view = view.into();
+        match self.slots.iter_mut().find(|(n, _)| *n == name) {
+            Some(slot) => slot.1 = view,
+            None => self.slots.push((name, view)),
+        }
+        self
+    }
+
+    /// Returns true if the named slot has been given content.
+    pub fn has(&self, name: &str) -> bool {
+        self.slots.iter().any(|(n, _)| *n == name)
+    }
+
+    /// Returns the content of the named slot, if it has been set.
+    pub fn get(&self, name: &str) -> Option<ViewHandle> {
+        self.slots
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, view)| view.clone())
+    }
+
+    /// Returns the content of the named slot, or an empty view if it has not been set.
+    pub fn get_or_empty(&self, name: &str) -> ViewHandle {
+        self.get(name).unwrap_or_default()
+    }
+
+    /// Returns the content of the named slot wrapped by `wrap`, such as a styled container,
+    /// or an empty view if the slot has not been set. This allows a control to omit the
+    /// container entirely for regions which have no content.
+    pub fn map<V: Into<ViewHandle>>(
+        &self,
+        name: &str,
+        wrap: impl FnOnce(ViewHandle) -> V,
+    ) -> ViewHandle {
+        match self.get(name) {
+            Some(view) => wrap(view).into(),
+            None => ViewHandle::default(),
+        }
+    }
+
+    /// Returns the names of the slots which have been set, in the order they were first set.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.slots.iter().map(|(name, _)| *name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    #[test]
+    fn test_slots() {
+        let body = ViewHandle::from("Body");
+        let slots = Slots::new()
+            .set("header", "Title")
+            .set("body", body.clone())
+            .set("header", "Other Title");
+        assert!(slots.has("header"));
+        assert!(!slots.has("footer"));
+        assert!(slots.get("footer").is_none());
+        assert!(Arc::ptr_eq(&slots.get("body").unwrap().0, &body.0));
+        assert_eq!(slots.names().collect::<Vec<_>>(), vec!["header", "body"]);
+    }
+}
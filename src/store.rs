@@ -0,0 +1,340 @@
+use std::{marker::PhantomData, sync::Arc};
+
+use bevy::{
+    ecs::{component::Component, entity::Entity},
+    utils::HashMap,
+};
+
+use crate::{RunContextRead, RunContextWrite};
+
+/// Contains the value of a [`Store`], along with a record of which fields have been written.
+#[derive(Component)]
+pub(crate) struct StoreCell<T> {
+    pub(crate) value: T,
+    /// Incremented on each write.
+    counter: u64,
+    /// For each field path, the last write to that field or to anything within it.
+    within: HashMap<Vec<usize>, u64>,
+    /// For each field path, the last write to that field as a whole.
+    direct: HashMap<Vec<usize>, u64>,
+}
+
+impl<T> StoreCell<T> {
+    pub(crate) fn new(value: T) -> Self {
+        Self {
+            value,
+            counter: 0,
+            within: HashMap::default(),
+            direct: HashMap::default(),
+        }
+    }
+
+    /// Record a write to the field at `path`. An empty path is the whole value.
+    pub(crate) fn touch(&mut self, path: &[usize]) {
+        self.counter += 1;
+        self.direct.insert(path.to_vec(), self.counter);
+        for len in 0..=path.len() {
+            self.within.insert(path[..len].to_vec(), self.counter);
+        }
+    }
+
+    /// The last write which may have changed the field at `path`: a write to the field, to
+    /// anything within it, or to any field which contains it.
+    fn version(&self, path: &[usize]) -> u64 {
+        (0..path.len())
+            .filter_map(|len| self.direct.get(&path[..len]))
+            .chain(self.within.get(path))
+            .copied()
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Function which selects a field within the store value.
+type Getter<T, U> = Arc<dyn Fn(&T) -> &U + Send + Sync>;
+
+/// Function which selects a field within the store value for writing.
+type GetterMut<T, U> = Arc<dyn Fn(&mut T) -> &mut U + Send + Sync>;
+
+/// Helpers which pin down the signature of a lens closure, so that the lifetime of the
+/// returned reference is tied to the argument.
+fn lens<T, U, F: Fn(&T) -> &U>(f: F) -> F {
+    f
+}
+
+fn lens_mut<T, U, F: Fn(&mut T) -> &mut U>(f: F) -> F {
+    f
+}
+
+/// A reactive container for a structured value, such as a document being edited. Unlike a
+/// [`Mutable`](crate::Mutable), readers subscribe to individual fields of the value rather
+/// than the whole thing: reading a [`StoreField`] only reacts when that field changes, so
+/// writing one field doesn't invalidate readers of its siblings.
+///
+/// Fields are selected with the accessors generated by
+/// [`#[derive(StoreFields)]`](crate::StoreFields), and can be nested:
+///
+/// ```ignore
+/// let inventory = store.root().player().inventory();
+/// let items = inventory.get(cx);
+/// ```
+///
+/// Readers of these fields are tracked by version: each write records which field it
+/// changed, so deciding whether a reader needs to react doesn't clone or compare the field's
+/// value. Types which can't derive [`StoreFields`](crate::StoreFields) can select fields with
+/// a pair of accessor functions, using [`field`](Self::field); readers of such fields are
+/// tracked by comparing their values instead.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Store<T> {
+    pub(crate) id: Entity,
+    pub(crate) marker: PhantomData<T>,
+}
+
+impl<T> Store<T> {
+    /// The entity that holds the store value.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+}
+
+impl<T> Copy for Store<T> {}
+impl<T> Clone for Store<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Send + Sync + 'static> Store<T> {
+    /// Returns a [`StoreField`] for the entire store value.
+    pub fn root(&self) -> StoreField<T, T> {
+        StoreField {
+            id: self.id,
+            path: Arc::from([]),
+            versioned: true,
+            get: Arc::new(lens(|t: &T| t)),
+            get_mut: Arc::new(lens_mut(|t: &mut T| t)),
+        }
+    }
+
+    /// Returns a [`StoreField`] for a field of the store value, selected by accessor
+    /// functions. Readers of the field react when its value changes.
+    ///
+    /// Arguments:
+    /// * `get` - Function which selects the field.
+    /// * `get_mut` - Function which selects the field for writing.
+    pub fn field<U: 'static>(
+        &self,
+        get: impl Fn(&T) -> &U + Send + Sync + 'static,
+        get_mut: impl Fn(&mut T) -> &mut U + Send + Sync + 'static,
+    ) -> StoreField<T, U> {
+        self.root().field(get, get_mut)
+    }
+}
+
+/// A path to a field within a [`Store`]. Reading the field adds a dependency on just that
+/// field to the current tracking scope.
+pub struct StoreField<T, U> {
+    id: Entity,
+    /// Indices of the fields selected by [`indexed_field`](Self::indexed_field). If
+    /// `versioned` is false, the field lies somewhere within the field at this path, having
+    /// been selected by accessor functions.
+    path: Arc<[usize]>,
+    versioned: bool,
+    get: Getter<T, U>,
+    get_mut: GetterMut<T, U>,
+}
+
+impl<T, U> Clone for StoreField<T, U> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            path: self.path.clone(),
+            versioned: self.versioned,
+            get: self.get.clone(),
+            get_mut: self.get_mut.clone(),
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static, U: 'static> StoreField<T, U> {
+    /// Returns a [`StoreField`] for a field nested within this one, selected by accessor
+    /// functions. Readers of the field react when its value changes.
+    ///
+    /// Arguments:
+    /// * `get` - Function which selects the nested field.
+    /// * `get_mut` - Function which selects the nested field for writing.
+    pub fn field<V: 'static>(
+        &self,
+        get: impl Fn(&U) -> &V + Send + Sync + 'static,
+        get_mut: impl Fn(&mut U) -> &mut V + Send + Sync + 'static,
+    ) -> StoreField<T, V> {
+        let outer = self.get.clone();
+        let outer_mut = self.get_mut.clone();
+        StoreField {
+            id: self.id,
+            path: self.path.clone(),
+            versioned: false,
+            get: Arc::new(lens(move |t: &T| get(outer(t)))),
+            get_mut: Arc::new(lens_mut(move |t: &mut T| get_mut(outer_mut(t)))),
+        }
+    }
+
+    /// Returns a [`StoreField`] for the field with the given index within this one. This is
+    /// used by the accessors generated by [`StoreFields`](crate::StoreFields).
+    #[doc(hidden)]
+    pub fn indexed_field<V: 'static>(
+        &self,
+        index: usize,
+        get: impl Fn(&U) -> &V + Send + Sync + 'static,
+        get_mut: impl Fn(&mut U) -> &mut V + Send + Sync + 'static,
+    ) -> StoreField<T, V> {
+        let mut field = self.field(get, get_mut);
+        if self.versioned {
+            field.path = self.path.iter().copied().chain([index]).collect();
+            field.versioned = true;
+        }
+        field
+    }
+
+    /// Read a value computed from this field. The current tracking scope will only react
+    /// when the result of `f` changes.
+    pub fn map<R: RunContextRead, V: PartialEq + Clone + Send + Sync + 'static>(
+        &self,
+        cx: &R,
+        f: impl Fn(&U) -> V + Send + Sync + 'static,
+    ) -> V {
+        let get = self.get.clone();
+        cx.use_component_field::<StoreCell<T>, _, _>(self.id, move |cell| f(get(&cell.value)))
+            .expect("Store has been despawned")
+    }
+
+    /// Update this field in place using a callback. Readers of this field, or of any field
+    /// which contains it, will react; readers of sibling fields will not.
+    pub fn update<W: RunContextWrite>(&self, cx: &mut W, updater: impl FnOnce(&mut U)) {
+        let mut cell = cx
+            .world_mut()
+            .get_mut::<StoreCell<T>>(self.id)
+            .expect("Store has been despawned");
+        (updater)((self.get_mut)(&mut cell.value));
+        cell.touch(&self.path);
+    }
+}
+
+impl<T: Send + Sync + 'static, U: PartialEq + Clone + Send + Sync + 'static> StoreField<T, U> {
+    /// Get the value of this field with Clone semantics.
+    pub fn get<R: RunContextRead>(&self, cx: &R) -> U {
+        if !self.versioned {
+            return self.map(cx, U::clone);
+        }
+        let path = self.path.clone();
+        cx.use_component_field::<StoreCell<T>, _, _>(self.id, move |cell| cell.version(&path))
+            .expect("Store has been despawned");
+        let cell = cx.use_component_untracked::<StoreCell<T>>(self.id).unwrap();
+        (self.get)(&cell.value).clone()
+    }
+
+    /// Set the value of this field. Does nothing if the value being set matches the
+    /// existing value.
+    pub fn set<W: RunContextWrite>(&self, cx: &mut W, value: U) {
+        let mut cell = cx
+            .world_mut()
+            .get_mut::<StoreCell<T>>(self.id)
+            .expect("Store has been despawned");
+        if *(self.get)(&cell.value) != value {
+            *(self.get_mut)(&mut cell.value) = value;
+            cell.touch(&self.path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::World;
+
+    use crate::{cx::Cx, tracking_scope::TrackingScope, Rcx, RunContextSetup, StoreFields};
+
+    #[derive(Clone, PartialEq, StoreFields)]
+    struct Player {
+        name: String,
+        health: i32,
+    }
+
+    #[derive(Clone, PartialEq, StoreFields)]
+    struct Game {
+        player: Player,
+        level: i32,
+    }
+
+    #[test]
+    fn test_store_field_tracking() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let store = Cx::new((), &mut world, &mut owner).create_store(Player {
+            name: "Alice".to_string(),
+            health: 10,
+        });
+        let name = store.field(|p| &p.name, |p| &mut p.name);
+        let health = store.field(|p| &p.health, |p| &mut p.health);
+
+        let mut name_scope = TrackingScope::new(world.read_change_tick());
+        let mut health_scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(
+            name.get(&Rcx::new(&world, &mut name_scope)),
+            "Alice".to_string()
+        );
+        assert_eq!(health.get(&Rcx::new(&world, &mut health_scope)), 10);
+
+        // Writing one field should not invalidate readers of the other.
+        world.increment_change_tick();
+        health.set(&mut Cx::new((), &mut world, &mut owner), 5);
+        assert!(!name_scope.dependencies_changed(&world));
+        assert!(health_scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_store_fields_derive() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let store = Cx::new((), &mut world, &mut owner).create_store(Game {
+            player: Player {
+                name: "Alice".to_string(),
+                health: 10,
+            },
+            level: 1,
+        });
+        let player = store.root().player();
+        let health = player.health();
+        let level = store.root().level();
+
+        let read = |world: &World, f: &dyn Fn(&Rcx)| {
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            f(&Rcx::new(world, &mut scope));
+            scope
+        };
+        let name_scope = read(&world, &|cx| assert_eq!(player.name().get(cx), "Alice"));
+        let health_scope = read(&world, &|cx| assert_eq!(health.get(cx), 10));
+        let level_scope = read(&world, &|cx| assert_eq!(level.get(cx), 1));
+        let player_scope = read(&world, &|cx| {
+            player.get(cx);
+        });
+
+        // Writing a field invalidates its readers and readers of the fields containing it,
+        // but not readers of its siblings.
+        world.increment_change_tick();
+        health.set(&mut Cx::new((), &mut world, &mut owner), 5);
+        assert!(health_scope.dependencies_changed(&world));
+        assert!(player_scope.dependencies_changed(&world));
+        assert!(!name_scope.dependencies_changed(&world));
+        assert!(!level_scope.dependencies_changed(&world));
+
+        // Replacing a field invalidates readers of the fields within it.
+        let name_scope = read(&world, &|cx| assert_eq!(player.name().get(cx), "Alice"));
+        let level_scope = read(&world, &|cx| assert_eq!(level.get(cx), 1));
+        world.increment_change_tick();
+        player.update(&mut Cx::new((), &mut world, &mut owner), |p| {
+            p.name = "Bob".to_string()
+        });
+        assert!(name_scope.dependencies_changed(&world));
+        assert!(!level_scope.dependencies_changed(&world));
+    }
+}
@@ -8,13 +8,93 @@ use bevy::{
 };
 use bevy_color::{LinearRgba, Srgba};
 
+use super::ElementClasses;
+use crate::TrackingScope;
+
 pub struct StyleBuilder<'a, 'w> {
     pub(crate) target: &'a mut EntityWorldMut<'w>,
     pub(crate) style: ui::Style,
     pub(crate) style_changed: bool,
+    /// Background color set by the styles, if any; `Some(None)` removes it. The colors are
+    /// written once all of the styles have been applied.
+    pub(crate) background_color: Option<Option<Color>>,
+    /// Border color set by the styles, if any; `Some(None)` removes it.
+    pub(crate) border_color: Option<Option<Color>>,
+    /// False once a style has changed a component of the target other than its style and
+    /// colors, in which case the result can't be shared with other elements.
+    pub(crate) shareable: bool,
+    pub(crate) tracking: &'a mut TrackingScope,
+    #[cfg(feature = "style-debug")]
+    pub(crate) provenance: super::StyleProvenance,
 }
 
 impl<'a, 'w> StyleBuilder<'a, 'w> {
+    /// Return a copy of a resource, such as a theme. The styles will be re-applied whenever
+    /// the resource changes.
+    pub fn use_resource<R: Resource + Clone>(&mut self) -> R {
+        let world = self.target.world();
+        self.tracking.track_resource::<R>(world);
+        world.resource::<R>().clone()
+    }
+
+    /// Return a copy of a single field of a resource, such as a color token within a theme.
+    /// The styles will only be re-applied when the value of that field changes, so that
+    /// changing one token doesn't re-style every element which uses the theme.
+    pub fn use_resource_field<
+        R: Resource,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        &mut self,
+        f: F,
+    ) -> T {
+        let value = f(self.target.world().resource::<R>()).clone();
+        self.tracking.track_resource_field(&value, f);
+        value
+    }
+
+    /// Returns true if the element has the style class `name`, as set by
+    /// [`WithStyles::class`](crate::WithStyles::class). The styles will be re-applied when
+    /// the class is added or removed.
+    pub fn has_class(&mut self, name: &'static str) -> bool {
+        let entity = self.target.id();
+        match self.target.get::<ElementClasses>() {
+            Some(classes) => {
+                let present = classes.contains(name);
+                self.tracking.track_component_field(
+                    entity,
+                    present,
+                    move |classes: &ElementClasses| classes.contains(name),
+                );
+                present
+            }
+            None => {
+                // Re-apply the styles when the element's classes are first set.
+                self.tracking
+                    .track_component::<ElementClasses>(entity, self.target.world());
+                false
+            }
+        }
+    }
+
+    /// Apply class-scoped rules, which only take effect while the element has the style class
+    /// `name`. When the class changes, all of the element's styles are re-applied in order, so
+    /// the rules should override properties which are set unconditionally before them; that
+    /// way, removing the class restores the original values:
+    ///
+    /// ```ignore
+    /// ss.background_color(colors::U3)
+    ///     .when_class("selected", |ss| {
+    ///         ss.background_color(colors::U4);
+    ///     });
+    /// ```
+    pub fn when_class(&mut self, name: &'static str, rules: impl FnOnce(&mut Self)) -> &mut Self {
+        if self.has_class(name) {
+            rules(self);
+        }
+        self
+    }
+
     pub fn load_asset<A: Asset>(&mut self, path: AssetPath<'_>) -> Handle<A> {
         self.target.world_scope(|world| {
             let server = world.get_resource::<AssetServer>().unwrap();
@@ -1,8 +1,4 @@
-use bevy::{
-    prelude::*,
-    render::texture::Image,
-    ui::{self, UiImage},
-};
+use bevy::{prelude::*, render::texture::Image, ui::UiImage};
 
 use super::builder::{AssetPathParam, ColorParam, StyleBuilder};
 
@@ -26,6 +22,7 @@ pub trait StyleBuilderBackground {
 
 impl<'a, 'w> StyleBuilderBackground for StyleBuilder<'a, 'w> {
     fn background_image<'p>(&mut self, path: impl AssetPathParam<'p>) -> &mut Self {
+        self.shareable = false;
         let texture = path.to_path().map(|p| self.load_asset::<Image>(p));
         match (texture, self.target.get_mut::<UiImage>()) {
             (Some(texture), Some(mut uii)) => {
@@ -51,6 +48,7 @@ impl<'a, 'w> StyleBuilderBackground for StyleBuilder<'a, 'w> {
         flip_x: bool,
         flip_y: bool,
     ) -> &mut Self {
+        self.shareable = false;
         let texture = path.to_path().map(|p| self.load_asset::<Image>(p));
         match (texture, self.target.get_mut::<UiImage>()) {
             (Some(texture), Some(mut uii)) => {
@@ -74,11 +72,7 @@ impl<'a, 'w> StyleBuilderBackground for StyleBuilder<'a, 'w> {
     }
 
     fn background_color(&mut self, color: impl ColorParam) -> &mut Self {
-        if let Some(color) = color.to_val() {
-            self.target.insert(ui::BackgroundColor(color));
-        } else {
-            self.target.remove::<ui::BackgroundColor>();
-        }
+        self.background_color = Some(color.to_val());
         self
     }
 }
@@ -1,5 +1,3 @@
-use bevy::ui;
-
 use super::builder::{ColorParam, StyleBuilder};
 
 #[allow(missing_docs)]
@@ -9,11 +7,7 @@ pub trait StyleBuilderBorderColor {
 
 impl<'a, 'w> StyleBuilderBorderColor for StyleBuilder<'a, 'w> {
     fn border_color(&mut self, color: impl ColorParam) -> &mut Self {
-        if let Some(color) = color.to_val() {
-            self.target.insert(ui::BorderColor(color));
-        } else {
-            self.target.remove::<ui::BorderColor>();
-        }
+        self.border_color = Some(color.to_val());
         self
     }
 }
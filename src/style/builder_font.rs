@@ -11,6 +11,7 @@ pub trait StyleBuilderFont {
 
 impl<'a, 'w> StyleBuilderFont for StyleBuilder<'a, 'w> {
     fn color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.shareable = false;
         match self.target.get_mut::<InheritableFontStyles>() {
             Some(mut text_style) => text_style.color = color.to_val(),
             None => {
@@ -24,6 +25,7 @@ impl<'a, 'w> StyleBuilderFont for StyleBuilder<'a, 'w> {
     }
 
     fn font<'p>(&mut self, path: impl AssetPathParam<'p>) -> &mut Self {
+        self.shareable = false;
         let font = path.to_path().map(|p| self.load_asset::<Font>(p));
         match self.target.get_mut::<InheritableFontStyles>() {
             Some(mut text_style) => {
@@ -40,6 +42,7 @@ impl<'a, 'w> StyleBuilderFont for StyleBuilder<'a, 'w> {
     }
 
     fn font_size(&mut self, val: impl OptFloatParam) -> &mut Self {
+        self.shareable = false;
         match self.target.get_mut::<InheritableFontStyles>() {
             Some(mut text_style) => {
                 text_style.font_size = val.to_val();
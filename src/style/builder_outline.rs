@@ -10,6 +10,7 @@ pub trait StyleBuilderOutline {
 
 impl<'a, 'w> StyleBuilderOutline for StyleBuilder<'a, 'w> {
     fn outline_color(&mut self, color: impl ColorParam) -> &mut Self {
+        self.shareable = false;
         match (color.to_val(), self.target.get_mut::<ui::Outline>()) {
             (Some(color), Some(mut outline)) => {
                 outline.color = color;
@@ -29,6 +30,7 @@ impl<'a, 'w> StyleBuilderOutline for StyleBuilder<'a, 'w> {
     }
 
     fn outline_width(&mut self, length: impl LengthParam) -> &mut Self {
+        self.shareable = false;
         match self.target.get_mut::<ui::Outline>() {
             Some(mut outline) => {
                 outline.width = length.to_val();
@@ -44,6 +46,7 @@ impl<'a, 'w> StyleBuilderOutline for StyleBuilder<'a, 'w> {
     }
 
     fn outline_offset(&mut self, length: impl LengthParam) -> &mut Self {
+        self.shareable = false;
         match self.target.get_mut::<ui::Outline>() {
             Some(mut outline) => {
                 outline.offset = length.to_val();
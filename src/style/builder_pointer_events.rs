@@ -9,6 +9,7 @@ pub trait StyleBuilderPointerEvents {
 
 impl<'a, 'w> StyleBuilderPointerEvents for StyleBuilder<'a, 'w> {
     fn pointer_events(&mut self, enabled: bool) -> &mut Self {
+        self.shareable = false;
         match enabled {
             true => self.target.remove::<Pickable>(),
             false => self.target.insert(Pickable {
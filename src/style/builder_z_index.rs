@@ -8,6 +8,7 @@ pub trait StyleBuilderZIndex {
 
 impl<'a, 'w> StyleBuilderZIndex for StyleBuilder<'a, 'w> {
     fn z_index(&mut self, index: impl ZIndexParam) -> &mut Self {
+        self.shareable = false;
         match index.to_val() {
             ZIndex::Local(0) => self.target.remove::<ZIndex>(),
             val => self.target.insert(val),
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+
+use crate::{
+    effect_target::InsertCondition, EntityEffect, Rcx, Reaction, ReactionTarget, TrackingScope,
+};
+
+/// Component which holds the style classes of an element, as set by
+/// [`WithStyles::class`](crate::WithStyles::class). Class-scoped style rules, written with
+/// [`StyleBuilder::when_class`](crate::StyleBuilder::when_class), only take effect while the
+/// element has the class.
+#[derive(Component, Clone, Debug, Default)]
+pub struct ElementClasses(Vec<&'static str>);
+
+impl ElementClasses {
+    /// Returns true if the element has the class `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|class| *class == name)
+    }
+
+    /// Iterate over the element's classes, in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+/// Add or remove a class on the target entity. The component is only modified if the class
+/// actually changes, so that styles aren't re-applied needlessly.
+fn set_class(world: &mut World, target: Entity, name: &'static str, present: bool) {
+    let Some(mut entt) = world.get_entity_mut(target) else {
+        return;
+    };
+    match entt.get_mut::<ElementClasses>() {
+        Some(mut classes) => match (classes.0.iter().position(|c| *c == name), present) {
+            (None, true) => classes.0.push(name),
+            (Some(index), false) => {
+                classes.0.remove(index);
+            }
+            _ => {}
+        },
+        // Insert the component even if the class is absent, so that styles which test for the
+        // class can track it.
+        None => {
+            entt.insert(ElementClasses(if present {
+                vec![name]
+            } else {
+                Vec::new()
+            }));
+        }
+    }
+}
+
+/// Effect which sets a class that never changes.
+pub(crate) struct StaticClassEffect {
+    pub(crate) name: &'static str,
+    pub(crate) present: bool,
+}
+
+impl EntityEffect for StaticClassEffect {
+    fn start(&mut self, target: Entity, world: &mut World, _tracking: &mut TrackingScope) {
+        set_class(world, target, self.name, self.present);
+    }
+}
+
+/// Reaction which adds a class to the target entity while a condition is true, and removes
+/// it when the condition becomes false.
+pub(crate) struct ClassReaction<C: InsertCondition> {
+    pub(crate) name: &'static str,
+    pub(crate) cond: C,
+}
+
+impl<C: InsertCondition> Reaction for ClassReaction<C> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = world.entity(owner).get::<ReactionTarget>().unwrap().0;
+        let present = self.cond.test(&Rcx::new(world, tracking));
+        set_class(world, target, self.name, present);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use bevy::ui;
+
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, Element, PresenterFn, RegisterSignal, StyleBuilder,
+        StyleBuilderLayout, View, WithStyles,
+    };
+
+    fn style_item(ss: &mut StyleBuilder) {
+        ss.width(100.).when_class("selected", |ss| {
+            ss.width(200.);
+        });
+    }
+
+    fn item(cx: &mut Cx) -> impl View {
+        let selected = cx.create_mutable(false);
+        cx.register_signal("selected", selected.signal());
+        Element::<NodeBundle>::new()
+            .with_styles(style_item)
+            .class("selected", selected.signal())
+            .class("item", true)
+    }
+
+    #[test]
+    fn test_classes() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(item.bind(()));
+        let element = harness.nodes(root)[0];
+        let width =
+            |harness: &TestHarness| harness.world().get::<ui::Style>(element).unwrap().width;
+        let classes = |harness: &TestHarness| -> Vec<&'static str> {
+            harness
+                .world()
+                .get::<ElementClasses>(element)
+                .unwrap()
+                .iter()
+                .collect()
+        };
+        assert_eq!(classes(&harness), vec!["item"]);
+        assert_eq!(width(&harness), ui::Val::Px(100.));
+
+        let selected = harness.mutable::<bool>("selected");
+        harness.set(selected, true);
+        harness.update();
+        assert_eq!(classes(&harness), vec!["item", "selected"]);
+        assert_eq!(width(&harness), ui::Val::Px(200.));
+
+        // Removing the class restores the unconditional rules.
+        harness.set(selected, false);
+        harness.update();
+        assert_eq!(classes(&harness), vec!["item"]);
+        assert_eq!(width(&harness), ui::Val::Px(100.));
+    }
+
+    /// Number of times `style_counted` has been applied.
+    static APPLIED: AtomicUsize = AtomicUsize::new(0);
+
+    fn style_counted(ss: &mut StyleBuilder) {
+        APPLIED.fetch_add(1, Ordering::Relaxed);
+        style_item(ss);
+    }
+
+    fn preselected_item(cx: &mut Cx) -> impl View {
+        let selected = cx.create_mutable(true);
+        Element::<NodeBundle>::new()
+            .with_styles(style_counted)
+            .class("selected", selected.signal())
+    }
+
+    #[test]
+    fn test_class_before_styles() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(preselected_item.bind(()));
+        harness.update();
+        let element = harness.nodes(root)[0];
+        assert_eq!(
+            harness.world().get::<ui::Style>(element).unwrap().width,
+            ui::Val::Px(200.)
+        );
+        // The class was set before the styles were first applied, so they didn't need to be
+        // applied again.
+        assert_eq!(APPLIED.load(Ordering::Relaxed), 1);
+    }
+}
@@ -5,25 +5,42 @@ mod builder_border_color;
 mod builder_font;
 mod builder_layout;
 mod builder_outline;
+#[cfg(feature = "picking")]
 mod builder_pointer_events;
 // mod builder_texture_atlas;
 mod builder_z_index;
+mod classes;
+#[cfg(feature = "style-debug")]
+mod provenance;
 
-use std::sync::Arc;
+use std::{
+    any::TypeId,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::Arc,
+};
 
-use crate::{effect_target::EffectTarget, Element, EntityEffect, TrackingScope};
+use crate::{
+    effect_target::{EffectTarget, InsertCondition, RunReactionEffect},
+    Element, EntityEffect, Rcx, Reaction, ReactionHandle, ReactionTarget, Signal, TrackingScope,
+};
 // pub use atlas_loader::TextureAtlasLoader;
-use bevy::{prelude::*, ui};
+use bevy::{ecs::component::Tick, prelude::*, ui, utils::HashMap};
 pub use builder::StyleBuilder;
 pub use builder_background::StyleBuilderBackground;
 pub use builder_border_color::StyleBuilderBorderColor;
 pub use builder_font::StyleBuilderFont;
 pub use builder_layout::StyleBuilderLayout;
 pub use builder_outline::StyleBuilderOutline;
+#[cfg(feature = "picking")]
 pub use builder_pointer_events::StyleBuilderPointerEvents;
 // pub use builder_texture_atlas::StyleBuilderTextureAtlas;
 pub use builder_z_index::StyleBuilderZIndex;
+pub use classes::ElementClasses;
+use classes::{ClassReaction, StaticClassEffect};
 use impl_trait_for_tuples::*;
+#[cfg(feature = "style-debug")]
+pub use provenance::StyleProvenance;
 
 pub(crate) use builder_font::{InheritableFontStyles, TextStyleChanged};
 
@@ -31,16 +48,56 @@ pub(crate) use builder_font::{InheritableFontStyles, TextStyleChanged};
 pub trait StyleTuple: Sync + Send {
     /// Method to apply the style to a target entity.
     fn apply(&self, ctx: &mut StyleBuilder);
+
+    /// Add the identity of the styles to `key`, so that the style computed for one element
+    /// can be reused for other elements with the same styles. Returns false if the styles
+    /// can't be identified.
+    #[doc(hidden)]
+    fn cache_key(&self, _key: &mut StyleKey) -> bool {
+        false
+    }
 }
 
 /// Empty tuple.
 impl StyleTuple for () {
     fn apply(&self, _ctx: &mut StyleBuilder) {}
+
+    fn cache_key(&self, _key: &mut StyleKey) -> bool {
+        true
+    }
 }
 
 impl<F: Fn(&mut StyleBuilder) + Send + Sync + 'static> StyleTuple for F {
     fn apply(&self, ctx: &mut StyleBuilder) {
-        (self)(ctx);
+        apply_style_fn(ctx, std::any::type_name::<F>(), self);
+    }
+
+    fn cache_key(&self, key: &mut StyleKey) -> bool {
+        // A function which captures nothing is identified by its type; closures which capture
+        // values may behave differently for each element.
+        if std::mem::size_of::<F>() != 0 {
+            return false;
+        }
+        key.0.push(StyleKeyPart::Fn(TypeId::of::<F>()));
+        true
+    }
+}
+
+/// Call a style function, recording the properties which it changes when style debugging is
+/// enabled.
+#[cfg_attr(not(feature = "style-debug"), allow(unused_variables))]
+fn apply_style_fn(
+    ctx: &mut StyleBuilder,
+    source: &'static str,
+    apply: impl FnOnce(&mut StyleBuilder),
+) {
+    #[cfg(feature = "style-debug")]
+    let before = provenance::StyleSnapshot::capture(ctx);
+    apply(ctx);
+    #[cfg(feature = "style-debug")]
+    {
+        let after = provenance::StyleSnapshot::capture(ctx);
+        ctx.provenance.record(source, &before, &after);
     }
 }
 
@@ -50,6 +107,14 @@ impl StyleTuple for StyleHandle {
             s.apply(ctx);
         }
     }
+
+    fn cache_key(&self, key: &mut StyleKey) -> bool {
+        // Clones of a handle share their styles, which can't change once the handle is made.
+        if let Some(s) = self.style.as_ref() {
+            key.0.push(StyleKeyPart::Handle(s.clone()));
+        }
+        true
+    }
 }
 
 #[impl_for_tuples(1, 16)]
@@ -59,42 +124,407 @@ impl StyleTuple for Tuple {
     fn apply(&self, ctx: &mut StyleBuilder) {
         for_tuples!( #( self.Tuple.apply(ctx); )* );
     }
+
+    fn cache_key(&self, key: &mut StyleKey) -> bool {
+        for_tuples!( #( if !self.Tuple.cache_key(key) { return false; } )* );
+        true
+    }
+}
+
+/// Identifies a set of styles, so that the style computed for one element can be reused for
+/// other elements with the same styles.
+#[derive(Default, Clone, PartialEq, Eq, Hash)]
+pub struct StyleKey(Vec<StyleKeyPart>);
+
+#[derive(Clone)]
+enum StyleKeyPart {
+    /// A style function which captures nothing, identified by its type.
+    Fn(TypeId),
+    /// The styles of a [`StyleHandle`], identified by address. The styles are kept alive so
+    /// that the address can't be reused while the key exists.
+    Handle(Arc<dyn StyleTuple>),
+}
+
+impl StyleKeyPart {
+    fn address(styles: &Arc<dyn StyleTuple>) -> *const () {
+        Arc::as_ptr(styles) as *const ()
+    }
+}
+
+impl PartialEq for StyleKeyPart {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Fn(a), Self::Fn(b)) => a == b,
+            (Self::Handle(a), Self::Handle(b)) => Self::address(a) == Self::address(b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for StyleKeyPart {}
+
+impl Hash for StyleKeyPart {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            Self::Fn(id) => id.hash(state),
+            Self::Handle(styles) => Self::address(styles).hash(state),
+        }
+    }
+}
+
+/// The changes made to an element by a set of styles: its new `Style`, if the styles
+/// changed it, and the colors which were set.
+#[derive(Clone)]
+struct ComputedStyle {
+    style: Option<ui::Style>,
+    background_color: Option<Option<Color>>,
+    border_color: Option<Option<Color>>,
+}
+
+impl ComputedStyle {
+    /// Write the computed style to the target. Components which already have the computed
+    /// value are skipped, so that re-applying a shared style doesn't trigger change detection
+    /// (and re-layout) on every entity.
+    fn write(&self, target: &mut EntityWorldMut) {
+        if let Some(style) = &self.style {
+            if target.get::<ui::Style>() != Some(style) {
+                target.insert(style.clone());
+            }
+        }
+        match self.background_color {
+            Some(Some(color)) => {
+                if target.get::<ui::BackgroundColor>().map(|c| c.0) != Some(color) {
+                    target.insert(ui::BackgroundColor(color));
+                }
+            }
+            Some(None) => {
+                target.remove::<ui::BackgroundColor>();
+            }
+            None => (),
+        }
+        match self.border_color {
+            Some(Some(color)) => {
+                if target.get::<ui::BorderColor>().map(|c| c.0) != Some(color) {
+                    target.insert(ui::BorderColor(color));
+                }
+            }
+            Some(None) => {
+                target.remove::<ui::BorderColor>();
+            }
+            None => (),
+        }
+    }
+}
+
+/// Resource which holds the styles computed during the current change tick for sets of
+/// styles shared by many elements, such as a [`StyleHandle`] passed to every row of a list.
+/// When a resource which the styles depend on changes, the styles are run once, and the
+/// result is written to every element which started out with the same style.
+#[derive(Resource, Default)]
+struct SharedStyles {
+    tick: Tick,
+    entries: HashMap<StyleKey, Vec<SharedStyle>>,
 }
 
-/// Inserts a static, pre-constructed bundle into the target entity. No reactivity.
+/// A style computed for a set of shared styles.
+struct SharedStyle {
+    /// The element's style before the styles were applied.
+    input: ui::Style,
+    computed: ComputedStyle,
+    /// The resources which the styles read.
+    deps: TrackingScope,
+}
+
+/// Apply a set of styles to the target entity, reusing the style computed for another
+/// element with the same styles and the same starting style if there is one.
+fn apply_shared_styles<S: StyleTuple + ?Sized>(
+    target: Entity,
+    world: &mut World,
+    tracking: &mut TrackingScope,
+    styles: &S,
+) {
+    let mut key = StyleKey::default();
+    // Style provenance is recorded per element, which needs the styles to run every time.
+    if cfg!(feature = "style-debug") || !styles.cache_key(&mut key) {
+        apply_styles(target, world, tracking, |ss| styles.apply(ss));
+        return;
+    }
+    let tick = world.read_change_tick();
+    let input = world.get::<ui::Style>(target).cloned().unwrap_or_default();
+    let shared = world
+        .get_resource::<SharedStyles>()
+        .filter(|cache| cache.tick == tick)
+        .and_then(|cache| cache.entries.get(&key))
+        .and_then(|entries| {
+            entries
+                .iter()
+                .find(|entry| entry.input == input && !entry.deps.dependencies_changed(world))
+        })
+        .map(|entry| {
+            tracking.copy_resource_deps(&entry.deps);
+            entry.computed.clone()
+        });
+    if let Some(computed) = shared {
+        computed.write(&mut world.entity_mut(target));
+        return;
+    }
+
+    let Some(computed) = apply_styles(target, world, tracking, |ss| styles.apply(ss)) else {
+        return;
+    };
+    // Styles which read the element's classes depend on the element.
+    if !tracking.has_only_resource_deps() {
+        return;
+    }
+    // The entry is checked against changes made to its resources later in this tick.
+    let mut deps = TrackingScope::new(Tick::new(tick.get().wrapping_sub(1)));
+    deps.copy_resource_deps(tracking);
+    let mut cache = world.get_resource_or_insert_with(SharedStyles::default);
+    if cache.tick != tick {
+        cache.tick = tick;
+        cache.entries.clear();
+    }
+    cache.entries.entry(key).or_default().push(SharedStyle {
+        input,
+        computed,
+        deps,
+    });
+}
+
+/// Applies a set of styles to the target entity. If any of the styles read a resource
+/// via the [`StyleBuilder`], the styles are re-applied whenever that resource (or the
+/// specific field of it that was read) changes; otherwise they are applied only once.
 pub struct ApplyStylesEffect<S: StyleTuple> {
-    pub(crate) styles: S,
+    pub(crate) styles: Arc<S>,
 }
 
-impl<S: StyleTuple> EntityEffect for ApplyStylesEffect<S> {
+impl<S: StyleTuple + 'static> EntityEffect for ApplyStylesEffect<S> {
     // For a style builder, run the builder over the target entity.
-    fn start(&mut self, target: Entity, world: &mut World, _tracking: &mut TrackingScope) {
-        let mut target = world.entity_mut(target);
-        let mut style = ui::Style::default();
-        if let Some(s) = target.get::<ui::Style>() {
-            style.clone_from(s);
+    fn start(&mut self, target: Entity, world: &mut World, parent_scope: &mut TrackingScope) {
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        apply_shared_styles(target, world, &mut scope, &*self.styles);
+        reapply_style_dyn(world, target);
+
+        // Only styles which depend on resources need to be reactive.
+        if scope.has_deps() {
+            let reaction_id = world
+                .spawn((
+                    ReactionHandle::new(ApplyStylesReaction {
+                        styles: self.styles.clone(),
+                    }),
+                    ReactionTarget(target),
+                    scope,
+                ))
+                .id();
+            parent_scope.add_owned(reaction_id);
+        }
+    }
+}
+
+/// Reaction which re-applies styles when the resources they depend on change.
+struct ApplyStylesReaction<S: StyleTuple> {
+    styles: Arc<S>,
+}
+
+impl<S: StyleTuple> Reaction for ApplyStylesReaction<S> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = world.entity(owner).get::<ReactionTarget>().unwrap().0;
+        if world.get_entity(target).is_some() {
+            apply_shared_styles(target, world, tracking, &*self.styles);
+            reapply_style_dyn(world, target);
         }
-        let mut ctx = StyleBuilder {
-            target: &mut target,
-            style,
-            style_changed: false,
+    }
+}
+
+/// Component which lists the [`WithStyles::style_dyn`] reactions of an element.
+#[derive(Component, Default)]
+struct StyleDynFragments(Vec<Entity>);
+
+/// Re-run the `style_dyn` reactions of an element after its styles have been applied from
+/// scratch, so that the dynamic styles are layered on top again rather than overwritten.
+fn reapply_style_dyn(world: &mut World, target: Entity) {
+    let Some(fragments) = world.get::<StyleDynFragments>(target) else {
+        return;
+    };
+    for fragment in fragments.0.clone() {
+        let Some(reaction) = world.get::<ReactionHandle>(fragment).map(|r| r.0.clone()) else {
+            continue;
         };
-        self.styles.apply(&mut ctx);
-        if ctx.style_changed {
-            ctx.target.insert(ctx.style);
+        // The values the fragment depends on have not changed, so neither have its
+        // dependencies, and the new scope can be discarded.
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        reaction.lock().unwrap().react(fragment, world, &mut scope);
+    }
+}
+
+/// Reaction which re-applies a style function when the value it depends on changes.
+struct StyleDynReaction<D, V: StyleDynSource<D>, F: Fn(D, &mut StyleBuilder)> {
+    source: V,
+    style_fn: F,
+    registered: bool,
+    marker: PhantomData<fn() -> D>,
+}
+
+impl<D, V: StyleDynSource<D>, F: Send + Sync + Fn(D, &mut StyleBuilder)> Reaction
+    for StyleDynReaction<D, V, F>
+{
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let target = world.entity(owner).get::<ReactionTarget>().unwrap().0;
+        let Some(mut entt) = world.get_entity_mut(target) else {
+            return;
+        };
+        if !self.registered {
+            // Register with the element so that the style function is re-applied whenever
+            // the element's other styles are.
+            self.registered = true;
+            match entt.get_mut::<StyleDynFragments>() {
+                Some(mut fragments) => fragments.0.push(owner),
+                None => {
+                    entt.insert(StyleDynFragments(vec![owner]));
+                }
+            }
         }
+        let value = self.source.get(&Rcx::new(world, tracking));
+        let style_fn = &self.style_fn;
+        apply_styles(target, world, tracking, |ss| {
+            apply_style_fn(ss, std::any::type_name::<F>(), |ss| style_fn(value, ss))
+        });
     }
 }
 
+/// The source of the value for [`WithStyles::style_dyn`]: a signal, or a closure which is
+/// evaluated reactively.
+pub trait StyleDynSource<D>: Send + Sync + 'static {
+    /// Read the value, adding its dependencies to the current tracking scope.
+    fn get(&self, rcx: &Rcx) -> D;
+}
+
+impl<D: Clone + Send + Sync + 'static> StyleDynSource<D> for Signal<D> {
+    fn get(&self, rcx: &Rcx) -> D {
+        self.get_clone(rcx)
+    }
+}
+
+impl<D, F: Send + Sync + 'static + Fn(&Rcx) -> D> StyleDynSource<D> for F {
+    fn get(&self, rcx: &Rcx) -> D {
+        self(rcx)
+    }
+}
+
+/// Run style functions over the target entity, starting from its current style, and update
+/// the entity's `Style` and colors if they changed them. Returns the computed style, unless
+/// the style functions changed other components, in which case it can't be shared with
+/// other elements.
+fn apply_styles(
+    target: Entity,
+    world: &mut World,
+    tracking: &mut TrackingScope,
+    apply: impl FnOnce(&mut StyleBuilder),
+) -> Option<ComputedStyle> {
+    let mut target = world.entity_mut(target);
+    let mut style = ui::Style::default();
+    if let Some(s) = target.get::<ui::Style>() {
+        style.clone_from(s);
+    }
+    #[cfg(feature = "style-debug")]
+    let provenance = target.get::<StyleProvenance>().cloned().unwrap_or_default();
+    let mut ctx = StyleBuilder {
+        target: &mut target,
+        style,
+        style_changed: false,
+        background_color: None,
+        border_color: None,
+        shareable: true,
+        tracking,
+        #[cfg(feature = "style-debug")]
+        provenance,
+    };
+    apply(&mut ctx);
+    #[cfg(feature = "style-debug")]
+    {
+        let provenance = std::mem::take(&mut ctx.provenance);
+        ctx.target.insert(provenance);
+    }
+    let computed = ComputedStyle {
+        style: ctx.style_changed.then_some(ctx.style),
+        background_color: ctx.background_color,
+        border_color: ctx.border_color,
+    };
+    let shareable = ctx.shareable;
+    computed.write(&mut target);
+    shareable.then_some(computed)
+}
+
 /// Trait to add a collection of styles to the receiver.
 pub trait WithStyles {
     /// Apply a set of style builders to a target.
     fn with_styles<S: StyleTuple + 'static>(self, styles: S) -> Self;
+
+    /// Apply a style function which depends on a reactive value, such as a hover signal. The
+    /// value can be a signal, or a closure which reads signals; whenever it changes, only
+    /// this style function is re-applied, on top of the element's current style. When the
+    /// element's other styles are re-applied, for example because the theme or the element's
+    /// classes changed, the style function is re-applied after them:
+    ///
+    /// ```ignore
+    /// .style_dyn(hovering, |hovering, ss| {
+    ///     ss.background_color(if hovering { colors::U4 } else { colors::U3 });
+    /// })
+    /// ```
+    #[track_caller]
+    fn style_dyn<
+        D: 'static,
+        V: StyleDynSource<D>,
+        F: Send + Sync + 'static + Fn(D, &mut StyleBuilder),
+    >(
+        mut self,
+        source: V,
+        style_fn: F,
+    ) -> Self
+    where
+        Self: EffectTarget,
+    {
+        self.add_reaction(StyleDynReaction {
+            source,
+            style_fn,
+            registered: false,
+            marker: PhantomData,
+        });
+        self
+    }
+
+    /// Give the element the style class `name` while a condition is true. The condition can be
+    /// a constant `bool`, a `Signal<bool>`, or a reactive closure. Style rules written with
+    /// [`StyleBuilder::when_class`] are re-applied whenever the class is added or removed:
+    ///
+    /// ```ignore
+    /// .with_styles(style_item)
+    /// .class("selected", selected)
+    /// ```
+    ///
+    /// Classes are set before the element's styles are first applied, so the order of
+    /// `class` and `with_styles` doesn't matter.
+    #[track_caller]
+    fn class<C: InsertCondition>(mut self, name: &'static str, cond: C) -> Self
+    where
+        Self: EffectTarget,
+    {
+        match cond.constant() {
+            Some(present) => self.add_early_effect(Box::new(StaticClassEffect { name, present })),
+            None => self.add_early_effect(Box::new(RunReactionEffect::new(ClassReaction {
+                name,
+                cond,
+            }))),
+        }
+        self
+    }
 }
 
 impl<B: Bundle + Default> WithStyles for Element<B> {
     fn with_styles<S: StyleTuple + 'static>(mut self, styles: S) -> Self {
-        self.add_effect(Box::new(ApplyStylesEffect { styles }));
+        self.add_effect(Box::new(ApplyStylesEffect {
+            styles: Arc::new(styles),
+        }));
         self
     }
 }
@@ -119,3 +549,128 @@ impl StyleHandle {
         Self { style: None }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, ParentView, PresenterFn, RegisterSignal, StyleBuilderBackground,
+        StyleBuilderLayout, View,
+    };
+
+    fn panel(cx: &mut Cx) -> impl View {
+        let wide = cx.create_mutable(false);
+        let tall = cx.create_mutable(false);
+        cx.register_signal("wide", wide.signal());
+        cx.register_signal("tall", tall.signal());
+        Element::<NodeBundle>::new()
+            .with_styles(|ss: &mut StyleBuilder| {
+                ss.width(50.).height(10.).when_class("tall", |ss| {
+                    ss.height(20.);
+                });
+            })
+            .class("tall", tall.signal())
+            .style_dyn(wide.signal(), |wide, ss| {
+                ss.width(if wide { 200. } else { 100. });
+            })
+    }
+
+    #[test]
+    fn test_style_dyn() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(panel.bind(()));
+        let element = harness.nodes(root)[0];
+        let style = |harness: &TestHarness| harness.world().get::<ui::Style>(element).cloned();
+        assert_eq!(style(&harness).unwrap().width, ui::Val::Px(100.));
+
+        let wide = harness.mutable::<bool>("wide");
+        harness.set(wide, true);
+        let updated = style(&harness).unwrap();
+        assert_eq!(updated.width, ui::Val::Px(200.));
+        // Properties set by other styles are left as they were.
+        assert_eq!(updated.height, ui::Val::Px(10.));
+
+        // Re-applying the other styles re-applies the dynamic style on top of them.
+        let tall = harness.mutable::<bool>("tall");
+        harness.set(tall, true);
+        harness.update();
+        let updated = style(&harness).unwrap();
+        assert_eq!(updated.height, ui::Val::Px(20.));
+        assert_eq!(updated.width, ui::Val::Px(200.));
+    }
+    #[derive(Resource)]
+    struct Theme {
+        size: f32,
+    }
+
+    static THEMED_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+    fn style_themed(ss: &mut StyleBuilder) {
+        THEMED_RUNS.fetch_add(1, Ordering::Relaxed);
+        let size = ss.use_resource_field(|theme: &Theme| &theme.size);
+        ss.width(size).height(10.).background_color(Color::WHITE);
+    }
+
+    fn style_pinned(ss: &mut StyleBuilder) {
+        ss.width(50.);
+    }
+
+    fn rows(_cx: &mut Cx) -> impl View {
+        let style = StyleHandle::new(style_themed);
+        Element::<NodeBundle>::new().with_children((
+            Element::<NodeBundle>::new().with_styles(style.clone()),
+            Element::<NodeBundle>::new().with_styles(style.clone()),
+            Element::<NodeBundle>::new().with_styles(style.clone()),
+            Element::<NodeBundle>::new().with_styles((style, style_pinned)),
+        ))
+    }
+
+    #[test]
+    fn test_shared_styles() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().insert_resource(Theme { size: 100. });
+        let root = harness.mount(rows.bind(()));
+        let rows = harness
+            .world()
+            .get::<Children>(harness.nodes(root)[0])
+            .unwrap()
+            .to_vec();
+        // The rows which only use the shared handle share one computed style.
+        assert_eq!(THEMED_RUNS.load(Ordering::Relaxed), 2);
+
+        let changed = |harness: &TestHarness| {
+            rows.iter()
+                .map(|row| {
+                    let entt = harness.world().entity(*row);
+                    (
+                        entt.get_change_ticks::<ui::Style>()
+                            .unwrap()
+                            .last_changed_tick(),
+                        entt.get_change_ticks::<BackgroundColor>()
+                            .unwrap()
+                            .last_changed_tick(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let before = changed(&harness);
+        harness.world_mut().resource_mut::<Theme>().size = 200.;
+        harness.update();
+        assert_eq!(THEMED_RUNS.load(Ordering::Relaxed), 4);
+        let after = changed(&harness);
+        for (index, row) in rows.iter().enumerate() {
+            let width = harness.world().get::<ui::Style>(*row).unwrap().width;
+            // Only the styles which changed are marked as changed.
+            if index < 3 {
+                assert_eq!(width, ui::Val::Px(200.));
+                assert_ne!(after[index].0, before[index].0);
+            } else {
+                assert_eq!(width, ui::Val::Px(50.));
+                assert_eq!(after[index].0, before[index].0);
+            }
+            assert_eq!(after[index].1, before[index].1);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use bevy::{prelude::*, ui};
+
+use super::{InheritableFontStyles, StyleBuilder};
+
+/// Records which style function last wrote each style property of an element. This is only
+/// available with the `style-debug` feature, and is intended for diagnosing conflicts between
+/// composed styles, such as `(text_default, style_dialog, |ss| ...)`, in tools like a layout
+/// inspector.
+///
+/// Sources are identified by the type name of the style function: a named function appears
+/// as its path, such as `obsidian_ui::controls::dialog::style_dialog`, while a closure appears
+/// as the path of the function which contains it, followed by `{{closure}}`. A style is only
+/// recorded as writing a property if applying it changed the property's value.
+#[derive(Component, Clone, Debug, Default)]
+pub struct StyleProvenance {
+    /// For each property, the styles which wrote it, in the order they were applied.
+    properties: Vec<(&'static str, Vec<&'static str>)>,
+}
+
+impl StyleProvenance {
+    /// The style which last wrote `property`, and whose value is therefore in effect.
+    /// Property names are the field names of [`ui::Style`], or `background_color`,
+    /// `border_color`, `outline`, `z_index`, `font`, `font_size` and `color`.
+    pub fn source(&self, property: &str) -> Option<&'static str> {
+        self.writers(property).last().copied()
+    }
+
+    /// All of the styles which wrote `property`, in the order they were applied.
+    pub fn writers(&self, property: &str) -> &[&'static str] {
+        self.properties
+            .iter()
+            .find(|(name, _)| *name == property)
+            .map_or(&[], |(_, writers)| writers.as_slice())
+    }
+
+    /// Iterate over the recorded properties, along with the styles which wrote them.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &[&'static str])> {
+        self.properties
+            .iter()
+            .map(|(name, writers)| (*name, writers.as_slice()))
+    }
+
+    /// Iterate over the properties which were written by more than one style.
+    pub fn conflicts(&self) -> impl Iterator<Item = (&'static str, &[&'static str])> {
+        self.iter().filter(|(_, writers)| writers.len() > 1)
+    }
+
+    /// Record that `source` changed the properties which differ between `before` and `after`.
+    pub(crate) fn record(
+        &mut self,
+        source: &'static str,
+        before: &StyleSnapshot,
+        after: &StyleSnapshot,
+    ) {
+        for ((property, old), (_, new)) in before.0.iter().zip(after.0.iter()) {
+            if old == new {
+                continue;
+            }
+            let index = match self
+                .properties
+                .iter()
+                .position(|(name, _)| name == property)
+            {
+                Some(index) => index,
+                None => {
+                    self.properties.push((property, Vec::new()));
+                    self.properties.len() - 1
+                }
+            };
+            // A style which is re-applied moves to the end, since it is now the last writer.
+            let writers = &mut self.properties[index].1;
+            writers.retain(|writer| *writer != source);
+            writers.push(source);
+        }
+    }
+}
+
+/// The value of every tracked style property, formatted for comparison.
+pub(crate) struct StyleSnapshot(Vec<(&'static str, String)>);
+
+macro_rules! snapshot_fields {
+    ($style:expr, $($field:ident),* $(,)?) => {
+        vec![$((stringify!($field), format!("{:?}", $style.$field))),*]
+    };
+}
+
+impl StyleSnapshot {
+    /// Capture the current values of the properties being built by `builder`.
+    pub(crate) fn capture(builder: &StyleBuilder) -> Self {
+        let style = &builder.style;
+        let mut values = snapshot_fields!(
+            style,
+            display,
+            position_type,
+            overflow,
+            direction,
+            left,
+            right,
+            top,
+            bottom,
+            width,
+            height,
+            min_width,
+            min_height,
+            max_width,
+            max_height,
+            aspect_ratio,
+            align_items,
+            justify_items,
+            align_self,
+            justify_self,
+            align_content,
+            justify_content,
+            margin,
+            padding,
+            border,
+            flex_direction,
+            flex_wrap,
+            flex_grow,
+            flex_shrink,
+            flex_basis,
+            row_gap,
+            column_gap,
+            grid_auto_flow,
+            grid_template_rows,
+            grid_template_columns,
+            grid_auto_rows,
+            grid_auto_columns,
+            grid_row,
+            grid_column,
+        );
+        let target = &builder.target;
+        let font = target.get::<InheritableFontStyles>();
+        // Colors set by the styles aren't written to the target until they have all run.
+        let background_color = builder
+            .background_color
+            .unwrap_or_else(|| target.get::<ui::BackgroundColor>().map(|c| c.0));
+        let border_color = builder
+            .border_color
+            .unwrap_or_else(|| target.get::<ui::BorderColor>().map(|c| c.0));
+        values.extend([
+            ("background_color", format!("{:?}", background_color)),
+            ("border_color", format!("{:?}", border_color)),
+            ("outline", format!("{:?}", target.get::<ui::Outline>())),
+            ("z_index", format!("{:?}", target.get::<ui::ZIndex>())),
+            ("font", format!("{:?}", font.and_then(|f| f.font.as_ref()))),
+            ("font_size", format!("{:?}", font.and_then(|f| f.font_size))),
+            ("color", format!("{:?}", font.and_then(|f| f.color))),
+        ]);
+        Self(values)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{StyleBuilderLayout, StyleTuple, TrackingScope};
+
+    fn style_base(ss: &mut StyleBuilder) {
+        ss.width(10.).height(10.);
+    }
+
+    fn style_wide(ss: &mut StyleBuilder) {
+        ss.width(20.);
+    }
+
+    #[test]
+    fn test_provenance() {
+        let mut world = World::new();
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        let mut target = world.spawn_empty();
+        let mut builder = StyleBuilder {
+            target: &mut target,
+            style: ui::Style::default(),
+            style_changed: false,
+            background_color: None,
+            border_color: None,
+            shareable: true,
+            tracking: &mut tracking,
+            provenance: StyleProvenance::default(),
+        };
+        (style_base, style_wide).apply(&mut builder);
+
+        let provenance = builder.provenance;
+        assert!(provenance.source("width").unwrap().ends_with("style_wide"));
+        assert!(provenance.source("height").unwrap().ends_with("style_base"));
+        assert_eq!(provenance.writers("width").len(), 2);
+        assert_eq!(provenance.conflicts().count(), 1);
+        assert_eq!(provenance.source("display"), None);
+    }
+}
@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::context::ContextValue;
+use crate::node_span::NodeSpan;
+use crate::{DespawnScopes, DisplayNodeChanged, Rcx, Signal, TrackingScope, View, ViewHandle};
+
+/// Context value which marks the view entity of a [`Suspense`], so that views within its
+/// content can find it.
+#[derive(Clone, Copy)]
+pub(crate) struct SuspenseBoundary;
+
+/// Component holding the readiness signals registered with a [`Suspense`] by its content.
+#[derive(Component, Default)]
+pub(crate) struct SuspenseDeps(pub(crate) Vec<Signal<bool>>);
+
+/// A view which renders a fallback, such as a spinner, while its content waits for data to
+/// load. Views within the content register the data they are waiting for using
+/// [`Cx::suspend_until`](crate::Cx::suspend_until) or
+/// [`Cx::suspend_until_loaded`](crate::Cx::suspend_until_loaded). The content is built
+/// straight away, but kept hidden until all registered dependencies are ready, and is then
+/// swapped in for the fallback.
+///
+/// Once the content has been shown, it remains shown, even if new dependencies are
+/// registered later.
+pub struct Suspense {
+    content: ViewHandle,
+    fallback: ViewHandle,
+    min_duration: Duration,
+    content_ent: Option<Entity>,
+    fallback_ent: Option<Entity>,
+    holder: Option<Entity>,
+    started: Duration,
+    resolved: bool,
+}
+
+impl Suspense {
+    /// Construct a new `Suspense` around the given content. By default, nothing is rendered
+    /// while the content is loading.
+    pub fn new(content: impl Into<ViewHandle>) -> Self {
+        Self {
+            content: content.into(),
+            fallback: ViewHandle::default(),
+            min_duration: Duration::ZERO,
+            content_ent: None,
+            fallback_ent: None,
+            holder: None,
+            started: Duration::ZERO,
+            resolved: false,
+        }
+    }
+
+    /// Set the view which is rendered while the content is loading.
+    pub fn with_fallback(mut self, fallback: impl Into<ViewHandle>) -> Self {
+        self.fallback = fallback.into();
+        self
+    }
+
+    /// Set the minimum amount of time for which the fallback is shown. This prevents the
+    /// fallback from flickering briefly when the data loads quickly.
+    pub fn with_min_duration(mut self, min_duration: Duration) -> Self {
+        self.min_duration = min_duration;
+        self
+    }
+
+    /// Parent the content's display nodes to the hidden holder node, so that they are not
+    /// displayed while the fallback is shown.
+    fn hide_content(&self, world: &mut World) {
+        let mut nodes = Vec::new();
+        self.content.nodes().flatten(&mut nodes);
+        world
+            .entity_mut(self.holder.unwrap())
+            .replace_children(&nodes);
+    }
+}
+
+fn elapsed(world: &World) -> Duration {
+    world
+        .get_resource::<Time>()
+        .map(|time| time.elapsed())
+        .unwrap_or_default()
+}
+
+impl View for Suspense {
+    fn nodes(&self) -> NodeSpan {
+        if self.resolved {
+            self.content.nodes()
+        } else {
+            self.fallback.nodes()
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world.entity_mut(view_entity).insert((
+            Name::new("Suspense"),
+            ContextValue(SuspenseBoundary),
+            SuspenseDeps::default(),
+        ));
+        self.started = elapsed(world);
+        self.holder = Some(
+            world
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        ..default()
+                    },
+                    ..default()
+                })
+                .id(),
+        );
+        self.content_ent = Some(ViewHandle::spawn(&self.content, view_entity, world));
+        self.fallback_ent = Some(ViewHandle::spawn(&self.fallback, view_entity, world));
+        self.hide_content(world);
+
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        if self.resolved {
+            return;
+        }
+
+        tracking.track_component::<SuspenseDeps>(view_entity, world);
+        let deps = world.get::<SuspenseDeps>(view_entity).unwrap().0.clone();
+        let rcx = Rcx::new(world, tracking);
+        if !deps.iter().all(|ready| ready.get(&rcx)) {
+            return;
+        }
+        if elapsed(world) < self.started + self.min_duration {
+            // Check again each frame until the minimum duration has passed.
+            tracking.track_resource::<Time>(world);
+            return;
+        }
+
+        self.resolved = true;
+        if let Some(fallback_ent) = self.fallback_ent.take() {
+            self.fallback.raze(fallback_ent, world);
+        }
+        if let Some(holder) = self.holder.take() {
+            world.entity_mut(holder).clear_children();
+            world.despawn(holder);
+        }
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some(content_ent) = self.content_ent.take() {
+            self.content.raze(content_ent, world);
+        }
+        if let Some(fallback_ent) = self.fallback_ent.take() {
+            self.fallback.raze(fallback_ent, world);
+        }
+        if let Some(holder) = self.holder.take() {
+            world.entity_mut(holder).despawn_recursive();
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+
+    fn children_changed(&mut self, _view_entity: Entity, world: &mut World) -> bool {
+        if !self.resolved {
+            // Keep any new content nodes hidden.
+            self.hide_content(world);
+        }
+        false
+    }
+}
+
+impl From<Suspense> for ViewHandle {
+    fn from(value: Suspense) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup, TextStatic,
+    };
+
+    fn content(cx: &mut Cx) -> impl View {
+        let loaded = cx.create_mutable(false);
+        cx.register_signal("loaded", loaded.signal());
+        cx.suspend_until(loaded.signal());
+        TextStatic::new("Content".to_string())
+    }
+
+    #[test]
+    fn test_suspense() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(Suspense::new(content.bind(())).with_fallback("Loading"));
+        assert_eq!(harness.text(root), "Loading");
+
+        let loaded = harness.mutable::<bool>("loaded");
+        harness.set(loaded, true);
+        assert_eq!(harness.text(root), "Content");
+    }
+}
@@ -71,10 +71,6 @@ impl Case {
     fn test(&self, re: &Rcx) -> bool {
         self.test.test(re)
     }
-
-    fn build(&self, parent: Entity, world: &mut World) -> (ViewHandle, Entity) {
-        self.view.build(parent, world)
-    }
 }
 
 /// A series of conditional expressions and corresponding views, only one of which can render.
@@ -93,6 +89,42 @@ impl Switch {
             state: None,
         }
     }
+
+    /// Construct a view which renders the case matching the value returned by `selector`.
+    /// Cases are added with [`SwitchOn::case`], and a [`SwitchOn::fallback`] can be given
+    /// for values with no matching case. As with [`Switch::new`], the view is only rebuilt
+    /// when the matched case changes.
+    pub fn on<T: PartialEq + Send + Sync + 'static, S: Fn(&Rcx) -> T + Send + Sync + 'static>(
+        selector: S,
+    ) -> SwitchOn<T, S> {
+        SwitchOn {
+            selector,
+            cases: Vec::new(),
+            fallback: None,
+            state_index: usize::MAX,
+            state: None,
+        }
+    }
+}
+
+/// Replace the rendered case of a switch view, if the selected case has changed.
+fn select_case(
+    view_entity: Entity,
+    world: &mut World,
+    selected: Option<(usize, &dyn CaseBody)>,
+    state_index: &mut usize,
+    state: &mut Option<(ViewHandle, Entity)>,
+) {
+    let index = selected.map_or(usize::MAX, |(index, _)| index);
+    if index == *state_index {
+        return;
+    }
+    if let Some((view, entity)) = state.take() {
+        view.raze(entity, world);
+        world.entity_mut(view_entity).insert(DisplayNodeChanged);
+    }
+    *state_index = index;
+    *state = selected.map(|(_, body)| body.build(view_entity, world));
 }
 
 impl View for Switch {
@@ -115,21 +147,21 @@ impl View for Switch {
 
     fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
         let re = Rcx::new(world, tracking);
-        if let Some((index, case)) = self.cases.iter().enumerate().find(|(_, c)| c.test(&re)) {
-            if index != self.state_index {
-                if let Some((ref mut state, entity)) = self.state {
-                    state.raze(entity, world);
-                }
-                self.state_index = index;
-                self.state = Some(case.build(view_entity, world));
-            }
-        } else {
-            if let Some((ref mut state, entity)) = self.state {
-                state.raze(entity, world);
-            }
-            self.state_index = usize::MAX;
-            self.state = None;
-        }
+        let selected = self
+            .cases
+            .iter()
+            .enumerate()
+            .find(|(_, c)| c.test(&re))
+            .map(|(index, case)| (index, case.view.clone()));
+        select_case(
+            view_entity,
+            world,
+            selected
+                .as_ref()
+                .map(|(index, body)| (*index, body.as_ref())),
+            &mut self.state_index,
+            &mut self.state,
+        );
     }
 
     fn raze(&mut self, view_entity: Entity, world: &mut World) {
@@ -145,3 +177,163 @@ impl From<Switch> for ViewHandle {
         ViewHandle::new(value)
     }
 }
+
+/// A view which renders one of several cases, chosen by matching a value. Constructed with
+/// [`Switch::on`].
+pub struct SwitchOn<T, S> {
+    selector: S,
+    cases: Vec<(T, Arc<dyn CaseBody>)>,
+    fallback: Option<Arc<dyn CaseBody>>,
+    state_index: usize,
+    state: Option<(ViewHandle, Entity)>,
+}
+
+impl<T, S> SwitchOn<T, S> {
+    /// Add a case which is rendered when the selector returns `value`.
+    pub fn case<V: Into<ViewHandle>, FV: Send + Sync + 'static + Fn() -> V>(
+        mut self,
+        value: T,
+        view: FV,
+    ) -> Self {
+        self.cases.push((value, Arc::new(view)));
+        self
+    }
+
+    /// Set the view which is rendered when no case matches.
+    pub fn fallback<V: Into<ViewHandle>, FV: Send + Sync + 'static + Fn() -> V>(
+        mut self,
+        view: FV,
+    ) -> Self {
+        self.fallback = Some(Arc::new(view));
+        self
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static, S: Fn(&Rcx) -> T + Send + Sync + 'static> View
+    for SwitchOn<T, S>
+{
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((ref state, _entity)) => state.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+        assert!(
+            world.entity_mut(view_entity).get::<Parent>().is_some(),
+            "Switch should have a parent view"
+        );
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let value = (self.selector)(&Rcx::new(world, tracking));
+        // The fallback is treated as the case following all of the others.
+        let selected = self
+            .cases
+            .iter()
+            .position(|(case, _)| *case == value)
+            .map(|index| (index, self.cases[index].1.clone()))
+            .or_else(|| Some((self.cases.len(), self.fallback.clone()?)));
+        select_case(
+            view_entity,
+            world,
+            selected
+                .as_ref()
+                .map(|(index, body)| (*index, body.as_ref())),
+            &mut self.state_index,
+            &mut self.state,
+        );
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((ref mut state, entity)) = self.state {
+            state.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<T: PartialEq + Send + Sync + 'static, S: Fn(&Rcx) -> T + Send + Sync + 'static>
+    From<SwitchOn<T, S>> for ViewHandle
+{
+    fn from(value: SwitchOn<T, S>) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup, TextStatic,
+        ViewFactory,
+    };
+
+    #[derive(Resource, Default)]
+    struct Builds(usize);
+
+    struct Label(&'static str);
+
+    impl ViewFactory for Label {
+        fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+            cx.world_mut().resource_mut::<Builds>().0 += 1;
+            TextStatic::new(self.0.to_string())
+        }
+    }
+
+    fn sign(cx: &mut Cx) -> impl View {
+        let count = cx.create_mutable::<i32>(0);
+        cx.register_signal("count", count);
+        Switch::on(move |cx| count.get(cx).signum())
+            .case(-1, || Label("Negative"))
+            .case(0, || Label("Zero"))
+            .fallback(|| Label("Positive"))
+    }
+
+    #[test]
+    fn test_switch_on() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Builds>();
+        let root = harness.mount(sign.bind(()));
+        assert_eq!(harness.text(root), "Zero");
+
+        let count = harness.mutable::<i32>("count");
+        harness.set(count, -3);
+        assert_eq!(harness.text(root), "Negative");
+        harness.set(count, 2);
+        assert_eq!(harness.text(root), "Positive");
+        assert_eq!(harness.world().resource::<Builds>().0, 3);
+
+        // A value which selects the same case keeps the existing view.
+        let nodes = harness.nodes(root);
+        harness.set(count, 5);
+        assert_eq!(harness.text(root), "Positive");
+        assert_eq!(harness.nodes(root), nodes);
+        assert_eq!(harness.world().resource::<Builds>().0, 3);
+    }
+
+    fn no_fallback(cx: &mut Cx) -> impl View {
+        let name = cx.create_mutable("a");
+        cx.register_signal("name", name);
+        Switch::on(move |cx| name.get(cx)).case("a", || Label("A"))
+    }
+
+    #[test]
+    fn test_switch_on_without_fallback() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<Builds>();
+        let root = harness.mount(no_fallback.bind(()));
+        assert_eq!(harness.text(root), "A");
+
+        // With no fallback, an unmatched value renders nothing.
+        let name = harness.mutable::<&'static str>("name");
+        harness.set(name, "b");
+        assert!(harness.nodes(root).is_empty());
+        harness.set(name, "a");
+        assert_eq!(harness.text(root), "A");
+    }
+}
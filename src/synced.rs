@@ -0,0 +1,167 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    ecs::{component::Component, entity::Entity, world::World},
+    time::Time,
+};
+
+use crate::{
+    signal::Signal, Cx, Mutable, Reaction, ReactionHandle, RunContextSetup, RunContextWrite,
+    TrackingScope, WriteMutable,
+};
+
+/// An external source of truth which a [`Synced`] signal is kept in step with, such as a
+/// CRDT document or a network replication channel.
+pub trait SyncSource<T>: Send + Sync + 'static {
+    /// Called when the value is written locally. The source should forward the new value
+    /// to its peers.
+    fn push(&mut self, value: &T);
+
+    /// Called once per frame to receive changes made by peers. Returns the latest value if
+    /// it has changed since the last poll. If the source merges concurrent edits, this
+    /// should return the merged value, which replaces any optimistic local write.
+    fn poll(&mut self) -> Option<T>;
+}
+
+/// Component which holds the [`SyncSource`] of a [`Synced`] signal.
+#[derive(Component)]
+pub(crate) struct SyncSourceCell<T>(Box<dyn SyncSource<T>>);
+
+/// Reaction which polls the sync source each frame and applies remote changes.
+pub(crate) struct SyncReaction<T> {
+    marker: PhantomData<T>,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Reaction for SyncReaction<T> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_resource::<Time>(world);
+        let remote = world
+            .get_mut::<SyncSourceCell<T>>(owner)
+            .and_then(|mut source| source.0.poll());
+        if let Some(value) = remote {
+            world.write_mutable_clone(owner, value);
+        }
+    }
+}
+
+/// A reactive value backed by an external [`SyncSource`], created by
+/// [`CreateSynced::create_synced`]. Local writes are applied optimistically, so that the UI
+/// updates immediately, and are forwarded to the source; remote changes reported by the
+/// source flow into the reactive graph like any other write.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Synced<T> {
+    mutable: Mutable<T>,
+}
+
+impl<T> Copy for Synced<T> {}
+impl<T> Clone for Synced<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Synced<T> {
+    /// The entity that holds the value and its source.
+    pub fn id(&self) -> Entity {
+        self.mutable.id
+    }
+
+    /// Returns a signal for reading the current value.
+    pub fn signal(&self) -> Signal<T> {
+        self.mutable.signal()
+    }
+
+    /// Write the value locally, and forward it to the source.
+    pub fn set<W: RunContextWrite>(&self, cx: &mut W, value: T) {
+        let world = cx.world_mut();
+        if let Some(mut source) = world.get_mut::<SyncSourceCell<T>>(self.mutable.id) {
+            source.0.push(&value);
+        }
+        world.write_mutable_clone(self.mutable.id, value);
+    }
+}
+
+/// Method to create signals backed by an external sync source.
+pub trait CreateSynced {
+    /// Create a [`Synced`] value with the given initial value, kept in step with `source`.
+    fn create_synced<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        init: T,
+        source: impl SyncSource<T>,
+    ) -> Synced<T>;
+}
+
+impl<'p, 'w, Props> CreateSynced for Cx<'p, 'w, Props> {
+    fn create_synced<T: Clone + PartialEq + Send + Sync + 'static>(
+        &mut self,
+        init: T,
+        source: impl SyncSource<T>,
+    ) -> Synced<T> {
+        let mutable = self.create_mutable(init);
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        tracking.track_resource::<Time>(self.world_mut());
+        self.world_mut().entity_mut(mutable.id).insert((
+            SyncSourceCell(Box::new(source)),
+            ReactionHandle::new(SyncReaction::<T> {
+                marker: PhantomData,
+            }),
+            tracking,
+        ));
+        Synced { mutable }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::{mutable::commit_mutables, tracking_scope::run_reactions, Rcx};
+
+    /// A source which records outgoing values and delivers queued incoming ones.
+    #[derive(Clone, Default)]
+    struct Channel {
+        sent: Arc<Mutex<Vec<i32>>>,
+        incoming: Arc<Mutex<Option<i32>>>,
+    }
+
+    impl SyncSource<i32> for Channel {
+        fn push(&mut self, value: &i32) {
+            self.sent.lock().unwrap().push(*value);
+        }
+
+        fn poll(&mut self) -> Option<i32> {
+            self.incoming.lock().unwrap().take()
+        }
+    }
+
+    #[test]
+    fn test_synced() {
+        let mut world = World::default();
+        world.init_resource::<Time>();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let channel = Channel::default();
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        let synced = cx.create_synced(0, channel.clone());
+
+        // Local writes are applied immediately and forwarded.
+        synced.set(&mut cx, 1);
+        commit_mutables(&mut world);
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        assert_eq!(synced.signal().get(&Rcx::new(&world, &mut scope)), 1);
+        assert_eq!(*channel.sent.lock().unwrap(), vec![1]);
+
+        // Remote changes are picked up on the next frame.
+        *channel.incoming.lock().unwrap() = Some(5);
+        world.increment_change_tick();
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(16));
+        run_reactions(&mut world);
+        commit_mutables(&mut world);
+        assert_eq!(synced.signal().get(&Rcx::new(&world, &mut scope)), 5);
+    }
+}
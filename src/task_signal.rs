@@ -0,0 +1,212 @@
+use std::{
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    tasks::AsyncComputeTaskPool,
+    time::Time,
+};
+
+use crate::{
+    mutable::MutableNextCell, signal::Signal, Cx, Reaction, ReactionHandle, RunContextSetup,
+    RunContextWrite, TrackingScope,
+};
+
+/// A future which can be run as a background task by [`CreateTaskSignal`] or [`SpawnTask`].
+///
+/// On native platforms, tasks run on Bevy's async compute task pool, so the future must be
+/// `Send`. In the browser, tasks run on the JavaScript event loop of the main thread, and
+/// the future doesn't need to be `Send`; this allows it to await JavaScript promises, such
+/// as those returned by the clipboard and file APIs.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait TaskFuture<T>: Future<Output = T> + Send + 'static {}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<T, F: Future<Output = T> + Send + 'static> TaskFuture<T> for F {}
+
+/// A future which can be run as a background task by [`CreateTaskSignal`] or [`SpawnTask`].
+///
+/// On native platforms, tasks run on Bevy's async compute task pool, so the future must be
+/// `Send`. In the browser, tasks run on the JavaScript event loop of the main thread, and
+/// the future doesn't need to be `Send`; this allows it to await JavaScript promises, such
+/// as those returned by the clipboard and file APIs.
+#[cfg(target_arch = "wasm32")]
+pub trait TaskFuture<T>: Future<Output = T> + 'static {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T, F: Future<Output = T> + 'static> TaskFuture<T> for F {}
+
+/// Slot which receives the output of a task when it completes.
+type TaskOutput<T> = Arc<Mutex<Option<T>>>;
+
+/// Start running a future on the async compute task pool. The output is stored in the
+/// returned slot.
+fn start_task<T: Send + 'static>(future: impl TaskFuture<T>) -> TaskOutput<T> {
+    let output = TaskOutput::<T>::default();
+    let slot = output.clone();
+    AsyncComputeTaskPool::get()
+        .spawn(async move {
+            let value = future.await;
+            *slot.lock().unwrap() = Some(value);
+        })
+        .detach();
+    output
+}
+
+/// Function which receives the output of a task spawned by [`SpawnTask`].
+type TaskCompleteFn<T> = Box<dyn FnOnce(&mut World, T) + Send + Sync>;
+
+/// What to do with the output of a task.
+enum TaskCompletion<T> {
+    /// Set the owner, which is a `Mutable<Option<T>>`.
+    Mutable,
+    /// Despawn the owner, and pass the output to a function.
+    Function(Option<TaskCompleteFn<T>>),
+}
+
+/// Reaction which checks once per frame whether a task has completed, and delivers its
+/// output.
+pub(crate) struct TaskReaction<T> {
+    output: TaskOutput<T>,
+    completion: TaskCompletion<T>,
+}
+
+impl<T: Send + Sync + 'static> Reaction for TaskReaction<T> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let Some(value) = self.output.lock().unwrap().take() else {
+            // Check again next frame.
+            tracking.track_resource::<Time>(world);
+            return;
+        };
+        match &mut self.completion {
+            TaskCompletion::Mutable => MutableNextCell::set(world, owner, Some(value)),
+            TaskCompletion::Function(on_complete) => {
+                world.despawn(owner);
+                if let Some(on_complete) = on_complete.take() {
+                    on_complete(world, value);
+                }
+            }
+        }
+    }
+}
+
+/// Method to create a signal from the result of an async task.
+pub trait CreateTaskSignal {
+    /// Run `future` in the background, and return a signal which is `None` until the future
+    /// completes, and then holds its output. The task keeps running if the signal's owner is
+    /// despawned, but the output is discarded. Combine this with
+    /// [`Suspense`](crate::Suspense) to show a fallback while the task runs.
+    fn create_task_signal<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        future: impl TaskFuture<T>,
+    ) -> Signal<Option<T>>;
+}
+
+impl<'p, 'w, Props> CreateTaskSignal for Cx<'p, 'w, Props> {
+    fn create_task_signal<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        future: impl TaskFuture<T>,
+    ) -> Signal<Option<T>> {
+        let mutable = self.create_mutable::<Option<T>>(None);
+        let mut reaction = TaskReaction {
+            output: start_task(future),
+            completion: TaskCompletion::Mutable,
+        };
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        reaction.react(mutable.id, self.world_mut(), &mut tracking);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert((ReactionHandle::new(reaction), tracking));
+        Signal::Mutable(mutable)
+    }
+}
+
+/// Method to run an async task from an event handler, callback or system.
+pub trait SpawnTask {
+    /// Run `future` in the background, and call `on_complete` with its output when it
+    /// completes. This is called during the reaction pass of a later frame; use
+    /// [`run_callback`](RunContextWrite::run_callback) within it to invoke a callback.
+    fn spawn_task<T: Send + Sync + 'static>(
+        &mut self,
+        future: impl TaskFuture<T>,
+        on_complete: impl FnOnce(&mut World, T) + Send + Sync + 'static,
+    );
+}
+
+impl<W: RunContextWrite> SpawnTask for W {
+    fn spawn_task<T: Send + Sync + 'static>(
+        &mut self,
+        future: impl TaskFuture<T>,
+        on_complete: impl FnOnce(&mut World, T) + Send + Sync + 'static,
+    ) {
+        let world = self.world_mut();
+        let owner = world.spawn_empty().id();
+        let mut reaction = TaskReaction {
+            output: start_task(future),
+            completion: TaskCompletion::Function(Some(Box::new(on_complete))),
+        };
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        reaction.react(owner, world, &mut tracking);
+        if let Some(mut entt) = world.get_entity_mut(owner) {
+            entt.insert((ReactionHandle::new(reaction), tracking));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    /// Run frames until `done` returns true, giving the task pool's threads time to run.
+    fn update_until(harness: &mut TestHarness, done: impl Fn(&World) -> bool) {
+        for _ in 0..100 {
+            harness.update();
+            if done(harness.world()) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        panic!("Task did not complete");
+    }
+
+    #[test]
+    fn test_task_signal() {
+        let mut harness = TestHarness::new();
+        let signal = {
+            let world = harness.world_mut();
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            Cx::new((), world, &mut scope).create_task_signal(async { 42 })
+        };
+        let Signal::Mutable(mutable) = signal else {
+            panic!("Expected a mutable");
+        };
+        update_until(&mut harness, |world| mutable.get(world).is_some());
+        assert_eq!(mutable.get(harness.world()), Some(42));
+    }
+
+    #[test]
+    fn test_spawn_task() {
+        let mut harness = TestHarness::new();
+        let (result, on_complete) = {
+            let world = harness.world_mut();
+            let mut scope = TrackingScope::new(world.read_change_tick());
+            let mut cx = Cx::new((), world, &mut scope);
+            let result = cx.create_mutable::<Option<usize>>(None);
+            let on_complete = cx.create_callback(move |cx: &mut Cx<String>| {
+                let len = cx.props.len();
+                result.set(cx, Some(len));
+            });
+            (result, on_complete)
+        };
+        harness
+            .world_mut()
+            .spawn_task(async { "done".to_string() }, move |world, value| {
+                world.run_callback(on_complete, value);
+            });
+        update_until(&mut harness, |world| result.get(world).is_some());
+        assert_eq!(result.get(harness.world()), Some(4));
+    }
+}
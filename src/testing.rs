@@ -0,0 +1,201 @@
+//! Utilities for unit-testing views without a window or renderer.
+//!
+//! [`TestHarness`] runs a minimal headless [`App`] with the [`ReactorPlugin`] installed.
+//! Views are mounted with [`TestHarness::mount`], and each call to [`TestHarness::update`]
+//! runs exactly one frame, with a fixed time step, so tests are deterministic:
+//!
+//! ```ignore
+//! let mut harness = TestHarness::new();
+//! let root = harness.mount(counter.bind(()));
+//! assert_eq!(harness.text(root), "Count: 0");
+//! harness.set(count, 1);
+//! assert_eq!(harness.text(root), "Count: 1");
+//! ```
+
+use std::{any::type_name, time::Duration};
+
+use bevy::{
+    asset::AssetPlugin,
+    hierarchy::HierarchyPlugin,
+    input::InputPlugin,
+    prelude::*,
+    render::texture::Image,
+    time::TimeUpdateStrategy,
+    window::{ExitCondition, WindowPlugin},
+};
+
+use crate::{mutable::MutableNextCell, Mutable, ReactorPlugin, Signal, Signals, View, ViewRoot};
+
+/// The amount by which the clock advances on each call to [`TestHarness::update`].
+pub const TEST_FRAME_TIME: Duration = Duration::from_millis(16);
+
+/// A headless app for testing views. See the [module documentation](self).
+pub struct TestHarness {
+    /// The underlying app, for tests which need to add their own resources or systems.
+    pub app: App,
+}
+
+impl Default for TestHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestHarness {
+    /// Create a new harness with the reactor plugin and the minimum set of Bevy plugins
+    /// needed to run it.
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            AssetPlugin::default(),
+            HierarchyPlugin,
+            InputPlugin,
+            WindowPlugin {
+                primary_window: None,
+                exit_condition: ExitCondition::DontExit,
+                close_when_requested: false,
+            },
+            ReactorPlugin::default(),
+        ))
+        .init_asset::<Font>()
+        .init_asset::<Image>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TEST_FRAME_TIME));
+        Self { app }
+    }
+
+    /// The app's world.
+    pub fn world(&self) -> &World {
+        &self.app.world
+    }
+
+    /// The app's world, mutably.
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.world
+    }
+
+    /// Mount a view in a new [`ViewRoot`] and run one frame so that it is built. Returns the
+    /// entity of the view root.
+    pub fn mount(&mut self, view: impl View + Sync + Send + 'static) -> Entity {
+        let root = self.app.world.spawn(ViewRoot::new(view)).id();
+        self.update();
+        root
+    }
+
+    /// Run a single frame: commit mutables, run reactions, and update display nodes.
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Run the given number of frames. Use this to advance timers and animations.
+    pub fn update_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.app.update();
+        }
+    }
+
+    /// Set the value of a mutable, then run one frame so that the change is reflected in the
+    /// display nodes.
+    pub fn set<T: Send + Sync + 'static>(&mut self, mutable: Mutable<T>, value: T) {
+        self.app
+            .world
+            .entity_mut(mutable.id)
+            .insert(MutableNextCell(Some(Box::new(value))));
+        self.update();
+    }
+
+    /// The signal registered under the given name in the [`Signals`] registry, for example
+    /// by a presenter calling [`register_signal`](crate::RegisterSignal::register_signal).
+    /// Panics if there is no such signal, or if it has a different value type.
+    pub fn signal<T: Clone + Send + Sync + 'static>(&self, name: &str) -> Signal<T> {
+        self.app
+            .world
+            .get_resource::<Signals>()
+            .and_then(|signals| signals.get_named(name))
+            .unwrap_or_else(|| panic!("No signal of type {} named '{}'", type_name::<T>(), name))
+    }
+
+    /// The mutable registered under the given name in the [`Signals`] registry. Panics if
+    /// there is no such signal, or if it is not a mutable.
+    pub fn mutable<T: Clone + Send + Sync + 'static>(&self, name: &str) -> Mutable<T> {
+        match self.signal(name) {
+            Signal::Mutable(mutable) => mutable,
+            _ => panic!("Signal '{}' is not a mutable", name),
+        }
+    }
+
+    /// The top-level display nodes produced by the view root.
+    pub fn nodes(&self, root: Entity) -> Vec<Entity> {
+        let mut nodes = Vec::new();
+        if let Some(root) = self.app.world.get::<ViewRoot>(root) {
+            root.view.lock().unwrap().nodes().flatten(&mut nodes);
+        }
+        nodes
+    }
+
+    /// All display nodes produced by the view root, including descendants of the top-level
+    /// nodes, in depth-first order.
+    pub fn descendants(&self, root: Entity) -> Vec<Entity> {
+        let mut out = Vec::new();
+        let mut stack: Vec<Entity> = self.nodes(root).into_iter().rev().collect();
+        while let Some(entity) = stack.pop() {
+            out.push(entity);
+            if let Some(children) = self.app.world.get::<Children>(entity) {
+                stack.extend(children.iter().rev());
+            }
+        }
+        out
+    }
+
+    /// The concatenated content of all text nodes produced by the view root.
+    pub fn text(&self, root: Entity) -> String {
+        self.descendants(root)
+            .into_iter()
+            .filter_map(|e| self.app.world.get::<Text>(e))
+            .flat_map(|text| text.sections.iter())
+            .map(|section| section.value.as_str())
+            .collect()
+    }
+
+    /// Find the first display node produced by the view root with the given [`Name`].
+    pub fn find_by_name(&self, root: Entity, name: &str) -> Option<Entity> {
+        self.descendants(root).into_iter().find(|e| {
+            self.app
+                .world
+                .get::<Name>(*e)
+                .is_some_and(|n| n.as_str() == name)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cx, PresenterFn, RegisterSignal, RunContextSetup, TextComputed};
+
+    fn counter(cx: &mut Cx) -> impl View {
+        let count = cx.create_mutable::<i32>(0);
+        cx.register_signal("count", count.signal());
+        TextComputed::new(move |cx| format!("Count: {}", count.get(cx)))
+    }
+
+    #[test]
+    fn test_harness() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(counter.bind(()));
+        assert_eq!(harness.text(root), "Count: 0");
+
+        let count = harness.mutable::<i32>("count");
+        harness.set(count, 5);
+        assert_eq!(harness.text(root), "Count: 5");
+        assert_eq!(harness.signal::<i32>("count").get(harness.world()), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "No signal of type i32 named 'missing'")]
+    fn test_harness_missing_signal() {
+        let mut harness = TestHarness::new();
+        harness.mount(counter.bind(()));
+        harness.mutable::<i32>("missing");
+    }
+}
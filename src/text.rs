@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use impl_trait_for_tuples::*;
 
 use crate::{
     node_span::NodeSpan,
@@ -135,14 +136,202 @@ impl<F: Send + Sync + 'static + FnMut(&Rcx) -> String> From<TextComputed<F>> for
     }
 }
 
+/// A reactive function which computes one property of a [`TextSpan`].
+type SpanFn<T> = Box<dyn FnMut(&Rcx) -> T + Send + Sync>;
+
+/// One section of a [`TextSpans`] view. Each span has its own text, and optionally its own
+/// color and font; properties which are not set are inherited from the parent element's font
+/// styles, like other text views.
+pub struct TextSpan {
+    text: SpanFn<String>,
+    color: Option<SpanFn<Color>>,
+    font: Option<SpanFn<Handle<Font>>>,
+}
+
+impl TextSpan {
+    /// Construct a span whose text is dynamically computed.
+    pub fn new<F: FnMut(&Rcx) -> String + Send + Sync + 'static>(text: F) -> Self {
+        Self {
+            text: Box::new(text),
+            color: None,
+            font: None,
+        }
+    }
+
+    /// Set the color of this span, overriding the inherited color.
+    pub fn with_color<F: FnMut(&Rcx) -> Color + Send + Sync + 'static>(mut self, color: F) -> Self {
+        self.color = Some(Box::new(color));
+        self
+    }
+
+    /// Set the font of this span, overriding the inherited font.
+    pub fn with_font<F: FnMut(&Rcx) -> Handle<Font> + Send + Sync + 'static>(
+        mut self,
+        font: F,
+    ) -> Self {
+        self.font = Some(Box::new(font));
+        self
+    }
+}
+
+impl From<&str> for TextSpan {
+    fn from(value: &str) -> Self {
+        let text = value.to_string();
+        TextSpan::new(move |_| text.clone())
+    }
+}
+
+impl From<String> for TextSpan {
+    fn from(value: String) -> Self {
+        TextSpan::new(move |_| value.clone())
+    }
+}
+
+/// Creates a text span whose text is dynamically computed.
+pub fn span<F: FnMut(&Rcx) -> String + Send + Sync + 'static>(text: F) -> TextSpan {
+    TextSpan::new(text)
+}
+
+/// A tuple of items which can be converted into [`TextSpan`]s.
+#[doc(hidden)]
+pub trait TextSpanTuple {
+    #[doc(hidden)]
+    fn get_spans(self, out: &mut Vec<TextSpan>);
+}
+
+impl<I: Into<TextSpan>> TextSpanTuple for I {
+    fn get_spans(self, out: &mut Vec<TextSpan>) {
+        out.push(self.into());
+    }
+}
+
+#[impl_for_tuples(1, 15)]
+#[tuple_types_custom_trait_bound(TextSpanTuple)]
+impl TextSpanTuple for Tuple {
+    fn get_spans(self, out: &mut Vec<TextSpan>) {
+        for_tuples!(#( self.Tuple.get_spans(out); )*)
+    }
+}
+
+/// Style overrides for the sections of a text node, set by [`TextSpans`].
+#[derive(Component, Clone, Default, PartialEq)]
+pub(crate) struct SectionStyles(Vec<SectionStyle>);
+
+#[derive(Clone, Default, PartialEq)]
+pub(crate) struct SectionStyle {
+    color: Option<Color>,
+    font: Option<Handle<Font>>,
+}
+
+/// A UI element that displays text made up of several spans, each of which is separately
+/// computed and styled. The spans are rendered as sections of a single text node, so part
+/// of a label, such as a hotkey or a search match, can be highlighted without nesting
+/// additional nodes.
+pub struct TextSpans {
+    /// The visible UI node for this element.
+    node: Option<Entity>,
+
+    /// The spans to display
+    spans: Vec<TextSpan>,
+}
+
+impl TextSpans {
+    /// Construct a new multi-span text view.
+    pub fn new<S: TextSpanTuple>(spans: S) -> Self {
+        let mut out = Vec::new();
+        spans.get_spans(&mut out);
+        Self {
+            node: None,
+            spans: out,
+        }
+    }
+
+    /// Compute the text and style of each span.
+    fn compute(&mut self, re: &Rcx) -> (Vec<String>, SectionStyles) {
+        let mut text = Vec::with_capacity(self.spans.len());
+        let mut styles = Vec::with_capacity(self.spans.len());
+        for span in self.spans.iter_mut() {
+            text.push((span.text)(re));
+            styles.push(SectionStyle {
+                color: span.color.as_mut().map(|color| color(re)),
+                font: span.font.as_mut().map(|font| font(re)),
+            });
+        }
+        (text, SectionStyles(styles))
+    }
+}
+
+impl View for TextSpans {
+    fn nodes(&self) -> NodeSpan {
+        NodeSpan::Node(self.node.unwrap())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        assert!(self.node.is_none());
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        let re = Rcx::new(world, &mut tracking);
+        let (text, styles) = self.compute(&re);
+        let node = Some(
+            world
+                .spawn((
+                    TextBundle {
+                        text: Text::from_sections(
+                            text.into_iter()
+                                .map(|value| TextSection::new(value, TextStyle::default())),
+                        ),
+                        ..default()
+                    },
+                    styles,
+                    TextStyleChanged,
+                ))
+                .id(),
+        );
+        self.node = node;
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, _view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let re = Rcx::new(world, tracking);
+        let (text, styles) = self.compute(&re);
+        let mut entt = world.entity_mut(self.node.unwrap());
+        let mut node_text = entt.get_mut::<Text>().unwrap();
+        for (section, value) in node_text.sections.iter_mut().zip(text) {
+            if section.value != value {
+                section.value = value;
+            }
+        }
+        if entt.get::<SectionStyles>() != Some(&styles) {
+            entt.insert((styles, TextStyleChanged));
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        let display = self.node.expect("Razing unbuilt TextSpans");
+        world.entity_mut(display).remove_parent();
+        world.entity_mut(display).despawn();
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+/// Creates a text view from a tuple of spans.
+pub fn text_spans<S: TextSpanTuple>(spans: S) -> TextSpans {
+    TextSpans::new(spans)
+}
+
+impl From<TextSpans> for ViewHandle {
+    fn from(value: TextSpans) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
 pub(crate) fn update_text_styles(
     mut commands: Commands,
-    mut query: Query<(Entity, &mut Text), With<TextStyleChanged>>,
+    mut query: Query<(Entity, &mut Text, Option<&SectionStyles>), With<TextStyleChanged>>,
     inherited: Query<&InheritableFontStyles>,
     parents: Query<&Parent>,
     server: Res<AssetServer>,
 ) {
-    for (entity, mut text) in query.iter_mut() {
+    for (entity, mut text, section_styles) in query.iter_mut() {
         let mut styles = InheritableFontStyles::default();
 
         // Search parents for inherited styles.
@@ -180,9 +369,59 @@ pub(crate) fn update_text_styles(
             color: styles.color.unwrap_or(Color::WHITE),
         };
 
-        for section in text.sections.iter_mut() {
+        for (index, section) in text.sections.iter_mut().enumerate() {
             section.style = style.clone();
+            if let Some(overrides) = section_styles.and_then(|styles| styles.0.get(index)) {
+                if let Some(color) = overrides.color {
+                    section.style.color = color;
+                }
+                if let Some(ref font) = overrides.font {
+                    section.style.font = font.clone();
+                }
+            }
         }
         commands.entity(entity).remove::<TextStyleChanged>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, PresenterFn, RegisterSignal};
+
+    fn label(cx: &mut Cx) -> impl View {
+        let highlight = cx.create_mutable(false);
+        cx.register_signal("highlight", highlight.signal());
+        text_spans((
+            "Press ",
+            span(|_| "K".to_string()).with_color(move |cx| {
+                if highlight.get(cx) {
+                    Color::RED
+                } else {
+                    Color::WHITE
+                }
+            }),
+            " to continue",
+        ))
+    }
+
+    #[test]
+    fn test_text_spans() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(label.bind(()));
+        assert_eq!(harness.text(root), "Press K to continue");
+
+        let node = harness.nodes(root)[0];
+        let color = |harness: &TestHarness, index: usize| {
+            harness.world().get::<Text>(node).unwrap().sections[index]
+                .style
+                .color
+        };
+        assert_eq!(color(&harness, 1), Color::WHITE);
+
+        let highlight = harness.mutable::<bool>("highlight");
+        harness.set(highlight, true);
+        assert_eq!(color(&harness, 0), Color::WHITE);
+        assert_eq!(color(&harness, 1), Color::RED);
+    }
+}
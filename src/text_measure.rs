@@ -0,0 +1,292 @@
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    prelude::*,
+    text::{BreakLineOn, TextMeasureInfo},
+    ui::{widget::measure_text_system, AvailableSpace, ContentSize, Measure, UiSystem},
+    utils::{HashMap, HashSet},
+    window::PrimaryWindow,
+};
+
+use crate::RunContextRead;
+
+/// Maximum number of width constraints remembered for each cached measurement. Layout only
+/// queries a handful of widths per node, so this is only reached when a container is being
+/// resized continuously.
+const MAX_CACHED_WIDTHS: usize = 32;
+
+/// Identifies a text measurement: the content, font and size of each section, along with the
+/// justification, line breaking and scale factor.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct TextMeasureKey {
+    sections: Vec<(String, AssetId<Font>, u32)>,
+    justify: JustifyText,
+    linebreak: BreakLineOn,
+    scale_factor: u32,
+}
+
+impl TextMeasureKey {
+    fn new(text: &Text, scale_factor: f32) -> Self {
+        Self {
+            sections: text
+                .sections
+                .iter()
+                .map(|section| {
+                    (
+                        section.value.clone(),
+                        section.style.font.id(),
+                        section.style.font_size.to_bits(),
+                    )
+                })
+                .collect(),
+            justify: text.justify,
+            linebreak: text.linebreak_behavior,
+            scale_factor: scale_factor.to_bits(),
+        }
+    }
+
+    fn uses_font(&self, fonts: &HashSet<AssetId<Font>>) -> bool {
+        self.sections
+            .iter()
+            .any(|(_, font, _)| fonts.contains(font))
+    }
+}
+
+/// A text measurement shared by all text nodes with the same key. Sizes computed for
+/// definite width constraints are remembered, so that identical labels only lay out their
+/// glyphs once per width.
+struct SharedTextMeasure {
+    info: TextMeasureInfo,
+    sizes: Mutex<HashMap<u32, Vec2>>,
+}
+
+impl SharedTextMeasure {
+    fn size_for_width(&self, width: f32) -> Vec2 {
+        let mut sizes = self.sizes.lock().unwrap();
+        if let Some(size) = sizes.get(&width.to_bits()) {
+            return *size;
+        }
+        let size = self.info.compute_size(Vec2::new(width, f32::MAX));
+        if sizes.len() >= MAX_CACHED_WIDTHS {
+            sizes.clear();
+        }
+        sizes.insert(width.to_bits(), size);
+        size
+    }
+}
+
+/// [`Measure`] which reads from a [`SharedTextMeasure`]. This behaves the same as Bevy's
+/// own text measure.
+struct CachedTextMeasure(Arc<SharedTextMeasure>);
+
+impl Measure for CachedTextMeasure {
+    fn measure(
+        &self,
+        width: Option<f32>,
+        height: Option<f32>,
+        available_width: AvailableSpace,
+        _available_height: AvailableSpace,
+    ) -> Vec2 {
+        let info = &self.0.info;
+        let x = width.unwrap_or(match available_width {
+            AvailableSpace::Definite(x) => x.max(info.min.x).min(info.max.x),
+            AvailableSpace::MinContent => info.min.x,
+            AvailableSpace::MaxContent => info.max.x,
+        });
+        height
+            .map_or_else(
+                || match available_width {
+                    AvailableSpace::Definite(_) => self.0.size_for_width(x),
+                    AvailableSpace::MinContent => Vec2::new(x, info.min.y),
+                    AvailableSpace::MaxContent => Vec2::new(x, info.max.y),
+                },
+                |y| Vec2::new(x, y),
+            )
+            .ceil()
+    }
+}
+
+/// Resource holding the text measurements shared between text nodes. See
+/// [`ReactorPlugin::with_text_measure_cache`](crate::ReactorPlugin::with_text_measure_cache).
+#[derive(Resource, Default)]
+pub(crate) struct TextMeasureCache {
+    entries: HashMap<TextMeasureKey, Arc<SharedTextMeasure>>,
+}
+
+/// Replaces the measure of each newly-measured text node with a shared, cached measure, and
+/// invalidates cached measurements when their fonts change. This runs after Bevy has created
+/// the node's own measure, which it does whenever the node's text changes.
+///
+/// Bevy's measurement is stored inside the node's [`ContentSize`], where it can't be read
+/// back, so on a cache miss the [`TextMeasureInfo`] is built a second time here. That only
+/// collects the fonts and section metrics, and happens once per distinct key. The expensive
+/// part is the glyph layout in [`TextMeasureInfo::compute_size`], which layout runs for
+/// several widths on every pass; with the cache, that is done once for all of the nodes
+/// which share a key, and is remembered across passes.
+pub(crate) fn apply_text_measure_cache(
+    mut cache: ResMut<TextMeasureCache>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    fonts: Res<Assets<Font>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    ui_scale: Res<UiScale>,
+    mut text_query: Query<(&mut Text, &mut ContentSize), With<Node>>,
+) {
+    let changed_fonts: HashSet<AssetId<Font>> = font_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id }
+            | AssetEvent::Removed { id }
+            | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            AssetEvent::Added { .. } | AssetEvent::Unused { .. } => None,
+        })
+        .collect();
+    if !changed_fonts.is_empty() {
+        cache
+            .entries
+            .retain(|key, _| !key.uses_font(&changed_fonts));
+    }
+
+    let scale_factor = ui_scale.0
+        * windows
+            .get_single()
+            .map(|window| window.resolution.scale_factor())
+            .unwrap_or(1.);
+
+    let mut updated = false;
+    for (mut text, mut content_size) in text_query.iter_mut() {
+        if !changed_fonts.is_empty()
+            && text
+                .sections
+                .iter()
+                .any(|section| changed_fonts.contains(&section.style.font.id()))
+        {
+            // Have Bevy re-measure the text on the next frame, which replaces the stale
+            // shared measure.
+            text.set_changed();
+            continue;
+        }
+
+        // Bevy uses a fixed measure for unwrapped text, which is already cheap.
+        if !content_size.is_changed() || text.linebreak_behavior == BreakLineOn::NoWrap {
+            continue;
+        }
+
+        let key = TextMeasureKey::new(&text, scale_factor);
+        let shared = match cache.entries.get(&key) {
+            Some(shared) => shared.clone(),
+            None => {
+                let Ok(info) = TextMeasureInfo::from_text(&text, &fonts, scale_factor) else {
+                    continue;
+                };
+                let shared = Arc::new(SharedTextMeasure {
+                    info,
+                    sizes: Mutex::new(HashMap::default()),
+                });
+                cache.entries.insert(key, shared.clone());
+                shared
+            }
+        };
+        content_size.set(CachedTextMeasure(shared));
+        updated = true;
+    }
+
+    if updated {
+        // Forget measurements which are no longer used by any text node.
+        cache
+            .entries
+            .retain(|_, shared| Arc::strong_count(shared) > 1);
+    }
+}
+
+/// Methods for measuring text without spawning a text entity, for example to size a column
+/// to fit its widest label, or to place labels on a canvas.
+pub trait MeasureText {
+    /// Returns the logical size of `text` laid out on a single line in the given style. The
+    /// current scope will react when fonts are loaded or modified. Returns zero if the font
+    /// has not been loaded yet.
+    fn measure_text(&self, style: &TextStyle, text: &str) -> Vec2;
+
+    /// Returns the logical size of `text` in the given style, wrapped to fit within
+    /// `max_width`.
+    fn measure_text_wrapped(&self, style: &TextStyle, text: &str, max_width: f32) -> Vec2;
+}
+
+impl<R: RunContextRead> MeasureText for R {
+    fn measure_text(&self, style: &TextStyle, text: &str) -> Vec2 {
+        self.measure_text_wrapped(style, text, f32::INFINITY)
+    }
+
+    fn measure_text_wrapped(&self, style: &TextStyle, text: &str, max_width: f32) -> Vec2 {
+        let fonts = self.use_resource::<Assets<Font>>();
+        // Measure in logical pixels, which is what the caller will use for layout.
+        TextMeasureInfo::from_text(&Text::from_section(text, style.clone()), fonts, 1.)
+            .map(|info| info.compute_size(Vec2::new(max_width, f32::INFINITY)))
+            .unwrap_or_default()
+    }
+}
+
+/// Add the text measure cache to the app.
+pub(crate) fn add_text_measure_cache(app: &mut App) {
+    app.init_resource::<TextMeasureCache>().add_systems(
+        PostUpdate,
+        apply_text_measure_cache
+            .after(measure_text_system)
+            .before(UiSystem::Layout),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    const FONT: &[u8] =
+        include_bytes!("../crates/obsidian_ui/assets/fonts/Open_Sans/static/OpenSans-Medium.ttf");
+
+    fn spawn_label(harness: &mut TestHarness, value: &str, font: &Handle<Font>) -> Entity {
+        let style = TextStyle {
+            font: font.clone(),
+            font_size: 14.,
+            ..default()
+        };
+        harness
+            .world_mut()
+            .spawn(TextBundle::from_section(value, style))
+            .id()
+    }
+
+    fn cached_count(harness: &TestHarness) -> usize {
+        harness.world().resource::<TextMeasureCache>().entries.len()
+    }
+
+    #[test]
+    fn test_text_measure_cache() {
+        let mut harness = TestHarness::new();
+        harness.world_mut().init_resource::<UiScale>();
+        add_text_measure_cache(&mut harness.app);
+        let font = harness
+            .world_mut()
+            .resource_mut::<Assets<Font>>()
+            .add(Font::try_from_bytes(FONT.to_vec()).unwrap());
+
+        // Identical labels share a single measurement.
+        spawn_label(&mut harness, "Name", &font);
+        spawn_label(&mut harness, "Name", &font);
+        let other = spawn_label(&mut harness, "Value", &font);
+        harness.update();
+        assert_eq!(cached_count(&harness), 2);
+
+        // Measurements which are no longer used are dropped.
+        harness.world_mut().despawn(other);
+        spawn_label(&mut harness, "Name", &font);
+        harness.update();
+        assert_eq!(cached_count(&harness), 1);
+
+        // Modifying the font invalidates every measurement which uses it.
+        harness
+            .world_mut()
+            .send_event(AssetEvent::Modified { id: font.id() });
+        harness.update();
+        assert_eq!(cached_count(&harness), 0);
+    }
+}
@@ -0,0 +1,234 @@
+use std::time::Duration;
+
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    time::Time,
+};
+
+use crate::{
+    mutable::MutableNextCell, signal::Signal, Cx, Rcx, Reaction, ReactionHandle, RunContextSetup,
+    TrackingScope,
+};
+
+/// Reaction which sets the associated mutable to `true` once the timer has elapsed.
+pub(crate) struct TimerReaction {
+    end: Duration,
+}
+
+impl Reaction for TimerReaction {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        if world.resource::<Time>().elapsed() >= self.end {
+            // Once fired, there is no need to track the time any further.
+            MutableNextCell::set(world, owner, true);
+        } else {
+            tracking.track_resource::<Time>(world);
+        }
+    }
+}
+
+/// Reaction which updates the associated mutable with the number of completed intervals.
+pub(crate) struct IntervalReaction {
+    start: Duration,
+    period: Duration,
+    count: u32,
+}
+
+impl Reaction for IntervalReaction {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        tracking.track_resource::<Time>(world);
+        let elapsed = world.resource::<Time>().elapsed() - self.start;
+        let count = (elapsed.as_secs_f64() / self.period.as_secs_f64()) as u32;
+        if count != self.count {
+            self.count = count;
+            MutableNextCell::set(world, owner, count);
+        }
+    }
+}
+
+/// Methods to create signals which change over time.
+pub trait CreateTimerSignal {
+    /// Signal which is `false` until `duration` has elapsed, then becomes `true`. Useful for
+    /// things like auto-dismissing a toast.
+    fn create_timer(&mut self, duration: Duration) -> Signal<bool>;
+
+    /// Signal which counts the number of times `period` has elapsed since the signal was
+    /// created. Use the count to drive periodic updates; for example, a blinking caret can
+    /// be shown whenever the count is even.
+    fn create_interval(&mut self, period: Duration) -> Signal<u32>;
+}
+
+impl<'p, 'w, Props> CreateTimerSignal for Cx<'p, 'w, Props> {
+    fn create_timer(&mut self, duration: Duration) -> Signal<bool> {
+        let now = self.world_mut().resource::<Time>().elapsed();
+        let mutable = self.create_mutable(false);
+        let mut reaction = TimerReaction {
+            end: now + duration,
+        };
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        reaction.react(mutable.id, self.world_mut(), &mut tracking);
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert((ReactionHandle::new(reaction), tracking));
+        Signal::Mutable(mutable)
+    }
+
+    fn create_interval(&mut self, period: Duration) -> Signal<u32> {
+        assert!(!period.is_zero(), "Interval period must not be zero");
+        let now = self.world_mut().resource::<Time>().elapsed();
+        let mutable = self.create_mutable(0u32);
+        let reaction = IntervalReaction {
+            start: now,
+            period,
+            count: 0,
+        };
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        tracking.track_resource::<Time>(self.world_mut());
+        self.world_mut()
+            .entity_mut(mutable.id)
+            .insert((ReactionHandle::new(reaction), tracking));
+        Signal::Mutable(mutable)
+    }
+}
+
+/// Reaction which copies the source signal to the associated mutable, either once the source
+/// has been quiet for `delay` (debounce), or at most once every `delay` (throttle).
+pub(crate) struct RateLimitReaction<T> {
+    source: Signal<T>,
+    delay: Duration,
+    throttle: bool,
+    /// The most recent value read from the source.
+    seen: T,
+    /// A value waiting to be emitted, and the time at which to emit it.
+    pending: Option<Duration>,
+    /// Earliest time at which a throttled signal may emit again.
+    next_allowed: Duration,
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Reaction for RateLimitReaction<T> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let now = world.resource::<Time>().elapsed();
+        let value = self.source.get_clone(&Rcx::new(world, tracking));
+        if value != self.seen {
+            self.seen = value;
+            self.pending = Some(if self.throttle {
+                self.next_allowed.max(now)
+            } else {
+                now + self.delay
+            });
+        }
+
+        match self.pending {
+            Some(deadline) if now >= deadline => {
+                self.pending = None;
+                self.next_allowed = now + self.delay;
+                MutableNextCell::set(world, owner, self.seen.clone());
+            }
+            // Keep polling the clock until the deadline is reached.
+            Some(_) => tracking.track_resource::<Time>(world),
+            None => {}
+        }
+    }
+}
+
+fn create_rate_limited<'p, T: Clone + PartialEq + Send + Sync + 'static>(
+    cx: &mut impl RunContextSetup<'p>,
+    source: Signal<T>,
+    delay: Duration,
+    throttle: bool,
+) -> Signal<T> {
+    let world = cx.world_mut();
+    let now = world.resource::<Time>().elapsed();
+    let mut tracking = TrackingScope::new(world.read_change_tick());
+    let init = source.get_clone(&Rcx::new(world, &mut tracking));
+    let mutable = cx.create_mutable(init.clone());
+    cx.world_mut().entity_mut(mutable.id).insert((
+        ReactionHandle::new(RateLimitReaction {
+            source,
+            delay,
+            throttle,
+            seen: init,
+            pending: None,
+            next_allowed: now,
+        }),
+        tracking,
+    ));
+    Signal::Mutable(mutable)
+}
+
+impl<T: Clone + PartialEq + Send + Sync + 'static> Signal<T> {
+    /// Returns a signal which follows this one, but only updates once this signal has stopped
+    /// changing for `delay`. Useful for things like search fields, where a query should only
+    /// be run once the user has stopped typing.
+    pub fn debounce<'p>(self, cx: &mut impl RunContextSetup<'p>, delay: Duration) -> Signal<T> {
+        create_rate_limited(cx, self, delay, false)
+    }
+
+    /// Returns a signal which follows this one, but updates at most once every `interval`.
+    /// The first change is passed through immediately; further changes within the interval
+    /// are coalesced, and the latest value is delivered when the interval ends.
+    pub fn throttle<'p>(self, cx: &mut impl RunContextSetup<'p>, interval: Duration) -> Signal<T> {
+        create_rate_limited(cx, self, interval, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Mutable};
+
+    /// Create a source mutable, and a rate-limited signal following it.
+    fn rate_limited(harness: &mut TestHarness, throttle: bool) -> (Mutable<i32>, Signal<i32>) {
+        let mut scope = TrackingScope::new(harness.world().read_change_tick());
+        let mut cx = Cx::new((), harness.world_mut(), &mut scope);
+        let source = cx.create_mutable(0);
+        let delay = Duration::from_millis(100);
+        let signal = if throttle {
+            source.signal().throttle(&mut cx, delay)
+        } else {
+            source.signal().debounce(&mut cx, delay)
+        };
+        (source, signal)
+    }
+
+    #[test]
+    fn test_debounce() {
+        let mut harness = TestHarness::new();
+        let (source, debounced) = rate_limited(&mut harness, false);
+
+        // Changes in quick succession are held back...
+        for value in 1..=5 {
+            harness.set(source, value);
+            assert_eq!(debounced.get(harness.world()), 0);
+        }
+        harness.update_frames(3);
+        assert_eq!(debounced.get(harness.world()), 0);
+
+        // ...until the source has been quiet for the delay; then only the latest is delivered.
+        harness.update_frames(6);
+        assert_eq!(debounced.get(harness.world()), 5);
+    }
+
+    #[test]
+    fn test_throttle() {
+        let mut harness = TestHarness::new();
+        let (source, throttled) = rate_limited(&mut harness, true);
+
+        // The first change passes straight through.
+        harness.set(source, 1);
+        harness.update();
+        assert_eq!(throttled.get(harness.world()), 1);
+
+        // Further changes within the interval are coalesced, and the latest is delivered when
+        // the interval ends.
+        harness.set(source, 2);
+        harness.set(source, 3);
+        let mut values = Vec::new();
+        for _ in 0..8 {
+            values.push(throttled.get(harness.world()));
+            harness.update();
+        }
+        assert!(!values.contains(&2));
+        assert_eq!(values[0], 1);
+        assert_eq!(throttled.get(harness.world()), 3);
+    }
+}
@@ -1,12 +1,28 @@
 use std::any::TypeId;
 
+use std::sync::{Arc, Mutex};
+
 use bevy::{
-    ecs::component::{ComponentId, Tick},
+    ecs::{
+        component::{ComponentId, Tick},
+        query::{FilteredAccess, QueryFilter},
+    },
     prelude::*,
-    utils::{HashMap, HashSet},
+    utils::{HashMap, HashSet, Instant},
 };
 
-use crate::{mutable::MutableCell, reaction::ReactionHandle, ViewHandle};
+use crate::{
+    callback::CallbackStack,
+    error_boundary::catch_reaction_panic,
+    frame_budget::FrameBudget,
+    local_state::LocalStateSlots,
+    mutable::MutableCell,
+    pause::ReactionsPaused,
+    reaction::{DeferredReaction, ReactionHandle},
+    reaction_report::{begin_pass, end_pass, PassRecorder},
+    write_conflicts::{begin_tracking_writes, end_tracking_writes, WriteConflicts},
+    ReactionTarget, ViewHandle,
+};
 
 /// A component that tracks the dependencies of a reactive task.
 #[derive(Component)]
@@ -17,29 +33,53 @@ pub struct TrackingScope {
     /// The set of mutables that this scope is subscribed to.
     mutable_deps: HashSet<Entity>,
 
-    /// Set of components that we are currently subscribed to.
-    component_deps: HashSet<(Entity, ComponentId)>,
+    /// Set of components that we are currently subscribed to, along with whether the
+    /// component was present on the entity at the time the dependency was recorded.
+    component_deps: HashMap<(Entity, ComponentId), bool>,
+
+    /// Set of individual component fields that we are currently subscribed to.
+    component_field_deps: Vec<TrackedComponentField>,
 
     /// Set of resources that we are currently subscribed to.
     resource_deps: HashMap<ComponentId, TrackedResource>,
 
+    /// Set of individual resource fields that we are currently subscribed to.
+    resource_field_deps: Vec<TrackedResourceField>,
+
+    /// Set of queries whose result sets we are currently subscribed to.
+    query_deps: Vec<TrackedQuery>,
+
     /// Engine tick used for determining if components have changed. This represents the
     /// time of the previous reaction.
     tick: Tick,
+
+    /// List of cleanup functions to call when the scope is re-run or despawned.
+    cleanups: Vec<CleanupFn>,
     // contexts
     // debug_name
-    // cleanups
 }
 
+/// Resource which, when present, causes each reaction to be logged along with the
+/// dependencies which triggered it. Inserted by [`crate::ReactorPlugin::with_trace`].
+#[derive(Resource, Default)]
+pub(crate) struct ReactorTrace;
+
+/// A function which releases resources held by a tracking scope.
+pub(crate) type CleanupFn = Box<dyn FnOnce(&mut World) + Send + Sync + 'static>;
+
 impl TrackingScope {
     /// Create a new tracking scope.
     pub fn new(tick: Tick) -> Self {
         Self {
             owned: Vec::new(),
             mutable_deps: HashSet::default(),
-            component_deps: HashSet::default(),
+            component_deps: HashMap::default(),
+            component_field_deps: Vec::new(),
             resource_deps: HashMap::default(),
+            resource_field_deps: Vec::new(),
+            query_deps: Vec::new(),
             tick,
+            cleanups: Vec::new(),
         }
     }
 
@@ -47,6 +87,14 @@ impl TrackingScope {
         self.owned.push(owned);
     }
 
+    pub(crate) fn owned(&self) -> &[Entity] {
+        &self.owned
+    }
+
+    pub(crate) fn add_cleanup(&mut self, cleanup: CleanupFn) {
+        self.cleanups.push(cleanup);
+    }
+
     pub(crate) fn add_mutable(&mut self, mutable: Entity) {
         self.mutable_deps.insert(mutable);
     }
@@ -67,49 +115,256 @@ impl TrackingScope {
         );
     }
 
-    /// Convenience method for adding a component dependency.
+    /// Add a dependency on a single field of a resource. The projected value is cached, and
+    /// the dependency only reports a change when the projection of the current resource
+    /// differs from the cached value.
+    pub(crate) fn track_resource_field<
+        R: Resource,
+        T: PartialEq + Clone + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        &mut self,
+        value: &T,
+        project: F,
+    ) {
+        self.resource_field_deps
+            .push(TrackedResourceField::new(value.clone(), project));
+    }
+
+    /// Add a dependency on a resource which only reports a change when `predicate`, called
+    /// with the cached and current values of the resource, returns true.
+    pub(crate) fn track_resource_when<
+        R: Resource + Clone,
+        F: Fn(&R, &R) -> bool + Send + Sync + 'static,
+    >(
+        &mut self,
+        value: R,
+        predicate: F,
+    ) {
+        self.resource_field_deps
+            .push(TrackedResourceField::when(value, predicate));
+    }
+
+    /// Add a dependency on a projection of a component on a single entity. The projected
+    /// value is cached, and the dependency only reports a change when the projection of the
+    /// current component differs from the cached value.
+    pub(crate) fn track_component_field<
+        C: Component,
+        T: PartialEq + Send + Sync + 'static,
+        F: Fn(&C) -> T + Send + Sync + 'static,
+    >(
+        &mut self,
+        entity: Entity,
+        value: T,
+        project: F,
+    ) {
+        self.component_field_deps
+            .push(TrackedComponentField::new(entity, value, project));
+    }
+
+    /// Add a dependency on whether a resource exists. The dependency reports a change when
+    /// the resource is inserted or removed, but not when it is modified.
+    pub(crate) fn track_resource_presence<R: Resource>(&mut self, present: bool) {
+        self.resource_field_deps
+            .push(TrackedResourceField::presence::<R>(present));
+    }
+
+    /// Add a dependency on the results of a query. `entities` is a query with the same filter
+    /// as the original query, and `access` is the original query's component access. The
+    /// dependency reports a change when the set of matching entities changes, or when any of
+    /// the components read by the query are modified on one of the matching entities.
+    pub(crate) fn track_query<F: QueryFilter + 'static>(
+        &mut self,
+        entities: QueryState<Entity, F>,
+        access: &FilteredAccess<ComponentId>,
+        world: &World,
+    ) {
+        let matched: Vec<Entity> = entities.iter_manual(world).collect();
+        for entity in matched.iter() {
+            for component in access.access().reads() {
+                self.track_component_id(*entity, component, world);
+            }
+        }
+        self.query_deps.push(TrackedQuery::new(entities, matched));
+    }
+
+    /// Convenience method for adding a component dependency. If the component type has never
+    /// been registered, no entity can have it yet, so the dependency only watches for the
+    /// component being inserted.
     pub(crate) fn track_component<C: Component>(&mut self, entity: Entity, world: &World) {
-        self.component_deps.insert((
-            entity,
-            world
-                .components()
-                .component_id::<C>()
-                .expect("Unknown component type"),
-        ));
+        match world.components().component_id::<C>() {
+            Some(component) => self.track_component_id(entity, component, world),
+            None => self
+                .component_field_deps
+                .push(TrackedComponentField::inserted::<C>(entity)),
+        }
     }
 
-    /// Convenience method for adding a component dependency by component id.
-    pub(crate) fn track_component_id(&mut self, entity: Entity, component: ComponentId) {
-        self.component_deps.insert((entity, component));
+    /// Convenience method for adding a component dependency by component id. Changes to
+    /// the component, as well as insertion or removal of the component, will trigger a
+    /// reaction. Dependencies on despawned entities are ignored.
+    pub(crate) fn track_component_id(
+        &mut self,
+        entity: Entity,
+        component: ComponentId,
+        world: &World,
+    ) {
+        if let Some(entt) = world.get_entity(entity) {
+            self.component_deps
+                .insert((entity, component), entt.contains_id(component));
+        }
+    }
+
+    /// Returns true if this scope has recorded any dependencies.
+    pub(crate) fn has_deps(&self) -> bool {
+        !self.mutable_deps.is_empty()
+            || !self.component_deps.is_empty()
+            || !self.component_field_deps.is_empty()
+            || !self.resource_deps.is_empty()
+            || !self.resource_field_deps.is_empty()
+            || !self.query_deps.is_empty()
     }
 
     /// Returns true if any of the dependencies of this scope have been updated since
     /// the previous reaction.
-    fn dependencies_changed(&self, world: &World) -> bool {
+    pub(crate) fn dependencies_changed(&self, world: &World) -> bool {
         self.components_changed(world)
             || self.mutables_changed(world)
-            || self.resource_deps.iter().any(|(_, c)| c.is_changed(world))
+            || self
+                .resource_deps
+                .iter()
+                .any(|(_, c)| c.is_changed(world, self.tick))
+            || self
+                .component_field_deps
+                .iter()
+                .any(|f| f.is_changed(world, self.tick))
+            || self
+                .resource_field_deps
+                .iter()
+                .any(|f| f.is_changed(world, self.tick))
+            || self.query_deps.iter().any(|q| q.is_changed(world))
     }
 
     fn components_changed(&self, world: &World) -> bool {
         let this_run = world.read_change_tick();
-        self.component_deps.iter().any(|(e, c)| {
-            world
-                .entity(*e)
-                .get_change_ticks_by_id(*c)
-                .map(|ct| ct.is_changed(self.tick, this_run))
-                .unwrap_or(false)
-        })
+        self.component_deps
+            .iter()
+            .any(|((e, c), present)| match world.get_entity(*e) {
+                Some(entt) => match entt.get_change_ticks_by_id(*c) {
+                    Some(ct) => !*present || ct.is_changed(self.tick, this_run),
+                    None => *present,
+                },
+                // Entity was despawned.
+                None => true,
+            })
     }
 
     fn mutables_changed(&self, world: &World) -> bool {
-        self.mutable_deps.iter().any(|m| {
-            world
-                .entity(*m)
-                .get_ref::<MutableCell>()
-                .map(|m| m.is_changed())
-                .unwrap_or(false)
-        })
+        self.mutable_deps
+            .iter()
+            .any(|m| self.mutable_changed(world, *m))
+    }
+
+    /// The mutables which have been written since the previous reaction.
+    pub(crate) fn changed_mutables<'a>(
+        &'a self,
+        world: &'a World,
+    ) -> impl Iterator<Item = Entity> + 'a {
+        self.mutable_deps
+            .iter()
+            .copied()
+            .filter(move |m| self.mutable_changed(world, *m))
+    }
+
+    /// Returns true if the mutable has been written since the previous reaction. This is
+    /// measured against the scope's own tick rather than the system's, so that a change is
+    /// only reported once even when reactions run several times per frame.
+    fn mutable_changed(&self, world: &World, mutable: Entity) -> bool {
+        world
+            .get_entity(mutable)
+            .and_then(|e| e.get_change_ticks::<MutableCell>())
+            .map(|ct| ct.is_changed(self.tick, world.read_change_tick()))
+            .unwrap_or(false)
+    }
+
+    /// Returns a description of each dependency that has changed since the previous
+    /// reaction. Used for tracing.
+    pub(crate) fn describe_changes(&self, world: &World) -> Vec<String> {
+        let this_run = world.read_change_tick();
+        let mut changes = Vec::new();
+        for m in self.mutable_deps.iter() {
+            if self.mutable_changed(world, *m) {
+                changes.push(format!("mutable {}", entity_label(world, *m)));
+            }
+        }
+        for ((e, c), present) in self.component_deps.iter() {
+            let changed = match world.get_entity(*e) {
+                Some(entt) => match entt.get_change_ticks_by_id(*c) {
+                    Some(ct) => !*present || ct.is_changed(self.tick, this_run),
+                    None => *present,
+                },
+                None => true,
+            };
+            if changed {
+                let name = world
+                    .components()
+                    .get_info(*c)
+                    .map_or("?", |info| info.name());
+                changes.push(format!("component {} on {}", name, entity_label(world, *e)));
+            }
+        }
+        for field in self.component_field_deps.iter() {
+            if field.is_changed(world, self.tick) {
+                changes.push(format!(
+                    "field of {} on {}",
+                    field.name,
+                    entity_label(world, field.entity)
+                ));
+            }
+        }
+        for (c, res) in self.resource_deps.iter() {
+            if res.is_changed(world, self.tick) {
+                let name = world
+                    .components()
+                    .get_info(*c)
+                    .map_or("?", |info| info.name());
+                changes.push(format!("resource {}", name));
+            }
+        }
+        for field in self.resource_field_deps.iter() {
+            if field.is_changed(world, self.tick) {
+                changes.push(format!("resource field of {}", field.name));
+            }
+        }
+        for query in self.query_deps.iter() {
+            if query.is_changed(world) {
+                changes.push(format!("query {}", query.name));
+            }
+        }
+        changes
+    }
+
+    /// Returns true if the scope only depends on resources, rather than on anything specific
+    /// to the entity it reacts for, and owns nothing.
+    pub(crate) fn has_only_resource_deps(&self) -> bool {
+        self.owned.is_empty()
+            && self.mutable_deps.is_empty()
+            && self.component_deps.is_empty()
+            && self.component_field_deps.is_empty()
+            && self.query_deps.is_empty()
+            && self.cleanups.is_empty()
+    }
+
+    /// Add the resource dependencies of another scope to this one.
+    pub(crate) fn copy_resource_deps(&mut self, other: &Self) {
+        self.resource_deps.extend(
+            other
+                .resource_deps
+                .iter()
+                .map(|(id, dep)| (*id, dep.clone())),
+        );
+        self.resource_field_deps
+            .extend(other.resource_field_deps.iter().cloned());
     }
 
     /// Take the dependencies from another scope. Typically the other scope is a temporary
@@ -117,13 +372,17 @@ impl TrackingScope {
     pub(crate) fn take_deps(&mut self, other: &mut Self) {
         self.mutable_deps = std::mem::take(&mut other.mutable_deps);
         self.component_deps = std::mem::take(&mut other.component_deps);
+        self.component_field_deps = std::mem::take(&mut other.component_field_deps);
         self.resource_deps = std::mem::take(&mut other.resource_deps);
+        self.resource_field_deps = std::mem::take(&mut other.resource_field_deps);
+        self.query_deps = std::mem::take(&mut other.query_deps);
+        self.cleanups = std::mem::take(&mut other.cleanups);
     }
 }
 
 /// Trait which allows despawning of any owned objects or reactions in the tracking scope
 /// associated with an entity. This operation is recursive in that an owned object may itself
-/// own other objects.
+/// own other objects. The entity itself is despawned as well.
 pub trait DespawnScopes {
     /// Despawn all owned objects and reactions associated with the given entity.
     fn despawn_owned_recursive(&mut self, scope_entity: Entity);
@@ -131,26 +390,212 @@ pub trait DespawnScopes {
 
 impl DespawnScopes for World {
     fn despawn_owned_recursive(&mut self, scope_entity: Entity) {
-        let mut entt = self.entity_mut(scope_entity);
+        if CallbackStack::defer_despawn(self, scope_entity) {
+            return;
+        }
+        let Some(mut entt) = self.get_entity_mut(scope_entity) else {
+            return;
+        };
+        // Persistent presenter state anchored to this view is released along with it.
+        if let Some(slots) = entt.take::<LocalStateSlots>() {
+            for state in slots.0.into_values() {
+                self.despawn(state);
+            }
+            entt = self.entity_mut(scope_entity);
+        }
+        // Owned entities which don't have a tracking scope, such as mutables or auxiliary
+        // entities spawned by a view, have nothing further to clean up.
         let Some(mut scope) = entt.get_mut::<TrackingScope>() else {
+            entt.despawn();
             return;
         };
         let owned_list = std::mem::take(&mut scope.owned);
+        let cleanups = std::mem::take(&mut scope.cleanups);
         entt.despawn();
+        for cleanup in cleanups {
+            cleanup(self);
+        }
         for owned in owned_list {
             self.despawn_owned_recursive(owned);
         }
     }
 }
 
+#[derive(Clone)]
 pub struct TrackedResource {
-    fn_is_changed: fn(&World) -> bool,
+    fn_is_changed: fn(&World, Tick) -> bool,
 }
 
 impl TrackedResource {
     pub(crate) fn new<T: Resource>() -> Self {
         Self {
-            fn_is_changed: |world| world.is_resource_changed::<T>(),
+            fn_is_changed: |world, tick| {
+                world
+                    .get_resource_change_ticks::<T>()
+                    .is_some_and(|ct| ct.is_changed(tick, world.read_change_tick()))
+            },
+        }
+    }
+
+    /// Returns true if the resource has changed since `tick`.
+    pub fn is_changed(&self, world: &World, tick: Tick) -> bool {
+        (self.fn_is_changed)(world, tick)
+    }
+}
+
+/// A dependency on a projection of a resource, along with the value of the projection at
+/// the time the dependency was recorded.
+#[derive(Clone)]
+pub struct TrackedResourceField {
+    name: &'static str,
+    fn_is_changed: Arc<dyn Fn(&World, Tick) -> bool + Send + Sync>,
+}
+
+impl TrackedResourceField {
+    pub(crate) fn new<
+        R: Resource,
+        T: PartialEq + Send + Sync + 'static,
+        F: Fn(&R) -> &T + Send + Sync + 'static,
+    >(
+        cached: T,
+        project: F,
+    ) -> Self {
+        Self {
+            name: std::any::type_name::<R>(),
+            fn_is_changed: Arc::new(move |world, tick| {
+                // Only bother comparing if the resource as a whole has changed since the
+                // scope last ran.
+                match world.get_resource_change_ticks::<R>() {
+                    Some(ct) => {
+                        ct.is_changed(tick, world.read_change_tick())
+                            && *project(world.resource::<R>()) != cached
+                    }
+                    None => true,
+                }
+            }),
+        }
+    }
+
+    /// A dependency on a resource which is filtered by a predicate comparing the cached value
+    /// of the resource with its current value.
+    pub(crate) fn when<R: Resource + Clone, F: Fn(&R, &R) -> bool + Send + Sync + 'static>(
+        cached: R,
+        predicate: F,
+    ) -> Self {
+        Self {
+            name: std::any::type_name::<R>(),
+            fn_is_changed: Arc::new(move |world, tick| {
+                match world.get_resource_change_ticks::<R>() {
+                    Some(ct) => {
+                        ct.is_changed(tick, world.read_change_tick())
+                            && predicate(&cached, world.resource::<R>())
+                    }
+                    None => true,
+                }
+            }),
+        }
+    }
+
+    /// A dependency on the presence of a resource, rather than on one of its fields.
+    pub(crate) fn presence<R: Resource>(present: bool) -> Self {
+        Self {
+            name: std::any::type_name::<R>(),
+            fn_is_changed: Arc::new(move |world, _tick| world.contains_resource::<R>() != present),
+        }
+    }
+
+    /// Returns true if the projected value has changed since `tick`.
+    pub fn is_changed(&self, world: &World, tick: Tick) -> bool {
+        (self.fn_is_changed)(world, tick)
+    }
+}
+
+/// A dependency on a projection of a component on a single entity, along with the value of
+/// the projection at the time the dependency was recorded.
+pub struct TrackedComponentField {
+    name: &'static str,
+    entity: Entity,
+    fn_is_changed: Box<dyn Fn(&World, Tick) -> bool + Send + Sync>,
+}
+
+impl TrackedComponentField {
+    pub(crate) fn new<
+        C: Component,
+        T: PartialEq + Send + Sync + 'static,
+        F: Fn(&C) -> T + Send + Sync + 'static,
+    >(
+        entity: Entity,
+        cached: T,
+        project: F,
+    ) -> Self {
+        Self {
+            name: std::any::type_name::<C>(),
+            entity,
+            fn_is_changed: Box::new(move |world, tick| {
+                let Some(entt) = world.get_entity(entity) else {
+                    return true;
+                };
+                // Only run the projection if the component has changed since the scope
+                // last ran.
+                match (entt.get_change_ticks::<C>(), entt.get::<C>()) {
+                    (Some(ct), Some(c)) => {
+                        ct.is_changed(tick, world.read_change_tick()) && project(c) != cached
+                    }
+                    _ => true,
+                }
+            }),
+        }
+    }
+
+    /// A dependency on a component being inserted on an entity which doesn't have it. This is
+    /// used for component types which haven't been registered with the world, and so have no
+    /// component id.
+    pub(crate) fn inserted<C: Component>(entity: Entity) -> Self {
+        Self {
+            name: std::any::type_name::<C>(),
+            entity,
+            fn_is_changed: Box::new(move |world, _tick| {
+                world.get_entity(entity).map_or(true, |e| e.contains::<C>())
+            }),
+        }
+    }
+
+    /// Returns true if the projected value has changed since `tick`.
+    pub fn is_changed(&self, world: &World, tick: Tick) -> bool {
+        (self.fn_is_changed)(world, tick)
+    }
+}
+
+/// A dependency on the set of entities matched by a query.
+pub struct TrackedQuery {
+    name: &'static str,
+    fn_is_changed: Box<dyn Fn(&World) -> bool + Send + Sync>,
+}
+
+impl TrackedQuery {
+    pub(crate) fn new<F: QueryFilter + 'static>(
+        entities: QueryState<Entity, F>,
+        matched: Vec<Entity>,
+    ) -> Self {
+        // Updating the query state for new archetypes requires mutable access.
+        let entities = Mutex::new(entities);
+        // Iteration order depends on archetype layout, which can change without the set of
+        // matching entities changing, so the results are compared as a set.
+        let matched: HashSet<Entity> = matched.into_iter().collect();
+        Self {
+            name: std::any::type_name::<F>(),
+            fn_is_changed: Box::new(move |world| {
+                let mut state = entities.lock().unwrap();
+                state.update_archetypes(world);
+                let mut count = 0;
+                for entity in state.iter_manual(world) {
+                    if !matched.contains(&entity) {
+                        return true;
+                    }
+                    count += 1;
+                }
+                count != matched.len()
+            }),
         }
     }
 
@@ -161,37 +606,470 @@ impl TrackedResource {
 
 /// Run reactions whose dependencies have changed.
 pub fn run_reactions(world: &mut World) {
-    let mut scopes = world.query::<(Entity, &mut TrackingScope)>();
+    let mut scopes = world.query_filtered::<
+        (Entity, &TrackingScope),
+        (Without<DeferredReaction>, Without<ReactionsPaused>),
+    >();
     let mut changed = HashSet::<Entity>::default();
     for (entity, scope) in scopes.iter(world) {
         if scope.dependencies_changed(world) {
             changed.insert(entity);
         }
     }
+    trace_reactions(world, &changed);
+    let recorder = begin_pass(world, &changed, false);
+    react_scopes(world, changed, recorder.as_ref());
+    end_pass(world, recorder);
+}
+
+/// Run deferred reactions whose dependencies have changed, or which have not yet run. This
+/// runs after UI layout, so that reactions can see the computed geometry of nodes.
+pub fn run_deferred_reactions(world: &mut World) {
+    let mut scopes = world
+        .query_filtered::<(Entity, &TrackingScope, &DeferredReaction), Without<ReactionsPaused>>();
+    let mut changed = HashSet::<Entity>::default();
+    for (entity, scope, deferred) in scopes.iter(world) {
+        if deferred.pending || scope.dependencies_changed(world) {
+            changed.insert(entity);
+        }
+    }
+    for entity in changed.iter() {
+        world.get_mut::<DeferredReaction>(*entity).unwrap().pending = false;
+    }
+    trace_reactions(world, &changed);
+    let recorder = begin_pass(world, &changed, true);
+    react_scopes(world, changed, recorder.as_ref());
+    end_pass(world, recorder);
+}
+
+/// If tracing is enabled, log each reaction that is about to run, along with the
+/// dependencies which triggered it.
+fn trace_reactions(world: &World, changed: &HashSet<Entity>) {
+    if !world.contains_resource::<ReactorTrace>() {
+        return;
+    }
+    for entity in changed.iter() {
+        let Some(scope) = world.get::<TrackingScope>(*entity) else {
+            continue;
+        };
+        let changes = scope.describe_changes(world);
+        info!(
+            "Reaction {} triggered by: {}",
+            entity_label(world, *entity),
+            if changes.is_empty() {
+                "first run".to_string()
+            } else {
+                changes.join(", ")
+            }
+        );
+    }
+}
 
+/// Returns the debug name of an entity if it has one, otherwise the entity id.
+pub(crate) fn entity_label(world: &World, entity: Entity) -> String {
+    match world.get::<Name>(entity) {
+        Some(name) => format!("'{}' ({:?})", name, entity),
+        None => format!("{:?}", entity),
+    }
+}
+
+fn react_scopes(world: &mut World, changed: HashSet<Entity>, recorder: Option<&PassRecorder>) {
     let tick = world.read_change_tick();
+    let track_writes = world.contains_resource::<WriteConflicts>();
     for scope_entity in changed.iter() {
+        let start = Instant::now();
         let mut next_scope = TrackingScope::new(tick);
+
+        // Run cleanups from the previous reaction before reacting again.
+        let cleanups = match world.get_mut::<TrackingScope>(*scope_entity) {
+            Some(mut scope) => std::mem::take(&mut scope.cleanups),
+            None => Vec::new(),
+        };
+        for cleanup in cleanups {
+            cleanup(world);
+        }
+
+        // Record which components of the target entity the reaction changes.
+        let pending_writes = match world.get::<ReactionTarget>(*scope_entity) {
+            Some(target) if track_writes => begin_tracking_writes(world, target.0),
+            _ => None,
+        };
+
+        if let Some(recorder) = recorder {
+            recorder.before_reaction(world, *scope_entity);
+        }
         if let Some(mut entt) = world.get_entity_mut(*scope_entity) {
+            // Panics are routed to the enclosing error boundary, if any.
             if let Some(view_handle) = entt.get_mut::<ViewHandle>() {
                 let inner = view_handle.0.clone();
-                inner
-                    .lock()
-                    .unwrap()
-                    .react(*scope_entity, world, &mut next_scope);
+                catch_reaction_panic(world, *scope_entity, &*inner, |view, world| {
+                    view.react(*scope_entity, world, &mut next_scope)
+                });
             } else if let Some(reaction) = entt.get_mut::<ReactionHandle>() {
                 let inner = reaction.0.clone();
-                inner
-                    .lock()
-                    .unwrap()
-                    .react(*scope_entity, world, &mut next_scope);
+                catch_reaction_panic(world, *scope_entity, &*inner, |reaction, world| {
+                    reaction.react(*scope_entity, world, &mut next_scope)
+                });
             }
         }
-        if let Ok((_, mut scope)) = scopes.get_mut(world, *scope_entity) {
+        if let Some(pending) = pending_writes {
+            end_tracking_writes(world, *scope_entity, pending);
+        }
+        if let Some(recorder) = recorder {
+            recorder.after_reaction(world);
+        }
+        if let Some(mut scope) = world.get_mut::<TrackingScope>(*scope_entity) {
             // Swap the scopes so that the next scope becomes the current scope.
             // The old scopes will be dropped at the end of the loop block.
             scope.take_deps(&mut next_scope);
             scope.tick = tick;
         }
+
+        // Each reaction is charged against the frame budget as soon as it finishes, so that
+        // deferrable work done by later reactions sees how much time is left.
+        if let Some(mut budget) = world.get_resource_mut::<FrameBudget>() {
+            budget.spend(start.elapsed());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Cx, Rcx, RunContextRead, RunContextSetup};
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Health(i32);
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Resource, Default)]
+    struct CleanupCount(usize);
+
+    #[derive(Resource, Clone)]
+    struct Settings {
+        volume: i32,
+        brightness: i32,
+    }
+
+    #[test]
+    fn test_component_deps() {
+        let mut world = World::default();
+        let target = world.spawn(Health(10)).id();
+        let other = world.spawn(Health(10)).id();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            assert_eq!(cx.use_component::<Health>(target).map(|h| h.0), Some(10));
+        }
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changing a different entity's component should not trigger.
+        world.increment_change_tick();
+        world.get_mut::<Health>(other).unwrap().0 = 5;
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changing the tracked component should trigger.
+        world.get_mut::<Health>(target).unwrap().0 = 5;
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_unregistered_component() {
+        let mut world = World::default();
+        let target = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            assert!(cx.use_component::<Player>(target).is_none());
+        }
+        assert!(scope.has_deps());
+        assert!(!scope.dependencies_changed(&world));
+
+        // Inserting the component should trigger even though its type was registered after
+        // the dependency was recorded.
+        world.increment_change_tick();
+        world.entity_mut(target).insert(Player);
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_component_removal() {
+        let mut world = World::default();
+        let target = world.spawn(Health(10)).id();
+        let missing = world.spawn_empty().id();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            assert!(cx.use_component::<Health>(missing).is_none());
+        }
+        assert!(!scope.dependencies_changed(&world));
+
+        // Inserting the component should trigger.
+        world.increment_change_tick();
+        world.entity_mut(missing).insert(Health(1));
+        assert!(scope.dependencies_changed(&world));
+
+        // Removing or despawning should trigger.
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            cx.use_component::<Health>(target);
+        }
+        world.entity_mut(target).remove::<Health>();
+        assert!(scope.dependencies_changed(&world));
+        world.despawn(target);
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_component_field_deps() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let mut world = World::default();
+        let target = world.spawn(Health(10)).id();
+        let projections = Arc::new(AtomicUsize::new(0));
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let count = projections.clone();
+            let cx = Rcx::new(&world, &mut scope);
+            let alive = cx.use_component_field(target, move |h: &Health| {
+                count.fetch_add(1, Ordering::Relaxed);
+                h.0 > 0
+            });
+            assert_eq!(alive, Some(true));
+        }
+
+        // The projection is not re-run while the component is unchanged.
+        world.increment_change_tick();
+        assert!(!scope.dependencies_changed(&world));
+        assert_eq!(projections.load(Ordering::Relaxed), 1);
+
+        // Changes which don't affect the projection should not trigger.
+        world.get_mut::<Health>(target).unwrap().0 = 5;
+        assert!(!scope.dependencies_changed(&world));
+        assert_eq!(projections.load(Ordering::Relaxed), 2);
+
+        // Changes to the projection should trigger, and keep triggering until the scope runs.
+        world.get_mut::<Health>(target).unwrap().0 = 0;
+        assert!(scope.dependencies_changed(&world));
+        world.clear_trackers();
+        world.increment_change_tick();
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_resource_field_deps() {
+        let mut world = World::default();
+        world.insert_resource(Settings {
+            volume: 5,
+            brightness: 5,
+        });
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            assert_eq!(*cx.use_resource_field(|s: &Settings| &s.volume), 5);
+        }
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changing a different field should not trigger.
+        world.increment_change_tick();
+        world.resource_mut::<Settings>().brightness = 3;
+        assert!(!scope.dependencies_changed(&world));
+
+        // Writing the same value to the tracked field should not trigger.
+        world.resource_mut::<Settings>().volume = 5;
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changing the tracked field should trigger.
+        world.resource_mut::<Settings>().volume = 7;
+        assert!(scope.dependencies_changed(&world));
+
+        // The change is still reported in later frames if the scope has not run since, as
+        // happens when reactions are paused.
+        world.clear_trackers();
+        world.increment_change_tick();
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_resource_when() {
+        let mut world = World::default();
+        world.insert_resource(Settings {
+            volume: 5,
+            brightness: 5,
+        });
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let cx = Rcx::new(&world, &mut scope);
+            let settings =
+                cx.use_resource_when(|old: &Settings, new: &Settings| new.volume - old.volume > 2);
+            assert_eq!(settings.volume, 5);
+        }
+
+        // Small changes should not trigger.
+        world.increment_change_tick();
+        world.resource_mut::<Settings>().volume = 6;
+        assert!(!scope.dependencies_changed(&world));
+
+        // Changes which satisfy the predicate should trigger.
+        world.resource_mut::<Settings>().volume = 8;
+        assert!(scope.dependencies_changed(&world));
+
+        // ...including in later frames, until the scope runs again.
+        world.clear_trackers();
+        world.increment_change_tick();
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_query_deps() {
+        let mut world = World::default();
+        let player = world.spawn((Player, Health(10))).id();
+        world.spawn(Health(10));
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let mut cx = Cx::new((), &mut world, &mut scope);
+            let rows = cx.use_query::<&Health, With<Player>>();
+            assert_eq!(rows.iter().map(|h| h.0).collect::<Vec<_>>(), vec![10]);
+        }
+        assert!(!scope.dependencies_changed(&world));
+
+        // Spawning a non-matching entity should not trigger.
+        world.increment_change_tick();
+        let other = world.spawn(Health(3)).id();
+        assert!(!scope.dependencies_changed(&world));
+
+        // A new matching entity should trigger.
+        world.entity_mut(other).insert(Player);
+        assert!(scope.dependencies_changed(&world));
+        world.entity_mut(other).remove::<Player>();
+        assert!(!scope.dependencies_changed(&world));
+
+        // Mutating a matched component should trigger.
+        world.get_mut::<Health>(player).unwrap().0 = 5;
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_query_order() {
+        let mut world = World::default();
+        let first = world.spawn((Player, Health(10))).id();
+        world.spawn((Player, Health(10)));
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let mut cx = Cx::new((), &mut world, &mut scope);
+            assert_eq!(cx.use_query::<&Health, With<Player>>().iter().count(), 2);
+        }
+
+        // Moving a matched entity to another archetype changes the iteration order, but not
+        // the set of results.
+        world.increment_change_tick();
+        world.entity_mut(first).insert(Name::new("first"));
+        assert!(!scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_trigger() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let trigger = {
+            let mut cx = Cx::new((), &mut world, &mut scope);
+            let trigger = cx.create_trigger();
+            trigger.track(&cx);
+            trigger
+        };
+        assert!(!scope.dependencies_changed(&world));
+
+        // Notifying the trigger should cause the scope to react.
+        world.increment_change_tick();
+        trigger.notify(&mut world);
+        assert!(scope.dependencies_changed(&world));
+    }
+
+    #[test]
+    fn test_selector() {
+        let mut world = World::default();
+        let mut owner = TrackingScope::new(world.read_change_tick());
+        let (selection, selector) = {
+            let mut cx = Cx::new((), &mut world, &mut owner);
+            let selection = cx.create_mutable::<i32>(1);
+            let selector = cx.create_selector(selection.signal());
+            (selection, selector)
+        };
+
+        let rows: Vec<TrackingScope> = (0..3)
+            .map(|key| {
+                let mut scope = TrackingScope::new(world.read_change_tick());
+                let cx = Rcx::new(&world, &mut scope);
+                assert_eq!(selector.is_selected(&cx, &key), key == 1);
+                scope
+            })
+            .collect();
+
+        // Moving the selection should only affect the rows whose state flipped.
+        world.increment_change_tick();
+        let mut cx = Cx::new((), &mut world, &mut owner);
+        selection.set(&mut cx, 2);
+        crate::mutable::commit_mutables(&mut world);
+        run_reactions(&mut world);
+        let changed: Vec<bool> = rows
+            .iter()
+            .map(|scope| scope.dependencies_changed(&world))
+            .collect();
+        assert_eq!(changed, vec![false, true, true]);
+    }
+
+    #[test]
+    fn test_cleanup() {
+        let mut world = World::default();
+        world.init_resource::<CleanupCount>();
+        let target = world.spawn(Health(10)).id();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        {
+            let mut cx = Cx::new((), &mut world, &mut scope);
+            cx.create_effect(move |cx| {
+                cx.use_component::<Health>(target);
+                cx.on_cleanup(|world| world.resource_mut::<CleanupCount>().0 += 1);
+            });
+        }
+        let scope_entity = world.spawn(scope).id();
+        assert_eq!(world.resource::<CleanupCount>().0, 0);
+
+        // Cleanup should run before the effect is re-run.
+        world.increment_change_tick();
+        world.get_mut::<Health>(target).unwrap().0 = 5;
+        run_reactions(&mut world);
+        assert_eq!(world.resource::<CleanupCount>().0, 1);
+
+        // And again when the owning scope is despawned.
+        world.despawn_owned_recursive(scope_entity);
+        assert_eq!(world.resource::<CleanupCount>().0, 2);
+    }
+
+    #[test]
+    fn test_despawn_owned() {
+        let mut world = World::default();
+        let mut scope = TrackingScope::new(world.read_change_tick());
+        let (aux, mutable) = {
+            let mut cx = Cx::new((), &mut world, &mut scope);
+            let aux = cx.create_owned_entity();
+            (aux, cx.create_mutable(0).id())
+        };
+        let scope_entity = world.spawn(scope).id();
+
+        // Despawning a scope should despawn everything it owns.
+        world.despawn_owned_recursive(scope_entity);
+        assert!(world.get_entity(scope_entity).is_none());
+        assert!(world.get_entity(aux).is_none());
+        assert!(world.get_entity(mutable).is_none());
+
+        // Despawning an entity which is already gone does nothing.
+        world.despawn_owned_recursive(aux);
     }
 }
@@ -0,0 +1,122 @@
+use bevy::{
+    ecs::{entity::Entity, world::World},
+    time::Time,
+};
+
+use crate::{
+    mutable::MutableNextCell, signal::Signal, Cx, Rcx, Reaction, ReactionHandle, RunContextSetup,
+    TrackingScope,
+};
+
+/// The result of [`CreateTransition::create_transition`]: a signal which lags behind its source
+/// while an update is pending, along with a flag indicating that an update is pending.
+#[derive(Clone)]
+pub struct Transition<T> {
+    /// The transitioned value. This keeps the previous value of the source until the update
+    /// has completed.
+    pub value: Signal<T>,
+
+    /// True while the source has changed but the new value has not yet been computed.
+    pub is_pending: Signal<bool>,
+}
+
+/// Reaction which alternates between noticing that the source has changed, and computing its
+/// new value on the following frame.
+pub(crate) struct TransitionReaction<T> {
+    source: Signal<T>,
+    pending: Entity,
+    is_pending: bool,
+}
+
+impl<T: Clone + Send + Sync + 'static> Reaction for TransitionReaction<T> {
+    fn react(&mut self, owner: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        if self.is_pending {
+            // Compute the new value; this also re-establishes the source's dependencies.
+            let value = self.source.get_clone(&Rcx::new(world, tracking));
+            self.is_pending = false;
+            MutableNextCell::set(world, owner, value);
+        } else {
+            // The source changed: flag the update, and defer the computation until the
+            // clock advances to the next frame. Further changes to the source in the meantime
+            // are picked up when the value is computed.
+            self.is_pending = true;
+            tracking.track_resource::<Time>(world);
+        }
+        MutableNextCell::set(world, self.pending, self.is_pending);
+    }
+}
+
+/// Method to create transition signals.
+pub trait CreateTransition {
+    /// Wrap a signal whose value is expensive to compute, such as a derived signal which
+    /// filters a large list. When the source changes, the returned value keeps serving the
+    /// previous result, and `is_pending` becomes true for a frame, before the new value is
+    /// computed. This gives the UI a chance to show stale data along with a busy indicator,
+    /// rather than hitching without feedback.
+    fn create_transition<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        source: Signal<T>,
+    ) -> Transition<T>;
+}
+
+impl<'p, 'w, Props> CreateTransition for Cx<'p, 'w, Props> {
+    fn create_transition<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        source: Signal<T>,
+    ) -> Transition<T> {
+        let mut tracking = TrackingScope::new(self.world_mut().read_change_tick());
+        let init = source.get_clone(&Rcx::new(self.world_mut(), &mut tracking));
+        let value = self.create_mutable(init);
+        let pending = self.create_mutable(false);
+        self.world_mut().entity_mut(value.id).insert((
+            ReactionHandle::new(TransitionReaction {
+                source,
+                pending: pending.id,
+                is_pending: false,
+            }),
+            tracking,
+        ));
+        Transition {
+            value: value.signal(),
+            is_pending: pending.signal(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::TestHarness;
+
+    #[test]
+    fn test_transition() {
+        let mut harness = TestHarness::new();
+        let mut scope = TrackingScope::new(harness.world().read_change_tick());
+        let (source, transition) = {
+            let mut cx = Cx::new((), harness.world_mut(), &mut scope);
+            let source = cx.create_mutable(1);
+            (source, cx.create_transition(source.signal()))
+        };
+        let state = |harness: &TestHarness| {
+            (
+                transition.value.get(harness.world()),
+                transition.is_pending.get(harness.world()),
+            )
+        };
+        assert_eq!(state(&harness), (1, false));
+
+        harness.set(source, 2);
+        let mut states = vec![state(&harness)];
+        for _ in 0..4 {
+            harness.update();
+            states.push(state(&harness));
+        }
+
+        // The previous value is served, flagged as pending, until the new value is computed
+        // on a later frame.
+        let computed = states.iter().position(|&(value, _)| value == 2).unwrap();
+        assert!(computed > 0);
+        assert!(states[..computed].contains(&(1, true)));
+        assert!(states[computed..].iter().all(|&state| state == (2, false)));
+    }
+}
@@ -0,0 +1,47 @@
+use bevy::ecs::{
+    change_detection::DetectChangesMut, component::Component, entity::Entity, world::World,
+};
+
+use crate::RunContextRead;
+
+/// Component which marks a trigger entity. The trigger carries no data; notifying it simply
+/// marks this component as changed.
+#[derive(Component)]
+pub(crate) struct TriggerCell;
+
+/// A reactive signal which carries no data, used to force dependent computations to re-run.
+/// This is useful when the reactor can't see a change on its own, for example after a
+/// bulk modification of ECS data that a derived computation or [`crate::For`] list reads
+/// without tracking.
+#[derive(PartialEq, Eq, Debug)]
+pub struct Trigger {
+    pub(crate) id: Entity,
+}
+
+impl Trigger {
+    /// The entity that holds the trigger.
+    pub fn id(&self) -> Entity {
+        self.id
+    }
+
+    /// Add the trigger as a dependency of the current tracking scope, so that the scope
+    /// re-runs when the trigger is notified.
+    pub fn track<R: RunContextRead>(&self, cx: &R) {
+        cx.use_component::<TriggerCell>(self.id);
+    }
+
+    /// Notify all scopes which track this trigger. They will re-run the next time reactions
+    /// are processed. Does nothing if the trigger has been despawned.
+    pub fn notify(&self, world: &mut World) {
+        if let Some(mut cell) = world.get_mut::<TriggerCell>(self.id) {
+            cell.set_changed();
+        }
+    }
+}
+
+impl Copy for Trigger {}
+impl Clone for Trigger {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
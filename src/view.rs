@@ -5,15 +5,19 @@ use bevy::{
     ecs::{
         component::Component,
         entity::Entity,
-        query::{Added, With},
+        query::{Added, With, Without},
         world::World,
     },
     hierarchy::{BuildWorldChildren, Parent},
     log::warn,
+    math::Vec3,
+    render::view::RenderLayers,
+    ui::TargetCamera,
 };
 
 use crate::{
-    node_span::NodeSpan, text::TextStatic, tracking_scope::TrackingScope, Cx, DespawnScopes,
+    node_span::NodeSpan, text::TextStatic, tracking_scope::TrackingScope,
+    world_anchor::WorldAnchor, Cx, DespawnScopes,
 };
 
 /// Trait that defines a view, which is a template that constructs a hierarchy of
@@ -82,20 +86,134 @@ impl From<String> for ViewHandle {
     }
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 /// Component which holds the top level of the view hierarchy.
-pub struct ViewRoot(pub(crate) Arc<Mutex<dyn View + Sync + Send + 'static>>);
+pub struct ViewRoot {
+    /// The root view.
+    pub(crate) view: Arc<Mutex<dyn View + Sync + Send + 'static>>,
+
+    /// The UI camera that the display nodes should be rendered with.
+    camera: Option<Entity>,
+
+    /// Render layers to assign to the display nodes.
+    render_layers: Option<RenderLayers>,
+
+    /// An existing entity that the display nodes should be attached to.
+    parent: Option<Entity>,
+
+    /// A 3D entity that the display nodes should be positioned over.
+    world_anchor: Option<(Entity, Vec3)>,
+
+    /// The camera used to project the world anchor onto the screen.
+    anchor_camera: Option<Entity>,
+}
 
 impl ViewRoot {
     /// Construct a new [`ViewRoot`].
     pub fn new(view: impl View + Sync + Send + 'static) -> Self {
-        Self(Arc::new(Mutex::new(view)))
+        Self {
+            view: Arc::new(Mutex::new(view)),
+            camera: None,
+            render_layers: None,
+            parent: None,
+            world_anchor: None,
+            anchor_camera: None,
+        }
+    }
+
+    /// Render the display nodes of this view root using the given UI camera, rather than
+    /// the default camera. This allows several independent UI stacks, such as a game HUD
+    /// and a debug overlay, to coexist.
+    pub fn with_camera(mut self, camera: Entity) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// Assign the given render layers to the top-level display nodes of this view root.
+    pub fn with_render_layers(mut self, layers: RenderLayers) -> Self {
+        self.render_layers = Some(layers);
+        self
+    }
+
+    /// Attach the top-level display nodes of this view root as children of an existing
+    /// entity, rather than making them UI roots.
+    pub fn with_parent(mut self, parent: Entity) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Position the top-level display nodes of this view root over a 3D entity, for health
+    /// bars and nameplates. Each frame, the nodes are centered horizontally on the screen
+    /// position of `target`, displaced by `offset` in world units, with their bottom edges at
+    /// that position; they are hidden when the point is behind the camera. The point is
+    /// projected using the camera given by [`with_anchor_camera`](Self::with_anchor_camera),
+    /// or else the first active 3D camera.
+    pub fn with_world_anchor(mut self, target: Entity, offset: Vec3) -> Self {
+        self.world_anchor = Some((target, offset));
+        self
+    }
+
+    /// Project the world anchor (see [`with_world_anchor`](Self::with_world_anchor)) using
+    /// the given 3D camera. This is independent of the UI camera given by
+    /// [`with_camera`](Self::with_camera), which renders the nodes.
+    pub fn with_anchor_camera(mut self, camera: Entity) -> Self {
+        self.anchor_camera = Some(camera);
+        self
+    }
+
+    /// Apply the camera, render layer and parent options to the view's current display nodes.
+    /// This is called after the view is built, and whenever its display nodes change.
+    fn attach_nodes(&self, world: &mut World) {
+        let nodes = self.view.lock().unwrap().nodes();
+        let mut flat: Vec<Entity> = Vec::with_capacity(nodes.count());
+        nodes.flatten(&mut flat);
+        for node in flat {
+            let mut entt = world.entity_mut(node);
+            if let Some(camera) = self.camera {
+                entt.insert(TargetCamera(camera));
+            }
+            if let Some(layers) = self.render_layers {
+                entt.insert(layers);
+            }
+            if let Some(parent) = self.parent {
+                if entt.get::<Parent>().map(|p| p.get()) != Some(parent) {
+                    entt.set_parent(parent);
+                }
+            }
+            if let Some((target, offset)) = self.world_anchor {
+                entt.insert(WorldAnchor {
+                    target,
+                    offset,
+                    camera: self.anchor_camera,
+                });
+            }
+        }
+    }
+
+    /// Returns the camera and render layers used by the view root which contains the given
+    /// view entity, if any.
+    pub(crate) fn find_target(
+        world: &World,
+        view_entity: Entity,
+    ) -> (Option<Entity>, Option<RenderLayers>) {
+        let mut e = view_entity;
+        loop {
+            if let Some(root) = world.get::<ViewRoot>(e) {
+                return (root.camera, root.render_layers);
+            }
+            e = match world.get::<Parent>(e) {
+                Some(parent) => parent.get(),
+                None => return (None, None),
+            };
+        }
     }
 
     /// Despawn the view, including the display nodes, and all descendant views.
     pub fn despawn(&mut self, root: Entity, world: &mut World) {
-        self.0.lock().unwrap().raze(root, world);
-        world.entity_mut(root).despawn();
+        self.view.lock().unwrap().raze(root, world);
+        if let Some(entt) = world.get_entity_mut(root) {
+            entt.despawn();
+        }
     }
 }
 
@@ -175,6 +293,14 @@ pub trait ViewFactory {
     fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static;
 }
 
+/// A shared factory creates the same view as the factory it points to. This allows a factory
+/// to be kept for comparison after it has been turned into a [`ViewHandle`].
+impl<VF: ViewFactory> ViewFactory for Arc<VF> {
+    fn create(&self, cx: &mut Cx) -> impl View + Send + Sync + 'static {
+        self.as_ref().create(cx)
+    }
+}
+
 /// Holds a [`ViewFactory`], and the entity and output nodes created by the [`View`] produced
 /// by the factory.
 pub struct ViewFactoryState<VF: ViewFactory> {
@@ -207,7 +333,7 @@ impl<W: ViewFactory> View for ViewFactoryState<W> {
     fn build(&mut self, view_entity: Entity, world: &mut World) {
         assert!(self.inner.is_none());
         let mut tracking = TrackingScope::new(world.read_change_tick());
-        let mut cx = Cx::new((), world, &mut tracking);
+        let mut cx = Cx::new((), world, &mut tracking).with_owner(view_entity);
         let mut view = self.factory.create(&mut cx);
         let inner = world.spawn(tracking).set_parent(view_entity).id();
         view.build(inner, world);
@@ -237,17 +363,27 @@ where
     }
 }
 
+/// Marks a [`ViewRoot`] which has already been built. Reactions may be processed several
+/// times per frame, so `Added` alone is not enough to prevent building a root twice.
+#[derive(Component)]
+pub(crate) struct ViewRootBuilt;
+
 /// System that initializes any views that have been added.
 pub(crate) fn build_added_view_roots(world: &mut World) {
     // Need to copy query result to avoid double-borrow of world.
-    let mut roots = world.query_filtered::<(Entity, &mut ViewRoot), Added<ViewRoot>>();
+    let mut roots = world
+        .query_filtered::<(Entity, &mut ViewRoot), (Added<ViewRoot>, Without<ViewRootBuilt>)>();
     let roots_copy: Vec<Entity> = roots.iter(world).map(|(e, _)| e).collect();
     for root_entity in roots_copy.iter() {
         let Ok((_, root)) = roots.get(world, *root_entity) else {
             continue;
         };
-        let inner = root.0.clone();
+        let inner = root.view.clone();
+        world.entity_mut(*root_entity).insert(ViewRootBuilt);
         inner.lock().unwrap().build(*root_entity, world);
+        if let Some(root) = world.get::<ViewRoot>(*root_entity).cloned() {
+            root.attach_nodes(world);
+        }
     }
 }
 
@@ -268,12 +404,13 @@ pub(crate) fn attach_child_views(world: &mut World) {
                 }
             }
 
-            if let Some(handle) = world.entity(e).get::<ViewRoot>() {
-                let inner = handle.0.clone();
-                if inner.lock().unwrap().children_changed(e, world) {
-                    finished = true;
-                    break;
+            if let Some(root) = world.entity(e).get::<ViewRoot>().cloned() {
+                if !root.view.lock().unwrap().children_changed(e, world) {
+                    // The root's own display nodes have changed.
+                    root.attach_nodes(world);
                 }
+                finished = true;
+                break;
             }
 
             e = match world.entity(e).get::<Parent>() {
@@ -0,0 +1,133 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::{
+    node_span::NodeSpan, view::View, DespawnScopes, DisplayNodeChanged, Rcx, Signal, TrackingScope,
+    ViewHandle,
+};
+
+/// A `ViewList` is like a [`Fragment`](crate::Fragment) whose list of child views is computed
+/// reactively. This allows the contents of a container to be contributed at runtime, for
+/// example plugins adding panels to a shared editor shell.
+///
+/// Child views are matched with the previous list by identity: a [`ViewHandle`] which is
+/// still present keeps its built state, even if it has moved, while new handles are built
+/// and removed handles are razed. A view can only be built in one place, so if the same
+/// handle is listed more than once, only its first occurrence is shown.
+pub struct ViewList<F: Fn(&Rcx) -> Vec<ViewHandle>> {
+    views_fn: F,
+    children: Vec<(ViewHandle, Entity)>,
+}
+
+impl<F: Fn(&Rcx) -> Vec<ViewHandle>> ViewList<F> {
+    /// Construct a new `ViewList` from a reactive function which returns the child views.
+    pub fn new(views_fn: F) -> Self {
+        Self {
+            views_fn,
+            children: Vec::new(),
+        }
+    }
+}
+
+impl ViewList<Box<dyn Fn(&Rcx) -> Vec<ViewHandle> + Send + Sync>> {
+    /// Construct a new `ViewList` whose child views are the contents of a signal.
+    pub fn from_signal(views: Signal<Vec<ViewHandle>>) -> Self {
+        Self::new(Box::new(move |cx| views.get_clone(cx)))
+    }
+}
+
+impl<F: Fn(&Rcx) -> Vec<ViewHandle>> View for ViewList<F> {
+    fn nodes(&self) -> NodeSpan {
+        let child_spans: Vec<NodeSpan> = self.children.iter().map(|(v, _)| v.nodes()).collect();
+        NodeSpan::Fragment(child_spans.into_boxed_slice())
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world.entity_mut(view_entity).insert(Name::new("ViewList"));
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let views = (self.views_fn)(&Rcx::new(world, tracking));
+        let mut prev = std::mem::take(&mut self.children);
+        let prev_entities: Vec<Entity> = prev.iter().map(|(_, e)| *e).collect();
+        for view in views {
+            if self
+                .children
+                .iter()
+                .any(|(v, _)| Arc::ptr_eq(&v.0, &view.0))
+            {
+                warn!("ViewList: the same view handle is listed more than once");
+                continue;
+            }
+            match prev.iter().position(|(v, _)| Arc::ptr_eq(&v.0, &view.0)) {
+                Some(index) => self.children.push(prev.remove(index)),
+                None => {
+                    let entity = ViewHandle::spawn(&view, view_entity, world);
+                    self.children.push((view, entity));
+                }
+            }
+        }
+        for (view, entity) in prev {
+            view.raze(entity, world);
+        }
+        if !self.children.iter().map(|(_, e)| *e).eq(prev_entities) {
+            world.entity_mut(view_entity).insert(DisplayNodeChanged);
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        for (view, entity) in self.children.drain(..) {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<F: Fn(&Rcx) -> Vec<ViewHandle> + Send + Sync + 'static> From<ViewList<F>> for ViewHandle {
+    fn from(value: ViewList<F>) -> Self {
+        ViewHandle::new(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Cx, PresenterFn, RegisterSignal, RunContextSetup};
+
+    fn shell(cx: &mut Cx) -> impl View {
+        let panels = cx.create_mutable::<Vec<ViewHandle>>(vec!["A".into(), "B".into()]);
+        cx.register_signal("panels", panels.signal());
+        ViewList::from_signal(Signal::Mutable(panels))
+    }
+
+    #[test]
+    fn test_view_list() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(shell.bind(()));
+        assert_eq!(harness.text(root), "AB");
+
+        let panels = harness.mutable::<Vec<ViewHandle>>("panels");
+        let b = panels.get_clone(harness.world_mut())[1].clone();
+        harness.set(panels, vec![b, "C".into()]);
+        assert_eq!(harness.text(root), "BC");
+    }
+
+    #[test]
+    fn test_view_list_duplicates() {
+        let mut harness = TestHarness::new();
+        let root = harness.mount(shell.bind(()));
+        let panels = harness.mutable::<Vec<ViewHandle>>("panels");
+        let a = panels.get_clone(harness.world_mut())[0].clone();
+        harness.set(panels, vec![a.clone(), "B".into(), a.clone()]);
+        assert_eq!(harness.text(root), "AB");
+        assert_eq!(harness.nodes(root).len(), 2);
+
+        // Removing the duplicate leaves the first occurrence in place.
+        harness.set(panels, vec![a]);
+        assert_eq!(harness.text(root), "A");
+    }
+}
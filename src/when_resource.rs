@@ -0,0 +1,81 @@
+use bevy::ecs::world::World;
+use bevy::prelude::*;
+
+use crate::node_span::NodeSpan;
+use crate::{DespawnScopes, DisplayNodeChanged, TrackingScope, View, ViewHandle};
+
+/// A view which renders its child only while the resource `R` exists. The child view is
+/// built when the resource is inserted, and razed when it is removed.
+///
+/// The factory function is passed the resource at the time the child is built, so that the
+/// child can capture whatever parts of it are needed, such as the id of a connected server.
+/// The child is not rebuilt when the resource is modified; views which need to react to
+/// changes in the resource should use [`crate::RunContextRead::use_resource`].
+pub struct WhenResource<R: Resource, V: Into<ViewHandle>, F: Fn(&R) -> V> {
+    factory: F,
+    state: Option<(ViewHandle, Entity)>,
+    marker: std::marker::PhantomData<R>,
+}
+
+impl<R: Resource, V: Into<ViewHandle>, F: Fn(&R) -> V> WhenResource<R, V, F> {
+    /// Construct a new `WhenResource` view from a function which produces the child view.
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            state: None,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<R: Resource, V: Into<ViewHandle>, F: Fn(&R) -> V> View for WhenResource<R, V, F> {
+    fn nodes(&self) -> NodeSpan {
+        match self.state {
+            Some((ref view, _)) => view.nodes(),
+            None => NodeSpan::Empty,
+        }
+    }
+
+    fn build(&mut self, view_entity: Entity, world: &mut World) {
+        world
+            .entity_mut(view_entity)
+            .insert(Name::new("WhenResource"));
+        let mut tracking = TrackingScope::new(world.read_change_tick());
+        self.react(view_entity, world, &mut tracking);
+        world.entity_mut(view_entity).insert(tracking);
+    }
+
+    fn react(&mut self, view_entity: Entity, world: &mut World, tracking: &mut TrackingScope) {
+        let present = world.contains_resource::<R>();
+        tracking.track_resource_presence::<R>(present);
+        match (present, self.state.is_some()) {
+            (true, false) => {
+                let view: ViewHandle = (self.factory)(world.resource::<R>()).into();
+                let entity = ViewHandle::spawn(&view, view_entity, world);
+                world.entity_mut(view_entity).insert(DisplayNodeChanged);
+                self.state = Some((view, entity));
+            }
+            (false, true) => {
+                let (view, entity) = self.state.take().unwrap();
+                view.raze(entity, world);
+                world.entity_mut(view_entity).insert(DisplayNodeChanged);
+            }
+            _ => {}
+        }
+    }
+
+    fn raze(&mut self, view_entity: Entity, world: &mut World) {
+        if let Some((view, entity)) = self.state.take() {
+            view.raze(entity, world);
+        }
+        world.despawn_owned_recursive(view_entity);
+    }
+}
+
+impl<R: Resource, V: 'static + Into<ViewHandle>, F: Send + Sync + 'static + Fn(&R) -> V>
+    From<WhenResource<R, V, F>> for ViewHandle
+{
+    fn from(value: WhenResource<R, V, F>) -> Self {
+        ViewHandle::new(value)
+    }
+}
@@ -0,0 +1,62 @@
+use bevy::{
+    math::Vec2,
+    prelude::*,
+    window::{PrimaryWindow, Window},
+};
+
+use crate::RunContextRead;
+
+/// Resource containing the metrics of the primary window. This is updated once per frame,
+/// and only modified when the metrics actually change, so that views depending on it are not
+/// re-run needlessly.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct WindowMetrics {
+    /// Logical size of the window, in pixels.
+    pub size: Vec2,
+
+    /// Ratio of physical pixels to logical pixels.
+    pub scale_factor: f32,
+}
+
+impl Default for WindowMetrics {
+    fn default() -> Self {
+        Self {
+            size: Vec2::ZERO,
+            scale_factor: 1.,
+        }
+    }
+}
+
+/// Methods for reading the metrics of the primary window reactively.
+pub trait UseWindowMetrics {
+    /// Returns the logical size of the primary window. The current scope will react when
+    /// the window is resized.
+    fn use_window_size(&self) -> Vec2;
+
+    /// Returns the scale factor of the primary window. The current scope will react when
+    /// the scale factor changes, for example when the window is moved to another monitor.
+    fn use_window_scale_factor(&self) -> f32;
+}
+
+impl<R: RunContextRead> UseWindowMetrics for R {
+    fn use_window_size(&self) -> Vec2 {
+        *self.use_resource_field(|metrics: &WindowMetrics| &metrics.size)
+    }
+
+    fn use_window_scale_factor(&self) -> f32 {
+        *self.use_resource_field(|metrics: &WindowMetrics| &metrics.scale_factor)
+    }
+}
+
+/// System which copies the metrics of the primary window into the [`WindowMetrics`] resource.
+pub(crate) fn update_window_metrics(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut metrics: ResMut<WindowMetrics>,
+) {
+    if let Ok(window) = windows.get_single() {
+        metrics.set_if_neq(WindowMetrics {
+            size: Vec2::new(window.width(), window.height()),
+            scale_factor: window.scale_factor(),
+        });
+    }
+}
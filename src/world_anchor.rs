@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+
+use crate::RunContextRead;
+
+/// Component which places a UI node over a 3D entity, like a billboard: the node is centered
+/// horizontally on the entity's projected screen position, with its bottom edge at that
+/// position, and is hidden when the entity is behind the camera. Added to the display nodes
+/// of a [`ViewRoot`](crate::ViewRoot) by
+/// [`with_world_anchor`](crate::ViewRoot::with_world_anchor).
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct WorldAnchor {
+    /// The entity which the node follows.
+    pub(crate) target: Entity,
+    /// Offset from the target's origin, in world units, such as the height of a character's
+    /// head.
+    pub(crate) offset: Vec3,
+    /// The camera used for projection, or `None` for the first active 3D camera.
+    pub(crate) camera: Option<Entity>,
+}
+
+/// Component which tracks the position of a 3D entity on screen, so that UI can follow it.
+/// Insert it on the entity to be tracked, and read it reactively with
+/// [`use_screen_position`](UseScreenPosition::use_screen_position). The position is updated
+/// once per frame, and only modified when it actually changes.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScreenPosition {
+    offset: Vec3,
+    camera: Option<Entity>,
+    position: Option<Vec2>,
+}
+
+impl ScreenPosition {
+    /// Construct a new `ScreenPosition`, which tracks the origin of the entity as seen by the
+    /// first active 3D camera.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a point offset from the entity's origin, in world units.
+    pub fn with_offset(mut self, offset: Vec3) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Project the position using the given camera.
+    pub fn with_camera(mut self, camera: Entity) -> Self {
+        self.camera = Some(camera);
+        self
+    }
+
+    /// The logical position of the tracked point within the camera's viewport, or `None` if
+    /// the point is behind the camera or there is no camera.
+    pub fn get(&self) -> Option<Vec2> {
+        self.position
+    }
+}
+
+/// Method for reading the screen position of an entity reactively.
+pub trait UseScreenPosition {
+    /// Returns the position on screen of an entity which has a [`ScreenPosition`] component,
+    /// or `None` if it is off-screen. The current scope will react when the position changes.
+    fn use_screen_position(&self, target: Entity) -> Option<Vec2>;
+}
+
+impl<R: RunContextRead> UseScreenPosition for R {
+    fn use_screen_position(&self, target: Entity) -> Option<Vec2> {
+        self.use_component_field(target, |position: &ScreenPosition| position.position)
+            .flatten()
+    }
+}
+
+/// Find the given camera, or else the first active 3D camera.
+fn find_camera<'a>(
+    camera: Option<Entity>,
+    cameras: &'a Query<(Entity, &Camera, &GlobalTransform, Has<Camera3d>)>,
+) -> Option<(&'a Camera, &'a GlobalTransform)> {
+    let (_, camera, transform, _) = match camera {
+        Some(camera) => cameras.get(camera).ok()?,
+        None => cameras
+            .iter()
+            .find(|(_, camera, _, is_3d)| *is_3d && camera.is_active)?,
+    };
+    Some((camera, transform))
+}
+
+/// Project a world-space point to a logical viewport position, using the given camera or the
+/// first active 3D camera.
+fn project(
+    point: Vec3,
+    camera: Option<Entity>,
+    cameras: &Query<(Entity, &Camera, &GlobalTransform, Has<Camera3d>)>,
+) -> Option<Vec2> {
+    let (camera, transform) = find_camera(camera, cameras)?;
+    camera.world_to_viewport(transform, point)
+}
+
+/// Project a world-space point to a logical window position, using the given camera or the
+/// first active 3D camera. Unlike [`project`], this accounts for the position of the camera's
+/// viewport within the window.
+fn project_to_window(
+    point: Vec3,
+    camera: Option<Entity>,
+    cameras: &Query<(Entity, &Camera, &GlobalTransform, Has<Camera3d>)>,
+) -> Option<Vec2> {
+    let (camera, transform) = find_camera(camera, cameras)?;
+    let origin = camera
+        .logical_viewport_rect()
+        .map_or(Vec2::ZERO, |viewport| viewport.min);
+    Some(origin + camera.world_to_viewport(transform, point)?)
+}
+
+/// Compute the top-left position, in UI pixels, of a billboard node of the given size, so
+/// that it is centered horizontally above `point`, which is in logical window pixels.
+fn billboard_position(point: Vec2, size: Vec2, ui_scale: f32) -> Vec2 {
+    let point = point / ui_scale;
+    Vec2::new(point.x - size.x * 0.5, point.y - size.y)
+}
+
+/// Updates the [`ScreenPosition`] of tracked entities. This runs before UI layout, using the
+/// transforms propagated in the previous frame.
+pub(crate) fn update_screen_positions(
+    mut tracked: Query<(&mut ScreenPosition, &GlobalTransform)>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, Has<Camera3d>)>,
+) {
+    for (mut screen, transform) in tracked.iter_mut() {
+        let position = project(
+            transform.transform_point(screen.offset),
+            screen.camera,
+            &cameras,
+        );
+        if screen.position != position {
+            screen.position = position;
+        }
+    }
+}
+
+/// Positions the display nodes of world-anchored view roots over their target entities.
+pub(crate) fn update_world_anchors(
+    mut nodes: Query<(&WorldAnchor, &Node, &mut Style, &mut Visibility)>,
+    targets: Query<&GlobalTransform>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform, Has<Camera3d>)>,
+    ui_scale: Option<Res<UiScale>>,
+) {
+    let ui_scale = ui_scale.map_or(1., |ui_scale| ui_scale.0);
+    for (anchor, node, mut style, mut visibility) in nodes.iter_mut() {
+        let position = targets.get(anchor.target).ok().and_then(|transform| {
+            project_to_window(
+                transform.transform_point(anchor.offset),
+                anchor.camera,
+                &cameras,
+            )
+        });
+        let Some(point) = position else {
+            // Behind the camera, or the target has gone away.
+            if *visibility != Visibility::Hidden {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        };
+        let pos = billboard_position(point, node.size(), ui_scale);
+        let (left, top) = (Val::Px(pos.x), Val::Px(pos.y));
+        if style.position_type != PositionType::Absolute {
+            style.position_type = PositionType::Absolute;
+        }
+        if style.left != left || style.top != top {
+            style.left = left;
+            style.top = top;
+        }
+        if *visibility == Visibility::Hidden {
+            *visibility = Visibility::Inherited;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{testing::TestHarness, Element, ViewRoot};
+
+    #[test]
+    fn test_billboard_position() {
+        assert_eq!(
+            billboard_position(Vec2::new(400., 300.), Vec2::new(100., 20.), 1.),
+            Vec2::new(350., 280.)
+        );
+        // Node sizes and positions are in UI pixels, which are scaled by the UI scale.
+        assert_eq!(
+            billboard_position(Vec2::new(400., 300.), Vec2::new(100., 20.), 2.),
+            Vec2::new(150., 130.)
+        );
+    }
+
+    #[test]
+    fn test_world_anchor() {
+        let mut harness = TestHarness::new();
+        let world = harness.world_mut();
+        let target = world.spawn(GlobalTransform::default()).id();
+        let ui_camera = world.spawn_empty().id();
+        let anchor_camera = world.spawn_empty().id();
+        let root = world
+            .spawn(
+                ViewRoot::new(Element::<NodeBundle>::new())
+                    .with_camera(ui_camera)
+                    .with_world_anchor(target, Vec3::Y)
+                    .with_anchor_camera(anchor_camera),
+            )
+            .id();
+        harness.update();
+
+        // The anchor is projected with its own camera, not the UI camera.
+        let node = harness.nodes(root)[0];
+        let anchor = harness.world().get::<WorldAnchor>(node).unwrap();
+        assert_eq!(anchor.target, target);
+        assert_eq!(anchor.offset, Vec3::Y);
+        assert_eq!(anchor.camera, Some(anchor_camera));
+
+        // The point can't be projected, since the camera has no `Camera` component, so the
+        // node is hidden.
+        harness.update();
+        assert_eq!(
+            harness.world().get::<Visibility>(node),
+            Some(&Visibility::Hidden)
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use std::panic::Location;
+
+use bevy::{
+    ecs::component::ComponentId,
+    prelude::*,
+    reflect::TypeRegistry,
+    utils::{HashMap, HashSet},
+};
+
+/// Number of consecutive frames in which a component must be changed by more than one effect
+/// before it is reported. A single frame with several writers is often legitimate, for
+/// example when one effect hands over to another.
+const CONFLICT_FRAMES: u32 = 3;
+
+/// The source location where an element effect was created, shown in diagnostics.
+#[derive(Component, Clone, Copy)]
+pub(crate) struct EffectLocation(pub(crate) &'static Location<'static>);
+
+/// Resource which records which reactions wrote each component of their target entities, so
+/// that components which are fought over by several effects can be reported. Only present
+/// when enabled with [`ReactorPlugin::with_write_conflict_warnings`](crate::ReactorPlugin).
+#[derive(Resource, Default)]
+pub(crate) struct WriteConflicts {
+    /// The reactions which changed the value of each component during the current frame.
+    writes: HashMap<(Entity, ComponentId), Vec<Entity>>,
+    /// The number of consecutive frames in which each component had more than one writer.
+    streaks: HashMap<(Entity, ComponentId), u32>,
+    /// Components which have already been reported.
+    reported: HashSet<(Entity, ComponentId)>,
+}
+
+/// The values of the target entity's components before a reaction runs.
+pub(crate) struct PendingWrites {
+    target: Entity,
+    values: Vec<(ComponentId, Box<dyn Reflect>)>,
+}
+
+/// Look up the reflection data for a component, if its type is registered.
+fn reflect_component<'r>(
+    world: &World,
+    registry: &'r TypeRegistry,
+    id: ComponentId,
+) -> Option<&'r ReflectComponent> {
+    let type_id = world.components().get_info(id)?.type_id()?;
+    registry.get_type_data::<ReflectComponent>(type_id)
+}
+
+/// Prepare to detect which components of `target` are written by the reaction which is about
+/// to run, by taking a copy of the value of each of its reflected components. Change ticks
+/// can't be used for this, because every reaction in a pass writes with the same tick.
+pub(crate) fn begin_tracking_writes(world: &World, target: Entity) -> Option<PendingWrites> {
+    let entt = world.get_entity(target)?;
+    let registry = world.get_resource::<AppTypeRegistry>()?.read();
+    let values = entt
+        .archetype()
+        .components()
+        .filter_map(|id| {
+            let value = reflect_component(world, &registry, id)?.reflect(entt)?;
+            Some((id, value.clone_value()))
+        })
+        .collect();
+    Some(PendingWrites { target, values })
+}
+
+/// Record the components of the target whose values were changed by `reaction`. Writing the
+/// value which a component already has is not a conflict, so it isn't recorded.
+pub(crate) fn end_tracking_writes(world: &mut World, reaction: Entity, pending: PendingWrites) {
+    let Some(entt) = world.get_entity(pending.target) else {
+        return;
+    };
+    let written: Vec<ComponentId> = {
+        let registry = world.resource::<AppTypeRegistry>().read();
+        pending
+            .values
+            .iter()
+            .filter(|(id, before)| {
+                reflect_component(world, &registry, *id)
+                    .and_then(|reflect| reflect.reflect(entt))
+                    .is_some_and(|after| after.reflect_partial_eq(before.as_ref()) == Some(false))
+            })
+            .map(|(id, _)| *id)
+            .collect()
+    };
+    let mut conflicts = world.resource_mut::<WriteConflicts>();
+    for id in written {
+        let writers = conflicts.writes.entry((pending.target, id)).or_default();
+        if !writers.contains(&reaction) {
+            writers.push(reaction);
+        }
+    }
+}
+
+/// Describe where a reaction came from: its creation location if known, otherwise its name
+/// or entity id.
+fn reaction_label(world: &World, reaction: Entity) -> String {
+    if let Some(location) = world.get::<EffectLocation>(reaction) {
+        format!("effect at {}", location.0)
+    } else if let Some(name) = world.get::<Name>(reaction) {
+        format!("'{}'", name)
+    } else {
+        format!("reaction {:?}", reaction)
+    }
+}
+
+/// At the end of each frame, warn about components which have been written by more than one
+/// reaction for several frames in a row.
+pub(crate) fn report_write_conflicts(world: &mut World) {
+    let Some(mut conflicts) = world.get_resource_mut::<WriteConflicts>() else {
+        return;
+    };
+    let writes = std::mem::take(&mut conflicts.writes);
+    let mut streaks = HashMap::default();
+    let mut report = Vec::new();
+    for (key, writers) in writes {
+        if writers.len() < 2 {
+            continue;
+        }
+        let streak = conflicts.streaks.get(&key).copied().unwrap_or(0) + 1;
+        streaks.insert(key, streak);
+        if streak >= CONFLICT_FRAMES && conflicts.reported.insert(key) {
+            report.push((key, writers));
+        }
+    }
+    conflicts.streaks = streaks;
+
+    for ((entity, component), writers) in report {
+        let component = world
+            .components()
+            .get_info(component)
+            .map_or("unknown", |info| info.name());
+        let writers: Vec<String> = writers
+            .iter()
+            .map(|writer| reaction_label(world, *writer))
+            .collect();
+        warn!(
+            "Component {} of {:?} is set to different values by several effects every frame, \
+            which may cause flickering: {}",
+            component,
+            entity,
+            writers.join(" and ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        testing::TestHarness, Cx, EffectTarget, Element, PresenterFn, RunContextRead, View,
+    };
+
+    fn fight(cx: &mut Cx<Color>) -> impl View {
+        let color = cx.props;
+        Element::<NodeBundle>::new()
+            .insert_computed(|cx| {
+                cx.use_resource::<Time>();
+                BackgroundColor(Color::RED)
+            })
+            .create_effect(move |cx, ent| {
+                cx.use_resource::<Time>();
+                cx.world_mut()
+                    .entity_mut(ent)
+                    .insert(BackgroundColor(color));
+            })
+    }
+
+    fn is_reported(color: Color) -> bool {
+        let mut harness = TestHarness::new();
+        harness.app.register_type::<BackgroundColor>();
+        harness.world_mut().init_resource::<WriteConflicts>();
+        let root = harness.mount(fight.bind(color));
+        let element = harness.nodes(root)[0];
+        harness.update_frames(CONFLICT_FRAMES as usize + 1);
+
+        let background = harness
+            .world()
+            .components()
+            .component_id::<BackgroundColor>()
+            .unwrap();
+        let conflicts = harness.world().resource::<WriteConflicts>();
+        conflicts.reported.contains(&(element, background))
+    }
+
+    #[test]
+    fn test_write_conflicts() {
+        assert!(is_reported(Color::BLUE));
+    }
+
+    #[test]
+    fn test_write_conflicts_same_value() {
+        // Effects which agree on the value don't conflict.
+        assert!(!is_reported(Color::RED));
+    }
+}